@@ -99,12 +99,18 @@ fn main() -> Result<()> {
                                 },
                                 amount: Uint128::new(1000000000),
                                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                                formula: None,
+                                alternatives: vec![],
                             }),
                             submission_policy: PreProposeSubmissionPolicy::Specific {
                                 dao_members: true,
                                 allowlist: vec![],
                                 denylist: vec![],
                             },
+                            sponsorship: None,
+                            forfeiture: None,
+                            rate_limit: None,
+                            conditions: None,
                             extension: Empty::default(),
                         })
                         .unwrap(),
@@ -83,12 +83,18 @@ pub fn create_dao(
                                 },
                                 amount: DEPOSIT_AMOUNT,
                                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                                formula: None,
+                                alternatives: vec![],
                             }),
                             submission_policy: PreProposeSubmissionPolicy::Specific {
                                 dao_members: true,
                                 allowlist: vec![],
                                 denylist: vec![],
                             },
+                            sponsorship: None,
+                            forfeiture: None,
+                            rate_limit: None,
+                            conditions: None,
                             extension: Empty::default(),
                         })
                         .unwrap(),
@@ -0,0 +1,330 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult,
+    SubMsg, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_paginate_storage::paginate_map_values;
+use dao_pre_propose_base::{
+    approval::{self, Approvers, Decision, Outcome, APPROVERS, EXPIRED_REASON},
+    error::PreProposeError,
+    msg::ExecuteMsg as ExecuteBase,
+    state::{OpenProposal, PreProposeContract},
+};
+use dao_voting::proposal::MultipleChoiceProposeMsg as ProposeMsg;
+
+use crate::msg::{
+    ApproverProposeMessage, ExecuteExt, ExecuteMsg, InstantiateExt, InstantiateMsg, MigrateMsg,
+    ProposeMessage, QueryExt, QueryMsg,
+};
+use crate::state::{advance_approval_id, Proposal, ProposalStatus, PROPOSALS};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-approval-multiple";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+type PrePropose = PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, Empty, ProposeMessage>;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, PreProposeError> {
+    let InstantiateExt {
+        approvers,
+        threshold,
+        approval_period,
+    } = msg.extension.clone();
+    let approvers = Approvers::new(deps.api, approvers, threshold, approval_period)?;
+    APPROVERS.save(deps.storage, &approvers)?;
+
+    if let Some(deposit_info) = &msg.deposit_info {
+        deposit_info.check_native_alternatives()?;
+    }
+
+    let resp = PrePropose::default().instantiate(deps.branch(), env, info, msg)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(resp
+        .add_attribute("approvers", approvers.approvers.len().to_string())
+        .add_attribute("threshold", approvers.threshold.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, PreProposeError> {
+    match msg {
+        ExecuteMsg::Propose { msg } => execute_propose(deps, env, info, msg),
+
+        ExecuteMsg::AddProposalSubmittedHook { address } => {
+            PrePropose::default().execute_add_approver_hook(deps, info, address)
+        }
+        ExecuteMsg::RemoveProposalSubmittedHook { address } => {
+            PrePropose::default().execute_remove_approver_hook(deps, info, address)
+        }
+
+        ExecuteMsg::Extension { msg } => match msg {
+            ExecuteExt::Approve { id } => {
+                execute_decide(deps, env, info, id, Decision::Approved {})
+            }
+            ExecuteExt::Reject { id, reason } => {
+                execute_decide(deps, env, info, id, Decision::Rejected { reason })
+            }
+            ExecuteExt::ExpirePending { id } => {
+                PrePropose::default().execute_expire_pending(deps, env, &PROPOSALS, id)
+            }
+            ExecuteExt::UpdateApprover { address } => {
+                PrePropose::default().execute_update_approver(deps, info, address)
+            }
+            ExecuteExt::UpdateApprovers {
+                approvers,
+                threshold,
+                approval_period,
+            } => PrePropose::default().execute_update_approvers(
+                deps,
+                info,
+                approvers,
+                threshold,
+                approval_period,
+            ),
+        },
+        // Proposals are gated by the approver rather than by
+        // sponsorship, so the sponsorship phase does not apply.
+        ExecuteMsg::UpdateSponsorship { .. }
+        | ExecuteMsg::Sponsor { .. }
+        | ExecuteMsg::ExpireUnsponsored { .. } => Err(PreProposeError::Unsupported {}),
+        // Deposits paid with cw20 tokens or NFTs are not supported here.
+        ExecuteMsg::Receive(_) | ExecuteMsg::ReceiveNft(_) => Err(PreProposeError::Unsupported {}),
+        ExecuteMsg::UpdateConfig {
+            ref deposit_info, ..
+        } => {
+            if let Some(deposit_info) = deposit_info {
+                deposit_info.check_native_alternatives()?;
+            }
+            PrePropose::default().execute(deps, env, info, msg)
+        }
+        // Default pre-propose-base behavior for all other messages
+        _ => PrePropose::default().execute(deps, env, info, msg),
+    }
+}
+
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ProposeMessage,
+) -> Result<Response, PreProposeError> {
+    let pre_propose_base = PrePropose::default();
+    let config = pre_propose_base.config.load(deps.storage)?;
+
+    pre_propose_base.check_can_submit(deps.as_ref(), info.sender.clone())?;
+    pre_propose_base.check_conditions(deps.as_ref(), &info.sender, &to_json_binary(&msg)?)?;
+    pre_propose_base.check_rate_limit(deps.as_ref(), &env.block, &info.sender)?;
+    pre_propose_base.prune_rejections(deps.storage, &env.block, &info.sender)?;
+
+    let deposit_info = pre_propose_base
+        .get_deposit_for(deps.as_ref(), &env.block, config.deposit_info, &info.sender)?
+        .map(|deposit_info| deposit_info.resolve_native_payment(&info))
+        .transpose()?;
+
+    // Take deposit, if configured.
+    let deposit_messages = if let Some(ref deposit_info) = deposit_info {
+        deposit_info.get_take_deposit_messages(&info.sender, &env.contract.address)?
+    } else {
+        vec![]
+    };
+
+    let approval_id = advance_approval_id(deps.storage)?;
+    pre_propose_base.record_submission(
+        deps.storage,
+        &env.block,
+        &info.sender,
+        OpenProposal::Approval { id: approval_id },
+    )?;
+    let expiration = APPROVERS.load(deps.storage)?.expiration(&env.block);
+
+    let propose_msg_internal = match msg {
+        ProposeMessage::Propose {
+            title,
+            description,
+            choices,
+            vote,
+        } => ProposeMsg {
+            title,
+            description,
+            choices,
+            proposer: Some(info.sender.to_string()),
+            vote,
+        },
+    };
+
+    // Prepare proposal submitted hooks msg to notify approver.  Make
+    // a proposal on the approver DAO to approve this pre-proposal
+    let hooks_msgs =
+        pre_propose_base
+            .proposal_submitted_hooks
+            .prepare_hooks(deps.storage, |a| {
+                let execute_msg = WasmMsg::Execute {
+                    contract_addr: a.into_string(),
+                    msg: to_json_binary(&ExecuteBase::<ApproverProposeMessage, Empty>::Propose {
+                        msg: ApproverProposeMessage::Propose {
+                            title: propose_msg_internal.title.clone(),
+                            description: propose_msg_internal.description.clone(),
+                            approval_id,
+                        },
+                    })?,
+                    funds: vec![],
+                };
+                Ok(SubMsg::new(execute_msg))
+            })?;
+
+    // Save the proposal and its information as pending.
+    PROPOSALS.pending.save(
+        deps.storage,
+        approval_id,
+        &Proposal {
+            status: ProposalStatus::Pending {},
+            approval_id,
+            proposer: info.sender,
+            msg: propose_msg_internal,
+            deposit: deposit_info,
+            expiration,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_messages(deposit_messages)
+        .add_submessages(hooks_msgs)
+        .add_attribute("method", "pre-propose")
+        .add_attribute("id", approval_id.to_string()))
+}
+
+pub fn execute_decide(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    decision: Decision,
+) -> Result<Response, PreProposeError> {
+    approval::load_for_approver(deps.storage, &info.sender)?;
+    let proposal = PROPOSALS
+        .pending
+        .may_load(deps.storage, id)?
+        .ok_or(PreProposeError::ProposalNotFound {})?;
+    if proposal.expiration.is_expired(&env.block) {
+        return PrePropose::default().reject_proposal(
+            deps,
+            &env,
+            &PROPOSALS,
+            proposal,
+            Some(EXPIRED_REASON.to_string()),
+        );
+    }
+
+    match approval::decide(deps.storage, &info.sender, id, decision)? {
+        Outcome::Approved => PrePropose::default().approve_proposal(deps, &PROPOSALS, proposal),
+        Outcome::Rejected { reason } => {
+            PrePropose::default().reject_proposal(deps, &env, &PROPOSALS, proposal, reason)
+        }
+        Outcome::Undecided {
+            approvals,
+            rejections,
+        } => Ok(Response::default()
+            .add_attribute("method", "record_decision")
+            .add_attribute("approval_id", id.to_string())
+            .add_attribute("approver", info.sender)
+            .add_attribute("approvals", approvals.to_string())
+            .add_attribute("rejections", rejections.to_string())),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::QueryExtension { msg } => match msg {
+            QueryExt::Approvers {} => to_json_binary(&APPROVERS.load(deps.storage)?),
+            QueryExt::Approvals { id } => {
+                to_json_binary(&approval::query_approvals(deps.storage, id)?)
+            }
+            QueryExt::IsPending { id } => {
+                let pending = PROPOSALS.pending.may_load(deps.storage, id)?.is_some();
+                // Force load completed proposal if not pending, throwing error
+                // if not found.
+                if !pending {
+                    PROPOSALS.completed.load(deps.storage, id)?;
+                }
+
+                to_json_binary(&pending)
+            }
+            QueryExt::Proposal { id } => {
+                if let Some(pending) = PROPOSALS.pending.may_load(deps.storage, id)? {
+                    to_json_binary(&pending)
+                } else {
+                    // Force load completed proposal if not pending, throwing
+                    // error if not found.
+                    to_json_binary(&PROPOSALS.completed.load(deps.storage, id)?)
+                }
+            }
+            QueryExt::PendingProposal { id } => {
+                to_json_binary(&PROPOSALS.pending.load(deps.storage, id)?)
+            }
+            QueryExt::PendingProposals { start_after, limit } => {
+                to_json_binary(&paginate_map_values(
+                    deps,
+                    &PROPOSALS.pending,
+                    start_after,
+                    limit,
+                    Order::Ascending,
+                )?)
+            }
+            QueryExt::ReversePendingProposals {
+                start_before,
+                limit,
+            } => to_json_binary(&paginate_map_values(
+                deps,
+                &PROPOSALS.pending,
+                start_before,
+                limit,
+                Order::Descending,
+            )?),
+            QueryExt::CompletedProposal { id } => {
+                to_json_binary(&PROPOSALS.completed.load(deps.storage, id)?)
+            }
+            QueryExt::CompletedProposals { start_after, limit } => {
+                to_json_binary(&paginate_map_values(
+                    deps,
+                    &PROPOSALS.completed,
+                    start_after,
+                    limit,
+                    Order::Ascending,
+                )?)
+            }
+            QueryExt::ReverseCompletedProposals {
+                start_before,
+                limit,
+            } => to_json_binary(&paginate_map_values(
+                deps,
+                &PROPOSALS.completed,
+                start_before,
+                limit,
+                Order::Descending,
+            )?),
+            QueryExt::CompletedProposalIdForCreatedProposalId { id } => {
+                to_json_binary(&PROPOSALS.created_to_completed.may_load(deps.storage, id)?)
+            }
+        },
+        _ => PrePropose::default().query(deps, env, msg),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, PreProposeError> {
+    let res = PrePropose::default().migrate(deps.branch(), msg);
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    res
+}
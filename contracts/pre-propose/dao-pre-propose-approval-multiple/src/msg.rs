@@ -0,0 +1,128 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Empty;
+use cw_utils::Duration;
+use dao_pre_propose_base::msg::{
+    ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, MigrateMsg as MigrateBase,
+    QueryMsg as QueryBase,
+};
+use dao_voting::{
+    multiple_choice::{MultipleChoiceAutoVote, MultipleChoiceOptions},
+    proposal::MultipleChoiceProposeMsg as ProposeMsg,
+};
+
+#[cw_serde]
+pub enum ApproverProposeMessage {
+    Propose {
+        title: String,
+        description: String,
+        approval_id: u64,
+    },
+}
+
+#[cw_serde]
+pub enum ProposeMessage {
+    Propose {
+        title: String,
+        description: String,
+        choices: MultipleChoiceOptions,
+        vote: Option<MultipleChoiceAutoVote>,
+    },
+}
+
+#[cw_serde]
+pub struct InstantiateExt {
+    /// The addresses that may approve or reject proposals.
+    pub approvers: Vec<String>,
+    /// The number of approvers that must approve a proposal before
+    /// it is created. Between one and the number of approvers.
+    pub threshold: u64,
+    /// How long proposals may remain pending before they are
+    /// rejected. If `None`, pending proposals never expire.
+    pub approval_period: Option<Duration>,
+}
+
+#[cw_serde]
+pub enum ExecuteExt {
+    /// Approve a proposal, only callable by an approver. The
+    /// proposal is created once `threshold` approvers have approved
+    /// it.
+    Approve { id: u64 },
+    /// Reject a proposal, only callable by an approver. The proposal
+    /// is rejected once so many approvers have rejected it that
+    /// `threshold` may no longer be reached.
+    Reject { id: u64, reason: Option<String> },
+    /// Rejects a pending proposal whose approval period has ended.
+    /// Callable by anyone.
+    ExpirePending { id: u64 },
+    /// Replaces the sender in the approver set with ADDRESS, can
+    /// only be called by a current approver.
+    UpdateApprover { address: String },
+    /// Replaces the approver set, threshold and approval period,
+    /// can only be called by the DAO.
+    UpdateApprovers {
+        approvers: Vec<String>,
+        threshold: u64,
+        approval_period: Option<Duration>,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryExt {
+    /// The approvers, threshold and approval period.
+    #[returns(crate::state::Approvers)]
+    Approvers {},
+    /// The decisions approvers have made on a proposal.
+    #[returns(Vec<crate::state::Approval>)]
+    Approvals { id: u64 },
+    /// Return whether or not the proposal is pending
+    #[returns(bool)]
+    IsPending { id: u64 },
+    /// A proposal, pending or completed.
+    #[returns(crate::state::Proposal)]
+    Proposal { id: u64 },
+    /// A pending proposal
+    #[returns(crate::state::Proposal)]
+    PendingProposal { id: u64 },
+    /// List of proposals awaiting approval
+    #[returns(Vec<crate::state::Proposal>)]
+    PendingProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(Vec<crate::state::Proposal>)]
+    ReversePendingProposals {
+        start_before: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// A completed proposal
+    #[returns(crate::state::Proposal)]
+    CompletedProposal { id: u64 },
+    /// List of completed proposals
+    #[returns(Vec<crate::state::Proposal>)]
+    CompletedProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(Vec<crate::state::Proposal>)]
+    ReverseCompletedProposals {
+        start_before: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// The completed approval ID for a created proposal ID.
+    #[returns(::std::option::Option<u64>)]
+    CompletedProposalIdForCreatedProposalId { id: u64 },
+}
+
+pub type InstantiateMsg = InstantiateBase<InstantiateExt>;
+pub type ExecuteMsg = ExecuteBase<ProposeMessage, ExecuteExt>;
+pub type QueryMsg = QueryBase<QueryExt>;
+pub type MigrateMsg = MigrateBase<Empty>;
+
+/// Internal version of the propose message that includes the
+/// `proposer` field. The module will fill this in based on the sender
+/// of the external message.
+#[cw_serde]
+pub(crate) enum ProposeMessageInternal {
+    Propose(ProposeMsg),
+}
@@ -0,0 +1,94 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Addr, Binary, StdResult, Storage};
+use cw_storage_plus::Item;
+use cw_utils::Expiration;
+
+pub use dao_pre_propose_base::approval::{Approval, Approvers, Decision};
+use dao_pre_propose_base::approval::{ApprovalProposal, ApprovalProposals};
+use dao_voting::deposit::CheckedDepositInfo;
+use dao_voting::proposal::MultipleChoiceProposeMsg as ProposeMsg;
+
+use crate::msg::ProposeMessageInternal;
+
+#[cw_serde]
+pub enum ProposalStatus {
+    /// The proposal is pending approval.
+    Pending {},
+    /// The proposal has been approved.
+    Approved {
+        /// The created proposal ID.
+        created_proposal_id: u64,
+    },
+    /// The proposal has been rejected.
+    Rejected {
+        /// The reason given by the approver whose rejection decided
+        /// the proposal, if any.
+        reason: Option<String>,
+    },
+}
+
+#[cw_serde]
+pub struct Proposal {
+    /// The status of a completed proposal.
+    pub status: ProposalStatus,
+    /// The approval ID used to identify this pending proposal.
+    pub approval_id: u64,
+    /// The address that created the proposal.
+    pub proposer: Addr,
+    /// The propose message that ought to be executed on the proposal
+    /// message if this proposal is approved.
+    pub msg: ProposeMsg,
+    /// Snapshot of the deposit info at the time of proposal
+    /// submission.
+    pub deposit: Option<CheckedDepositInfo>,
+    /// When the proposal stops accepting approvals and may be
+    /// rejected as expired.
+    pub expiration: Expiration,
+}
+
+impl ApprovalProposal for Proposal {
+    fn approval_id(&self) -> u64 {
+        self.approval_id
+    }
+
+    fn proposer(&self) -> &Addr {
+        &self.proposer
+    }
+
+    fn deposit(&self) -> Option<&CheckedDepositInfo> {
+        self.deposit.as_ref()
+    }
+
+    fn expiration(&self) -> &Expiration {
+        &self.expiration
+    }
+
+    fn propose_msg(&self) -> StdResult<Binary> {
+        to_json_binary(&ProposeMessageInternal::Propose(self.msg.clone()))
+    }
+
+    fn set_approved(&mut self, created_proposal_id: u64) {
+        self.status = ProposalStatus::Approved {
+            created_proposal_id,
+        };
+    }
+
+    fn set_rejected(&mut self, reason: Option<String>) {
+        self.status = ProposalStatus::Rejected { reason };
+    }
+}
+
+pub const PROPOSALS: ApprovalProposals<Proposal> = ApprovalProposals::new(
+    "pending_proposals",
+    "completed_proposals",
+    "created_to_completed_proposal",
+);
+
+/// Used internally to track the current approval_id.
+const CURRENT_ID: Item<u64> = Item::new("current_id");
+
+pub(crate) fn advance_approval_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = CURRENT_ID.may_load(store)?.unwrap_or_default() + 1;
+    CURRENT_ID.save(store, &id)?;
+    Ok(id)
+}
@@ -0,0 +1,540 @@
+use cosmwasm_std::{coins, to_json_binary, Addr, Coin, Decimal, Uint128};
+use cw_denom::UncheckedDenom;
+use cw_multi_test::{App, BankSudo, Executor};
+use cw_utils::Duration;
+use dao_interface::state::ProposalModule;
+use dao_interface::state::{Admin, ModuleInstantiateInfo};
+use dao_pre_propose_approver::msg::InstantiateMsg as ApproverInstantiateMsg;
+use dao_pre_propose_base::error::PreProposeError;
+use dao_proposal_multiple as dpm;
+use dao_proposal_single as dps;
+use dao_testing::contracts::{
+    dao_pre_propose_approval_multiple_contract, dao_pre_propose_approver_contract,
+    dao_proposal_multiple_contract, dao_proposal_single_contract,
+};
+use dao_testing::helpers::instantiate_with_cw4_groups_governance;
+use dao_voting::{
+    deposit::{DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    multiple_choice::{MultipleChoiceOption, MultipleChoiceOptions, VotingStrategy},
+    pre_propose::{PreProposeInfo, PreProposeSubmissionPolicy, ProposalCreationPolicy},
+    status::Status,
+    threshold::{PercentageThreshold, Threshold},
+    voting::Vote,
+};
+
+use crate::msg::*;
+use crate::state::{Proposal, ProposalStatus};
+
+// The approver DAO is the 6th contract instantiated when the
+// approver DAO is set up after the DAO being approved for.
+const APPROVER_DAO: &str = "contract6";
+
+fn get_proposal_module_instantiate(
+    app: &mut App,
+    deposit_info: Option<UncheckedDepositInfo>,
+    approvers: Vec<String>,
+    threshold: u64,
+) -> dpm::msg::InstantiateMsg {
+    let pre_propose_id = app.store_code(dao_pre_propose_approval_multiple_contract());
+
+    dpm::msg::InstantiateMsg {
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+        },
+        max_voting_period: Duration::Time(86400),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        pre_propose_info: PreProposeInfo::ModuleMayPropose {
+            info: ModuleInstantiateInfo {
+                code_id: pre_propose_id,
+                msg: to_json_binary(&InstantiateMsg {
+                    deposit_info,
+                    submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+                    sponsorship: None,
+                    forfeiture: None,
+                    rate_limit: None,
+                    conditions: None,
+                    extension: InstantiateExt {
+                        approvers,
+                        threshold,
+                        approval_period: None,
+                    },
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "multiple choice pre-propose module, needs supervision".to_string(),
+            },
+        },
+        close_proposal_on_execution_failure: false,
+        veto: None,
+    }
+}
+
+fn members() -> Option<Vec<cw20::Cw20Coin>> {
+    Some(vec![
+        cw20::Cw20Coin {
+            address: "ekez".to_string(),
+            amount: Uint128::new(9),
+        },
+        cw20::Cw20Coin {
+            address: "keze".to_string(),
+            amount: Uint128::new(8),
+        },
+    ])
+}
+
+/// Returns the (module, pre_propose) pair of a DAO with a single
+/// proposal module that has a pre-propose module.
+fn modules(app: &App, core_addr: &Addr) -> (Addr, Addr) {
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr,
+            &dao_interface::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(proposal_modules.len(), 1);
+    let module = proposal_modules.into_iter().next().unwrap().address;
+    let policy: ProposalCreationPolicy = app
+        .wrap()
+        .query_wasm_smart(
+            module.clone(),
+            &dpm::msg::QueryMsg::ProposalCreationPolicy {},
+        )
+        .unwrap();
+    match policy {
+        ProposalCreationPolicy::Module { addr } => (module, addr),
+        _ => panic!("expected a module for the proposal creation policy"),
+    }
+}
+
+struct DefaultTestSetup {
+    core_addr: Addr,
+    proposal_multiple: Addr,
+    pre_propose: Addr,
+}
+
+fn setup_default_test(
+    app: &mut App,
+    deposit_info: Option<UncheckedDepositInfo>,
+    approvers: &[&str],
+    threshold: u64,
+) -> DefaultTestSetup {
+    let dpm_id = app.store_code(dao_proposal_multiple_contract());
+    let proposal_module_instantiate = get_proposal_module_instantiate(
+        app,
+        deposit_info,
+        approvers.iter().map(|a| a.to_string()).collect(),
+        threshold,
+    );
+    let core_addr = instantiate_with_cw4_groups_governance(
+        app,
+        dpm_id,
+        to_json_binary(&proposal_module_instantiate).unwrap(),
+        members(),
+    );
+    let (proposal_multiple, pre_propose) = modules(app, &core_addr);
+
+    DefaultTestSetup {
+        core_addr,
+        proposal_multiple,
+        pre_propose,
+    }
+}
+
+fn options() -> MultipleChoiceOptions {
+    MultipleChoiceOptions {
+        options: vec![
+            MultipleChoiceOption {
+                description: "multiple choice option 1".to_string(),
+                msgs: vec![],
+                title: "title".to_string(),
+            },
+            MultipleChoiceOption {
+                description: "multiple choice option 2".to_string(),
+                msgs: vec![],
+                title: "title".to_string(),
+            },
+        ],
+    }
+}
+
+fn make_pre_proposal(app: &mut App, pre_propose: Addr, proposer: &str, funds: &[Coin]) -> u64 {
+    app.execute_contract(
+        Addr::unchecked(proposer),
+        pre_propose.clone(),
+        &ExecuteMsg::Propose {
+            msg: ProposeMessage::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                choices: options(),
+                vote: None,
+            },
+        },
+        funds,
+    )
+    .unwrap();
+
+    // Query for pending proposal and return latest id.
+    let mut pending: Vec<Proposal> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose,
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::PendingProposals {
+                    start_after: None,
+                    limit: None,
+                },
+            },
+        )
+        .unwrap();
+    pending.pop().unwrap().approval_id
+}
+
+fn decide(
+    app: &mut App,
+    module: Addr,
+    sender: &str,
+    msg: ExecuteExt,
+) -> Result<(), PreProposeError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        module,
+        &ExecuteMsg::Extension { msg },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn query_pre_proposal(app: &App, module: Addr, id: u64) -> Proposal {
+    app.wrap()
+        .query_wasm_smart(
+            module,
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Proposal { id },
+            },
+        )
+        .unwrap()
+}
+
+fn query_completed_id(app: &App, module: Addr, created_id: u64) -> Option<u64> {
+    app.wrap()
+        .query_wasm_smart(
+            module,
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::CompletedProposalIdForCreatedProposalId { id: created_id },
+            },
+        )
+        .unwrap()
+}
+
+fn query_proposal(app: &App, module: Addr, proposal_id: u64) -> dpm::query::ProposalResponse {
+    app.wrap()
+        .query_wasm_smart(module, &dpm::msg::QueryMsg::Proposal { proposal_id })
+        .unwrap()
+}
+
+#[test]
+fn test_approve_creates_multiple_choice_proposal() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr: _,
+        proposal_multiple,
+        pre_propose,
+    } = setup_default_test(&mut app, None, &["approver"], 1);
+
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).status,
+        ProposalStatus::Pending {}
+    );
+
+    // Only the approver may approve.
+    let err = decide(
+        &mut app,
+        pre_propose.clone(),
+        "ekez",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap_err();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).status,
+        ProposalStatus::Approved {
+            created_proposal_id: 1
+        }
+    );
+    assert_eq!(query_completed_id(&app, pre_propose, 1), Some(id));
+
+    let proposal = query_proposal(&app, proposal_multiple, 1);
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("ekez"));
+    // The two options and "none of the above".
+    assert_eq!(proposal.proposal.choices.len(), 3);
+}
+
+#[test]
+fn test_reject_refunds_deposit() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr: _,
+        proposal_multiple: _,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
+        }),
+        &["approver"],
+        1,
+    );
+
+    app.sudo(cw_multi_test::SudoMsg::Bank(BankSudo::Mint {
+        to_address: "ekez".to_string(),
+        amount: coins(10, "ujuno"),
+    }))
+    .unwrap();
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+    assert!(app
+        .wrap()
+        .query_balance("ekez", "ujuno")
+        .unwrap()
+        .amount
+        .is_zero());
+
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Reject {
+            id,
+            reason: Some("duplicate".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose, id).status,
+        ProposalStatus::Rejected {
+            reason: Some("duplicate".to_string())
+        }
+    );
+    assert_eq!(
+        app.wrap().query_balance("ekez", "ujuno").unwrap().amount,
+        Uint128::new(10)
+    );
+}
+
+#[test]
+fn test_threshold_approval() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_multiple: _,
+        pre_propose,
+    } = setup_default_test(&mut app, None, &["a", "b"], 2);
+
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "a",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).status,
+        ProposalStatus::Pending {}
+    );
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "b",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).status,
+        ProposalStatus::Approved {
+            created_proposal_id: 1
+        }
+    );
+
+    // Only the DAO may replace the approvers.
+    let err = decide(
+        &mut app,
+        pre_propose.clone(),
+        "a",
+        ExecuteExt::UpdateApprovers {
+            approvers: vec!["a".to_string()],
+            threshold: 1,
+            approval_period: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, PreProposeError::NotDao {});
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        core_addr.as_str(),
+        ExecuteExt::UpdateApprovers {
+            approvers: vec!["a".to_string()],
+            threshold: 1,
+            approval_period: None,
+        },
+    )
+    .unwrap();
+
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "a",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose, id).status,
+        ProposalStatus::Approved {
+            created_proposal_id: 2
+        }
+    );
+}
+
+#[test]
+fn test_approver_dao() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr: _,
+        proposal_multiple,
+        pre_propose,
+    } = setup_default_test(&mut app, None, &[APPROVER_DAO], 1);
+
+    // Set up an approver DAO whose proposals approve or reject
+    // proposals pending in the pre-propose module.
+    let dps_id = app.store_code(dao_proposal_single_contract());
+    let approver_id = app.store_code(dao_pre_propose_approver_contract());
+    let approver_dao = instantiate_with_cw4_groups_governance(
+        &mut app,
+        dps_id,
+        to_json_binary(&dps::msg::InstantiateMsg {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period: Duration::Time(86400),
+            min_voting_period: None,
+            only_members_execute: false,
+            allow_revoting: false,
+            pre_propose_info: PreProposeInfo::ModuleMayPropose {
+                info: ModuleInstantiateInfo {
+                    code_id: approver_id,
+                    msg: to_json_binary(&ApproverInstantiateMsg {
+                        pre_propose_approval_contract: pre_propose.to_string(),
+                    })
+                    .unwrap(),
+                    admin: Some(Admin::CoreModule {}),
+                    funds: vec![],
+                    label: "approver module".to_string(),
+                },
+            },
+            close_proposal_on_execution_failure: false,
+            veto: None,
+        })
+        .unwrap(),
+        members(),
+    );
+    assert_eq!(approver_dao, Addr::unchecked(APPROVER_DAO));
+    let (proposal_single_approver, pre_propose_approver) = modules(&app, &approver_dao);
+
+    // The approver module took the approver DAO's place.
+    let approvers: crate::state::Approvers = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Approvers {},
+            },
+        )
+        .unwrap();
+    assert_eq!(approvers.approvers, vec![pre_propose_approver]);
+
+    // Submitting a proposal creates a proposal in the approver DAO.
+    let first = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    let second = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+
+    // Passing and executing the approver DAO's proposal approves.
+    for (sender, vote) in [("ekez", Vote::Yes), ("keze", Vote::Yes)] {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            proposal_single_approver.clone(),
+            &dps::msg::ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote,
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_single_approver.clone(),
+        &dps::msg::ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), first).status,
+        ProposalStatus::Approved {
+            created_proposal_id: 1
+        }
+    );
+    assert_eq!(
+        query_proposal(&app, proposal_multiple, 1).proposal.status,
+        Status::Open
+    );
+
+    // Rejecting and closing it rejects.
+    for (sender, vote) in [("ekez", Vote::No), ("keze", Vote::No)] {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            proposal_single_approver.clone(),
+            &dps::msg::ExecuteMsg::Vote {
+                proposal_id: 2,
+                vote,
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_single_approver,
+        &dps::msg::ExecuteMsg::Close { proposal_id: 2 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose, second).status,
+        ProposalStatus::Rejected { reason: None }
+    );
+}
@@ -1,30 +1,32 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult,
-    SubMsg, WasmMsg,
+    to_json_binary, Addr, Binary, BlockInfo, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
+    Order, Response, StdResult, Storage, SubMsg, WasmMsg,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw_paginate_storage::paginate_map_values;
 use dao_pre_propose_base::{
-    error::PreProposeError, msg::ExecuteMsg as ExecuteBase, state::PreProposeContract,
+    approval::{self, Approvers, Decision, Outcome, APPROVERS, EXPIRED_REASON},
+    error::PreProposeError,
+    msg::ExecuteMsg as ExecuteBase,
+    state::{OpenProposal, PreProposeContract},
 };
-use dao_voting::deposit::DepositRefundPolicy;
 use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
 
 use crate::msg::{
-    ApproverProposeMessage, ExecuteExt, ExecuteMsg, InstantiateExt, InstantiateMsg, MigrateMsg,
-    ProposeMessage, ProposeMessageInternal, QueryExt, QueryMsg,
+    ApproverProposeMessage, ExecuteExt, ExecuteMsg, InstantiateExt, InstantiateMsg, MigrateExt,
+    MigrateMsg, ProposeMessage, QueryExt, QueryMsg,
 };
 use crate::state::{
-    advance_approval_id, Proposal, ProposalStatus, APPROVER, COMPLETED_PROPOSALS,
-    CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL, PENDING_PROPOSALS,
+    advance_approval_id, migrate_legacy_approver, Proposal, ProposalStatus, PROPOSALS,
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-approval-single";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-type PrePropose = PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, Empty, ProposeMessage>;
+type PrePropose =
+    PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposeMessage>;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -33,12 +35,23 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, PreProposeError> {
-    let approver = deps.api.addr_validate(&msg.extension.approver)?;
-    APPROVER.save(deps.storage, &approver)?;
+    let InstantiateExt {
+        approvers,
+        threshold,
+        approval_period,
+    } = msg.extension.clone();
+    let approvers = Approvers::new(deps.api, approvers, threshold, approval_period)?;
+    APPROVERS.save(deps.storage, &approvers)?;
+
+    if let Some(deposit_info) = &msg.deposit_info {
+        deposit_info.check_native_alternatives()?;
+    }
 
     let resp = PrePropose::default().instantiate(deps.branch(), env, info, msg)?;
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(resp.add_attribute("approver", approver.to_string()))
+    Ok(resp
+        .add_attribute("approvers", approvers.approvers.len().to_string())
+        .add_attribute("threshold", approvers.threshold.to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -52,17 +65,67 @@ pub fn execute(
         ExecuteMsg::Propose { msg } => execute_propose(deps, env, info, msg),
 
         ExecuteMsg::AddProposalSubmittedHook { address } => {
-            execute_add_approver_hook(deps, info, address)
+            PrePropose::default().execute_add_approver_hook(deps, info, address)
         }
         ExecuteMsg::RemoveProposalSubmittedHook { address } => {
-            execute_remove_approver_hook(deps, info, address)
+            PrePropose::default().execute_remove_approver_hook(deps, info, address)
         }
 
         ExecuteMsg::Extension { msg } => match msg {
-            ExecuteExt::Approve { id } => execute_approve(deps, info, id),
-            ExecuteExt::Reject { id } => execute_reject(deps, info, id),
-            ExecuteExt::UpdateApprover { address } => execute_update_approver(deps, info, address),
+            ExecuteExt::Approve { id } => {
+                execute_decide(deps, env, info, id, Decision::Approved {})
+            }
+            ExecuteExt::Reject { id, reason } => {
+                execute_decide(deps, env, info, id, Decision::Rejected { reason })
+            }
+            ExecuteExt::Amend {
+                id,
+                title,
+                description,
+                msgs,
+            } => execute_amend(deps, env, info, id, title, description, msgs),
+            ExecuteExt::RequestChanges { id, comment } => {
+                execute_request_changes(deps, env, info, id, comment)
+            }
+            ExecuteExt::Resubmit {
+                id,
+                title,
+                description,
+                msgs,
+            } => execute_resubmit(deps, env, info, id, title, description, msgs),
+            ExecuteExt::ExpirePending { id } => {
+                PrePropose::default().execute_expire_pending(deps, env, &PROPOSALS, id)
+            }
+            ExecuteExt::UpdateApprover { address } => {
+                PrePropose::default().execute_update_approver(deps, info, address)
+            }
+            ExecuteExt::UpdateApprovers {
+                approvers,
+                threshold,
+                approval_period,
+            } => PrePropose::default().execute_update_approvers(
+                deps,
+                info,
+                approvers,
+                threshold,
+                approval_period,
+            ),
         },
+        // Proposals are gated by the approver rather than by
+        // sponsorship, so the sponsorship phase does not apply.
+        ExecuteMsg::UpdateSponsorship { .. }
+        | ExecuteMsg::Sponsor { .. }
+        | ExecuteMsg::ExpireUnsponsored { .. } => Err(PreProposeError::Unsupported {}),
+        // Deposits paid with cw20 tokens or NFTs are not supported here.
+        ExecuteMsg::Receive(_) | ExecuteMsg::ReceiveNft(_) => Err(PreProposeError::Unsupported {}),
+        ExecuteMsg::UpdateConfig {
+            ref deposit_info, ..
+        } => {
+            if let Some(deposit_info) = deposit_info {
+                deposit_info.check_native_alternatives()?;
+            }
+            PrePropose::default().execute(deps, env, info, msg)
+        }
         // Default pre-propose-base behavior for all other messages
         _ => PrePropose::default().execute(deps, env, info, msg),
     }
@@ -78,16 +141,30 @@ pub fn execute_propose(
     let config = pre_propose_base.config.load(deps.storage)?;
 
     pre_propose_base.check_can_submit(deps.as_ref(), info.sender.clone())?;
+    pre_propose_base.check_conditions(deps.as_ref(), &info.sender, &to_json_binary(&msg)?)?;
+    pre_propose_base.check_rate_limit(deps.as_ref(), &env.block, &info.sender)?;
+    pre_propose_base.prune_rejections(deps.storage, &env.block, &info.sender)?;
+
+    let deposit_info = pre_propose_base
+        .get_deposit_for(deps.as_ref(), &env.block, config.deposit_info, &info.sender)?
+        .map(|deposit_info| deposit_info.resolve_native_payment(&info))
+        .transpose()?;
 
     // Take deposit, if configured.
-    let deposit_messages = if let Some(ref deposit_info) = config.deposit_info {
-        deposit_info.check_native_deposit_paid(&info)?;
+    let deposit_messages = if let Some(ref deposit_info) = deposit_info {
         deposit_info.get_take_deposit_messages(&info.sender, &env.contract.address)?
     } else {
         vec![]
     };
 
     let approval_id = advance_approval_id(deps.storage)?;
+    pre_propose_base.record_submission(
+        deps.storage,
+        &env.block,
+        &info.sender,
+        OpenProposal::Approval { id: approval_id },
+    )?;
+    let expiration = APPROVERS.load(deps.storage)?.expiration(&env.block);
 
     let propose_msg_internal = match msg {
         ProposeMessage::Propose {
@@ -106,26 +183,10 @@ pub fn execute_propose(
 
     // Prepare proposal submitted hooks msg to notify approver.  Make
     // a proposal on the approver DAO to approve this pre-proposal
-    let hooks_msgs =
-        pre_propose_base
-            .proposal_submitted_hooks
-            .prepare_hooks(deps.storage, |a| {
-                let execute_msg = WasmMsg::Execute {
-                    contract_addr: a.into_string(),
-                    msg: to_json_binary(&ExecuteBase::<ApproverProposeMessage, Empty>::Propose {
-                        msg: ApproverProposeMessage::Propose {
-                            title: propose_msg_internal.title.clone(),
-                            description: propose_msg_internal.description.clone(),
-                            approval_id,
-                        },
-                    })?,
-                    funds: vec![],
-                };
-                Ok(SubMsg::new(execute_msg))
-            })?;
+    let hooks_msgs = prepare_submitted_hooks(deps.storage, approval_id, &propose_msg_internal)?;
 
     // Save the proposal and its information as pending.
-    PENDING_PROPOSALS.save(
+    PROPOSALS.pending.save(
         deps.storage,
         approval_id,
         &Proposal {
@@ -133,7 +194,9 @@ pub fn execute_propose(
             approval_id,
             proposer: info.sender,
             msg: propose_msg_internal,
-            deposit: config.deposit_info,
+            deposit: deposit_info,
+            expiration,
+            revisions: vec![],
         },
     )?;
 
@@ -144,221 +207,222 @@ pub fn execute_propose(
         .add_attribute("id", approval_id.to_string()))
 }
 
-pub fn execute_approve(
-    deps: DepsMut,
-    info: MessageInfo,
-    id: u64,
-) -> Result<Response, PreProposeError> {
-    // Check sender is the approver
-    let approver = APPROVER.load(deps.storage)?;
-    if approver != info.sender {
-        return Err(PreProposeError::Unauthorized {});
-    }
-
-    // Load proposal and send propose message to the proposal module
-    let proposal = PENDING_PROPOSALS.may_load(deps.storage, id)?;
-    match proposal {
-        Some(proposal) => {
-            let proposal_module = PrePropose::default().proposal_module.load(deps.storage)?;
-
-            // Snapshot the deposit for the proposal that we're about
-            // to create.
-            let proposal_id = deps.querier.query_wasm_smart(
-                &proposal_module,
-                &dao_interface::proposal::Query::NextProposalId {},
-            )?;
-            PrePropose::default().deposits.save(
-                deps.storage,
-                proposal_id,
-                &(proposal.deposit.clone(), proposal.proposer.clone()),
-            )?;
-
-            let propose_messsage = WasmMsg::Execute {
-                contract_addr: proposal_module.into_string(),
-                msg: to_json_binary(&ProposeMessageInternal::Propose(proposal.msg.clone()))?,
+/// Prepares the proposal submitted hook messages for a pending
+/// proposal. These are sent when a proposal is submitted and again
+/// each time it is revised, with the same `approval_id`.
+fn prepare_submitted_hooks(
+    storage: &dyn Storage,
+    approval_id: u64,
+    msg: &ProposeMsg,
+) -> StdResult<Vec<SubMsg>> {
+    PrePropose::default()
+        .proposal_submitted_hooks
+        .prepare_hooks(storage, |a| {
+            let execute_msg = WasmMsg::Execute {
+                contract_addr: a.into_string(),
+                msg: to_json_binary(&ExecuteBase::<ApproverProposeMessage, Empty>::Propose {
+                    msg: ApproverProposeMessage::Propose {
+                        title: msg.title.clone(),
+                        description: msg.description.clone(),
+                        approval_id,
+                    },
+                })?,
                 funds: vec![],
             };
-
-            COMPLETED_PROPOSALS.save(
-                deps.storage,
-                id,
-                &Proposal {
-                    status: ProposalStatus::Approved {
-                        created_proposal_id: proposal_id,
-                    },
-                    approval_id: proposal.approval_id,
-                    proposer: proposal.proposer,
-                    msg: proposal.msg,
-                    deposit: proposal.deposit,
-                },
-            )?;
-            CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL.save(deps.storage, proposal_id, &id)?;
-            PENDING_PROPOSALS.remove(deps.storage, id);
-
-            Ok(Response::default()
-                .add_message(propose_messsage)
-                .add_attribute("method", "proposal_approved")
-                .add_attribute("approval_id", id.to_string())
-                .add_attribute("proposal_id", proposal_id.to_string()))
-        }
-        None => Err(PreProposeError::ProposalNotFound {}),
-    }
+            Ok(SubMsg::new(execute_msg))
+        })
 }
 
-pub fn execute_reject(
+pub fn execute_decide(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     id: u64,
+    decision: Decision,
 ) -> Result<Response, PreProposeError> {
-    // Check sender is the approver
-    let approver = APPROVER.load(deps.storage)?;
-    if approver != info.sender {
-        return Err(PreProposeError::Unauthorized {});
-    }
-
-    let Proposal {
-        approval_id,
-        proposer,
-        msg,
-        deposit,
-        ..
-    } = PENDING_PROPOSALS
+    approval::load_for_approver(deps.storage, &info.sender)?;
+    let proposal = PROPOSALS
+        .pending
         .may_load(deps.storage, id)?
         .ok_or(PreProposeError::ProposalNotFound {})?;
+    if proposal.expiration.is_expired(&env.block) {
+        return PrePropose::default().reject_proposal(
+            deps,
+            &env,
+            &PROPOSALS,
+            proposal,
+            Some(EXPIRED_REASON.to_string()),
+        );
+    }
+    if let (ProposalStatus::ChangesRequested { .. }, Decision::Approved {}) =
+        (&proposal.status, &decision)
+    {
+        return Err(PreProposeError::AwaitingChanges { id });
+    }
 
-    COMPLETED_PROPOSALS.save(
-        deps.storage,
-        id,
-        &Proposal {
-            status: ProposalStatus::Rejected {},
-            approval_id,
-            proposer: proposer.clone(),
-            msg: msg.clone(),
-            deposit: deposit.clone(),
-        },
-    )?;
-    PENDING_PROPOSALS.remove(deps.storage, id);
-
-    let messages = if let Some(ref deposit_info) = deposit {
-        // Refund can be issued if proposal if deposits are always
-        // refunded. `OnlyPassed` and `Never` refund deposit policies
-        // do not apply here.
-        if deposit_info.refund_policy == DepositRefundPolicy::Always {
-            deposit_info.get_return_deposit_message(&proposer)?
-        } else {
-            // If the proposer doesn't get the deposit, the DAO does.
-            let dao = PrePropose::default().dao.load(deps.storage)?;
-            deposit_info.get_return_deposit_message(&dao)?
+    match approval::decide(deps.storage, &info.sender, id, decision)? {
+        Outcome::Approved => PrePropose::default().approve_proposal(deps, &PROPOSALS, proposal),
+        Outcome::Rejected { reason } => {
+            PrePropose::default().reject_proposal(deps, &env, &PROPOSALS, proposal, reason)
         }
-    } else {
-        vec![]
-    };
+        Outcome::Undecided {
+            approvals,
+            rejections,
+        } => Ok(Response::default()
+            .add_attribute("method", "record_decision")
+            .add_attribute("approval_id", id.to_string())
+            .add_attribute("approver", info.sender)
+            .add_attribute("approvals", approvals.to_string())
+            .add_attribute("rejections", rejections.to_string())),
+    }
+}
 
-    Ok(Response::default()
-        .add_attribute("method", "proposal_rejected")
-        .add_attribute("proposal", id.to_string())
-        .add_attribute("deposit_info", to_json_binary(&deposit)?.to_string())
-        .add_messages(messages))
+/// Loads a pending proposal that an approver may edit, failing if
+/// SENDER is not an approver or the proposal has expired.
+fn load_for_approver(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    sender: &Addr,
+    id: u64,
+) -> Result<Proposal, PreProposeError> {
+    approval::load_for_approver(storage, sender)?;
+    let proposal = PROPOSALS
+        .pending
+        .may_load(storage, id)?
+        .ok_or(PreProposeError::ProposalNotFound {})?;
+    if proposal.expiration.is_expired(block) {
+        return Err(PreProposeError::ApprovalExpired { id });
+    }
+    Ok(proposal)
 }
 
-pub fn execute_update_approver(
+/// Saves a revised proposal, clears the decisions made on its
+/// previous version and restarts its approval period. Returns the
+/// proposal submitted hook messages for the revised proposal.
+fn save_revision(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    proposal: &mut Proposal,
+) -> StdResult<Vec<SubMsg>> {
+    proposal.expiration = APPROVERS.load(storage)?.expiration(block);
+    approval::clear_decisions(storage, proposal.approval_id)?;
+    PROPOSALS
+        .pending
+        .save(storage, proposal.approval_id, proposal)?;
+    prepare_submitted_hooks(storage, proposal.approval_id, &proposal.msg)
+}
+
+pub fn execute_amend(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    address: String,
+    id: u64,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
 ) -> Result<Response, PreProposeError> {
-    // Check sender is the approver
-    let approver = APPROVER.load(deps.storage)?;
-    if approver != info.sender {
-        return Err(PreProposeError::Unauthorized {});
-    }
+    let mut proposal = load_for_approver(deps.storage, &env.block, &info.sender, id)?;
+    proposal.revise(&env.block, info.sender.clone(), title, description, msgs);
+    let hooks_msgs = save_revision(deps.storage, &env.block, &mut proposal)?;
 
-    // Validate address and save new approver
-    let addr = deps.api.addr_validate(&address)?;
-    APPROVER.save(deps.storage, &addr)?;
-
-    Ok(Response::default())
+    Ok(Response::default()
+        .add_submessages(hooks_msgs)
+        .add_attribute("method", "amend")
+        .add_attribute("approval_id", id.to_string())
+        .add_attribute("approver", info.sender)
+        .add_attribute("revision", proposal.revisions.len().to_string()))
 }
 
-pub fn execute_add_approver_hook(
+pub fn execute_request_changes(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    address: String,
+    id: u64,
+    comment: Option<String>,
 ) -> Result<Response, PreProposeError> {
-    let pre_propose_base = PrePropose::default();
-
-    let dao = pre_propose_base.dao.load(deps.storage)?;
-    let approver = APPROVER.load(deps.storage)?;
-
-    // Check sender is the approver or the parent DAO
-    if approver != info.sender && dao != info.sender {
-        return Err(PreProposeError::Unauthorized {});
-    }
-
-    let addr = deps.api.addr_validate(&address)?;
-    pre_propose_base
-        .proposal_submitted_hooks
-        .add_hook(deps.storage, addr)?;
+    let mut proposal = load_for_approver(deps.storage, &env.block, &info.sender, id)?;
+    proposal.status = ProposalStatus::ChangesRequested {
+        comment: comment.clone(),
+    };
+    // The proposer has a full approval period to resubmit.
+    proposal.expiration = APPROVERS.load(deps.storage)?.expiration(&env.block);
+    PROPOSALS.pending.save(deps.storage, id, &proposal)?;
 
-    Ok(Response::default())
+    Ok(Response::default()
+        .add_attribute("method", "request_changes")
+        .add_attribute("approval_id", id.to_string())
+        .add_attribute("approver", info.sender)
+        .add_attribute("comment", comment.unwrap_or_default()))
 }
 
-pub fn execute_remove_approver_hook(
+pub fn execute_resubmit(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    address: String,
+    id: u64,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
 ) -> Result<Response, PreProposeError> {
-    let pre_propose_base = PrePropose::default();
-
-    let dao = pre_propose_base.dao.load(deps.storage)?;
-    let approver = APPROVER.load(deps.storage)?;
-
-    // Check sender is the approver or the parent DAO
-    if approver != info.sender && dao != info.sender {
+    let mut proposal = PROPOSALS
+        .pending
+        .may_load(deps.storage, id)?
+        .ok_or(PreProposeError::ProposalNotFound {})?;
+    if proposal.proposer != info.sender {
         return Err(PreProposeError::Unauthorized {});
     }
+    if proposal.expiration.is_expired(&env.block) {
+        return Err(PreProposeError::ApprovalExpired { id });
+    }
+    if !matches!(proposal.status, ProposalStatus::ChangesRequested { .. }) {
+        return Err(PreProposeError::ChangesNotRequested { id });
+    }
+    proposal.revise(&env.block, info.sender.clone(), title, description, msgs);
+    let hooks_msgs = save_revision(deps.storage, &env.block, &mut proposal)?;
 
-    // Validate address
-    let addr = deps.api.addr_validate(&address)?;
-
-    // remove hook
-    pre_propose_base
-        .proposal_submitted_hooks
-        .remove_hook(deps.storage, addr)?;
-
-    Ok(Response::default())
+    Ok(Response::default()
+        .add_submessages(hooks_msgs)
+        .add_attribute("method", "resubmit")
+        .add_attribute("approval_id", id.to_string())
+        .add_attribute("proposer", info.sender)
+        .add_attribute("revision", proposal.revisions.len().to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::QueryExtension { msg } => match msg {
-            QueryExt::Approver {} => to_json_binary(&APPROVER.load(deps.storage)?),
+            QueryExt::Approver {} => to_json_binary(&APPROVERS.load(deps.storage)?.approvers[0]),
+            QueryExt::Approvers {} => to_json_binary(&APPROVERS.load(deps.storage)?),
+            QueryExt::Approvals { id } => {
+                to_json_binary(&approval::query_approvals(deps.storage, id)?)
+            }
             QueryExt::IsPending { id } => {
-                let pending = PENDING_PROPOSALS.may_load(deps.storage, id)?.is_some();
+                let pending = PROPOSALS.pending.may_load(deps.storage, id)?.is_some();
                 // Force load completed proposal if not pending, throwing error
                 // if not found.
                 if !pending {
-                    COMPLETED_PROPOSALS.load(deps.storage, id)?;
+                    PROPOSALS.completed.load(deps.storage, id)?;
                 }
 
                 to_json_binary(&pending)
             }
             QueryExt::Proposal { id } => {
-                if let Some(pending) = PENDING_PROPOSALS.may_load(deps.storage, id)? {
+                if let Some(pending) = PROPOSALS.pending.may_load(deps.storage, id)? {
                     to_json_binary(&pending)
                 } else {
                     // Force load completed proposal if not pending, throwing
                     // error if not found.
-                    to_json_binary(&COMPLETED_PROPOSALS.load(deps.storage, id)?)
+                    to_json_binary(&PROPOSALS.completed.load(deps.storage, id)?)
                 }
             }
             QueryExt::PendingProposal { id } => {
-                to_json_binary(&PENDING_PROPOSALS.load(deps.storage, id)?)
+                to_json_binary(&PROPOSALS.pending.load(deps.storage, id)?)
             }
             QueryExt::PendingProposals { start_after, limit } => {
                 to_json_binary(&paginate_map_values(
                     deps,
-                    &PENDING_PROPOSALS,
+                    &PROPOSALS.pending,
                     start_after,
                     limit,
                     Order::Ascending,
@@ -369,18 +433,18 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 limit,
             } => to_json_binary(&paginate_map_values(
                 deps,
-                &PENDING_PROPOSALS,
+                &PROPOSALS.pending,
                 start_before,
                 limit,
                 Order::Descending,
             )?),
             QueryExt::CompletedProposal { id } => {
-                to_json_binary(&COMPLETED_PROPOSALS.load(deps.storage, id)?)
+                to_json_binary(&PROPOSALS.completed.load(deps.storage, id)?)
             }
             QueryExt::CompletedProposals { start_after, limit } => {
                 to_json_binary(&paginate_map_values(
                     deps,
-                    &COMPLETED_PROPOSALS,
+                    &PROPOSALS.completed,
                     start_after,
                     limit,
                     Order::Ascending,
@@ -391,13 +455,13 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 limit,
             } => to_json_binary(&paginate_map_values(
                 deps,
-                &COMPLETED_PROPOSALS,
+                &PROPOSALS.completed,
                 start_before,
                 limit,
                 Order::Descending,
             )?),
             QueryExt::CompletedProposalIdForCreatedProposalId { id } => {
-                to_json_binary(&CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL.may_load(deps.storage, id)?)
+                to_json_binary(&PROPOSALS.created_to_completed.may_load(deps.storage, id)?)
             }
         },
         _ => PrePropose::default().query(deps, env, msg),
@@ -406,7 +470,32 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, PreProposeError> {
-    let res = PrePropose::default().migrate(deps.branch(), msg);
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    res
+    match msg {
+        MigrateMsg::Extension {
+            msg: MigrateExt::FromV250 {},
+        } => {
+            let required = "2.5.0";
+            let ContractVersion { version, .. } = get_contract_version(deps.storage)?;
+            if version != required {
+                return Err(PreProposeError::CannotMigrateVersion {
+                    required: required.to_string(),
+                    actual: version,
+                });
+            }
+
+            migrate_legacy_approver(deps.storage)?;
+            set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+            Ok(Response::default()
+                .add_attribute("action", "migrate")
+                .add_attribute("from", version)
+                .add_attribute("to", CONTRACT_VERSION))
+        }
+        MigrateMsg::FromUnderV250 { .. } => {
+            let res = PrePropose::default().migrate(deps.branch(), msg)?;
+            migrate_legacy_approver(deps.storage)?;
+            set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+            Ok(res)
+        }
+    }
 }
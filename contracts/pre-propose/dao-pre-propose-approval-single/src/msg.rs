@@ -1,5 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{CosmosMsg, Empty};
+use cw_utils::Duration;
 use dao_pre_propose_base::msg::{
     ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, MigrateMsg as MigrateBase,
     QueryMsg as QueryBase,
@@ -27,25 +28,83 @@ pub enum ProposeMessage {
 
 #[cw_serde]
 pub struct InstantiateExt {
-    pub approver: String,
+    /// The addresses that may approve or reject proposals.
+    pub approvers: Vec<String>,
+    /// The number of approvers that must approve a proposal before
+    /// it is created. Between one and the number of approvers.
+    pub threshold: u64,
+    /// How long proposals may remain pending before they are
+    /// rejected. If `None`, pending proposals never expire.
+    pub approval_period: Option<Duration>,
 }
 
 #[cw_serde]
 pub enum ExecuteExt {
-    /// Approve a proposal, only callable by approver
+    /// Approve a proposal, only callable by an approver. The
+    /// proposal is created once `threshold` approvers have approved
+    /// it.
     Approve { id: u64 },
-    /// Reject a proposal, only callable by approver
-    Reject { id: u64 },
-    /// Updates the approver, can only be called the current approver
+    /// Reject a proposal, only callable by an approver. The proposal
+    /// is rejected once so many approvers have rejected it that
+    /// `threshold` may no longer be reached.
+    ///
+    /// Proposals are only decided when an approver approves or
+    /// rejects them, so proposals that meet a threshold lowered by
+    /// `UpdateApprovers` remain pending until the next decision. An
+    /// approver that has already decided may repeat their decision
+    /// to have the proposal re-evaluated.
+    Reject { id: u64, reason: Option<String> },
+    /// Replaces the title, description and messages of a pending
+    /// proposal, only callable by an approver. Approvals and
+    /// rejections of the previous version are cleared.
+    Amend {
+        id: u64,
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+    /// Asks the proposer to revise a pending proposal, only callable
+    /// by an approver. The proposal may not be approved until the
+    /// proposer resubmits it.
+    RequestChanges { id: u64, comment: Option<String> },
+    /// Resubmits a proposal that changes were requested for, only
+    /// callable by its proposer. The deposit paid when the proposal
+    /// was first submitted is kept, and approvals and rejections of
+    /// the previous version are cleared.
+    Resubmit {
+        id: u64,
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+    /// Rejects a pending proposal whose approval period has ended.
+    /// Callable by anyone.
+    ExpirePending { id: u64 },
+    /// Replaces the sender in the approver set with ADDRESS, can
+    /// only be called by a current approver.
     UpdateApprover { address: String },
+    /// Replaces the approver set, threshold and approval period,
+    /// can only be called by the DAO.
+    UpdateApprovers {
+        approvers: Vec<String>,
+        threshold: u64,
+        approval_period: Option<Duration>,
+    },
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryExt {
-    /// List the approver address
+    /// The first approver in the approver set. Deprecated, use
+    /// `Approvers` instead.
     #[returns(cosmwasm_std::Addr)]
     Approver {},
+    /// The approvers, threshold and approval period.
+    #[returns(crate::state::Approvers)]
+    Approvers {},
+    /// The decisions approvers have made on a proposal.
+    #[returns(Vec<crate::state::Approval>)]
+    Approvals { id: u64 },
     /// Return whether or not the proposal is pending
     #[returns(bool)]
     IsPending { id: u64 },
@@ -85,10 +144,17 @@ pub enum QueryExt {
     CompletedProposalIdForCreatedProposalId { id: u64 },
 }
 
+#[cw_serde]
+pub enum MigrateExt {
+    /// Migrates a v2.5.0 module, which has a single approver, to an
+    /// approver set of one with a threshold of one.
+    FromV250 {},
+}
+
 pub type InstantiateMsg = InstantiateBase<InstantiateExt>;
 pub type ExecuteMsg = ExecuteBase<ProposeMessage, ExecuteExt>;
 pub type QueryMsg = QueryBase<QueryExt>;
-pub type MigrateMsg = MigrateBase<Empty>;
+pub type MigrateMsg = MigrateBase<MigrateExt>;
 
 /// Internal version of the propose message that includes the
 /// `proposer` field. The module will fill this in based on the sender
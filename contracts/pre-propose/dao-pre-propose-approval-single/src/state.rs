@@ -1,10 +1,15 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, StdResult, Storage};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{to_json_binary, Addr, Binary, BlockInfo, CosmosMsg, StdResult, Storage};
+use cw_storage_plus::Item;
+use cw_utils::Expiration;
 
+pub use dao_pre_propose_base::approval::{Approval, Approvers, Decision};
+use dao_pre_propose_base::approval::{ApprovalProposal, ApprovalProposals, APPROVERS};
 use dao_voting::deposit::CheckedDepositInfo;
 use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
 
+use crate::msg::ProposeMessageInternal;
+
 #[cw_serde]
 pub enum ProposalStatus {
     /// The proposal is pending approval.
@@ -14,8 +19,19 @@ pub enum ProposalStatus {
         /// The created proposal ID.
         created_proposal_id: u64,
     },
+    /// An approver has asked the proposer to revise the proposal.
+    /// The proposal remains pending and keeps its deposit until the
+    /// proposer resubmits it.
+    ChangesRequested {
+        /// What the approver would like changed.
+        comment: Option<String>,
+    },
     /// The proposal has been rejected.
-    Rejected {},
+    Rejected {
+        /// The reason given by the approver whose rejection decided
+        /// the proposal, if any.
+        reason: Option<String>,
+    },
 }
 
 #[cw_serde]
@@ -32,13 +48,91 @@ pub struct Proposal {
     /// Snapshot of the deposit info at the time of proposal
     /// submission.
     pub deposit: Option<CheckedDepositInfo>,
+    /// When the proposal stops accepting approvals and may be
+    /// rejected as expired.
+    #[serde(default)]
+    pub expiration: Expiration,
+    /// Earlier versions of the proposal, oldest first.
+    #[serde(default)]
+    pub revisions: Vec<Revision>,
+}
+
+/// A change made to a pending proposal by an approver's amendment or
+/// the proposer's resubmission.
+#[cw_serde]
+pub struct Revision {
+    /// The address that made the change.
+    pub editor: Addr,
+    /// The block height the change was made at.
+    pub height: u64,
+    /// The propose message before the change.
+    pub previous: ProposeMsg,
+}
+
+impl Proposal {
+    /// Replaces the proposal's title, description and messages,
+    /// recording the previous version, and returns it to pending.
+    pub(crate) fn revise(
+        &mut self,
+        block: &BlockInfo,
+        editor: Addr,
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg>,
+    ) {
+        self.revisions.push(Revision {
+            editor,
+            height: block.height,
+            previous: self.msg.clone(),
+        });
+        self.msg.title = title;
+        self.msg.description = description;
+        self.msg.msgs = msgs;
+        self.status = ProposalStatus::Pending {};
+    }
 }
 
-pub const APPROVER: Item<Addr> = Item::new("approver");
-pub const PENDING_PROPOSALS: Map<u64, Proposal> = Map::new("pending_proposals");
-pub const COMPLETED_PROPOSALS: Map<u64, Proposal> = Map::new("completed_proposals");
-pub const CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL: Map<u64, u64> =
-    Map::new("created_to_completed_proposal");
+impl ApprovalProposal for Proposal {
+    fn approval_id(&self) -> u64 {
+        self.approval_id
+    }
+
+    fn proposer(&self) -> &Addr {
+        &self.proposer
+    }
+
+    fn deposit(&self) -> Option<&CheckedDepositInfo> {
+        self.deposit.as_ref()
+    }
+
+    fn expiration(&self) -> &Expiration {
+        &self.expiration
+    }
+
+    fn propose_msg(&self) -> StdResult<Binary> {
+        to_json_binary(&ProposeMessageInternal::Propose(self.msg.clone()))
+    }
+
+    fn set_approved(&mut self, created_proposal_id: u64) {
+        self.status = ProposalStatus::Approved {
+            created_proposal_id,
+        };
+    }
+
+    fn set_rejected(&mut self, reason: Option<String>) {
+        self.status = ProposalStatus::Rejected { reason };
+    }
+}
+
+pub const PROPOSALS: ApprovalProposals<Proposal> = ApprovalProposals::new(
+    "pending_proposals",
+    "completed_proposals",
+    "created_to_completed_proposal",
+);
+
+/// The single approver of versions of this module before approver
+/// sets were supported.
+const LEGACY_APPROVER: Item<Addr> = Item::new("approver");
 
 /// Used internally to track the current approval_id.
 const CURRENT_ID: Item<u64> = Item::new("current_id");
@@ -48,3 +142,20 @@ pub(crate) fn advance_approval_id(store: &mut dyn Storage) -> StdResult<u64> {
     CURRENT_ID.save(store, &id)?;
     Ok(id)
 }
+
+/// Moves the approver of an older version of this module into an
+/// approver set of one.
+pub(crate) fn migrate_legacy_approver(store: &mut dyn Storage) -> StdResult<()> {
+    if let Some(approver) = LEGACY_APPROVER.may_load(store)? {
+        APPROVERS.save(
+            store,
+            &Approvers {
+                approvers: vec![approver],
+                threshold: 1,
+                approval_period: None,
+            },
+        )?;
+        LEGACY_APPROVER.remove(store);
+    }
+    Ok(())
+}
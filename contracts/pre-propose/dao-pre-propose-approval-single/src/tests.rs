@@ -1,13 +1,20 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env};
 use cosmwasm_std::{coins, from_json, to_json_binary, Addr, Coin, CosmosMsg, Uint128, WasmMsg};
 use cw2::ContractVersion;
 use cw20::Cw20Coin;
 use cw_denom::UncheckedDenom;
 use cw_multi_test::{App, BankSudo, Executor};
-use cw_utils::Duration;
+use cw_storage_plus::Item;
+use cw_utils::{Duration, Expiration};
 use dao_interface::proposal::InfoResponse;
 use dao_interface::state::ProposalModule;
 use dao_interface::state::{Admin, ModuleInstantiateInfo};
-use dao_pre_propose_base::{error::PreProposeError, msg::DepositInfoResponse, state::Config};
+use dao_pre_propose_base::{
+    approval::{Approvers, APPROVERS},
+    error::PreProposeError,
+    msg::DepositInfoResponse,
+    state::Config,
+};
 use dao_proposal_single::query::ProposalResponse;
 use dao_testing::{
     contracts::{
@@ -22,7 +29,10 @@ use dao_testing::{
 };
 use dao_voting::pre_propose::{PreProposeSubmissionPolicy, PreProposeSubmissionPolicyError};
 use dao_voting::{
-    deposit::{CheckedDepositInfo, DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    deposit::{
+        CheckedDepositInfo, DepositError, DepositRefundPolicy, DepositToken,
+        UncheckedDepositAlternative, UncheckedDepositInfo,
+    },
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     status::Status,
     threshold::{PercentageThreshold, Threshold},
@@ -70,8 +80,14 @@ fn get_default_proposal_module_instantiate(
                 msg: to_json_binary(&InstantiateMsg {
                     deposit_info,
                     submission_policy,
+                    sponsorship: None,
+                    forfeiture: None,
+                    rate_limit: None,
+                    conditions: None,
                     extension: InstantiateExt {
-                        approver: "approver".to_string(),
+                        approvers: vec!["approver".to_string()],
+                        threshold: 1,
+                        approval_period: None,
                     },
                 })
                 .unwrap(),
@@ -440,7 +456,10 @@ fn reject_proposal(app: &mut App, module: Addr, sender: &str, proposal_id: u64)
         Addr::unchecked(sender),
         module,
         &ExecuteMsg::Extension {
-            msg: ExecuteExt::Reject { id: proposal_id },
+            msg: ExecuteExt::Reject {
+                id: proposal_id,
+                reason: None,
+            },
         },
         &[],
     )
@@ -482,6 +501,8 @@ fn test_native_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -556,6 +577,8 @@ fn test_cw20_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -809,6 +832,8 @@ fn test_multiple_open_proposals() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -896,6 +921,8 @@ fn test_pending_proposal_queries() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -979,6 +1006,8 @@ fn test_completed_proposal_queries() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -1067,7 +1096,7 @@ fn test_completed_proposal_queries() {
             },
         )
         .unwrap();
-    assert_eq!(prop2.status, ProposalStatus::Rejected {});
+    assert_eq!(prop2.status, ProposalStatus::Rejected { reason: None });
 
     // Query for the pre-propose proposals
     let pre_propose_props: Vec<Proposal> = app
@@ -1121,6 +1150,8 @@ fn test_set_version() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -1157,6 +1188,8 @@ fn test_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false, // no open proposal submission.
     );
@@ -1216,6 +1249,8 @@ fn test_approval_and_rejection_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         true, // yes, open proposal submission.
     );
@@ -1250,7 +1285,10 @@ fn test_approval_and_rejection_permissions() {
             Addr::unchecked("nonmember"),
             pre_propose,
             &ExecuteMsg::Extension {
-                msg: ExecuteExt::Reject { id: pre_propose_id },
+                msg: ExecuteExt::Reject {
+                    id: pre_propose_id,
+                    reason: None,
+                },
             },
             &[],
         )
@@ -1275,6 +1313,8 @@ fn test_propose_open_proposal_submission() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         true, // yes, open proposal submission.
     );
@@ -1595,14 +1635,22 @@ fn test_instantiate_with_zero_native_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            formula: None,
+                            alternatives: vec![],
                         }),
                         submission_policy: PreProposeSubmissionPolicy::Specific {
                             dao_members: true,
                             allowlist: vec![],
                             denylist: vec![],
                         },
+                        sponsorship: None,
+                        forfeiture: None,
+                        rate_limit: None,
+                        conditions: None,
                         extension: InstantiateExt {
-                            approver: "approver".to_string(),
+                            approvers: vec!["approver".to_string()],
+                            threshold: 1,
+                            approval_period: None,
                         },
                     })
                     .unwrap(),
@@ -1664,14 +1712,22 @@ fn test_instantiate_with_zero_cw20_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            formula: None,
+                            alternatives: vec![],
                         }),
                         submission_policy: PreProposeSubmissionPolicy::Specific {
                             dao_members: true,
                             allowlist: vec![],
                             denylist: vec![],
                         },
+                        sponsorship: None,
+                        forfeiture: None,
+                        rate_limit: None,
+                        conditions: None,
                         extension: InstantiateExt {
-                            approver: "approver".to_string(),
+                            approvers: vec!["approver".to_string()],
+                            threshold: 1,
+                            approval_period: None,
                         },
                     })
                     .unwrap(),
@@ -1721,7 +1777,11 @@ fn test_update_config() {
                 dao_members: true,
                 allowlist: vec![],
                 denylist: vec![]
-            }
+            },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1740,6 +1800,8 @@ fn test_update_config() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Never,
+            formula: None,
+            alternatives: vec![],
         }),
         PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
     );
@@ -1751,9 +1813,16 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                formula: None,
+                alternatives: vec![],
+                escrowed_nft: None,
             }),
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1787,7 +1856,10 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                formula: None,
+                alternatives: vec![],
+                escrowed_nft: None,
             }),
             proposer: Addr::unchecked("ekez"),
         }
@@ -1849,6 +1921,71 @@ fn test_update_config() {
     );
 }
 
+#[test]
+fn test_update_config_deposit_alternatives() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single: _,
+        pre_propose,
+    } = setup_default_test(&mut app, None, false);
+
+    let deposit_with = |alternative: UncheckedDepositAlternative| UncheckedDepositInfo {
+        denom: DepositToken::Token {
+            denom: UncheckedDenom::Native("ujuno".to_string()),
+        },
+        amount: Uint128::new(10),
+        refund_policy: DepositRefundPolicy::Always,
+        formula: None,
+        alternatives: vec![alternative],
+    };
+    let policy = PreProposeSubmissionPolicy::Anyone { denylist: vec![] };
+
+    // NFT and cw20 alternatives can not be collected as this module
+    // does not receive tokens.
+    let err = update_config_should_fail(
+        &mut app,
+        pre_propose.clone(),
+        core_addr.as_str(),
+        Some(deposit_with(UncheckedDepositAlternative::Nft {
+            collection: "collection".to_string(),
+        })),
+        policy.clone(),
+    );
+    assert_eq!(
+        err,
+        PreProposeError::Deposit(DepositError::UnsupportedAlternative {})
+    );
+
+    let err = update_config_should_fail(
+        &mut app,
+        pre_propose.clone(),
+        core_addr.as_str(),
+        Some(deposit_with(UncheckedDepositAlternative::Token {
+            denom: UncheckedDenom::Cw20("token".to_string()),
+            amount: Uint128::new(5),
+        })),
+        policy.clone(),
+    );
+    assert_eq!(
+        err,
+        PreProposeError::Deposit(DepositError::UnsupportedAlternative {})
+    );
+
+    // Native alternatives are paid along with the propose message.
+    let config = update_config(
+        &mut app,
+        pre_propose,
+        core_addr.as_str(),
+        Some(deposit_with(UncheckedDepositAlternative::Token {
+            denom: UncheckedDenom::Native("uatom".to_string()),
+            amount: Uint128::new(5),
+        })),
+        policy,
+    );
+    assert_eq!(config.deposit_info.unwrap().alternatives.len(), 1);
+}
+
 #[test]
 fn test_update_submission_policy() {
     let mut app = App::default();
@@ -1864,6 +2001,10 @@ fn test_update_submission_policy() {
         Config {
             deposit_info: None,
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1909,6 +2050,10 @@ fn test_update_submission_policy() {
             submission_policy: PreProposeSubmissionPolicy::Anyone {
                 denylist: vec![Addr::unchecked("ekez")],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1935,6 +2080,10 @@ fn test_update_submission_policy() {
             submission_policy: PreProposeSubmissionPolicy::Anyone {
                 denylist: vec![Addr::unchecked("else"), Addr::unchecked("someone")],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1959,6 +2108,10 @@ fn test_update_submission_policy() {
         Config {
             deposit_info: None,
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -2056,6 +2209,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -2084,6 +2241,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![Addr::unchecked("ekez")],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -2112,6 +2273,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![Addr::unchecked("else"), Addr::unchecked("someone")],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -2140,6 +2305,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![]
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -2168,6 +2337,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![Addr::unchecked("ekez")],
                 denylist: vec![],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -2196,6 +2369,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![Addr::unchecked("else"), Addr::unchecked("someone")],
                 denylist: vec![],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -2224,6 +2401,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![]
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -2274,6 +2455,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![Addr::unchecked("ekez")],
                 denylist: vec![]
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -2342,6 +2527,8 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -2393,6 +2580,8 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -2746,7 +2935,11 @@ fn test_migrate_from_v241() {
                 dao_members: true,
                 allowlist: vec![],
                 denylist: vec![]
-            }
+            },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         },
         config
     );
@@ -3079,7 +3272,11 @@ fn test_migrate_from_v241_with_policy_update() {
                 dao_members: false,
                 allowlist: vec![Addr::unchecked("noob")],
                 denylist: vec![]
-            }
+            },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         },
         config
     );
@@ -3158,3 +3355,851 @@ fn test_migrate_from_v241_with_policy_update() {
         .unwrap();
     assert_eq!(proposal.proposal.status, Status::Executed);
 }
+
+fn update_approvers(
+    app: &mut App,
+    module: Addr,
+    sender: &Addr,
+    approvers: &[&str],
+    threshold: u64,
+    approval_period: Option<Duration>,
+) -> Result<(), PreProposeError> {
+    app.execute_contract(
+        sender.clone(),
+        module,
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::UpdateApprovers {
+                approvers: approvers.iter().map(|a| a.to_string()).collect(),
+                threshold,
+                approval_period,
+            },
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn decide(
+    app: &mut App,
+    module: Addr,
+    sender: &str,
+    msg: ExecuteExt,
+) -> Result<(), PreProposeError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        module,
+        &ExecuteMsg::Extension { msg },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn query_pre_proposal(app: &App, module: Addr, id: u64) -> Proposal {
+    app.wrap()
+        .query_wasm_smart(
+            module,
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Proposal { id },
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_threshold_approval() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app, None, true);
+    update_approvers(
+        &mut app,
+        pre_propose.clone(),
+        &core_addr,
+        &["a", "b", "c"],
+        2,
+        None,
+    )
+    .unwrap();
+
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+
+    // The previous approver is no longer in the set.
+    let err = decide(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap_err();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+
+    // One approval is not enough.
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "a",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).status,
+        ProposalStatus::Pending {}
+    );
+    let err = decide(
+        &mut app,
+        pre_propose.clone(),
+        "a",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap_err();
+    assert_eq!(err, PreProposeError::AlreadyDecided {});
+
+    // A rejection does not decide the proposal while the threshold
+    // may still be reached.
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "c",
+        ExecuteExt::Reject {
+            id,
+            reason: Some("not yet".to_string()),
+        },
+    )
+    .unwrap();
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "b",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).status,
+        ProposalStatus::Approved {
+            created_proposal_id: 1
+        }
+    );
+    let created: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &dao_proposal_single::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(created.proposal.proposer, Addr::unchecked("ekez"));
+
+    let approvals: Vec<crate::state::Approval> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose,
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Approvals { id },
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        approvals,
+        vec![
+            crate::state::Approval {
+                approver: Addr::unchecked("a"),
+                decision: crate::state::Decision::Approved {},
+            },
+            crate::state::Approval {
+                approver: Addr::unchecked("b"),
+                decision: crate::state::Decision::Approved {},
+            },
+            crate::state::Approval {
+                approver: Addr::unchecked("c"),
+                decision: crate::state::Decision::Rejected {
+                    reason: Some("not yet".to_string())
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_threshold_rejection() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single: _,
+        pre_propose,
+    } = setup_default_test(&mut app, None, true);
+    update_approvers(
+        &mut app,
+        pre_propose.clone(),
+        &core_addr,
+        &["a", "b", "c"],
+        2,
+        None,
+    )
+    .unwrap();
+
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "a",
+        ExecuteExt::Reject {
+            id,
+            reason: Some("too vague".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).status,
+        ProposalStatus::Pending {}
+    );
+
+    // With two of three rejecting, two approvals are no longer
+    // possible. The deciding rejection's reason is recorded.
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "b",
+        ExecuteExt::Reject {
+            id,
+            reason: Some("spam".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).status,
+        ProposalStatus::Rejected {
+            reason: Some("spam".to_string())
+        }
+    );
+
+    let err = decide(&mut app, pre_propose, "c", ExecuteExt::Approve { id }).unwrap_err();
+    assert_eq!(err, PreProposeError::ProposalNotFound {});
+}
+
+#[test]
+fn test_approval_expiry() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single: _,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
+        }),
+        true,
+    );
+    update_approvers(
+        &mut app,
+        pre_propose.clone(),
+        &core_addr,
+        &["approver"],
+        1,
+        Some(Duration::Height(10)),
+    )
+    .unwrap();
+
+    mint_natives(&mut app, "ekez", coins(20, "ujuno"));
+    let first = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+    let second = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+
+    let err = decide(
+        &mut app,
+        pre_propose.clone(),
+        "someone",
+        ExecuteExt::ExpirePending { id: first },
+    )
+    .unwrap_err();
+    assert_eq!(err, PreProposeError::ApprovalNotExpired { id: first });
+
+    app.update_block(|b| b.height += 10);
+
+    // Anyone may reject an expired proposal.
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "someone",
+        ExecuteExt::ExpirePending { id: first },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), first).status,
+        ProposalStatus::Rejected {
+            reason: Some("approval period expired".to_string())
+        }
+    );
+
+    // Approving an expired proposal rejects it instead.
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Approve { id: second },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose, second).status,
+        ProposalStatus::Rejected {
+            reason: Some("approval period expired".to_string())
+        }
+    );
+
+    // Deposits are always refunded under this policy.
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(20));
+}
+
+#[test]
+fn test_update_approvers() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single: _,
+        pre_propose,
+    } = setup_default_test(&mut app, None, true);
+
+    let err = update_approvers(
+        &mut app,
+        pre_propose.clone(),
+        &Addr::unchecked("approver"),
+        &["a"],
+        1,
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(err, PreProposeError::NotDao {});
+
+    let err = update_approvers(
+        &mut app,
+        pre_propose.clone(),
+        &core_addr,
+        &["a", "b"],
+        3,
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::InvalidApprovers {
+            reason: "threshold must be between one and the number of approvers".to_string()
+        }
+    );
+    let err =
+        update_approvers(&mut app, pre_propose.clone(), &core_addr, &[], 0, None).unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::InvalidApprovers {
+            reason: "threshold must be between one and the number of approvers".to_string()
+        }
+    );
+    let err = update_approvers(
+        &mut app,
+        pre_propose.clone(),
+        &core_addr,
+        &["a", "a"],
+        1,
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::InvalidApprovers {
+            reason: "a is listed more than once".to_string()
+        }
+    );
+
+    update_approvers(
+        &mut app,
+        pre_propose.clone(),
+        &core_addr,
+        &["a", "b"],
+        2,
+        None,
+    )
+    .unwrap();
+
+    // Approvers may hand their place in the set to another address,
+    // but not to an existing approver.
+    let err = decide(
+        &mut app,
+        pre_propose.clone(),
+        "a",
+        ExecuteExt::UpdateApprover {
+            address: "b".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::InvalidApprovers {
+            reason: "b is already an approver".to_string()
+        }
+    );
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "a",
+        ExecuteExt::UpdateApprover {
+            address: "d".to_string(),
+        },
+    )
+    .unwrap();
+
+    let approvers: crate::state::Approvers = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose,
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Approvers {},
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        approvers,
+        crate::state::Approvers {
+            approvers: vec![Addr::unchecked("d"), Addr::unchecked("b")],
+            threshold: 2,
+            approval_period: None,
+        }
+    );
+}
+
+#[test]
+fn test_lower_threshold() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single: _,
+        pre_propose,
+    } = setup_default_test(&mut app, None, true);
+    update_approvers(
+        &mut app,
+        pre_propose.clone(),
+        &core_addr,
+        &["a", "b", "c"],
+        3,
+        None,
+    )
+    .unwrap();
+
+    // The deprecated query returns the first approver.
+    let approver: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Approver {},
+            },
+        )
+        .unwrap();
+    assert_eq!(approver, Addr::unchecked("a"));
+
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "a",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "b",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+
+    // Lowering the threshold does not decide pending proposals.
+    update_approvers(
+        &mut app,
+        pre_propose.clone(),
+        &core_addr,
+        &["a", "b", "c"],
+        2,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).status,
+        ProposalStatus::Pending {}
+    );
+
+    // Repeating a decision re-evaluates the proposal, and the
+    // recorded decision is kept.
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "a",
+        ExecuteExt::Reject { id, reason: None },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose, id).status,
+        ProposalStatus::Approved {
+            created_proposal_id: 1
+        }
+    );
+}
+
+#[test]
+fn test_amend_pending_proposal() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app, None, true);
+    update_approvers(
+        &mut app,
+        pre_propose.clone(),
+        &core_addr,
+        &["a", "b"],
+        2,
+        None,
+    )
+    .unwrap();
+
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "a",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+
+    // Only approvers may amend proposals.
+    let amend = ExecuteExt::Amend {
+        id,
+        title: "amended title".to_string(),
+        description: "amended description".to_string(),
+        msgs: vec![],
+    };
+    let err = decide(&mut app, pre_propose.clone(), "ekez", amend.clone()).unwrap_err();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+    decide(&mut app, pre_propose.clone(), "b", amend).unwrap();
+
+    let proposal = query_pre_proposal(&app, pre_propose.clone(), id);
+    assert_eq!(proposal.status, ProposalStatus::Pending {});
+    assert_eq!(proposal.msg.title, "amended title");
+    assert_eq!(proposal.msg.description, "amended description");
+    assert_eq!(proposal.revisions.len(), 1);
+    assert_eq!(proposal.revisions[0].editor, Addr::unchecked("b"));
+    assert_eq!(proposal.revisions[0].previous.title, "title");
+    assert_eq!(proposal.revisions[0].previous.description, "description");
+
+    // The approval of the previous version was cleared, so both
+    // approvers must approve the amended proposal.
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "a",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).status,
+        ProposalStatus::Pending {}
+    );
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "b",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+
+    let created: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &dao_proposal_single::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(created.proposal.title, "amended title");
+    assert_eq!(created.proposal.proposer, Addr::unchecked("ekez"));
+
+    // Completed proposals may not be amended.
+    let err = decide(
+        &mut app,
+        pre_propose,
+        "a",
+        ExecuteExt::Amend {
+            id,
+            title: "again".to_string(),
+            description: "again".to_string(),
+            msgs: vec![],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, PreProposeError::ProposalNotFound {});
+}
+
+#[test]
+fn test_request_changes_and_resubmit() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr: _,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
+        }),
+        true,
+    );
+
+    mint_natives(&mut app, "ekez", coins(10, "ujuno"));
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+
+    // Changes may only be resubmitted once requested.
+    let resubmit = ExecuteExt::Resubmit {
+        id,
+        title: "revised title".to_string(),
+        description: "revised description".to_string(),
+        msgs: vec![],
+    };
+    let err = decide(&mut app, pre_propose.clone(), "ekez", resubmit.clone()).unwrap_err();
+    assert_eq!(err, PreProposeError::ChangesNotRequested { id });
+
+    let err = decide(
+        &mut app,
+        pre_propose.clone(),
+        "ekez",
+        ExecuteExt::RequestChanges { id, comment: None },
+    )
+    .unwrap_err();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::RequestChanges {
+            id,
+            comment: Some("needs a budget".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).status,
+        ProposalStatus::ChangesRequested {
+            comment: Some("needs a budget".to_string())
+        }
+    );
+
+    // The proposal may not be approved until it is resubmitted.
+    let err = decide(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap_err();
+    assert_eq!(err, PreProposeError::AwaitingChanges { id });
+
+    // Only the proposer may resubmit.
+    let err = decide(&mut app, pre_propose.clone(), "approver", resubmit.clone()).unwrap_err();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+    decide(&mut app, pre_propose.clone(), "ekez", resubmit).unwrap();
+
+    let proposal = query_pre_proposal(&app, pre_propose.clone(), id);
+    assert_eq!(proposal.status, ProposalStatus::Pending {});
+    assert_eq!(proposal.msg.title, "revised title");
+    assert_eq!(proposal.revisions.len(), 1);
+    assert_eq!(proposal.revisions[0].editor, Addr::unchecked("ekez"));
+    assert_eq!(proposal.revisions[0].previous.title, "title");
+
+    // The original deposit is kept with the resubmitted proposal.
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::zero());
+    decide(
+        &mut app,
+        pre_propose,
+        "approver",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+    let created: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &dao_proposal_single::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(created.proposal.title, "revised title");
+}
+
+#[test]
+fn test_revision_restarts_approval_period() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single: _,
+        pre_propose,
+    } = setup_default_test(&mut app, None, true);
+    update_approvers(
+        &mut app,
+        pre_propose.clone(),
+        &core_addr,
+        &["approver"],
+        1,
+        Some(Duration::Height(10)),
+    )
+    .unwrap();
+
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    let height = app.block_info().height;
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).expiration,
+        Expiration::AtHeight(height + 10)
+    );
+
+    // Requesting changes gives the proposer a full approval period
+    // to resubmit.
+    app.update_block(|b| b.height += 6);
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::RequestChanges { id, comment: None },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).expiration,
+        Expiration::AtHeight(height + 16)
+    );
+
+    // Resubmitting restarts the approval period.
+    app.update_block(|b| b.height += 6);
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "ekez",
+        ExecuteExt::Resubmit {
+            id,
+            title: "revised title".to_string(),
+            description: "revised description".to_string(),
+            msgs: vec![],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).expiration,
+        Expiration::AtHeight(height + 22)
+    );
+
+    // As does amending.
+    app.update_block(|b| b.height += 6);
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Amend {
+            id,
+            title: "amended title".to_string(),
+            description: "amended description".to_string(),
+            msgs: vec![],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose.clone(), id).expiration,
+        Expiration::AtHeight(height + 28)
+    );
+
+    app.update_block(|b| b.height += 6);
+    decide(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+    assert_eq!(
+        query_pre_proposal(&app, pre_propose, id).status,
+        ProposalStatus::Approved {
+            created_proposal_id: 1
+        }
+    );
+}
+
+#[test]
+fn test_migrate_from_v250() {
+    let mut deps = mock_dependencies();
+
+    // A v2.5.0 module stores its single approver under "approver".
+    cw2::set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "2.5.0").unwrap();
+    Item::<Addr>::new("approver")
+        .save(deps.as_mut().storage, &Addr::unchecked("approver"))
+        .unwrap();
+
+    // v2.5.0 modules can't be migrated as older modules.
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg::FromUnderV250 { policy: None },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::CannotMigrateVersion {
+            required: ">=2.4.1, <2.5.0".to_string(),
+            actual: "2.5.0".to_string(),
+        }
+    );
+
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg::Extension {
+            msg: MigrateExt::FromV250 {},
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        APPROVERS.load(deps.as_ref().storage).unwrap(),
+        Approvers {
+            approvers: vec![Addr::unchecked("approver")],
+            threshold: 1,
+            approval_period: None,
+        }
+    );
+    assert!(Item::<Addr>::new("approver")
+        .may_load(deps.as_ref().storage)
+        .unwrap()
+        .is_none());
+
+    // Other versions can't be migrated as v2.5.0 modules.
+    cw2::set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "2.4.1").unwrap();
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg::Extension {
+            msg: MigrateExt::FromV250 {},
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::CannotMigrateVersion {
+            required: "2.5.0".to_string(),
+            actual: "2.4.1".to_string(),
+        }
+    );
+}
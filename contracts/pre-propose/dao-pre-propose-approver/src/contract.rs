@@ -6,7 +6,7 @@ use cosmwasm_std::{
 };
 use cw2::set_contract_version;
 
-use dao_interface::state::ModuleInstantiateCallback;
+use dao_interface::{condition::CanProposeResponse, state::ModuleInstantiateCallback};
 use dao_pre_propose_approval_single::msg::{
     ApproverProposeMessage, ExecuteExt as ApprovalExt, ExecuteMsg as PreProposeApprovalExecuteMsg,
 };
@@ -44,6 +44,10 @@ pub fn instantiate(
             allowlist: vec![],
             denylist: vec![],
         },
+        sponsorship: None,
+        forfeiture: None,
+        rate_limit: None,
+        conditions: None,
         extension: Empty {},
     };
     // Default pre-propose-base instantiation
@@ -101,6 +105,14 @@ pub fn execute(
         // Override config updates since they don't apply.
         ExecuteMsg::UpdateConfig { .. } => Err(PreProposeError::Unsupported {}),
         ExecuteMsg::UpdateSubmissionPolicy { .. } => Err(PreProposeError::Unsupported {}),
+        ExecuteMsg::UpdateSponsorship { .. }
+        | ExecuteMsg::UpdateForfeiture { .. }
+        | ExecuteMsg::UpdateRateLimit { .. }
+        | ExecuteMsg::UpdateConditions { .. }
+        | ExecuteMsg::Sponsor { .. }
+        | ExecuteMsg::ExpireUnsponsored { .. } => Err(PreProposeError::Unsupported {}),
+        // Deposits paid with cw20 tokens or NFTs are not supported here.
+        ExecuteMsg::Receive(_) | ExecuteMsg::ReceiveNft(_) => Err(PreProposeError::Unsupported {}),
         _ => PrePropose::default().execute(deps, env, info, msg),
     }
 }
@@ -139,6 +151,14 @@ pub fn execute_propose(
         &proposal_module,
         &dao_interface::proposal::Query::NextProposalId {},
     )?;
+    // A pre-proposal that is revised while pending is proposed
+    // again. The approval proposal for the previous version no longer
+    // decides it.
+    if let Some(superseded) =
+        PRE_PROPOSE_ID_TO_PROPOSAL_ID.may_load(deps.storage, pre_propose_id)?
+    {
+        PROPOSAL_ID_TO_PRE_PROPOSE_ID.remove(deps.storage, superseded);
+    }
     PROPOSAL_ID_TO_PRE_PROPOSE_ID.save(deps.storage, proposal_id, &pre_propose_id)?;
     PRE_PROPOSE_ID_TO_PROPOSAL_ID.save(deps.storage, pre_propose_id, &proposal_id)?;
 
@@ -162,8 +182,15 @@ pub fn execute_proposal_completed(
         return Err(PreProposeError::NotModule {});
     }
 
-    // Get approval pre-propose id
-    let pre_propose_id = PROPOSAL_ID_TO_PRE_PROPOSE_ID.load(deps.storage, proposal_id)?;
+    // Get approval pre-propose id. Approval proposals for versions of
+    // a pre-proposal that have since been revised are ignored.
+    let Some(pre_propose_id) = PROPOSAL_ID_TO_PRE_PROPOSE_ID.may_load(deps.storage, proposal_id)?
+    else {
+        return Ok(Response::default()
+            .add_attribute("method", "execute_proposal_completed_hook")
+            .add_attribute("proposal", proposal_id.to_string())
+            .add_attribute("superseded", "true"));
+    };
 
     // Get approval contract address
     let approval_contract = PRE_PROPOSE_APPROVAL_CONTRACT.load(deps.storage)?;
@@ -173,7 +200,10 @@ pub fn execute_proposal_completed(
         Status::Closed => Some(WasmMsg::Execute {
             contract_addr: approval_contract.into_string(),
             msg: to_json_binary(&PreProposeApprovalExecuteMsg::Extension {
-                msg: ApprovalExt::Reject { id: pre_propose_id },
+                msg: ApprovalExt::Reject {
+                    id: pre_propose_id,
+                    reason: None,
+                },
             })?,
             funds: vec![],
         }),
@@ -242,6 +272,16 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             let can_propose = address == approval_contract;
             to_json_binary(&can_propose)
         }
+        QueryMsg::CanProposeWithReasons { address, .. } => {
+            let approval_contract = PRE_PROPOSE_APPROVAL_CONTRACT.load(deps.storage)?;
+            if address == approval_contract {
+                to_json_binary(&CanProposeResponse::allowed())
+            } else {
+                to_json_binary(&CanProposeResponse::denied(
+                    "only the approval contract may propose",
+                ))
+            }
+        }
         QueryMsg::QueryExtension { msg } => match msg {
             QueryExt::PreProposeApprovalContract {} => {
                 to_json_binary(&PRE_PROPOSE_APPROVAL_CONTRACT.load(deps.storage)?)
@@ -13,7 +13,7 @@ use dao_pre_propose_approval_single::{
     msg::{
         ExecuteExt, ExecuteMsg, InstantiateExt, InstantiateMsg, ProposeMessage, QueryExt, QueryMsg,
     },
-    state::Proposal,
+    state::{Approvers, Proposal},
 };
 use dao_pre_propose_base::{error::PreProposeError, msg::DepositInfoResponse, state::Config};
 use dao_proposal_single as dps;
@@ -71,8 +71,14 @@ fn get_proposal_module_approval_single_instantiate(
                 msg: to_json_binary(&InstantiateMsg {
                     deposit_info,
                     submission_policy,
+                    sponsorship: None,
+                    forfeiture: None,
+                    rate_limit: None,
+                    conditions: None,
                     extension: InstantiateExt {
-                        approver: APPROVER.to_string(),
+                        approvers: vec![APPROVER.to_string()],
+                        threshold: 1,
+                        approval_period: None,
                     },
                 })
                 .unwrap(),
@@ -587,6 +593,8 @@ fn test_native_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -695,6 +703,8 @@ fn test_cw20_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -987,6 +997,8 @@ fn test_multiple_open_proposals() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -1084,6 +1096,8 @@ fn test_set_version() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -1126,6 +1140,8 @@ fn test_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false, // no open proposal submission.
     );
@@ -1192,6 +1208,8 @@ fn test_approval_and_rejection_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         true, // yes, open proposal submission.
     );
@@ -1226,7 +1244,10 @@ fn test_approval_and_rejection_permissions() {
             Addr::unchecked("nonmember"),
             pre_propose,
             &ExecuteMsg::Extension {
-                msg: ExecuteExt::Reject { id: pre_propose_id },
+                msg: ExecuteExt::Reject {
+                    id: pre_propose_id,
+                    reason: None,
+                },
             },
             &[],
         )
@@ -1258,6 +1279,8 @@ fn test_propose_open_proposal_submission() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         true, // yes, open proposal submission.
     );
@@ -1327,7 +1350,11 @@ fn test_update_config() {
                 dao_members: true,
                 allowlist: vec![],
                 denylist: vec![]
-            }
+            },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1353,6 +1380,8 @@ fn test_update_config() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Never,
+            formula: None,
+            alternatives: vec![],
         }),
         PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
     );
@@ -1364,9 +1393,16 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                formula: None,
+                alternatives: vec![],
+                escrowed_nft: None,
             }),
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1402,7 +1438,10 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                formula: None,
+                alternatives: vec![],
+                escrowed_nft: None,
             }),
             proposer: Addr::unchecked("ekez"),
         }
@@ -1599,6 +1638,8 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -1652,6 +1693,8 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -1748,21 +1791,23 @@ fn test_reset_approver() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
 
     // Ensure approver is set to the pre_propose_approver
-    let approver: Addr = app
+    let approvers: Approvers = app
         .wrap()
         .query_wasm_smart(
             pre_propose.clone(),
             &QueryMsg::QueryExtension {
-                msg: QueryExt::Approver {},
+                msg: QueryExt::Approvers {},
             },
         )
         .unwrap();
-    assert_eq!(approver, pre_propose_approver);
+    assert_eq!(approvers.approvers, vec![pre_propose_approver.clone()]);
 
     // Fail to change approver by non-approver.
     let err: PreProposeError = app
@@ -1808,14 +1853,110 @@ fn test_reset_approver() {
     .unwrap();
 
     // Ensure approver is reset back to the approver DAO
-    let approver: Addr = app
+    let approvers: Approvers = app
         .wrap()
         .query_wasm_smart(
             pre_propose.clone(),
             &QueryMsg::QueryExtension {
-                msg: QueryExt::Approver {},
+                msg: QueryExt::Approvers {},
+            },
+        )
+        .unwrap();
+    assert_eq!(approvers.approvers, vec![approver_core_addr]);
+}
+
+#[test]
+fn test_revised_pre_proposal() {
+    let mut app = App::default();
+
+    // Need to instantiate this so contract addresses match with cw20 test cases
+    let _ = instantiate_cw20_base_default(&mut app);
+
+    let DefaultTestSetup {
+        core_addr: _,
+        proposal_single,
+        pre_propose,
+        approver_core_addr: _,
+        proposal_single_approver,
+        pre_propose_approver,
+    } = setup_default_test(&mut app, None, true);
+
+    let pre_propose_id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    let first = get_latest_proposal_id(&app, proposal_single_approver.clone());
+
+    // Resubmitting the pre-proposal creates a new approval proposal
+    // in the approver DAO.
+    app.execute_contract(
+        pre_propose_approver.clone(),
+        pre_propose.clone(),
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::RequestChanges {
+                id: pre_propose_id,
+                comment: None,
             },
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        pre_propose.clone(),
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::Resubmit {
+                id: pre_propose_id,
+                title: "revised title".to_string(),
+                description: "revised description".to_string(),
+                msgs: vec![],
+            },
+        },
+        &[],
+    )
+    .unwrap();
+    let second = get_latest_proposal_id(&app, proposal_single_approver.clone());
+    assert_eq!(second, first + 1);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single_approver.clone(),
+            &dps::msg::QueryMsg::Proposal {
+                proposal_id: second,
+            },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.title, "revised title");
+
+    let superseded: Option<u64> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose_approver.clone(),
+            &ApproverQueryMsg::QueryExtension {
+                msg: ApproverQueryExt::PreProposeApprovalIdForApproverProposalId { id: first },
+            },
+        )
+        .unwrap();
+    assert_eq!(superseded, None);
+
+    // Passing the approval proposal for the previous version does
+    // not approve the revised pre-proposal.
+    approve_proposal(&mut app, proposal_single_approver.clone(), "ekez", first);
+    let pending: bool = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::IsPending { id: pre_propose_id },
+            },
+        )
+        .unwrap();
+    assert!(pending);
+
+    approve_proposal(&mut app, proposal_single_approver, "ekez", second);
+    let created: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &dps::msg::QueryMsg::Proposal { proposal_id: 1 },
         )
         .unwrap();
-    assert_eq!(approver, approver_core_addr);
+    assert_eq!(created.proposal.title, "revised title");
 }
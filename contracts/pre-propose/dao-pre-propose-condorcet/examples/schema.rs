@@ -0,0 +1,11 @@
+use cosmwasm_schema::write_api;
+use dao_pre_propose_condorcet::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+
+fn main() {
+    write_api! {
+        instantiate: InstantiateMsg,
+        query: QueryMsg,
+        execute: ExecuteMsg,
+        migrate: MigrateMsg
+    }
+}
@@ -0,0 +1,331 @@
+use cosmwasm_std::{coins, to_json_binary, Addr, Coin, Decimal, Empty, Uint128};
+use cw_denom::UncheckedDenom;
+use cw_multi_test::{App, BankSudo, Executor};
+use cw_utils::Duration;
+use dao_interface::state::{Admin, ModuleInstantiateInfo, ProposalModule};
+use dao_proposal_condorcet::{
+    msg::{Choice, ProposeMsg},
+    proposal::{ProposalResponse, Status},
+};
+use dao_testing::{
+    contracts::{dao_pre_propose_condorcet_contract, dao_proposal_condorcet_contract},
+    helpers::instantiate_with_cw4_groups_governance,
+};
+use dao_voting::{
+    deposit::{DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    pre_propose::{PreProposeInfo, PreProposeSubmissionPolicy, ProposalCreationPolicy},
+    threshold::PercentageThreshold,
+};
+
+use crate::contract::*;
+
+struct DefaultTestSetup {
+    core_addr: Addr,
+    condorcet: Addr,
+    pre_propose: Addr,
+}
+
+fn setup_default_test(
+    app: &mut App,
+    deposit_info: Option<UncheckedDepositInfo>,
+) -> DefaultTestSetup {
+    let condorcet_id = app.store_code(dao_proposal_condorcet_contract());
+    let pre_propose_id = app.store_code(dao_pre_propose_condorcet_contract());
+
+    let proposal_module_instantiate = dao_proposal_condorcet::msg::InstantiateMsg {
+        quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+        voting_period: Duration::Time(86400),
+        min_voting_period: None,
+        close_proposals_on_execution_failure: false,
+        allow_revoting: false,
+        completion: None,
+        pre_propose_info: PreProposeInfo::ModuleMayPropose {
+            info: ModuleInstantiateInfo {
+                code_id: pre_propose_id,
+                msg: to_json_binary(&InstantiateMsg {
+                    deposit_info,
+                    submission_policy: PreProposeSubmissionPolicy::Specific {
+                        dao_members: true,
+                        allowlist: vec![],
+                        denylist: vec![],
+                    },
+                    sponsorship: None,
+                    forfeiture: None,
+                    rate_limit: None,
+                    conditions: None,
+                    extension: Empty::default(),
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "condorcet pre-propose module".to_string(),
+            },
+        },
+    };
+
+    let core_addr = instantiate_with_cw4_groups_governance(
+        app,
+        condorcet_id,
+        to_json_binary(&proposal_module_instantiate).unwrap(),
+        Some(vec![
+            cw20::Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(9),
+            },
+            cw20::Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(8),
+            },
+        ]),
+    );
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &dao_interface::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let condorcet = proposal_modules.into_iter().next().unwrap().address;
+
+    let proposal_creation_policy = app
+        .wrap()
+        .query_wasm_smart(
+            condorcet.clone(),
+            &dao_proposal_condorcet::msg::QueryMsg::ProposalCreationPolicy {},
+        )
+        .unwrap();
+    let pre_propose = match proposal_creation_policy {
+        ProposalCreationPolicy::Module { addr } => addr,
+        _ => panic!("expected a module for the proposal creation policy"),
+    };
+
+    DefaultTestSetup {
+        core_addr,
+        condorcet,
+        pre_propose,
+    }
+}
+
+fn native_deposit(refund_policy: DepositRefundPolicy) -> Option<UncheckedDepositInfo> {
+    Some(UncheckedDepositInfo {
+        denom: DepositToken::Token {
+            denom: UncheckedDenom::Native("ujuno".to_string()),
+        },
+        amount: Uint128::new(10),
+        refund_policy,
+        formula: None,
+        alternatives: vec![],
+    })
+}
+
+fn mint_natives(app: &mut App, receiver: &str, coins: Vec<Coin>) {
+    app.sudo(cw_multi_test::SudoMsg::Bank(BankSudo::Mint {
+        to_address: receiver.to_string(),
+        amount: coins,
+    }))
+    .unwrap();
+}
+
+fn choices() -> Vec<Choice> {
+    vec![Choice {
+        title: "choice".to_string(),
+        description: "description".to_string(),
+        msgs: vec![],
+    }]
+}
+
+fn make_proposal(
+    app: &mut App,
+    pre_propose: Addr,
+    condorcet: Addr,
+    proposer: &str,
+    funds: &[Coin],
+) -> u32 {
+    app.execute_contract(
+        Addr::unchecked(proposer),
+        pre_propose,
+        &ExecuteMsg::Propose {
+            msg: ProposeMessage::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                choices: choices(),
+            },
+        },
+        funds,
+    )
+    .unwrap();
+
+    let id: u32 = app
+        .wrap()
+        .query_wasm_smart(
+            &condorcet,
+            &dao_proposal_condorcet::msg::QueryMsg::NextProposalId {},
+        )
+        .unwrap();
+    let id = id - 1;
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            condorcet,
+            &dao_proposal_condorcet::msg::QueryMsg::Proposal { id },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked(proposer));
+    assert_eq!(proposal.proposal.title, "title".to_string());
+    assert_eq!(proposal.proposal.choices[0].title, "choice".to_string());
+
+    id
+}
+
+fn query_status(app: &App, condorcet: &Addr, id: u32) -> Status {
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            condorcet,
+            &dao_proposal_condorcet::msg::QueryMsg::Proposal { id },
+        )
+        .unwrap();
+    proposal.proposal.status(&app.block_info(), &proposal.tally)
+}
+
+fn balance(app: &App, address: &str) -> u128 {
+    app.wrap()
+        .query_balance(address, "ujuno")
+        .unwrap()
+        .amount
+        .u128()
+}
+
+#[test]
+fn test_deposit_refunded_on_execution() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        condorcet,
+        pre_propose,
+        ..
+    } = setup_default_test(&mut app, native_deposit(DepositRefundPolicy::OnlyPassed));
+
+    mint_natives(&mut app, "ekez", coins(10, "ujuno"));
+    let id = make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        condorcet.clone(),
+        "ekez",
+        &coins(10, "ujuno"),
+    );
+    assert_eq!(balance(&app, "ekez"), 0);
+    assert_eq!(balance(&app, pre_propose.as_str()), 10);
+
+    for voter in ["ekez", "keze"] {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            condorcet.clone(),
+            &dao_proposal_condorcet::msg::ExecuteMsg::Vote {
+                proposal_id: id,
+                vote: vec![0, 1],
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    assert_eq!(
+        query_status(&app, &condorcet, id),
+        Status::Passed { winner: 0 }
+    );
+
+    app.execute_contract(
+        Addr::unchecked("keze"),
+        condorcet.clone(),
+        &dao_proposal_condorcet::msg::ExecuteMsg::Execute { proposal_id: id },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(balance(&app, "ekez"), 10);
+    assert_eq!(balance(&app, pre_propose.as_str()), 0);
+}
+
+#[test]
+fn test_deposit_forfeited_on_close() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        condorcet,
+        pre_propose,
+    } = setup_default_test(&mut app, native_deposit(DepositRefundPolicy::OnlyPassed));
+
+    mint_natives(&mut app, "ekez", coins(10, "ujuno"));
+    let id = make_proposal(
+        &mut app,
+        pre_propose,
+        condorcet.clone(),
+        "ekez",
+        &coins(10, "ujuno"),
+    );
+
+    app.update_block(|b| b.time = b.time.plus_seconds(86400));
+    assert_eq!(query_status(&app, &condorcet, id), Status::Rejected);
+
+    app.execute_contract(
+        Addr::unchecked("keze"),
+        condorcet,
+        &dao_proposal_condorcet::msg::ExecuteMsg::Close { proposal_id: id },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(balance(&app, "ekez"), 0);
+    assert_eq!(balance(&app, core_addr.as_str()), 10);
+}
+
+#[test]
+fn test_propose_through_module_only() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        condorcet,
+        pre_propose,
+        ..
+    } = setup_default_test(&mut app, None);
+
+    // Proposals may not bypass the pre-propose module.
+    let err: dao_proposal_condorcet::ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            condorcet.clone(),
+            &dao_proposal_condorcet::msg::ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                choices: choices(),
+                proposer: None,
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        dao_proposal_condorcet::ContractError::Unauthorized {}
+    ));
+
+    // Non-members may not propose.
+    app.execute_contract(
+        Addr::unchecked("someone"),
+        pre_propose.clone(),
+        &ExecuteMsg::Propose {
+            msg: ProposeMessage::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                choices: choices(),
+            },
+        },
+        &[],
+    )
+    .unwrap_err();
+
+    make_proposal(&mut app, pre_propose, condorcet, "keze", &[]);
+}
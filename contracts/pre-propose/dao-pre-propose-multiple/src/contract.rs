@@ -1,8 +1,12 @@
 use cosmwasm_schema::cw_serde;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    from_json, to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+};
 use cw2::set_contract_version;
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
 
 use dao_pre_propose_base::{
     error::PreProposeError,
@@ -70,23 +74,31 @@ pub fn execute(
     // internal message which sets it.
     type ExecuteInternal = ExecuteBase<ProposeMessageInternal, Empty>;
     let internalized = match msg {
-        ExecuteMsg::Propose {
-            msg:
-                ProposeMessage::Propose {
-                    title,
-                    description,
-                    choices,
-                    vote,
-                },
-        } => ExecuteInternal::Propose {
-            msg: ProposeMessageInternal::Propose(ProposeMsg {
-                proposer: Some(info.sender.to_string()),
-                title,
-                description,
-                choices,
-                vote,
-            }),
+        ExecuteMsg::Propose { msg } => ExecuteInternal::Propose {
+            // Fill in proposer based on message sender.
+            msg: internalize(msg, info.sender.to_string()),
         },
+        // Deposits paid with cw20 tokens or NFTs carry the propose
+        // message along with them. The proposer is the account which
+        // sent the tokens, not the token contract.
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender,
+            amount,
+            msg,
+        }) => ExecuteInternal::Receive(Cw20ReceiveMsg {
+            msg: to_json_binary(&internalize(from_json(&msg)?, sender.clone()))?,
+            sender,
+            amount,
+        }),
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender,
+            token_id,
+            msg,
+        }) => ExecuteInternal::ReceiveNft(Cw721ReceiveMsg {
+            msg: to_json_binary(&internalize(from_json(&msg)?, sender.clone()))?,
+            sender,
+            token_id,
+        }),
         ExecuteMsg::Extension { msg } => ExecuteInternal::Extension { msg },
         ExecuteMsg::Withdraw { denom } => ExecuteInternal::Withdraw { denom },
         ExecuteMsg::UpdateConfig {
@@ -109,6 +121,20 @@ pub fn execute(
             allowlist_add,
             allowlist_remove,
         },
+        ExecuteMsg::UpdateSponsorship { sponsorship } => {
+            ExecuteInternal::UpdateSponsorship { sponsorship }
+        }
+        ExecuteMsg::UpdateForfeiture { forfeiture } => {
+            ExecuteInternal::UpdateForfeiture { forfeiture }
+        }
+        ExecuteMsg::UpdateRateLimit { rate_limit } => {
+            ExecuteInternal::UpdateRateLimit { rate_limit }
+        }
+        ExecuteMsg::UpdateConditions { conditions } => {
+            ExecuteInternal::UpdateConditions { conditions }
+        }
+        ExecuteMsg::Sponsor { id } => ExecuteInternal::Sponsor { id },
+        ExecuteMsg::ExpireUnsponsored { id } => ExecuteInternal::ExpireUnsponsored { id },
         ExecuteMsg::AddProposalSubmittedHook { address } => {
             ExecuteInternal::AddProposalSubmittedHook { address }
         }
@@ -127,6 +153,24 @@ pub fn execute(
     PrePropose::default().execute(deps, env, info, internalized)
 }
 
+/// Converts an external propose message into an internal one with
+/// PROPOSER filled in.
+fn internalize(msg: ProposeMessage, proposer: String) -> ProposeMessageInternal {
+    let ProposeMessage::Propose {
+        title,
+        description,
+        choices,
+        vote,
+    } = msg;
+    ProposeMessageInternal::Propose(ProposeMsg {
+        proposer: Some(proposer),
+        title,
+        description,
+        choices,
+        vote,
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     PrePropose::default().query(deps, env, msg)
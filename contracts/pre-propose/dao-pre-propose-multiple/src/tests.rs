@@ -76,6 +76,10 @@ fn get_default_proposal_module_instantiate(
                 msg: to_json_binary(&InstantiateMsg {
                     deposit_info,
                     submission_policy,
+                    sponsorship: None,
+                    forfeiture: None,
+                    rate_limit: None,
+                    conditions: None,
                     extension: Empty::default(),
                 })
                 .unwrap(),
@@ -502,6 +506,8 @@ fn test_native_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -574,6 +580,8 @@ fn test_cw20_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -750,6 +758,8 @@ fn test_multiple_open_proposals() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -832,6 +842,8 @@ fn test_set_version() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -868,6 +880,8 @@ fn test_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false, // no open proposal submission.
     );
@@ -933,6 +947,8 @@ fn test_propose_open_proposal_submission() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         true, // yes, open proposal submission.
     );
@@ -1352,12 +1368,18 @@ fn test_instantiate_with_zero_native_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            formula: None,
+                            alternatives: vec![],
                         }),
                         submission_policy: PreProposeSubmissionPolicy::Specific {
                             dao_members: true,
                             allowlist: vec![],
                             denylist: vec![],
                         },
+                        sponsorship: None,
+                        forfeiture: None,
+                        rate_limit: None,
+                        conditions: None,
                         extension: Empty::default(),
                     })
                     .unwrap(),
@@ -1419,12 +1441,18 @@ fn test_instantiate_with_zero_cw20_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            formula: None,
+                            alternatives: vec![],
                         }),
                         submission_policy: PreProposeSubmissionPolicy::Specific {
                             dao_members: true,
                             allowlist: vec![],
                             denylist: vec![],
                         },
+                        sponsorship: None,
+                        forfeiture: None,
+                        rate_limit: None,
+                        conditions: None,
                         extension: Empty::default(),
                     })
                     .unwrap(),
@@ -1474,7 +1502,11 @@ fn test_update_config() {
                 dao_members: true,
                 allowlist: vec![],
                 denylist: vec![]
-            }
+            },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1496,6 +1528,8 @@ fn test_update_config() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Never,
+            formula: None,
+            alternatives: vec![],
         }),
         PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
     );
@@ -1507,9 +1541,16 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                formula: None,
+                alternatives: vec![],
+                escrowed_nft: None,
             }),
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1539,7 +1580,10 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                formula: None,
+                alternatives: vec![],
+                escrowed_nft: None,
             }),
             proposer: Addr::unchecked("ekez"),
         }
@@ -1628,6 +1672,10 @@ fn test_update_submission_policy() {
         Config {
             deposit_info: None,
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1673,6 +1721,10 @@ fn test_update_submission_policy() {
             submission_policy: PreProposeSubmissionPolicy::Anyone {
                 denylist: vec![Addr::unchecked("ekez")],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1699,6 +1751,10 @@ fn test_update_submission_policy() {
             submission_policy: PreProposeSubmissionPolicy::Anyone {
                 denylist: vec![Addr::unchecked("else"), Addr::unchecked("someone")],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1723,6 +1779,10 @@ fn test_update_submission_policy() {
         Config {
             deposit_info: None,
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1820,6 +1880,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1848,6 +1912,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![Addr::unchecked("ekez")],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1876,6 +1944,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![Addr::unchecked("else"), Addr::unchecked("someone")],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1904,6 +1976,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![]
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1932,6 +2008,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![Addr::unchecked("ekez")],
                 denylist: vec![],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1960,6 +2040,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![Addr::unchecked("else"), Addr::unchecked("someone")],
                 denylist: vec![],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1988,6 +2072,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![]
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -2038,6 +2126,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![Addr::unchecked("ekez")],
                 denylist: vec![]
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -2106,6 +2198,8 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -2154,6 +2248,8 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -2521,7 +2617,11 @@ fn test_migrate_from_v241() {
     assert_eq!(
         Config {
             deposit_info: None,
-            submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] }
+            submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         },
         config
     );
@@ -2883,7 +2983,11 @@ fn test_migrate_from_v241_with_policy_update() {
                 dao_members: false,
                 allowlist: vec![Addr::unchecked("noob")],
                 denylist: vec![]
-            }
+            },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         },
         config
     );
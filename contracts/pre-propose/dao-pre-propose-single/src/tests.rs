@@ -24,7 +24,10 @@ use dao_testing::{
 };
 use dao_voting::pre_propose::{PreProposeSubmissionPolicy, PreProposeSubmissionPolicyError};
 use dao_voting::{
-    deposit::{CheckedDepositInfo, DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    deposit::{
+        CheckedDepositInfo, DepositError, DepositRefundPolicy, DepositToken,
+        UncheckedDepositAlternative, UncheckedDepositInfo,
+    },
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     status::Status,
     threshold::{PercentageThreshold, Threshold},
@@ -72,6 +75,10 @@ fn get_default_proposal_module_instantiate(
                 msg: to_json_binary(&InstantiateMsg {
                     deposit_info,
                     submission_policy,
+                    sponsorship: None,
+                    forfeiture: None,
+                    rate_limit: None,
+                    conditions: None,
                     extension: Empty::default(),
                 })
                 .unwrap(),
@@ -481,6 +488,8 @@ fn test_native_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -545,6 +554,8 @@ fn test_cw20_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -698,6 +709,108 @@ fn test_cw20_failed_passed_refund() {
 }
 
 // See: <https://github.com/DA0-DA0/dao-contracts/pull/465#discussion_r960092321>
+#[test]
+fn test_cw20_receive_deposit() {
+    let mut app = App::default();
+
+    let cw20_address = instantiate_cw20_base_default(&mut app);
+
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![UncheckedDepositAlternative::Token {
+                denom: UncheckedDenom::Cw20(cw20_address.to_string()),
+                amount: Uint128::new(5),
+            }],
+        }),
+        false,
+    );
+
+    let propose = to_json_binary(&ProposeMessage::Propose {
+        title: "title".to_string(),
+        description: "description".to_string(),
+        msgs: vec![],
+        vote: None,
+    })
+    .unwrap();
+
+    // Sending the wrong amount of the alternative fails.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            cw20_address.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: pre_propose.to_string(),
+                amount: Uint128::new(4),
+                msg: propose.clone(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeError::Deposit(DepositError::InvalidDeposit {
+            actual: Uint128::new(4),
+            expected: Uint128::new(5),
+        })
+    );
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        cw20_address.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: pre_propose.to_string(),
+            amount: Uint128::new(5),
+            msg: propose,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        get_balance_cw20(&app, cw20_address.clone(), "ekez"),
+        Uint128::new(5)
+    );
+
+    // The proposer is the sender of the tokens and the deposit is
+    // recorded in the token that was paid.
+    let DepositInfoResponse {
+        deposit_info,
+        proposer,
+    } = get_deposit_info(&app, pre_propose, 1);
+    assert_eq!(proposer, Addr::unchecked("ekez"));
+    let deposit_info = deposit_info.unwrap();
+    assert_eq!(
+        deposit_info.denom,
+        cw_denom::CheckedDenom::Cw20(cw20_address.clone())
+    );
+    assert_eq!(deposit_info.amount, Uint128::new(5));
+
+    let new_status = vote(&mut app, proposal_single.clone(), "ekez", 1, Vote::No);
+    assert_eq!(new_status, Status::Rejected);
+    close_proposal(&mut app, proposal_single, "ekez", 1);
+
+    assert_eq!(
+        get_balance_cw20(&app, cw20_address.clone(), "ekez"),
+        Uint128::new(10)
+    );
+    assert_eq!(
+        get_balance_cw20(&app, cw20_address, core_addr),
+        Uint128::zero()
+    );
+}
+
 #[test]
 fn test_multiple_open_proposals() {
     let mut app = App::default();
@@ -714,6 +827,8 @@ fn test_multiple_open_proposals() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -796,6 +911,8 @@ fn test_set_version() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -832,6 +949,8 @@ fn test_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false, // no open proposal submission.
     );
@@ -891,6 +1010,8 @@ fn test_propose_open_proposal_submission() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         true, // yes, open proposal submission.
     );
@@ -1262,12 +1383,18 @@ fn test_instantiate_with_zero_native_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            formula: None,
+                            alternatives: vec![],
                         }),
                         submission_policy: PreProposeSubmissionPolicy::Specific {
                             dao_members: true,
                             allowlist: vec![],
                             denylist: vec![],
                         },
+                        sponsorship: None,
+                        forfeiture: None,
+                        rate_limit: None,
+                        conditions: None,
                         extension: Empty::default(),
                     })
                     .unwrap(),
@@ -1329,12 +1456,18 @@ fn test_instantiate_with_zero_cw20_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            formula: None,
+                            alternatives: vec![],
                         }),
                         submission_policy: PreProposeSubmissionPolicy::Specific {
                             dao_members: true,
                             allowlist: vec![],
                             denylist: vec![],
                         },
+                        sponsorship: None,
+                        forfeiture: None,
+                        rate_limit: None,
+                        conditions: None,
                         extension: Empty::default(),
                     })
                     .unwrap(),
@@ -1385,6 +1518,10 @@ fn test_update_config() {
                 allowlist: vec![],
                 denylist: vec![]
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1406,6 +1543,8 @@ fn test_update_config() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Never,
+            formula: None,
+            alternatives: vec![],
         }),
         PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
     );
@@ -1417,9 +1556,16 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                formula: None,
+                alternatives: vec![],
+                escrowed_nft: None,
             }),
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1449,7 +1595,10 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                formula: None,
+                alternatives: vec![],
+                escrowed_nft: None,
             }),
             proposer: Addr::unchecked("ekez"),
         }
@@ -1528,6 +1677,10 @@ fn test_update_config() {
         Config {
             deposit_info: None,
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 }
@@ -1547,6 +1700,10 @@ fn test_update_submission_policy() {
         Config {
             deposit_info: None,
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1592,6 +1749,10 @@ fn test_update_submission_policy() {
             submission_policy: PreProposeSubmissionPolicy::Anyone {
                 denylist: vec![Addr::unchecked("ekez")],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1618,6 +1779,10 @@ fn test_update_submission_policy() {
             submission_policy: PreProposeSubmissionPolicy::Anyone {
                 denylist: vec![Addr::unchecked("else"), Addr::unchecked("someone")],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1642,6 +1807,10 @@ fn test_update_submission_policy() {
         Config {
             deposit_info: None,
             submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1739,6 +1908,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1767,6 +1940,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![Addr::unchecked("ekez")],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1795,6 +1972,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![Addr::unchecked("else"), Addr::unchecked("someone")],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1823,6 +2004,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![]
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1851,6 +2036,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![Addr::unchecked("ekez")],
                 denylist: vec![],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1879,6 +2068,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![Addr::unchecked("else"), Addr::unchecked("someone")],
                 denylist: vec![],
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1907,6 +2100,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![],
                 denylist: vec![]
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -1957,6 +2154,10 @@ fn test_update_submission_policy() {
                 allowlist: vec![Addr::unchecked("ekez")],
                 denylist: vec![]
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -2025,6 +2226,8 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -2073,6 +2276,8 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         PreProposeSubmissionPolicy::Specific {
             dao_members: true,
@@ -2422,7 +2627,11 @@ fn test_migrate_from_v241() {
                 dao_members: true,
                 allowlist: vec![],
                 denylist: vec![]
-            }
+            },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         },
         config
     );
@@ -2726,7 +2935,11 @@ fn test_migrate_from_v241_with_policy_update() {
                 dao_members: false,
                 allowlist: vec![Addr::unchecked("noob")],
                 denylist: vec![]
-            }
+            },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         },
         config
     );
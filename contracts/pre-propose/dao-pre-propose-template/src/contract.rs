@@ -0,0 +1,271 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response,
+    StdError, StdResult,
+};
+use cw2::set_contract_version;
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
+use cw_paginate_storage::paginate_map_values;
+use dao_pre_propose_base::{
+    error::PreProposeError, msg::ExecuteMsg as ExecuteBase, state::PreProposeContract,
+};
+use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteExt, ExecuteMsg, InstantiateExt, InstantiateMsg, MigrateMsg, NewTemplate,
+    ProposeMessage, ProposeMessageInternal, QueryExt, QueryMsg,
+};
+use crate::state::{advance_template_id, Template, TEMPLATES};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-template";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+type PrePropose =
+    PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, Empty, ProposeMessageInternal>;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    for template in msg.extension.templates.clone() {
+        add_template(deps.branch(), &env, template)?;
+    }
+    let resp = PrePropose::default().instantiate(deps.branch(), env, info, msg)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(resp)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    // Proposals made to this module name a template and its
+    // parameters. Here, we render the template into the propose
+    // message used by dao-proposal-single, filling in the proposer
+    // based on the sender.
+    type ExecuteInternal = ExecuteBase<ProposeMessageInternal, ExecuteExt>;
+    let internalized = match msg {
+        ExecuteMsg::Propose { msg } => ExecuteInternal::Propose {
+            msg: internalize(deps.as_ref(), msg, info.sender.to_string())?,
+        },
+        // Deposits paid with cw20 tokens or NFTs carry the propose
+        // message along with them. The proposer is the account which
+        // sent the tokens, not the token contract.
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender,
+            amount,
+            msg,
+        }) => ExecuteInternal::Receive(Cw20ReceiveMsg {
+            msg: to_json_binary(&internalize(
+                deps.as_ref(),
+                from_json(&msg)?,
+                sender.clone(),
+            )?)?,
+            sender,
+            amount,
+        }),
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender,
+            token_id,
+            msg,
+        }) => ExecuteInternal::ReceiveNft(Cw721ReceiveMsg {
+            msg: to_json_binary(&internalize(
+                deps.as_ref(),
+                from_json(&msg)?,
+                sender.clone(),
+            )?)?,
+            sender,
+            token_id,
+        }),
+        ExecuteMsg::Extension { msg } => {
+            return match msg {
+                ExecuteExt::AddTemplate { template } => {
+                    execute_add_template(deps, env, info, template)
+                }
+                ExecuteExt::RemoveTemplate { id } => execute_remove_template(deps, info, id),
+            }
+        }
+        ExecuteMsg::Withdraw { denom } => ExecuteInternal::Withdraw { denom },
+        ExecuteMsg::UpdateConfig {
+            deposit_info,
+            submission_policy,
+        } => ExecuteInternal::UpdateConfig {
+            deposit_info,
+            submission_policy,
+        },
+        ExecuteMsg::UpdateSubmissionPolicy {
+            denylist_add,
+            denylist_remove,
+            set_dao_members,
+            allowlist_add,
+            allowlist_remove,
+        } => ExecuteInternal::UpdateSubmissionPolicy {
+            denylist_add,
+            denylist_remove,
+            set_dao_members,
+            allowlist_add,
+            allowlist_remove,
+        },
+        ExecuteMsg::UpdateSponsorship { sponsorship } => {
+            ExecuteInternal::UpdateSponsorship { sponsorship }
+        }
+        ExecuteMsg::UpdateForfeiture { forfeiture } => {
+            ExecuteInternal::UpdateForfeiture { forfeiture }
+        }
+        ExecuteMsg::UpdateRateLimit { rate_limit } => {
+            ExecuteInternal::UpdateRateLimit { rate_limit }
+        }
+        ExecuteMsg::UpdateConditions { conditions } => {
+            ExecuteInternal::UpdateConditions { conditions }
+        }
+        ExecuteMsg::Sponsor { id } => ExecuteInternal::Sponsor { id },
+        ExecuteMsg::ExpireUnsponsored { id } => ExecuteInternal::ExpireUnsponsored { id },
+        ExecuteMsg::AddProposalSubmittedHook { address } => {
+            ExecuteInternal::AddProposalSubmittedHook { address }
+        }
+        ExecuteMsg::RemoveProposalSubmittedHook { address } => {
+            ExecuteInternal::RemoveProposalSubmittedHook { address }
+        }
+        ExecuteMsg::ProposalCompletedHook {
+            proposal_id,
+            new_status,
+        } => ExecuteInternal::ProposalCompletedHook {
+            proposal_id,
+            new_status,
+        },
+    };
+
+    Ok(PrePropose::default().execute(deps, env, info, internalized)?)
+}
+
+/// Renders the template named by MSG into a propose message with
+/// PROPOSER filled in.
+fn internalize(
+    deps: Deps,
+    msg: ProposeMessage,
+    proposer: String,
+) -> Result<ProposeMessageInternal, ContractError> {
+    let ProposeMessage::ProposeFromTemplate {
+        template_id,
+        title,
+        description,
+        params,
+        vote,
+    } = msg;
+    let template = TEMPLATES
+        .may_load(deps.storage, template_id)?
+        .ok_or(ContractError::TemplateNotFound { id: template_id })?;
+    Ok(ProposeMessageInternal::Propose(ProposeMsg {
+        proposer: Some(proposer),
+        title,
+        description,
+        msgs: template.render(deps.api, &params)?,
+        vote,
+    }))
+}
+
+fn add_template(
+    deps: DepsMut,
+    env: &Env,
+    NewTemplate {
+        name,
+        description,
+        msgs,
+        params,
+    }: NewTemplate,
+) -> Result<u64, ContractError> {
+    let id = advance_template_id(deps.storage)?;
+    let template = Template {
+        id,
+        name,
+        description,
+        msgs,
+        params,
+    };
+    template.validate(env.contract.address.as_str())?;
+    TEMPLATES.save(deps.storage, id, &template)?;
+    Ok(id)
+}
+
+pub fn execute_add_template(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    template: NewTemplate,
+) -> Result<Response, ContractError> {
+    let dao = PrePropose::default().dao.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(PreProposeError::NotDao {}.into());
+    }
+
+    let id = add_template(deps, &env, template)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "add_template")
+        .add_attribute("template_id", id.to_string()))
+}
+
+pub fn execute_remove_template(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let dao = PrePropose::default().dao.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(PreProposeError::NotDao {}.into());
+    }
+    if !TEMPLATES.has(deps.storage, id) {
+        return Err(ContractError::TemplateNotFound { id });
+    }
+    TEMPLATES.remove(deps.storage, id);
+
+    Ok(Response::default()
+        .add_attribute("method", "remove_template")
+        .add_attribute("template_id", id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::QueryExtension { msg } => match msg {
+            QueryExt::Template { id } => to_json_binary(&TEMPLATES.load(deps.storage, id)?),
+            QueryExt::Templates { start_after, limit } => to_json_binary(&paginate_map_values(
+                deps,
+                &TEMPLATES,
+                start_after,
+                limit,
+                Order::Ascending,
+            )?),
+            QueryExt::Render {
+                template_id,
+                params,
+            } => {
+                let template = TEMPLATES.load(deps.storage, template_id)?;
+                let msgs = template
+                    .render(deps.api, &params)
+                    .map_err(|err| match err {
+                        ContractError::Std(err) => err,
+                        err => StdError::generic_err(err.to_string()),
+                    })?;
+                to_json_binary(&msgs)
+            }
+        },
+        _ => PrePropose::default().query(deps, env, msg),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let res = PrePropose::default().migrate(deps.branch(), msg)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(res)
+}
@@ -0,0 +1,21 @@
+use cosmwasm_std::StdError;
+use dao_pre_propose_base::error::PreProposeError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    PreProposeError(#[from] PreProposeError),
+
+    #[error("No template with ID ({id})")]
+    TemplateNotFound { id: u64 },
+
+    #[error("Invalid template: {reason}")]
+    InvalidTemplate { reason: String },
+
+    #[error("Invalid template parameter ({name}): {reason}")]
+    InvalidTemplateParam { name: String, reason: String },
+}
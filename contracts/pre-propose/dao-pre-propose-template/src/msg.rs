@@ -0,0 +1,79 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Empty;
+use dao_pre_propose_base::msg::{
+    ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, MigrateMsg as MigrateBase,
+    QueryMsg as QueryBase,
+};
+use dao_voting::{proposal::SingleChoiceProposeMsg as ProposeMsg, voting::SingleChoiceAutoVote};
+
+use crate::state::{Param, ParamSpec, TemplateMsg};
+
+#[cw_serde]
+pub enum ProposeMessage {
+    /// Creates a proposal whose messages are rendered from the
+    /// template identified by TEMPLATE_ID with PARAMS.
+    ProposeFromTemplate {
+        template_id: u64,
+        title: String,
+        description: String,
+        params: Vec<Param>,
+        vote: Option<SingleChoiceAutoVote>,
+    },
+}
+
+/// A template to be registered with this module.
+#[cw_serde]
+pub struct NewTemplate {
+    pub name: String,
+    pub description: String,
+    pub msgs: Vec<TemplateMsg>,
+    pub params: Vec<ParamSpec>,
+}
+
+#[cw_serde]
+pub struct InstantiateExt {
+    /// Templates to register on instantiation.
+    pub templates: Vec<NewTemplate>,
+}
+
+#[cw_serde]
+pub enum ExecuteExt {
+    /// Registers a template. Only the DAO may execute this message.
+    AddTemplate { template: NewTemplate },
+    /// Removes a template. Proposals already made from it are not
+    /// affected. Only the DAO may execute this message.
+    RemoveTemplate { id: u64 },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryExt {
+    /// Gets the template identified by ID.
+    #[returns(crate::state::Template)]
+    Template { id: u64 },
+    /// Lists registered templates.
+    #[returns(Vec<crate::state::Template>)]
+    Templates {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Renders the messages of the template identified by
+    /// TEMPLATE_ID with PARAMS, as they would be proposed.
+    #[returns(Vec<cosmwasm_std::CosmosMsg>)]
+    Render {
+        template_id: u64,
+        params: Vec<Param>,
+    },
+}
+
+pub type InstantiateMsg = InstantiateBase<InstantiateExt>;
+pub type ExecuteMsg = ExecuteBase<ProposeMessage, ExecuteExt>;
+pub type QueryMsg = QueryBase<QueryExt>;
+pub type MigrateMsg = MigrateBase<Empty>;
+
+/// Internal version of the propose message that is sent to
+/// dao-proposal-single once a template has been rendered.
+#[cw_serde]
+pub(crate) enum ProposeMessageInternal {
+    Propose(ProposeMsg),
+}
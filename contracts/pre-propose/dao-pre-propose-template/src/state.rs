@@ -0,0 +1,251 @@
+use std::str::FromStr;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    from_json, to_json_string, Api, Binary, Coin, CosmosMsg, Empty, StdResult, Storage, Uint128,
+    WasmMsg,
+};
+use cw_storage_plus::{Item, Map};
+
+use crate::error::ContractError;
+
+/// A message in a template. Placeholders are written as `{{name}}`,
+/// where `name` is the name of one of the template's parameters.
+#[cw_serde]
+pub enum TemplateMsg {
+    /// A `CosmosMsg` written as JSON. Placeholders must be JSON
+    /// strings, i.e. `"{{name}}"`, and are replaced with the
+    /// parameter's value as a JSON string.
+    Cosmos { json: String },
+    /// A `WasmMsg::Execute` whose execute message is written as
+    /// JSON, with placeholders as in `Cosmos`. The contract address
+    /// and the denom and amount of each coin may either be a
+    /// placeholder or a literal value.
+    WasmExecute {
+        contract_addr: String,
+        msg: String,
+        funds: Vec<TemplateCoin>,
+    },
+}
+
+#[cw_serde]
+pub struct TemplateCoin {
+    pub denom: String,
+    pub amount: String,
+}
+
+#[cw_serde]
+pub enum ParamKind {
+    /// A valid address.
+    Address {},
+    /// An amount no greater than MAX.
+    Amount { max: Uint128 },
+    /// One of OPTIONS.
+    Choice { options: Vec<String> },
+}
+
+/// A parameter that proposers fill in when proposing from a
+/// template.
+#[cw_serde]
+pub struct ParamSpec {
+    pub name: String,
+    pub kind: ParamKind,
+}
+
+/// The value of a template parameter.
+#[cw_serde]
+pub struct Param {
+    pub name: String,
+    pub value: String,
+}
+
+#[cw_serde]
+pub struct Template {
+    pub id: u64,
+    pub name: String,
+    pub description: String,
+    /// The messages proposals made from this template execute.
+    pub msgs: Vec<TemplateMsg>,
+    /// The parameters proposers must fill in.
+    pub params: Vec<ParamSpec>,
+}
+
+impl Template {
+    /// Checks that this template's parameters are well formed and
+    /// that its messages render with example values. SAMPLE_ADDRESS
+    /// is used as the example value of address parameters.
+    pub fn validate(&self, sample_address: &str) -> Result<(), ContractError> {
+        if self.msgs.is_empty() {
+            return Err(ContractError::InvalidTemplate {
+                reason: "templates must have at least one message".to_string(),
+            });
+        }
+        let mut samples: Vec<Param> = Vec::with_capacity(self.params.len());
+        for ParamSpec { name, kind } in &self.params {
+            if name.is_empty() {
+                return Err(ContractError::InvalidTemplate {
+                    reason: "parameter names must not be empty".to_string(),
+                });
+            }
+            if samples.iter().any(|p| p.name == *name) {
+                return Err(ContractError::InvalidTemplate {
+                    reason: format!("parameter ({name}) is declared more than once"),
+                });
+            }
+            let value = match kind {
+                ParamKind::Address {} => sample_address.to_string(),
+                ParamKind::Amount { max } => max.to_string(),
+                ParamKind::Choice { options } => options
+                    .first()
+                    .ok_or_else(|| ContractError::InvalidTemplate {
+                        reason: format!("choice parameter ({name}) has no options"),
+                    })?
+                    .clone(),
+            };
+            samples.push(Param {
+                name: name.clone(),
+                value,
+            });
+        }
+        self.render_msgs(&samples)?;
+        Ok(())
+    }
+
+    /// Validates PARAMS against this template's parameters and
+    /// renders its messages with them.
+    pub fn render(&self, api: &dyn Api, params: &[Param]) -> Result<Vec<CosmosMsg>, ContractError> {
+        if let Some(unknown) = params
+            .iter()
+            .find(|p| !self.params.iter().any(|spec| spec.name == p.name))
+        {
+            return Err(ContractError::InvalidTemplateParam {
+                name: unknown.name.clone(),
+                reason: "not a parameter of this template".to_string(),
+            });
+        }
+        let mut values = Vec::with_capacity(self.params.len());
+        for ParamSpec { name, kind } in &self.params {
+            let invalid = |reason: &str| ContractError::InvalidTemplateParam {
+                name: name.clone(),
+                reason: reason.to_string(),
+            };
+            let mut given = params.iter().filter(|p| p.name == *name);
+            let value = match (given.next(), given.next()) {
+                (Some(param), None) => &param.value,
+                (None, _) => return Err(invalid("missing")),
+                (Some(_), Some(_)) => return Err(invalid("given more than once")),
+            };
+            let value = match kind {
+                ParamKind::Address {} => api
+                    .addr_validate(value)
+                    .map_err(|_| invalid("invalid address"))?
+                    .into_string(),
+                ParamKind::Amount { max } => {
+                    let amount = Uint128::from_str(value).map_err(|_| invalid("invalid amount"))?;
+                    if amount > *max {
+                        return Err(invalid(&format!("amount may not exceed {max}")));
+                    }
+                    amount.to_string()
+                }
+                ParamKind::Choice { options } => {
+                    if !options.contains(value) {
+                        return Err(invalid("not one of the allowed options"));
+                    }
+                    value.clone()
+                }
+            };
+            values.push(Param {
+                name: name.clone(),
+                value,
+            });
+        }
+        self.render_msgs(&values)
+    }
+
+    fn render_msgs(&self, values: &[Param]) -> Result<Vec<CosmosMsg>, ContractError> {
+        self.msgs
+            .iter()
+            .map(|msg| -> Result<CosmosMsg, ContractError> {
+                match msg {
+                    TemplateMsg::Cosmos { json } => {
+                        Ok(from_json::<CosmosMsg<Empty>>(render_json(json, values)?)?)
+                    }
+                    TemplateMsg::WasmExecute {
+                        contract_addr,
+                        msg,
+                        funds,
+                    } => {
+                        let msg = render_json(msg, values)?;
+                        // Make sure the rendered message is valid JSON.
+                        from_json::<serde_cw_value::Value>(&msg)?;
+                        let funds = funds
+                            .iter()
+                            .map(|TemplateCoin { denom, amount }| {
+                                Ok(Coin {
+                                    denom: render_str(denom, values)?,
+                                    amount: Uint128::from_str(&render_str(amount, values)?)?,
+                                })
+                            })
+                            .collect::<Result<Vec<_>, ContractError>>()?;
+                        Ok(WasmMsg::Execute {
+                            contract_addr: render_str(contract_addr, values)?,
+                            msg: Binary::from(msg.into_bytes()),
+                            funds,
+                        }
+                        .into())
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+fn lookup<'a>(values: &'a [Param], name: &str) -> Result<&'a str, ContractError> {
+    values
+        .iter()
+        .find(|p| p.name == name)
+        .map(|p| p.value.as_str())
+        .ok_or_else(|| ContractError::InvalidTemplate {
+            reason: format!("placeholder ({name}) is not a parameter of this template"),
+        })
+}
+
+/// Replaces S with the value of the parameter it names if it is a
+/// placeholder.
+fn render_str(s: &str, values: &[Param]) -> Result<String, ContractError> {
+    match s.strip_prefix("{{").and_then(|s| s.strip_suffix("}}")) {
+        Some(name) => Ok(lookup(values, name)?.to_string()),
+        None => Ok(s.to_string()),
+    }
+}
+
+/// Replaces each `"{{name}}"` in JSON with the value of the named
+/// parameter as a JSON string. Values are escaped, so they may not
+/// change the structure of the message.
+fn render_json(json: &str, values: &[Param]) -> Result<String, ContractError> {
+    let mut rendered = String::with_capacity(json.len());
+    let mut rest = json;
+    while let Some(start) = rest.find("\"{{") {
+        let len = rest[start..]
+            .find("}}\"")
+            .ok_or_else(|| ContractError::InvalidTemplate {
+                reason: "unterminated placeholder".to_string(),
+            })?;
+        let name = &rest[start + 3..start + len];
+        rendered.push_str(&rest[..start]);
+        rendered.push_str(&to_json_string(lookup(values, name)?)?);
+        rest = &rest[start + len + 3..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+pub const TEMPLATES: Map<u64, Template> = Map::new("templates");
+const TEMPLATE_ID: Item<u64> = Item::new("template_id");
+
+/// Increments and returns the ID assigned to the next template.
+pub(crate) fn advance_template_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id = TEMPLATE_ID.may_load(store)?.unwrap_or_default() + 1;
+    TEMPLATE_ID.save(store, &id)?;
+    Ok(id)
+}
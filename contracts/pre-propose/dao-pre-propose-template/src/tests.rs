@@ -0,0 +1,512 @@
+use cosmwasm_std::{coins, to_json_binary, Addr, BankMsg, CosmosMsg, Uint128, WasmMsg};
+use cw_multi_test::{App, BankSudo, Executor};
+use cw_utils::Duration;
+use dao_interface::state::ProposalModule;
+use dao_interface::state::{Admin, ModuleInstantiateInfo};
+use dao_pre_propose_base::error::PreProposeError;
+use dao_proposal_single as dps;
+use dao_testing::contracts::{dao_pre_propose_template_contract, dao_proposal_single_contract};
+use dao_testing::helpers::instantiate_with_cw4_groups_governance;
+use dao_voting::{
+    pre_propose::{PreProposeInfo, PreProposeSubmissionPolicy, ProposalCreationPolicy},
+    status::Status,
+    threshold::{PercentageThreshold, Threshold},
+    voting::Vote,
+};
+
+use crate::error::ContractError;
+use crate::msg::*;
+use crate::state::{Param, ParamKind, ParamSpec, Template, TemplateCoin, TemplateMsg};
+
+const GRANT: &str = r#"{"bank":{"send":{"to_address":"{{recipient}}","amount":[{"denom":"ujuno","amount":"{{amount}}"}]}}}"#;
+
+fn grant_template() -> NewTemplate {
+    NewTemplate {
+        name: "grant".to_string(),
+        description: "pays a grant of up to 100 ujuno".to_string(),
+        msgs: vec![TemplateMsg::Cosmos {
+            json: GRANT.to_string(),
+        }],
+        params: vec![
+            ParamSpec {
+                name: "recipient".to_string(),
+                kind: ParamKind::Address {},
+            },
+            ParamSpec {
+                name: "amount".to_string(),
+                kind: ParamKind::Amount {
+                    max: Uint128::new(100),
+                },
+            },
+        ],
+    }
+}
+
+fn params(params: &[(&str, &str)]) -> Vec<Param> {
+    params
+        .iter()
+        .map(|(name, value)| Param {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+        .collect()
+}
+
+struct DefaultTestSetup {
+    core_addr: Addr,
+    proposal_single: Addr,
+    pre_propose: Addr,
+}
+
+fn setup_default_test(app: &mut App) -> DefaultTestSetup {
+    let dps_id = app.store_code(dao_proposal_single_contract());
+    let pre_propose_id = app.store_code(dao_pre_propose_template_contract());
+
+    let proposal_module_instantiate = dps::msg::InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Time(86400),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        pre_propose_info: PreProposeInfo::ModuleMayPropose {
+            info: ModuleInstantiateInfo {
+                code_id: pre_propose_id,
+                msg: to_json_binary(&InstantiateMsg {
+                    deposit_info: None,
+                    submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+                    sponsorship: None,
+                    forfeiture: None,
+                    rate_limit: None,
+                    conditions: None,
+                    extension: InstantiateExt {
+                        templates: vec![grant_template()],
+                    },
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "template pre-propose module".to_string(),
+            },
+        },
+        close_proposal_on_execution_failure: false,
+        veto: None,
+    };
+    let core_addr = instantiate_with_cw4_groups_governance(
+        app,
+        dps_id,
+        to_json_binary(&proposal_module_instantiate).unwrap(),
+        Some(vec![
+            cw20::Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(9),
+            },
+            cw20::Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(8),
+            },
+        ]),
+    );
+
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &dao_interface::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
+    let policy: ProposalCreationPolicy = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single.clone(),
+            &dps::msg::QueryMsg::ProposalCreationPolicy {},
+        )
+        .unwrap();
+    let pre_propose = match policy {
+        ProposalCreationPolicy::Module { addr } => addr,
+        _ => panic!("expected a module for the proposal creation policy"),
+    };
+
+    DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    }
+}
+
+fn propose_from_template(
+    app: &mut App,
+    pre_propose: &Addr,
+    proposer: &str,
+    template_id: u64,
+    params: Vec<Param>,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(proposer),
+        pre_propose.clone(),
+        &ExecuteMsg::Propose {
+            msg: ProposeMessage::ProposeFromTemplate {
+                template_id,
+                title: "title".to_string(),
+                description: "description".to_string(),
+                params,
+                vote: None,
+            },
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn add_template(
+    app: &mut App,
+    pre_propose: &Addr,
+    sender: &Addr,
+    template: NewTemplate,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        sender.clone(),
+        pre_propose.clone(),
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::AddTemplate { template },
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+#[test]
+fn test_propose_from_template() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app);
+
+    propose_from_template(
+        &mut app,
+        &pre_propose,
+        "keze",
+        1,
+        params(&[("recipient", "grantee"), ("amount", "50")]),
+    )
+    .unwrap();
+
+    let proposal: dps::query::ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single.clone(),
+            &dps::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("keze"));
+    assert_eq!(
+        proposal.proposal.msgs,
+        vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: "grantee".to_string(),
+            amount: coins(50, "ujuno"),
+        })]
+    );
+
+    app.sudo(cw_multi_test::SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_addr.to_string(),
+        amount: coins(100, "ujuno"),
+    }))
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_single.clone(),
+        &dps::msg::ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_single.clone(),
+        &dps::msg::ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: dps::query::ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &dps::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    assert_eq!(
+        app.wrap().query_balance("grantee", "ujuno").unwrap().amount,
+        Uint128::new(50)
+    );
+}
+
+#[test]
+fn test_template_params() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        proposal_single,
+        pre_propose,
+        ..
+    } = setup_default_test(&mut app);
+
+    let err = propose_from_template(
+        &mut app,
+        &pre_propose,
+        "ekez",
+        1,
+        params(&[("recipient", "grantee"), ("amount", "101")]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidTemplateParam {
+            name: "amount".to_string(),
+            reason: "amount may not exceed 100".to_string(),
+        }
+    );
+
+    let err = propose_from_template(
+        &mut app,
+        &pre_propose,
+        "ekez",
+        1,
+        params(&[("recipient", "grantee")]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidTemplateParam {
+            name: "amount".to_string(),
+            reason: "missing".to_string(),
+        }
+    );
+
+    let err = propose_from_template(
+        &mut app,
+        &pre_propose,
+        "ekez",
+        1,
+        params(&[("recipient", "grantee"), ("amount", "1"), ("memo", "hi")]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidTemplateParam {
+            name: "memo".to_string(),
+            reason: "not a parameter of this template".to_string(),
+        }
+    );
+
+    let err = propose_from_template(
+        &mut app,
+        &pre_propose,
+        "ekez",
+        1,
+        params(&[("recipient", "grantee"), ("amount", "lots")]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidTemplateParam {
+            name: "amount".to_string(),
+            reason: "invalid amount".to_string(),
+        }
+    );
+
+    let err = propose_from_template(&mut app, &pre_propose, "ekez", 2, vec![]).unwrap_err();
+    assert_eq!(err, ContractError::TemplateNotFound { id: 2 });
+
+    // Values are escaped, so they can not change the shape of the
+    // message.
+    let recipient = r#"grantee","amount":[]}}}"#;
+    propose_from_template(
+        &mut app,
+        &pre_propose,
+        "ekez",
+        1,
+        params(&[("recipient", recipient), ("amount", "1")]),
+    )
+    .unwrap();
+    let proposal: dps::query::ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &dps::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(
+        proposal.proposal.msgs,
+        vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(1, "ujuno"),
+        })]
+    );
+}
+
+#[test]
+fn test_manage_templates() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        pre_propose,
+        ..
+    } = setup_default_test(&mut app);
+
+    let transfer = NewTemplate {
+        name: "cw20 transfer".to_string(),
+        description: "transfers tokens from the treasury".to_string(),
+        msgs: vec![TemplateMsg::WasmExecute {
+            contract_addr: "{{token}}".to_string(),
+            msg: r#"{"transfer":{"recipient":"{{recipient}}","amount":"{{amount}}"}}"#.to_string(),
+            funds: vec![TemplateCoin {
+                denom: "ujuno".to_string(),
+                amount: "1".to_string(),
+            }],
+        }],
+        params: vec![
+            ParamSpec {
+                name: "token".to_string(),
+                kind: ParamKind::Choice {
+                    options: vec!["usdc".to_string(), "atom".to_string()],
+                },
+            },
+            ParamSpec {
+                name: "recipient".to_string(),
+                kind: ParamKind::Address {},
+            },
+            ParamSpec {
+                name: "amount".to_string(),
+                kind: ParamKind::Amount {
+                    max: Uint128::new(1000),
+                },
+            },
+        ],
+    };
+
+    let err = add_template(
+        &mut app,
+        &pre_propose,
+        &Addr::unchecked("ekez"),
+        transfer.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PreProposeError(PreProposeError::NotDao {})
+    );
+
+    let mut undeclared = transfer.clone();
+    undeclared.params.pop();
+    let err = add_template(&mut app, &pre_propose, &core_addr, undeclared).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidTemplate {
+            reason: "placeholder (amount) is not a parameter of this template".to_string(),
+        }
+    );
+
+    let mut duplicate = transfer.clone();
+    duplicate.params.push(duplicate.params[0].clone());
+    let err = add_template(&mut app, &pre_propose, &core_addr, duplicate).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidTemplate {
+            reason: "parameter (token) is declared more than once".to_string(),
+        }
+    );
+
+    let mut empty = transfer.clone();
+    empty.msgs = vec![];
+    let err = add_template(&mut app, &pre_propose, &core_addr, empty).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidTemplate {
+            reason: "templates must have at least one message".to_string(),
+        }
+    );
+
+    // Messages must render to valid messages.
+    let mut malformed = grant_template();
+    malformed.msgs = vec![TemplateMsg::Cosmos {
+        json: r#"{"bank":{"burn":{"to_address":"{{recipient}}"}}}"#.to_string(),
+    }];
+    add_template(&mut app, &pre_propose, &core_addr, malformed).unwrap_err();
+
+    add_template(&mut app, &pre_propose, &core_addr, transfer).unwrap();
+
+    let msgs: Vec<CosmosMsg> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Render {
+                    template_id: 2,
+                    params: params(&[("token", "usdc"), ("recipient", "ekez"), ("amount", "5")]),
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        msgs,
+        vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "usdc".to_string(),
+            msg: br#"{"transfer":{"recipient":"ekez","amount":"5"}}"#.to_vec().into(),
+            funds: coins(1, "ujuno"),
+        })]
+    );
+
+    let err = propose_from_template(
+        &mut app,
+        &pre_propose,
+        "ekez",
+        2,
+        params(&[("token", "osmo"), ("recipient", "ekez"), ("amount", "5")]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidTemplateParam {
+            name: "token".to_string(),
+            reason: "not one of the allowed options".to_string(),
+        }
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        pre_propose.clone(),
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::RemoveTemplate { id: 1 },
+        },
+        &[],
+    )
+    .unwrap();
+    let templates: Vec<Template> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose,
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Templates {
+                    start_after: None,
+                    limit: None,
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0].id, 2);
+    assert_eq!(templates[0].name, "cw20 transfer");
+}
@@ -0,0 +1,192 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
+
+use crate::{cell::Cell, m::M};
+
+/// A method for electing a winner from the pairwise margins in M
+/// when voting ends without a Condorcet winner (i.e. when the
+/// candidates' preferences form a cycle). Both methods elect the
+/// Condorcet winner when one exists.
+#[cw_serde]
+#[derive(Copy)]
+pub enum CompletionMethod {
+    /// The Schulze method. The winner is the candidate whose
+    /// strongest path to every other candidate is at least as strong
+    /// as that candidate's strongest path back.
+    Schulze,
+    /// Tideman's ranked pairs. Victories are locked in from largest
+    /// to smallest margin, skipping any that would create a cycle,
+    /// and the winner is the candidate no locked victory points to.
+    RankedPairs,
+}
+
+impl CompletionMethod {
+    /// Computes the winner over M. Returns `None` if more than one
+    /// candidate is tied for first.
+    pub(crate) fn winner(&self, m: &M) -> Option<u32> {
+        match self {
+            CompletionMethod::Schulze => schulze(m),
+            CompletionMethod::RankedPairs => ranked_pairs(m),
+        }
+    }
+}
+
+/// The margin by which x beats y, or zero if x does not beat y.
+fn victory(m: &M, (x, y): (u32, u32)) -> Uint128 {
+    match m.get((x, y)) {
+        Cell::Positive(p) => p,
+        Cell::Zero | Cell::Negative(_) => Uint128::zero(),
+    }
+}
+
+/// Returns the only candidate for which `wins(candidate)` holds, or
+/// `None` if zero or more than one candidate satisfies it.
+fn unique(n: u32, wins: impl Fn(u32) -> bool) -> Option<u32> {
+    let mut winners = (0..n).filter(|c| wins(*c));
+    match (winners.next(), winners.next()) {
+        (Some(winner), None) => Some(winner),
+        _ => None,
+    }
+}
+
+fn schulze(m: &M) -> Option<u32> {
+    let n = m.n as usize;
+    // p[x][y] is the strength of the strongest path from x to y,
+    // where a path's strength is the smallest margin along it.
+    let mut p: Vec<Vec<Uint128>> = (0..m.n)
+        .map(|x| {
+            (0..m.n)
+                .map(|y| {
+                    if x == y {
+                        Uint128::zero()
+                    } else {
+                        victory(m, (x, y))
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    for i in 0..n {
+        for j in 0..n {
+            if i != j {
+                for k in 0..n {
+                    if i != k && j != k {
+                        p[j][k] = p[j][k].max(p[j][i].min(p[i][k]))
+                    }
+                }
+            }
+        }
+    }
+    unique(m.n, |x| {
+        (0..n).all(|y| x as usize == y || p[x as usize][y] >= p[y][x as usize])
+    })
+}
+
+fn ranked_pairs(m: &M) -> Option<u32> {
+    let n = m.n as usize;
+    let mut victories = vec![];
+    for x in 0..m.n {
+        for y in (0..m.n).filter(|y| *y != x) {
+            let margin = victory(m, (x, y));
+            if !margin.is_zero() {
+                victories.push((margin, x, y))
+            }
+        }
+    }
+    // largest margins first. ties are broken by candidate index so
+    // that the outcome is deterministic.
+    victories.sort_by(|(a, ax, ay), (b, bx, by)| b.cmp(a).then((ax, ay).cmp(&(bx, by))));
+
+    // locked[x][y] is true if x's victory over y has been locked in.
+    let mut locked = vec![vec![false; n]; n];
+    for (_, x, y) in victories {
+        if !reachable(&locked, y as usize, x as usize) {
+            locked[x as usize][y as usize] = true;
+        }
+    }
+    unique(m.n, |y| (0..n).all(|x| !locked[x][y as usize]))
+}
+
+/// Returns true if `to` may be reached from `from` by following
+/// locked victories.
+fn reachable(locked: &[Vec<bool>], from: usize, to: usize) -> bool {
+    let mut seen = vec![false; locked.len()];
+    let mut stack = vec![from];
+    while let Some(node) = stack.pop() {
+        if node == to {
+            return true;
+        }
+        if !seen[node] {
+            seen[node] = true;
+            stack.extend((0..locked.len()).filter(|next| locked[node][*next] && !seen[*next]));
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a M from (winner, loser, margin) triples.
+    fn m_from(n: u32, victories: &[(u32, u32, u128)]) -> M {
+        let mut m = M::new(n);
+        for (x, y, margin) in victories {
+            m.increment((*x, *y), Uint128::new(*margin))
+        }
+        m
+    }
+
+    #[test]
+    fn test_condorcet_winner() {
+        let m = m_from(3, &[(1, 0, 2), (1, 2, 1), (0, 2, 3)]);
+        assert_eq!(CompletionMethod::Schulze.winner(&m), Some(1));
+        assert_eq!(CompletionMethod::RankedPairs.winner(&m), Some(1));
+    }
+
+    #[test]
+    fn test_cycle() {
+        // 0 > 1 by 5, 1 > 2 by 3, 2 > 0 by 1. the weakest link in
+        // the cycle is 2's victory over 0 so 0 wins.
+        let m = m_from(3, &[(0, 1, 5), (1, 2, 3), (2, 0, 1)]);
+        assert_eq!(CompletionMethod::Schulze.winner(&m), Some(0));
+        assert_eq!(CompletionMethod::RankedPairs.winner(&m), Some(0));
+    }
+
+    #[test]
+    fn test_four_candidate_cycle() {
+        // 0 > 1 by 6, 1 > 2 by 5, 2 > 3 by 4, 3 > 0 by 3,
+        // 0 > 2 by 2, 1 > 3 by 1
+        //
+        // locking victories from the largest margin down skips only
+        // 3 > 0, which would close the cycle 0 > 1 > 2 > 3 > 0, so
+        // nothing beats 0.
+        let m = m_from(
+            4,
+            &[
+                (0, 1, 6),
+                (1, 2, 5),
+                (2, 3, 4),
+                (3, 0, 3),
+                (0, 2, 2),
+                (1, 3, 1),
+            ],
+        );
+        assert_eq!(CompletionMethod::Schulze.winner(&m), Some(0));
+        assert_eq!(CompletionMethod::RankedPairs.winner(&m), Some(0));
+    }
+
+    #[test]
+    fn test_tie() {
+        let m = M::new(4);
+        assert_eq!(CompletionMethod::Schulze.winner(&m), None);
+        assert_eq!(CompletionMethod::RankedPairs.winner(&m), None);
+
+        // a perfectly balanced cycle has no Schulze winner.
+        let m = m_from(3, &[(0, 1, 1), (1, 2, 1), (2, 0, 1)]);
+        assert_eq!(CompletionMethod::Schulze.winner(&m), None);
+        // ranked pairs breaks the tie in margins by index: 0 > 1 and
+        // 1 > 2 are locked and 2 > 0 is skipped.
+        assert_eq!(CompletionMethod::RankedPairs.winner(&m), Some(0));
+    }
+}
@@ -5,7 +5,7 @@ use dao_voting::{
     voting::validate_voting_period,
 };
 
-use crate::ContractError;
+use crate::{completion::CompletionMethod, ContractError};
 
 #[cw_serde]
 pub struct UncheckedConfig {
@@ -13,6 +13,8 @@ pub struct UncheckedConfig {
     pub voting_period: Duration,
     pub min_voting_period: Option<Duration>,
     pub close_proposals_on_execution_failure: bool,
+    pub allow_revoting: bool,
+    pub completion: Option<CompletionMethod>,
 }
 
 #[cw_serde]
@@ -21,6 +23,8 @@ pub(crate) struct Config {
     pub voting_period: Duration,
     pub min_voting_period: Option<Duration>,
     pub close_proposals_on_execution_failure: bool,
+    pub allow_revoting: bool,
+    pub completion: Option<CompletionMethod>,
 }
 
 impl UncheckedConfig {
@@ -31,6 +35,8 @@ impl UncheckedConfig {
         Ok(Config {
             quorum: self.quorum,
             close_proposals_on_execution_failure: self.close_proposals_on_execution_failure,
+            allow_revoting: self.allow_revoting,
+            completion: self.completion,
             voting_period,
             min_voting_period,
         })
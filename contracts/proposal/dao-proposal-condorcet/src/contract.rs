@@ -1,20 +1,36 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
+    to_json_binary, to_json_string, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply,
+    Response, StdResult, Storage,
 };
 
 use cw2::set_contract_version;
-use dao_voting::reply::TaggedReplyId;
+use cw_hooks::Hooks;
+use cw_storage_plus::Bound;
+use cw_utils::parse_reply_instantiate_data;
+use dao_hooks::proposal::{
+    new_proposal_hooks, proposal_completed_hooks, proposal_status_changed_hooks,
+};
+use dao_hooks::vote::new_vote_hooks;
+use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
+use dao_voting::proposal::DEFAULT_LIMIT;
+use dao_voting::reply::{failed_pre_propose_module_hook_id, TaggedReplyId};
 use dao_voting::voting::{get_total_power, get_voting_power};
 
 use crate::config::UncheckedConfig;
 use crate::error::ContractError;
-use crate::msg::{Choice, ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::msg::{
+    Choice, ExecuteMsg, InstantiateMsg, ProposalListResponse, ProposeMsg, QueryMsg, VoteInfo,
+    VoteListResponse,
+};
 use crate::proposal::{Proposal, ProposalResponse, Status};
-use crate::state::{next_proposal_id, CONFIG, DAO, PROPOSAL, TALLY, VOTE};
+use crate::state::{
+    next_proposal_id, CONFIG, CREATION_POLICY, DAO, PROPOSAL, PROPOSAL_HOOKS, TALLY, VOTE,
+    VOTE_HOOKS,
+};
 use crate::tally::Tally;
-use crate::vote::Vote;
+use crate::vote::{Ballot, Vote};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-proposal-condorcet";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -28,10 +44,24 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    let config = UncheckedConfig {
+        quorum: msg.quorum,
+        voting_period: msg.voting_period,
+        min_voting_period: msg.min_voting_period,
+        close_proposals_on_execution_failure: msg.close_proposals_on_execution_failure,
+        allow_revoting: msg.allow_revoting,
+        completion: msg.completion,
+    };
+    let (initial_policy, pre_propose_messages) = msg
+        .pre_propose_info
+        .into_initial_policy_and_messages(info.sender.clone())?;
+
     DAO.save(deps.storage, &info.sender)?;
-    CONFIG.save(deps.storage, &msg.into_checked()?)?;
+    CONFIG.save(deps.storage, &config.into_checked()?)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
 
     Ok(Response::default()
+        .add_submessages(pre_propose_messages)
         .add_attribute("method", "instantiate")
         .add_attribute("creator", info.sender))
 }
@@ -63,22 +93,31 @@ pub fn instantiate(
 // usize, so `sizeof(Vec<u32>) <= sizeof(Vec<usize>) <=
 // sizeof(Vec<Vec<CosmosMsg>) => sizeof(vote) <= sizeof(proposal)`.
 //
+// a vote is stored along with the voting power it was cast with, a
+// `Uint128`, which is smaller than the proposal's `total_power`.
+//
 // in terms of other costs:
 //
-// propose: query_voting_power + compute_winner [2]
+// propose: query_voting_power + record_ballot + remove_ballot + compute_winner [2]
 // execute: query_voting_power
-// vote:    query_voting_power + compute_winner
+// vote:    query_voting_power + record_ballot + compute_winner
+// revote:  query_voting_power + record_ballot + remove_ballot + compute_winner
 //
 // so we're good there as well.
 //
+// hooks are not counted above. vote hooks are only loaded when voting
+// and proposal hooks when proposing and executing. the number of hook
+// consumers is bounded by the DAO which adds them, and a DAO that
+// adds enough consumers to exhaust gas limits may remove them.
+//
 // [1] we need to be gas efficent in this way because the size of the
 //     Tally type grows with candidates^2 and thus can be too large to
 //     load from storage. we need to make sure that if this is the
 //     case, the proposal fails to be created. the bad outcome we're
 //     trying to avoid here is a proposal that is created but can not
 //     be voted on or executed.
-// [2] Tally::new computes the winner over the new matrix so that this
-//     is the case.
+// [2] Tally::new records and removes a ballot, and computes the winner
+//     over the new matrix so that this is the case.
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
@@ -88,12 +127,27 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Propose { choices } => execute_propose(deps, env, info, choices),
+        ExecuteMsg::Propose(propose) => execute_propose(deps, env, info, propose),
         ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
 
         ExecuteMsg::SetConfig(config) => execute_set_config(deps, info, config),
+        ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
+            execute_update_proposal_creation_policy(deps, info, new_info)
+        }
+        ExecuteMsg::AddProposalHook { address } => {
+            execute_add_hook(deps, info, PROPOSAL_HOOKS, "add_proposal_hook", address)
+        }
+        ExecuteMsg::RemoveProposalHook { address } => {
+            execute_remove_hook(deps, info, PROPOSAL_HOOKS, "remove_proposal_hook", address)
+        }
+        ExecuteMsg::AddVoteHook { address } => {
+            execute_add_hook(deps, info, VOTE_HOOKS, "add_vote_hook", address)
+        }
+        ExecuteMsg::RemoveVoteHook { address } => {
+            execute_remove_hook(deps, info, VOTE_HOOKS, "remove_vote_hook", address)
+        }
     }
 }
 
@@ -101,14 +155,40 @@ fn execute_propose(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    choices: Vec<Choice>,
+    ProposeMsg {
+        title,
+        description,
+        choices,
+        proposer,
+    }: ProposeMsg,
 ) -> Result<Response, ContractError> {
     let dao = DAO.load(deps.storage)?;
-    let sender_voting_power = get_voting_power(deps.as_ref(), info.sender.clone(), &dao, None)?;
-    if sender_voting_power.is_zero() {
-        return Err(ContractError::ZeroVotingPower {});
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+
+    // Check that the sender is permitted to create proposals.
+    if !proposal_creation_policy.is_permitted(&info.sender) {
+        return Err(ContractError::Unauthorized {});
     }
 
+    // Determine the appropriate proposer. If this is coming from our
+    // pre-propose module, it must be specified and the module is
+    // responsible for deciding who may propose. Otherwise, the
+    // proposer should not be specified and must have voting power.
+    let proposer = match (proposer, &proposal_creation_policy) {
+        (None, ProposalCreationPolicy::Anyone {}) => {
+            let sender_voting_power =
+                get_voting_power(deps.as_ref(), info.sender.clone(), &dao, None)?;
+            if sender_voting_power.is_zero() {
+                return Err(ContractError::ZeroVotingPower {});
+            }
+            info.sender
+        }
+        (Some(proposer), ProposalCreationPolicy::Module { .. }) => {
+            deps.api.addr_validate(&proposer)?
+        }
+        _ => return Err(ContractError::InvalidProposer {}),
+    };
+
     let config = CONFIG.load(deps.storage)?;
 
     let id = next_proposal_id(deps.storage)?;
@@ -118,7 +198,11 @@ fn execute_propose(
         return Err(ContractError::ZeroChoices {});
     }
 
-    let none_of_the_above = Choice { msgs: vec![] };
+    let none_of_the_above = Choice {
+        title: "None of the above".to_string(),
+        description: "".to_string(),
+        msgs: vec![],
+    };
     let mut choices = choices;
     choices.push(none_of_the_above);
 
@@ -127,14 +211,33 @@ fn execute_propose(
         total_power,
         env.block.height,
         config.voting_period.after(&env.block),
+        config.allow_revoting,
+        config.completion,
     );
     TALLY.save(deps.storage, id, &tally)?;
 
-    let mut proposal = Proposal::new(&env.block, &config, info.sender, id, choices, total_power);
+    let mut proposal = Proposal::new(
+        &env.block,
+        &config,
+        proposer,
+        id,
+        title,
+        description,
+        choices,
+        total_power,
+    );
     proposal.update_status(&env.block, &tally);
     PROPOSAL.save(deps.storage, id, &proposal)?;
 
+    let hooks = new_proposal_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        id as u64,
+        proposal.proposer.as_str(),
+    )?;
+
     Ok(Response::default()
+        .add_submessages(hooks)
         .add_attribute("method", "propose")
         .add_attribute("proposal_id", proposal.id.to_string())
         .add_attribute("proposer", proposal.proposer))
@@ -154,21 +257,49 @@ fn execute_vote(
         &DAO.load(deps.storage)?,
         Some(tally.start_height),
     )?;
+    let previous = VOTE.may_load(deps.storage, (proposal_id, info.sender.clone()))?;
     if sender_power.is_zero() {
         Err(ContractError::ZeroVotingPower {})
-    } else if VOTE.has(deps.storage, (proposal_id, info.sender.clone())) {
+    } else if previous.is_some() && !tally.allow_revoting {
         Err(ContractError::Voted {})
     } else if tally.expired(&env.block) {
         Err(ContractError::Expired {})
     } else {
         let vote = Vote::new(vote, tally.candidates())?;
-        VOTE.save(deps.storage, (proposal_id, info.sender.clone()), &vote)?;
+        if previous
+            .as_ref()
+            .map_or(false, |ballot| ballot.vote == vote)
+        {
+            return Err(ContractError::AlreadyCast {});
+        }
+        VOTE.save(
+            deps.storage,
+            (proposal_id, info.sender.clone()),
+            &Ballot {
+                vote: vote.clone(),
+                power: sender_power,
+            },
+        )?;
+
+        let hooks = new_vote_hooks(
+            VOTE_HOOKS,
+            deps.storage,
+            proposal_id as u64,
+            info.sender.to_string(),
+            to_json_string(&vote)?,
+        )?;
 
         let mut tally = tally;
-        tally.add_vote(vote, sender_power);
+        match previous {
+            Some(Ballot { vote: old, power }) => {
+                tally.change_vote((old, power), (vote, sender_power))
+            }
+            None => tally.add_vote(vote, sender_power),
+        }
         TALLY.save(deps.storage, proposal_id, &tally)?;
 
         Ok(Response::default()
+            .add_submessages(hooks)
             .add_attribute("method", "vote")
             .add_attribute("proposal_id", proposal_id.to_string())
             .add_attribute("voter", info.sender)
@@ -195,11 +326,21 @@ fn execute_execute(
     }
 
     let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
-    if let Status::Passed { winner } = proposal.update_status(&env.block, &tally) {
+    let old_status = proposal.update_status(&env.block, &tally);
+    if let Status::Passed { winner } = old_status {
         let msgs = proposal.set_executed(dao, winner)?;
         PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
 
+        let hooks = completion_hooks(
+            deps.storage,
+            proposal_id,
+            old_status,
+            Status::Executed,
+            dao_voting::status::Status::Executed,
+        )?;
+
         Ok(Response::default()
+            .add_submessages(hooks)
             .add_attribute("method", "execute")
             .add_attribute("proposal_id", proposal_id.to_string())
             .add_attribute("executor", info.sender)
@@ -217,11 +358,21 @@ fn execute_close(
 ) -> Result<Response, ContractError> {
     let tally = TALLY.load(deps.storage, proposal_id)?;
     let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
-    if let Status::Rejected = proposal.update_status(&env.block, &tally) {
+    let old_status = proposal.update_status(&env.block, &tally);
+    if let Status::Rejected = old_status {
         proposal.set_closed();
         PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
 
+        let hooks = completion_hooks(
+            deps.storage,
+            proposal_id,
+            old_status,
+            Status::Closed,
+            dao_voting::status::Status::Closed,
+        )?;
+
         Ok(Response::default()
+            .add_submessages(hooks)
             .add_attribute("method", "close")
             .add_attribute("proposal_id", proposal_id.to_string())
             .add_attribute("closer", info.sender))
@@ -245,6 +396,86 @@ fn execute_set_config(
     }
 }
 
+/// Proposal status changed hooks along with the hook notifying the
+/// pre-propose module (if any) that the proposal has completed.
+fn completion_hooks(
+    storage: &dyn Storage,
+    proposal_id: u32,
+    old_status: Status,
+    new_status: Status,
+    completed_status: dao_voting::status::Status,
+) -> Result<Vec<cosmwasm_std::SubMsg>, ContractError> {
+    let mut hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        storage,
+        proposal_id as u64,
+        old_status.to_string(),
+        new_status.to_string(),
+    )?;
+    hooks.extend(proposal_completed_hooks(
+        CREATION_POLICY.load(storage)?,
+        proposal_id as u64,
+        completed_status,
+    )?);
+    Ok(hooks)
+}
+
+fn execute_update_proposal_creation_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_info: PreProposeInfo,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::NotDao {});
+    }
+
+    let (initial_policy, messages) = new_info.into_initial_policy_and_messages(dao)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+
+    Ok(Response::default()
+        .add_submessages(messages)
+        .add_attribute("method", "update_proposal_creation_policy")
+        .add_attribute("sender", info.sender)
+        .add_attribute("new_policy", format!("{initial_policy:?}")))
+}
+
+fn execute_add_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    hooks: Hooks,
+    method: &str,
+    address: String,
+) -> Result<Response, ContractError> {
+    if info.sender != DAO.load(deps.storage)? {
+        return Err(ContractError::NotDao {});
+    }
+    let address = deps.api.addr_validate(&address)?;
+    hooks.add_hook(deps.storage, address.clone())?;
+
+    Ok(Response::default()
+        .add_attribute("method", method)
+        .add_attribute("address", address))
+}
+
+fn execute_remove_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    hooks: Hooks,
+    method: &str,
+    address: String,
+) -> Result<Response, ContractError> {
+    if info.sender != DAO.load(deps.storage)? {
+        return Err(ContractError::NotDao {});
+    }
+    let address = deps.api.addr_validate(&address)?;
+    hooks.remove_hook(deps.storage, address.clone())?;
+
+    Ok(Response::default()
+        .add_attribute("method", method)
+        .add_attribute("address", address))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -252,8 +483,55 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             let mut proposal = PROPOSAL.load(deps.storage, id)?;
             let tally = TALLY.load(deps.storage, id)?;
             proposal.update_status(&env.block, &tally);
-            to_json_binary(&ProposalResponse { proposal, tally })
+            to_json_binary(&ProposalResponse::new(proposal, tally))
         }
+        QueryMsg::ListProposals { start_after, limit } => {
+            let limit = limit.map_or(DEFAULT_LIMIT, |l| l as u64) as usize;
+            let proposals = PROPOSAL
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .map(|item| {
+                    let (id, mut proposal) = item?;
+                    let tally = TALLY.load(deps.storage, id)?;
+                    proposal.update_status(&env.block, &tally);
+                    Ok(ProposalResponse::new(proposal, tally))
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&ProposalListResponse { proposals })
+        }
+        QueryMsg::ListVotes {
+            proposal_id,
+            start_after,
+            limit,
+        } => {
+            let limit = limit.map_or(DEFAULT_LIMIT, |l| l as u64) as usize;
+            let start_after = start_after
+                .map(|addr| deps.api.addr_validate(&addr))
+                .transpose()?;
+            let votes = VOTE
+                .prefix(proposal_id)
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .map(|item| {
+                    let (voter, Ballot { vote, power }) = item?;
+                    Ok(VoteInfo { voter, vote, power })
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&VoteListResponse { votes })
+        }
+        QueryMsg::ProposalCreationPolicy {} => to_json_binary(&CREATION_POLICY.load(deps.storage)?),
+        QueryMsg::ProposalHooks {} => to_json_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
+        QueryMsg::VoteHooks {} => to_json_binary(&VOTE_HOOKS.query_hooks(deps)?),
         QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
         QueryMsg::NextProposalId {} => to_json_binary(&next_proposal_id(deps.storage)?),
         QueryMsg::Dao {} => to_json_binary(&DAO.load(deps.storage)?),
@@ -279,6 +557,47 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
                     msg.result.into_result().err().unwrap_or("None".to_string()),
                 ))
         }
-        _ => unimplemented!("pre-propose and hooks not yet supported"),
+        TaggedReplyId::FailedProposalHook(idx) => {
+            let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::new().add_attribute("removed_proposal_hook", format!("{addr}:{idx}")))
+        }
+        TaggedReplyId::FailedVoteHook(idx) => {
+            let addr = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::new().add_attribute("removed_vote_hook", format!("{addr}:{idx}")))
+        }
+        TaggedReplyId::PreProposeModuleInstantiation => {
+            let res = parse_reply_instantiate_data(msg)?;
+
+            let module = deps.api.addr_validate(&res.contract_address)?;
+            CREATION_POLICY.save(
+                deps.storage,
+                &ProposalCreationPolicy::Module { addr: module },
+            )?;
+
+            match res.data {
+                Some(data) => Ok(Response::new()
+                    .add_attribute("update_pre_propose_module", res.contract_address)
+                    .set_data(data)),
+                None => Ok(Response::new()
+                    .add_attribute("update_pre_propose_module", res.contract_address)),
+            }
+        }
+        TaggedReplyId::FailedPreProposeModuleHook => {
+            let addr: Addr = match CREATION_POLICY.load(deps.storage)? {
+                ProposalCreationPolicy::Anyone {} => {
+                    // This should be unreachable as we only send
+                    // hooks to a pre-propose module if one is
+                    // installed.
+                    return Err(ContractError::InvalidReplyID {
+                        id: failed_pre_propose_module_hook_id(),
+                    });
+                }
+                ProposalCreationPolicy::Module { addr } => {
+                    CREATION_POLICY.save(deps.storage, &ProposalCreationPolicy::Anyone {})?;
+                    addr
+                }
+            };
+            Ok(Response::new().add_attribute("failed_prepropose_hook", format!("{addr}")))
+        }
     }
 }
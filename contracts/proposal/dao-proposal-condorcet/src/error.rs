@@ -1,4 +1,6 @@
 use cosmwasm_std::StdError;
+use cw_hooks::HookError;
+use cw_utils::ParseReplyError;
 use dao_voting::{error::VotingError, reply::error::TagError, threshold::ThresholdError};
 use thiserror::Error;
 
@@ -16,6 +18,10 @@ pub enum ContractError {
     Voting(#[from] VotingError),
     #[error(transparent)]
     Tag(#[from] TagError),
+    #[error(transparent)]
+    Hook(#[from] HookError),
+    #[error(transparent)]
+    ParseReply(#[from] ParseReplyError),
 
     #[error("non-zero voting power required to perform this action")]
     ZeroVotingPower {},
@@ -29,9 +35,21 @@ pub enum ContractError {
     #[error("only the DAO my perform this action")]
     NotDao {},
 
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("the proposer must be specified if and only if proposals are created by a pre-propose module")]
+    InvalidProposer {},
+
+    #[error("received reply for an unknown ID ({id})")]
+    InvalidReplyID { id: u64 },
+
     #[error("already voted")]
     Voted {},
 
+    #[error("already cast a vote with that ranking")]
+    AlreadyCast {},
+
     #[error("only non-expired proposals may be voted on")]
     Expired {},
 
@@ -1,24 +1,89 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{CosmosMsg, Empty};
+use cosmwasm_std::{Addr, CosmosMsg, Empty, Uint128};
+use cw_utils::Duration;
 
 use dao_dao_macros::proposal_module_query;
+use dao_voting::{pre_propose::PreProposeInfo, threshold::PercentageThreshold};
 
-use crate::config::UncheckedConfig;
+use crate::{
+    completion::CompletionMethod, config::UncheckedConfig, proposal::ProposalResponse, vote::Vote,
+};
 
-pub type InstantiateMsg = UncheckedConfig;
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub quorum: PercentageThreshold,
+    pub voting_period: Duration,
+    pub min_voting_period: Option<Duration>,
+    pub close_proposals_on_execution_failure: bool,
+    /// Whether voters may change their ranking while a proposal is
+    /// open. If enabled, proposals do not pass or get rejected
+    /// before their voting period ends.
+    pub allow_revoting: bool,
+    /// The method used to elect a winner when voting ends without a
+    /// Condorcet winner. If `None`, such proposals are rejected.
+    pub completion: Option<CompletionMethod>,
+    /// Information about what addresses may create proposals.
+    pub pre_propose_info: PreProposeInfo,
+}
 
 #[cw_serde]
 pub struct Choice {
+    /// The title of the choice.
+    pub title: String,
+    /// A description of the choice.
+    pub description: String,
+    /// The messages to be executed if this choice wins.
     pub msgs: Vec<CosmosMsg<Empty>>,
 }
 
+#[cw_serde]
+pub struct ProposeMsg {
+    /// The title of the proposal.
+    pub title: String,
+    /// A description of the proposal.
+    pub description: String,
+    /// The choices being voted on. A "none of the above" choice is
+    /// added to the end of this list.
+    pub choices: Vec<Choice>,
+    /// The address creating the proposal. If no pre-propose module
+    /// is attached to this module this must always be None as the
+    /// proposer is the sender of the propose message. If a
+    /// pre-propose module is attached, this must be Some and will
+    /// set the proposer of the proposal it creates.
+    pub proposer: Option<String>,
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
-    Propose { choices: Vec<Choice> },
-    Vote { proposal_id: u32, vote: Vec<u32> },
-    Execute { proposal_id: u32 },
-    Close { proposal_id: u32 },
+    Propose(ProposeMsg),
+    Vote {
+        proposal_id: u32,
+        vote: Vec<u32>,
+    },
+    Execute {
+        proposal_id: u32,
+    },
+    Close {
+        proposal_id: u32,
+    },
     SetConfig(UncheckedConfig),
+    /// Update's the proposal creation policy used for this
+    /// module. Only the DAO may call this method.
+    UpdatePreProposeInfo {
+        info: PreProposeInfo,
+    },
+    AddProposalHook {
+        address: String,
+    },
+    RemoveProposalHook {
+        address: String,
+    },
+    AddVoteHook {
+        address: String,
+    },
+    RemoveVoteHook {
+        address: String,
+    },
 }
 
 #[proposal_module_query]
@@ -27,6 +92,50 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(crate::proposal::ProposalResponse)]
     Proposal { id: u32 },
+    /// Lists the proposals in this module in ascending order of ID.
+    #[returns(ProposalListResponse)]
+    ListProposals {
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+    /// Lists the votes cast on a proposal in ascending order of
+    /// voter address.
+    #[returns(VoteListResponse)]
+    ListVotes {
+        proposal_id: u32,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     #[returns(crate::config::Config)]
     Config {},
+    /// Gets the current proposal creation policy for this module.
+    #[returns(::dao_voting::pre_propose::ProposalCreationPolicy)]
+    ProposalCreationPolicy {},
+    /// Lists all of the consumers of proposal hooks for this module.
+    #[returns(::cw_hooks::HooksResponse)]
+    ProposalHooks {},
+    /// Lists all of the consumers of vote hooks for this module.
+    #[returns(::cw_hooks::HooksResponse)]
+    VoteHooks {},
+}
+
+#[cw_serde]
+pub struct ProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+#[cw_serde]
+pub struct VoteInfo {
+    /// The address that voted.
+    pub voter: Addr,
+    /// The voter's ranking of the proposal's choices, from most to
+    /// least preferred.
+    pub vote: Vote,
+    /// The voting power of the voter.
+    pub power: Uint128,
+}
+
+#[cw_serde]
+pub struct VoteListResponse {
+    pub votes: Vec<VoteInfo>,
 }
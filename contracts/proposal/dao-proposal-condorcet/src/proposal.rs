@@ -7,6 +7,7 @@ use dao_voting::{
 };
 
 use crate::{
+    cell::Cell,
     config::Config,
     msg::Choice,
     tally::{Tally, Winner},
@@ -25,6 +26,8 @@ pub struct Proposal {
     pub total_power: Uint128,
 
     pub id: u32,
+    pub title: String,
+    pub description: String,
     pub choices: Vec<Choice>,
 }
 
@@ -46,10 +49,35 @@ pub enum Status {
     ExecutionFailed,
 }
 
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Open => write!(f, "open"),
+            Status::Rejected => write!(f, "rejected"),
+            Status::Passed { .. } => write!(f, "passed"),
+            Status::Executed => write!(f, "executed"),
+            Status::Closed => write!(f, "closed"),
+            Status::ExecutionFailed => write!(f, "execution_failed"),
+        }
+    }
+}
+
 #[cw_serde]
 pub struct ProposalResponse {
     pub proposal: Proposal,
     pub tally: Tally,
+    /// The tally's full pairwise matrix. See `Tally::pairwise`.
+    pub pairwise: Vec<Vec<Cell>>,
+}
+
+impl ProposalResponse {
+    pub(crate) fn new(proposal: Proposal, tally: Tally) -> Self {
+        Self {
+            pairwise: tally.pairwise(),
+            proposal,
+            tally,
+        }
+    }
 }
 
 fn status(block: &BlockInfo, proposal: &Proposal, tally: &Tally) -> Status {
@@ -69,6 +97,13 @@ fn status(block: &BlockInfo, proposal: &Proposal, tally: &Tally) -> Status {
 
             let winner = tally.winner;
             let expired = tally.expiration.is_expired(block);
+
+            // votes may change until the proposal expires so no
+            // outcome is final before then.
+            if tally.allow_revoting && !expired {
+                return Status::Open;
+            }
+
             let quorum = does_vote_count_pass(
                 proposal.total_power - tally.power_outstanding,
                 proposal.total_power,
@@ -79,9 +114,15 @@ fn status(block: &BlockInfo, proposal: &Proposal, tally: &Tally) -> Status {
                 Status::Rejected
             } else {
                 match winner {
-                    Winner::Never => Status::Rejected,
-                    Winner::None => {
+                    Winner::Never | Winner::None => {
                         if expired {
+                            // quorum has been reached but there is no
+                            // Condorcet winner. fall back to the
+                            // completion method, if any.
+                            tally
+                                .complete()
+                                .map_or(Status::Rejected, |winner| Status::Passed { winner })
+                        } else if winner == Winner::Never && tally.completion.is_none() {
                             Status::Rejected
                         } else {
                             Status::Open
@@ -108,11 +149,14 @@ fn status(block: &BlockInfo, proposal: &Proposal, tally: &Tally) -> Status {
 }
 
 impl Proposal {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         block: &BlockInfo,
         config: &Config,
         proposer: Addr,
         id: u32,
+        title: String,
+        description: String,
         choices: Vec<Choice>,
         total_power: Uint128,
     ) -> Self {
@@ -125,6 +169,8 @@ impl Proposal {
 
             id,
             proposer,
+            title,
+            description,
             choices,
             total_power,
         }
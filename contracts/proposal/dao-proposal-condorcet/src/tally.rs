@@ -3,6 +3,8 @@ use cosmwasm_std::{BlockInfo, Uint128};
 use cw_utils::Expiration;
 
 use crate::{
+    cell::Cell,
+    completion::CompletionMethod,
     m::{Stats, M},
     vote::Vote,
 };
@@ -30,6 +32,13 @@ pub struct Tally {
     pub power_outstanding: Uint128,
     /// The current winner. Always up to date and updated on vote.
     pub winner: Winner,
+    /// Whether voters may change their votes. Stored here rather
+    /// than on the proposal so that voting need not load the
+    /// proposal.
+    pub allow_revoting: bool,
+    /// The method used to elect a winner if voting ends without a
+    /// Condorcet winner. If `None`, such proposals are rejected.
+    pub completion: Option<CompletionMethod>,
 }
 
 #[cw_serde]
@@ -47,6 +56,8 @@ impl Tally {
         total_power: Uint128,
         start_height: u64,
         expiration: Expiration,
+        allow_revoting: bool,
+        completion: Option<CompletionMethod>,
     ) -> Self {
         let mut tally = Self {
             m: M::new(candidates),
@@ -54,11 +65,24 @@ impl Tally {
             winner: Winner::None,
             start_height,
             expiration,
+            allow_revoting,
+            completion,
         };
-        // compute even though this will always be Winner::None so
-        // that creating a tally has the same compute cost of adding a
+        // record and then remove a ballot, and compute the winner
+        // even though this will always be Winner::None so that
+        // creating a tally has the same compute cost of changing a
         // vote which is needed so that gas(proposal_creation) >=
         // gas(vote).
+        //
+        // the completion method is run while the ballot is recorded
+        // as every victory is then locked in by ranked pairs, its
+        // most expensive case, so that gas(proposal_creation) >=
+        // gas(execute). `black_box` keeps the result from being
+        // optimized away.
+        let ballot = (0..candidates).collect::<Vec<u32>>();
+        tally.record(&ballot, Uint128::one());
+        std::hint::black_box(tally.complete());
+        tally.unrecord(&ballot, Uint128::one());
         tally.winner = tally.winner();
         tally
     }
@@ -82,6 +106,57 @@ impl Tally {
     /// - Voter has not already voted.
     /// - Tally is not expired.
     pub fn add_vote(&mut self, vote: Vote, power: Uint128) {
+        self.record(vote.as_slice(), power);
+        self.power_outstanding -= power;
+        self.winner = self.winner();
+    }
+
+    /// Replaces a vote previously recorded in the tally with a new
+    /// one. The tally must not be expired.
+    ///
+    ///  - `old` the vote being replaced and the power it was cast with
+    ///  - `new` the new vote and the power it is cast with
+    ///
+    /// Invariants:
+    ///
+    /// - `old` was previously added with `add_vote` or `change_vote`.
+    /// - Tally is not expired.
+    pub fn change_vote(&mut self, old: (Vote, Uint128), new: (Vote, Uint128)) {
+        self.unrecord(old.0.as_slice(), old.1);
+        self.power_outstanding += old.1;
+        self.record(new.0.as_slice(), new.1);
+        self.power_outstanding -= new.1;
+        self.winner = self.winner();
+    }
+
+    /// Computes the winner using the tally's completion method. This
+    /// is only used once voting has ended without a Condorcet
+    /// winner. Returns `None` if there is no completion method or if
+    /// it ends in a tie.
+    pub fn complete(&self) -> Option<u32> {
+        self.completion.and_then(|method| method.winner(&self.m))
+    }
+
+    /// The full pairwise matrix where `pairwise[x][y]` is the number
+    /// of times x has beaten y minus the number of times y has beaten
+    /// x, weighted by voting power.
+    pub fn pairwise(&self) -> Vec<Vec<Cell>> {
+        (0..self.m.n)
+            .map(|x| {
+                (0..self.m.n)
+                    .map(|y| {
+                        if x == y {
+                            Cell::Zero
+                        } else {
+                            self.m.get((x, y))
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn record(&mut self, vote: &[u32], power: Uint128) {
         for (index, preference) in vote.iter().enumerate() {
             // an interesting property of the symetry of M is that in
             // recording all the defeats, we also record all of the
@@ -90,8 +165,16 @@ impl Tally {
                 self.m.decrement((*preference, vote[defeat]), power)
             }
         }
-        self.power_outstanding -= power;
-        self.winner = self.winner();
+    }
+
+    /// Inverse of `record`. Incrementing where `record` decrements
+    /// removes a ballot's pairwise contributions from M.
+    fn unrecord(&mut self, vote: &[u32], power: Uint128) {
+        for (index, preference) in vote.iter().enumerate() {
+            for defeat in 0..index {
+                self.m.increment((*preference, vote[defeat]), power)
+            }
+        }
     }
 
     fn winner(&self) -> Winner {
@@ -24,6 +24,8 @@ fn test_instantiate_conflicting_proposal_durations() {
         voting_period: Duration::Height(10),
         min_voting_period: Some(Duration::Height(11)),
         close_proposals_on_execution_failure: true,
+        allow_revoting: false,
+        completion: None,
     })
     .build();
 }
@@ -38,6 +40,8 @@ fn test_instantiate_conflicting_duration_types() {
         voting_period: Duration::Height(10),
         min_voting_period: Some(Duration::Time(9)),
         close_proposals_on_execution_failure: true,
+        allow_revoting: false,
+        completion: None,
     })
     .build();
 }
@@ -49,6 +53,8 @@ fn test_instantiate_open_til_expiry() {
         voting_period: Duration::Height(10),
         min_voting_period: Some(Duration::Height(10)),
         close_proposals_on_execution_failure: true,
+        allow_revoting: false,
+        completion: None,
     })
     .build();
     SuiteBuilder::with_config(UncheckedConfig {
@@ -56,6 +62,8 @@ fn test_instantiate_open_til_expiry() {
         voting_period: Duration::Time(10),
         min_voting_period: Some(Duration::Time(10)),
         close_proposals_on_execution_failure: true,
+        allow_revoting: false,
+        completion: None,
     })
     .build();
 }
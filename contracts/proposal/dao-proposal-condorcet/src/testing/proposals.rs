@@ -1,12 +1,15 @@
-use cosmwasm_std::{to_json_binary, WasmMsg};
+use cosmwasm_std::{to_json_binary, Uint128, WasmMsg};
 use cw_utils::Duration;
 
 use crate::{
+    cell::Cell,
+    completion::CompletionMethod,
     config::UncheckedConfig,
     msg::ExecuteMsg,
     proposal::{ProposalResponse, Status},
     tally::Winner,
     testing::suite::unimportant_message,
+    vote::Vote,
     ContractError,
 };
 
@@ -52,7 +55,9 @@ fn test_make_proposal() {
     let id = suite
         .propose(suite.sender(), vec![vec![unimportant_message()]])
         .unwrap();
-    let ProposalResponse { proposal, tally } = suite.query_proposal(id);
+    let ProposalResponse {
+        proposal, tally, ..
+    } = suite.query_proposal(id);
 
     assert_eq!(proposal.id, id);
     assert_eq!(proposal.choices.len(), 2);
@@ -176,6 +181,8 @@ fn test_proposal_set_config() {
                     voting_period: config.voting_period,
                     min_voting_period: None,
                     close_proposals_on_execution_failure: false,
+                    allow_revoting: false,
+                    completion: None,
                 }))
                 .unwrap(),
                 funds: vec![],
@@ -199,6 +206,8 @@ fn test_proposal_set_config() {
                     voting_period: config.voting_period,
                     min_voting_period: Some(Duration::Height(10)),
                     close_proposals_on_execution_failure: false,
+                    allow_revoting: false,
+                    completion: None,
                 }))
                 .unwrap(),
                 funds: vec![],
@@ -236,3 +245,144 @@ fn test_execution_fail_handling() {
     // no state changes get committed.
     suite.execute(suite.sender(), 1).unwrap_err();
 }
+
+#[test]
+fn test_list_proposals_and_votes() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("blue", 10), ("violet", 5)])
+        .with_proposal(2)
+        .build();
+    suite
+        .propose("blue", vec![vec![unimportant_message()]])
+        .unwrap();
+
+    let proposals = suite.query_list_proposals(None, None).proposals;
+    assert_eq!(proposals.len(), 2);
+    assert_eq!(proposals[0].proposal.title, "title");
+    assert_eq!(proposals[0].proposal.choices[0].title, "choice 0");
+    assert_eq!(proposals[0].proposal.choices[2].title, "None of the above");
+
+    let proposals = suite.query_list_proposals(Some(1), Some(10)).proposals;
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposals[0].proposal.id, 2);
+
+    suite.vote("violet", 1, vec![1, 0, 2]).unwrap();
+    suite.vote("blue", 1, vec![0, 2, 1]).unwrap();
+
+    let votes = suite.query_list_votes(1, None, None).votes;
+    assert_eq!(votes.len(), 2);
+    assert_eq!(votes[0].voter.as_str(), "blue");
+    assert_eq!(votes[0].power.u128(), 10);
+    assert_eq!(votes[1].voter.as_str(), "violet");
+    assert_eq!(votes[1].power.u128(), 5);
+
+    let votes = suite
+        .query_list_votes(1, Some("blue".to_string()), None)
+        .votes;
+    assert_eq!(votes.len(), 1);
+    assert_eq!(votes[0].voter.as_str(), "violet");
+}
+
+#[test]
+fn test_vote_hooks() {
+    let mut suite = SuiteBuilder::default().with_proposal(2).build();
+
+    let err = suite.add_vote_hook(suite.sender(), "consumer");
+    is_error!(err, &ContractError::NotDao {}.to_string());
+
+    suite.add_vote_hook(suite.core.clone(), "consumer").unwrap();
+    assert_eq!(suite.query_vote_hooks(), vec!["consumer".to_string()]);
+
+    // "consumer" is not a contract so the hook fails and is removed.
+    suite.vote(suite.sender(), 1, vec![0, 1, 2]).unwrap();
+    assert!(suite.query_vote_hooks().is_empty());
+}
+
+#[test]
+fn test_revoting() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("blue", 10), ("violet", 5)])
+        .with_proposal(2);
+    suite.instantiate.allow_revoting = true;
+    let mut suite = suite.build();
+
+    suite.vote("blue", 1, vec![0, 1, 2]).unwrap();
+    suite.vote("violet", 1, vec![0, 1, 2]).unwrap();
+
+    // an undisputed winner does not pass the proposal early as votes
+    // may still change.
+    suite.a_day_passes();
+    let (winner, status) = suite.query_winner_and_status(1);
+    assert_eq!(winner, Winner::Undisputed(0));
+    assert_eq!(status, Status::Open);
+
+    suite.vote("blue", 1, vec![1, 0, 2]).unwrap();
+    let (winner, _) = suite.query_winner_and_status(1);
+    assert_eq!(winner, Winner::Undisputed(1));
+
+    let err = suite.vote("blue", 1, vec![1, 0, 2]);
+    is_error!(err, &ContractError::AlreadyCast {}.to_string());
+
+    let votes = suite.query_list_votes(1, None, None).votes;
+    assert_eq!(votes.len(), 2);
+    assert_eq!(votes[0].vote, Vote::new(vec![1, 0, 2], 3).unwrap());
+
+    suite.a_week_passes();
+    let err = suite.vote("blue", 1, vec![0, 1, 2]);
+    is_error!(err, &ContractError::Expired {}.to_string());
+
+    let (_, status) = suite.query_winner_and_status(1);
+    assert_eq!(status, Status::Passed { winner: 1 });
+    suite.execute("blue", 1).unwrap();
+}
+
+#[test]
+fn test_no_revoting() {
+    let mut suite = SuiteBuilder::default().with_proposal(2).build();
+
+    suite.vote(suite.sender(), 1, vec![0, 1, 2]).unwrap();
+    let err = suite.vote(suite.sender(), 1, vec![1, 0, 2]);
+    is_error!(err, &ContractError::Voted {}.to_string());
+}
+
+// there is no Condorcet winner as 0 and 1 tie, but 0 beats 2 which
+// beats 1 so the completion method elects 0 once voting ends.
+#[test]
+fn test_completion_method() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[
+            ("blue", 10),
+            ("violet", 10),
+            ("magenta", 10),
+            ("gold", 10),
+            ("crimson", 10),
+            ("turquoise", 10),
+        ])
+        .with_proposal(2);
+    suite.instantiate.completion = Some(CompletionMethod::Schulze);
+    let mut suite = suite.build();
+
+    suite.vote("blue", 1, vec![0, 2, 1]).unwrap();
+    suite.vote("violet", 1, vec![1, 0, 2]).unwrap();
+    suite.vote("magenta", 1, vec![2, 1, 0]).unwrap();
+    suite.vote("gold", 1, vec![1, 0, 2]).unwrap();
+    suite.vote("crimson", 1, vec![0, 2, 1]).unwrap();
+    suite.vote("turquoise", 1, vec![2, 0, 1]).unwrap();
+
+    // not rejected early as the completion method may still elect a
+    // winner.
+    suite.a_day_passes();
+    let (winner, status) = suite.query_winner_and_status(1);
+    assert_eq!(winner, Winner::Never);
+    assert_eq!(status, Status::Open);
+
+    let ProposalResponse { pairwise, .. } = suite.query_proposal(1);
+    assert_eq!(pairwise[0][1], Cell::Zero);
+    assert_eq!(pairwise[0][2], Cell::Positive(Uint128::new(20)));
+    assert_eq!(pairwise[2][1], Cell::Positive(Uint128::new(20)));
+    assert_eq!(pairwise[1][2], Cell::Negative(Uint128::new(20)));
+
+    suite.a_week_passes();
+    let (_, status) = suite.query_winner_and_status(1);
+    assert_eq!(status, Status::Passed { winner: 0 });
+}
@@ -9,13 +9,16 @@ use dao_testing::contracts::{
     cw4_group_contract, dao_dao_core_contract, dao_proposal_condorcet_contract,
     dao_voting_cw4_contract,
 };
-use dao_voting::threshold::PercentageThreshold;
+use dao_voting::{pre_propose::PreProposeInfo, threshold::PercentageThreshold};
 use dao_voting_cw4::msg::GroupContract;
 
 use crate::{
     config::{Config, UncheckedConfig},
     contract::{CONTRACT_NAME, CONTRACT_VERSION},
-    msg::{Choice, ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{
+        Choice, ExecuteMsg, InstantiateMsg, ProposalListResponse, ProposeMsg, QueryMsg,
+        VoteListResponse,
+    },
     proposal::{ProposalResponse, Status},
     tally::Winner,
 };
@@ -28,7 +31,7 @@ pub(crate) struct Suite {
 }
 
 pub(crate) struct SuiteBuilder {
-    pub instantiate: InstantiateMsg,
+    pub instantiate: UncheckedConfig,
     with_proposal: Option<u32>,
     with_voters: Vec<(String, u64)>,
 }
@@ -41,6 +44,8 @@ impl Default for SuiteBuilder {
                 voting_period: Duration::Time(60 * 60 * 24 * 7),
                 min_voting_period: Some(Duration::Time(60 * 60 * 24)),
                 close_proposals_on_execution_failure: true,
+                allow_revoting: false,
+                completion: None,
             },
             with_proposal: None,
             with_voters: vec![("sender".to_string(), 10)],
@@ -102,7 +107,18 @@ impl SuiteBuilder {
             },
             proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
                 code_id: condorcet_id,
-                msg: to_json_binary(&self.instantiate).unwrap(),
+                msg: to_json_binary(&InstantiateMsg {
+                    quorum: self.instantiate.quorum,
+                    voting_period: self.instantiate.voting_period,
+                    min_voting_period: self.instantiate.min_voting_period,
+                    close_proposals_on_execution_failure: self
+                        .instantiate
+                        .close_proposals_on_execution_failure,
+                    allow_revoting: self.instantiate.allow_revoting,
+                    completion: self.instantiate.completion,
+                    pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+                })
+                .unwrap(),
                 admin: Some(Admin::CoreModule {}),
                 funds: vec![],
                 label: "condorcet module".to_string(),
@@ -233,6 +249,48 @@ impl Suite {
             .query_wasm_smart(&self.condorcet, &QueryMsg::Info {})
             .unwrap()
     }
+
+    pub fn query_list_proposals(
+        &self,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    ) -> ProposalListResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.condorcet,
+                &QueryMsg::ListProposals { start_after, limit },
+            )
+            .unwrap()
+    }
+
+    pub fn query_list_votes(
+        &self,
+        proposal_id: u32,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> VoteListResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.condorcet,
+                &QueryMsg::ListVotes {
+                    proposal_id,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_vote_hooks(&self) -> Vec<String> {
+        let hooks: cw_hooks::HooksResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.condorcet, &QueryMsg::VoteHooks {})
+            .unwrap();
+        hooks.hooks
+    }
 }
 
 // execute
@@ -246,9 +304,20 @@ impl Suite {
         self.app.execute_contract(
             Addr::unchecked(sender),
             self.condorcet.clone(),
-            &ExecuteMsg::Propose {
-                choices: choices.into_iter().map(|msgs| Choice { msgs }).collect(),
-            },
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                choices: choices
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, msgs)| Choice {
+                        title: format!("choice {i}"),
+                        description: "description".to_string(),
+                        msgs,
+                    })
+                    .collect(),
+                proposer: None,
+            }),
             &[],
         )?;
         Ok(id)
@@ -281,6 +350,23 @@ impl Suite {
             .map(|_| ())
     }
 
+    pub fn add_vote_hook<S: Into<String>>(
+        &mut self,
+        sender: S,
+        address: &str,
+    ) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.condorcet.clone(),
+                &ExecuteMsg::AddVoteHook {
+                    address: address.to_string(),
+                },
+                &[],
+            )
+            .map(|_| ())
+    }
+
     pub fn close<S: Into<String>>(&mut self, sender: S, proposal_id: u32) -> anyhow::Result<()> {
         self.app
             .execute_contract(
@@ -9,7 +9,14 @@ use crate::{
 #[test]
 fn test_pair_election() {
     let candidates = 2;
-    let mut tally = Tally::new(candidates, Uint128::new(3), 0, Expiration::Never {});
+    let mut tally = Tally::new(
+        candidates,
+        Uint128::new(3),
+        0,
+        Expiration::Never {},
+        false,
+        None,
+    );
 
     tally.add_vote(Vote::new(vec![0, 1], candidates).unwrap(), Uint128::one());
     tally.add_vote(Vote::new(vec![1, 0], candidates).unwrap(), Uint128::one());
@@ -21,7 +28,14 @@ fn test_pair_election() {
 #[test]
 fn test_triplet_election() {
     let candidates = 3;
-    let mut tally = Tally::new(candidates, Uint128::new(3), 0, Expiration::Never {});
+    let mut tally = Tally::new(
+        candidates,
+        Uint128::new(3),
+        0,
+        Expiration::Never {},
+        false,
+        None,
+    );
 
     tally.add_vote(
         Vote::new(vec![0, 1, 2], candidates).unwrap(),
@@ -45,7 +59,14 @@ fn test_triplet_election() {
 #[test]
 fn test_condorcet_paradox() {
     let candidates = 3;
-    let mut tally = Tally::new(candidates, Uint128::new(6), 0, Expiration::Never {});
+    let mut tally = Tally::new(
+        candidates,
+        Uint128::new(6),
+        0,
+        Expiration::Never {},
+        false,
+        None,
+    );
 
     tally.add_vote(
         Vote::new(vec![0, 2, 1], candidates).unwrap(),
@@ -96,7 +117,14 @@ fn test_condorcet_paradox() {
 #[test]
 fn test_tally_overflow() {
     let candidates = 6;
-    let mut tally = Tally::new(candidates, Uint128::MAX, 0, Expiration::Never {});
+    let mut tally = Tally::new(
+        candidates,
+        Uint128::MAX,
+        0,
+        Expiration::Never {},
+        false,
+        None,
+    );
 
     tally.add_vote(
         Vote::new(vec![1, 2, 3, 4, 5, 0], candidates).unwrap(),
@@ -117,7 +145,14 @@ fn test_tally_overflow() {
 #[test]
 fn test_winner_none() {
     let candidates = 6;
-    let mut tally = Tally::new(candidates, Uint128::new(9), 0, Expiration::Never {});
+    let mut tally = Tally::new(
+        candidates,
+        Uint128::new(9),
+        0,
+        Expiration::Never {},
+        false,
+        None,
+    );
 
     tally.add_vote(
         Vote::new(vec![1, 2, 3, 4, 5, 0], candidates).unwrap(),
@@ -151,3 +186,53 @@ fn test_winner_none() {
     // -2 -2  2  2  4  \
     assert_eq!(tally.winner, Winner::None)
 }
+
+#[test]
+fn test_change_vote() {
+    let candidates = 4;
+    let new_tally = || {
+        Tally::new(
+            candidates,
+            Uint128::new(10),
+            0,
+            Expiration::Never {},
+            true,
+            None,
+        )
+    };
+
+    let mut changed = new_tally();
+    changed.add_vote(
+        Vote::new(vec![2, 0, 1, 3], candidates).unwrap(),
+        Uint128::new(3),
+    );
+    changed.add_vote(
+        Vote::new(vec![0, 1, 2, 3], candidates).unwrap(),
+        Uint128::new(4),
+    );
+    changed.change_vote(
+        (
+            Vote::new(vec![0, 1, 2, 3], candidates).unwrap(),
+            Uint128::new(4),
+        ),
+        (
+            Vote::new(vec![3, 2, 1, 0], candidates).unwrap(),
+            Uint128::new(4),
+        ),
+    );
+
+    let mut fresh = new_tally();
+    fresh.add_vote(
+        Vote::new(vec![2, 0, 1, 3], candidates).unwrap(),
+        Uint128::new(3),
+    );
+    fresh.add_vote(
+        Vote::new(vec![3, 2, 1, 0], candidates).unwrap(),
+        Uint128::new(4),
+    );
+
+    // removing a ballot leaves no trace of it in M.
+    assert_eq!(changed, fresh);
+    assert_eq!(changed.power_outstanding, Uint128::new(3));
+    assert_eq!(changed.winner, Winner::Some(3));
+}
@@ -1,11 +1,19 @@
 use std::ops::Index;
 
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
 use thiserror::Error;
 
 #[cw_serde]
 pub struct Vote(Vec<u32>);
 
+/// A vote along with the voting power it was cast with.
+#[cw_serde]
+pub struct Ballot {
+    pub vote: Vote,
+    pub power: Uint128,
+}
+
 impl Vote {
     pub(crate) fn new(vote: Vec<u32>, candidates: u32) -> Result<Self, VoteError> {
         if vote.len() != candidates as usize {
@@ -30,6 +38,10 @@ impl Vote {
     pub fn iter(&self) -> std::slice::Iter<'_, u32> {
         self.0.iter()
     }
+
+    pub fn as_slice(&self) -> &[u32] {
+        self.0.as_slice()
+    }
 }
 
 impl Index<usize> for Vote {
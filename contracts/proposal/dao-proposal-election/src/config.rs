@@ -0,0 +1,45 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Api;
+use cw_utils::Duration;
+use dao_voting::threshold::{validate_quorum, PercentageThreshold};
+
+use crate::{election::ElectionMethod, proposal::ElectionTarget, ContractError};
+
+#[cw_serde]
+pub struct UncheckedConfig {
+    /// The share of voting power that must cast a ballot for an
+    /// election to be decided.
+    pub quorum: PercentageThreshold,
+    /// How long elections are open for voting.
+    pub voting_period: Duration,
+    /// The method used to count ballots and elect winners.
+    pub method: ElectionMethod,
+    /// The targets elections may apply their results to. Proposals
+    /// choose one of these by index, so only the DAO decides where
+    /// seats are granted.
+    pub targets: Vec<ElectionTarget>,
+}
+
+#[cw_serde]
+pub struct Config {
+    pub quorum: PercentageThreshold,
+    pub voting_period: Duration,
+    pub method: ElectionMethod,
+    pub targets: Vec<ElectionTarget>,
+}
+
+impl UncheckedConfig {
+    pub(crate) fn into_checked(self, api: &dyn Api) -> Result<Config, ContractError> {
+        validate_quorum(&self.quorum)?;
+        Ok(Config {
+            quorum: self.quorum,
+            voting_period: self.voting_period,
+            method: self.method,
+            targets: self
+                .targets
+                .into_iter()
+                .map(|target| target.into_checked(api))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
@@ -0,0 +1,416 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Reply,
+    Response, StdResult, Storage, WasmMsg,
+};
+
+use cw2::set_contract_version;
+use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
+use cw_storage_plus::Bound;
+use dao_cw721_extensions::roles::{ExecuteExt, MetadataExt};
+use dao_voting::proposal::DEFAULT_LIMIT;
+use dao_voting::reply::TaggedReplyId;
+use dao_voting::voting::{get_total_power, get_voting_power};
+
+use crate::config::UncheckedConfig;
+use crate::election::{Ballot, Tally};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, ProposeMsg, QueryMsg};
+use crate::proposal::{ElectionTarget, Proposal, Status};
+use crate::state::{
+    next_proposal_id, SeatChanges, CONFIG, DAO, PROPOSAL, SEATS, SEAT_CHANGES, TALLY, VOTE,
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-proposal-election";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    DAO.save(deps.storage, &info.sender)?;
+    CONFIG.save(deps.storage, &msg.into_checked(deps.api)?)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "instantiate")
+        .add_attribute("creator", info.sender))
+}
+
+// unlike single winner elections, the winners of multi-winner
+// elections depend on whole ballots rather than running totals per
+// candidate. ballots are tallied as they are cast with identical
+// ballots combined, and the tally is counted when the proposal is
+// executed. the cost of execution grows with the number of distinct
+// ballots and the number of candidates so elections with very many
+// candidates may be too expensive to execute.
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose(propose) => execute_propose(deps, env, info, propose),
+        ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+
+        ExecuteMsg::SetConfig(config) => execute_set_config(deps, info, config),
+    }
+}
+
+fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    ProposeMsg {
+        title,
+        description,
+        candidates,
+        seats,
+        target,
+    }: ProposeMsg,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    let sender_voting_power = get_voting_power(deps.as_ref(), info.sender.clone(), &dao, None)?;
+    if sender_voting_power.is_zero() {
+        return Err(ContractError::ZeroVotingPower {});
+    }
+
+    if seats == 0 || seats as usize > candidates.len() {
+        return Err(ContractError::InvalidSeats {
+            seats,
+            candidates: candidates.len() as u32,
+        });
+    }
+    let mut nominees: Vec<Addr> = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        let candidate = deps.api.addr_validate(&candidate)?;
+        if nominees.contains(&candidate) {
+            return Err(ContractError::DuplicateNominee {
+                address: candidate.into_string(),
+            });
+        }
+        nominees.push(candidate);
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let target = config
+        .targets
+        .get(target as usize)
+        .cloned()
+        .ok_or(ContractError::InvalidTarget { target })?;
+    let id = next_proposal_id(deps.storage)?;
+    let total_power = get_total_power(deps.as_ref(), &dao, None)?;
+
+    let proposal = Proposal::new(
+        &env.block,
+        id,
+        title,
+        description,
+        info.sender,
+        nominees,
+        seats,
+        target,
+        &config,
+        total_power,
+    );
+    PROPOSAL.save(deps.storage, id, &proposal)?;
+    TALLY.save(deps.storage, id, &Tally::default())?;
+
+    Ok(Response::default()
+        .add_attribute("method", "propose")
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("proposer", proposal.proposer))
+}
+
+fn execute_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u32,
+    vote: Vec<u32>,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    if proposal.update_status(&env.block) != Status::Open {
+        return Err(ContractError::Expired {});
+    }
+    let sender_power = get_voting_power(
+        deps.as_ref(),
+        info.sender.clone(),
+        &DAO.load(deps.storage)?,
+        Some(proposal.start_height),
+    )?;
+    if sender_power.is_zero() {
+        return Err(ContractError::ZeroVotingPower {});
+    }
+    if VOTE.has(deps.storage, (proposal_id, &info.sender)) {
+        return Err(ContractError::Voted {});
+    }
+
+    let vote = proposal
+        .method
+        .ballot(vote, proposal.candidates.len() as u32)?;
+    let mut tally = TALLY.load(deps.storage, proposal_id)?;
+    tally.add_vote(vote.clone(), sender_power);
+    TALLY.save(deps.storage, proposal_id, &tally)?;
+    VOTE.save(
+        deps.storage,
+        (proposal_id, &info.sender),
+        &Ballot {
+            vote,
+            power: sender_power,
+        },
+    )?;
+
+    proposal.power_cast += sender_power;
+    PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "vote")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("voter", info.sender)
+        .add_attribute("power", sender_power))
+}
+
+fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u32,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    let dao = DAO.load(deps.storage)?;
+    let sender_power = get_voting_power(
+        deps.as_ref(),
+        info.sender.clone(),
+        &dao,
+        Some(proposal.start_height),
+    )?;
+    if sender_power.is_zero() {
+        return Err(ContractError::ZeroVotingPower {});
+    }
+    if proposal.update_status(&env.block) != Status::Passed {
+        return Err(ContractError::Unexecutable {});
+    }
+
+    let tally = TALLY.load(deps.storage, proposal_id)?;
+    let winners: Vec<Addr> = proposal
+        .method
+        .elect(
+            proposal.candidates.len() as u32,
+            proposal.seats,
+            &tally.ballots,
+        )
+        .into_iter()
+        .map(|winner| proposal.candidates[winner as usize].clone())
+        .collect();
+
+    let msgs = election_msgs(deps.storage, &env, &proposal, &winners)?;
+    let message = proposal.set_executed(dao, winners, msgs)?;
+    PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+
+    // if applying the result fails, the proposal's status is set to
+    // `ExecutionFailed` in `reply` and seat changes are undone.
+    Ok(Response::default()
+        .add_attribute("method", "execute")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("executor", info.sender)
+        .add_submessage(message))
+}
+
+/// Messages that apply the result of an election to its target. Seat
+/// changes are recorded in `SEAT_CHANGES` so that `reply` may undo
+/// them.
+fn election_msgs(
+    storage: &mut dyn Storage,
+    env: &Env,
+    proposal: &Proposal,
+    winners: &[Addr],
+) -> StdResult<Vec<CosmosMsg>> {
+    let losers = proposal.candidates.iter().filter(|c| !winners.contains(c));
+    match &proposal.target {
+        ElectionTarget::Cw4Group { address, weight } => Ok(vec![WasmMsg::Execute {
+            contract_addr: address.clone(),
+            msg: to_json_binary(&cw4::Cw4ExecuteMsg::UpdateMembers {
+                remove: losers.map(|l| l.to_string()).collect(),
+                add: winners
+                    .iter()
+                    .map(|w| cw4::Member {
+                        addr: w.to_string(),
+                        weight: *weight,
+                    })
+                    .collect(),
+            })?,
+            funds: vec![],
+        }
+        .into()]),
+        ElectionTarget::Cw721Roles {
+            address,
+            role,
+            weight,
+        } => {
+            let collection = Addr::unchecked(address);
+            let mut changes = SeatChanges {
+                collection: collection.clone(),
+                burned: vec![],
+                minted: vec![],
+            };
+            let mut msgs = vec![];
+            for loser in losers {
+                if let Some(token_id) = SEATS.may_load(storage, (&collection, loser))? {
+                    SEATS.remove(storage, (&collection, loser));
+                    changes.burned.push((loser.clone(), token_id.clone()));
+                    msgs.push(Cw721ExecuteMsg::<MetadataExt, ExecuteExt>::Burn { token_id });
+                }
+            }
+            for (seat, winner) in winners.iter().enumerate() {
+                if !SEATS.has(storage, (&collection, winner)) {
+                    let token_id = format!("{}-{}-{}", env.contract.address, proposal.id, seat);
+                    SEATS.save(storage, (&collection, winner), &token_id)?;
+                    changes.minted.push(winner.clone());
+                    msgs.push(Cw721ExecuteMsg::Mint {
+                        token_id,
+                        owner: winner.to_string(),
+                        token_uri: None,
+                        extension: MetadataExt {
+                            role: role.clone(),
+                            weight: *weight,
+                        },
+                    });
+                }
+            }
+            SEAT_CHANGES.save(storage, proposal.id, &changes)?;
+            msgs.into_iter()
+                .map(|msg| {
+                    Ok(WasmMsg::Execute {
+                        contract_addr: address.clone(),
+                        msg: to_json_binary(&msg)?,
+                        funds: vec![],
+                    }
+                    .into())
+                })
+                .collect()
+        }
+    }
+}
+
+fn execute_close(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u32,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    if let Status::Rejected = proposal.update_status(&env.block) {
+        proposal.set_closed();
+        PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "close")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("closer", info.sender))
+    } else {
+        Err(ContractError::Unclosable {})
+    }
+}
+
+fn execute_set_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: UncheckedConfig,
+) -> Result<Response, ContractError> {
+    if info.sender != DAO.load(deps.storage)? {
+        Err(ContractError::NotDao {})
+    } else {
+        CONFIG.save(deps.storage, &config.into_checked(deps.api)?)?;
+        Ok(Response::default()
+            .add_attribute("method", "update_config")
+            .add_attribute("updater", info.sender))
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let repl = TaggedReplyId::new(msg.id)?;
+    match repl {
+        TaggedReplyId::FailedProposalExecution(proposal_id) => {
+            let proposal_id = proposal_id as u32;
+            let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+            proposal.set_execution_failed();
+            PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+
+            // the burns and mints were reverted with the failed
+            // message, so the seats they recorded are restored.
+            if let Some(SeatChanges {
+                collection,
+                burned,
+                minted,
+            }) = SEAT_CHANGES.may_load(deps.storage, proposal_id)?
+            {
+                for holder in minted {
+                    SEATS.remove(deps.storage, (&collection, &holder));
+                }
+                for (holder, token_id) in burned {
+                    SEATS.save(deps.storage, (&collection, &holder), &token_id)?;
+                }
+                SEAT_CHANGES.remove(deps.storage, proposal_id);
+            }
+
+            Ok(Response::default()
+                .add_attribute("proposal_execution_failed", proposal_id.to_string())
+                .add_attribute(
+                    "error",
+                    msg.result.into_result().err().unwrap_or("None".to_string()),
+                ))
+        }
+        _ => Err(ContractError::InvalidReplyID { id: msg.id }),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Proposal { id } => {
+            let mut proposal = PROPOSAL.load(deps.storage, id)?;
+            proposal.update_status(&env.block);
+            to_json_binary(&proposal)
+        }
+        QueryMsg::ListProposals { start_after, limit } => {
+            let limit = limit.map_or(DEFAULT_LIMIT, |l| l as u64) as usize;
+            let proposals = PROPOSAL
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .map(|item| {
+                    let (_, mut proposal) = item?;
+                    proposal.update_status(&env.block);
+                    Ok(proposal)
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&proposals)
+        }
+        QueryMsg::Ballot { proposal_id, voter } => {
+            let voter = deps.api.addr_validate(&voter)?;
+            to_json_binary(&VOTE.may_load(deps.storage, (proposal_id, &voter))?)
+        }
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::NextProposalId {} => to_json_binary(&next_proposal_id(deps.storage)?),
+        QueryMsg::Dao {} => to_json_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Info {} => to_json_binary(&dao_interface::voting::InfoResponse {
+            info: cw2::get_contract_version(deps.storage)?,
+        }),
+    }
+}
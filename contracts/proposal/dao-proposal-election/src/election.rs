@@ -0,0 +1,269 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal256, Uint128};
+
+use crate::ContractError;
+
+/// A ballot and the voting power it was cast with. Identical ballots
+/// are stored together with their combined voting power.
+#[cw_serde]
+pub struct Ballot {
+    pub vote: Vec<u32>,
+    pub power: Uint128,
+}
+
+/// The ballots cast in an election with identical ballots combined.
+/// Updated as each vote is cast so that counting the election only
+/// loads the tally.
+#[cw_serde]
+#[derive(Default)]
+pub struct Tally {
+    pub ballots: Vec<Ballot>,
+}
+
+impl Tally {
+    /// Adds a validated VOTE cast with POWER to the tally.
+    pub(crate) fn add_vote(&mut self, vote: Vec<u32>, power: Uint128) {
+        match self.ballots.iter_mut().find(|ballot| ballot.vote == vote) {
+            Some(ballot) => ballot.power += power,
+            None => self.ballots.push(Ballot { vote, power }),
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum ElectionMethod {
+    /// Single transferable vote. Ballots rank any number of
+    /// candidates from most to least preferred. Candidates whose
+    /// votes reach the Droop quota are elected and their surplus is
+    /// transferred to the next preference on their ballots at a
+    /// reduced value. When no candidate reaches the quota, the
+    /// candidate with the fewest votes is eliminated and their votes
+    /// are transferred at their current value.
+    SingleTransferableVote,
+    /// Sequential proportional approval voting. Ballots approve of
+    /// any number of candidates. Seats are filled one at a time by
+    /// the candidate with the highest score, where a ballot
+    /// contributes `power / (1 + elected)` to each candidate it
+    /// approves of and `elected` is the number of candidates it
+    /// approves of that have already been elected.
+    ProportionalApproval,
+}
+
+impl ElectionMethod {
+    /// Validates a vote for an election with `candidates`
+    /// candidates. Approval votes are sorted so that identical
+    /// approvals are stored together.
+    pub(crate) fn ballot(
+        &self,
+        vote: Vec<u32>,
+        candidates: u32,
+    ) -> Result<Vec<u32>, ContractError> {
+        if vote.is_empty() {
+            return Err(ContractError::EmptyBallot {});
+        }
+        let mut seen = Vec::with_capacity(vote.len());
+        for candidate in vote {
+            if candidate >= candidates {
+                return Err(ContractError::InvalidCandidate { candidate });
+            }
+            if seen.contains(&candidate) {
+                return Err(ContractError::DuplicateCandidate { candidate });
+            }
+            seen.push(candidate);
+        }
+        if let ElectionMethod::ProportionalApproval = self {
+            seen.sort_unstable();
+        }
+        Ok(seen)
+    }
+
+    /// Elects `seats` of `candidates` candidates. Returns the winners
+    /// in the order they were elected. Ties are broken in favor of
+    /// the candidate with the lower index.
+    pub(crate) fn elect(&self, candidates: u32, seats: u32, ballots: &[Ballot]) -> Vec<u32> {
+        match self {
+            ElectionMethod::SingleTransferableVote => stv(candidates, seats, ballots),
+            ElectionMethod::ProportionalApproval => pav(candidates, seats, ballots),
+        }
+    }
+}
+
+fn stv(candidates: u32, seats: u32, ballots: &[Ballot]) -> Vec<u32> {
+    let total: Uint128 = ballots.iter().map(|b| b.power).sum();
+    let quota = Decimal256::from_ratio(total / Uint128::from(seats + 1) + Uint128::one(), 1u128);
+
+    // the current value of each ballot. reduced when surplus is
+    // transferred from an elected candidate.
+    let mut values: Vec<Decimal256> = ballots
+        .iter()
+        .map(|b| Decimal256::from_ratio(b.power, 1u128))
+        .collect();
+    let mut hopeful: Vec<u32> = (0..candidates).collect();
+    let mut elected: Vec<u32> = Vec::with_capacity(seats as usize);
+
+    while elected.len() < seats as usize {
+        if elected.len() + hopeful.len() <= seats as usize {
+            elected.append(&mut hopeful);
+            break;
+        }
+
+        // a ballot counts towards its most preferred hopeful
+        // candidate, if any.
+        let tops: Vec<Option<u32>> = ballots
+            .iter()
+            .map(|b| b.vote.iter().copied().find(|c| hopeful.contains(c)))
+            .collect();
+        let mut tallies = vec![Decimal256::zero(); candidates as usize];
+        for (top, value) in tops.iter().zip(values.iter()) {
+            if let Some(top) = top {
+                tallies[*top as usize] += *value;
+            }
+        }
+
+        // `hopeful` is sorted, so taking the first maximum and the
+        // last minimum favors lower indexes.
+        let leader = hopeful.iter().copied().fold(hopeful[0], |best, c| {
+            if tallies[c as usize] > tallies[best as usize] {
+                c
+            } else {
+                best
+            }
+        });
+        if tallies[leader as usize] >= quota {
+            let ratio = (tallies[leader as usize] - quota) / tallies[leader as usize];
+            for (top, value) in tops.iter().zip(values.iter_mut()) {
+                if *top == Some(leader) {
+                    *value *= ratio;
+                }
+            }
+            elected.push(leader);
+            hopeful.retain(|c| *c != leader);
+        } else {
+            let loser = hopeful.iter().copied().fold(hopeful[0], |worst, c| {
+                if tallies[c as usize] <= tallies[worst as usize] {
+                    c
+                } else {
+                    worst
+                }
+            });
+            hopeful.retain(|c| *c != loser);
+        }
+    }
+    elected
+}
+
+fn pav(candidates: u32, seats: u32, ballots: &[Ballot]) -> Vec<u32> {
+    let mut elected: Vec<u32> = Vec::with_capacity(seats as usize);
+    while elected.len() < seats as usize {
+        let mut best: Option<(u32, Decimal256)> = None;
+        for candidate in (0..candidates).filter(|c| !elected.contains(c)) {
+            let score: Decimal256 = ballots
+                .iter()
+                .filter(|b| b.vote.contains(&candidate))
+                .map(|b| {
+                    let satisfied = b.vote.iter().filter(|c| elected.contains(c)).count();
+                    Decimal256::from_ratio(b.power, satisfied as u128 + 1)
+                })
+                .sum();
+            if best.map_or(true, |(_, high)| score > high) {
+                best = Some((candidate, score))
+            }
+        }
+        match best {
+            Some((candidate, _)) => elected.push(candidate),
+            None => break,
+        }
+    }
+    elected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_ballots(ballots: &[(&[u32], u128)]) -> Vec<Ballot> {
+        ballots
+            .iter()
+            .map(|(vote, power)| Ballot {
+                vote: vote.to_vec(),
+                power: Uint128::new(*power),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ballot_validation() {
+        let stv = ElectionMethod::SingleTransferableVote;
+        assert_eq!(stv.ballot(vec![2, 0], 3).unwrap(), vec![2, 0]);
+        assert_eq!(
+            ElectionMethod::ProportionalApproval
+                .ballot(vec![2, 0], 3)
+                .unwrap(),
+            vec![0, 2]
+        );
+        assert!(matches!(
+            stv.ballot(vec![], 3).unwrap_err(),
+            ContractError::EmptyBallot {}
+        ));
+        assert!(matches!(
+            stv.ballot(vec![3], 3).unwrap_err(),
+            ContractError::InvalidCandidate { candidate: 3 }
+        ));
+        assert!(matches!(
+            stv.ballot(vec![1, 1], 3).unwrap_err(),
+            ContractError::DuplicateCandidate { candidate: 1 }
+        ));
+    }
+
+    #[test]
+    fn test_tally_combines_ballots() {
+        let mut tally = Tally::default();
+        tally.add_vote(vec![0, 1], Uint128::new(10));
+        tally.add_vote(vec![1], Uint128::new(5));
+        tally.add_vote(vec![0, 1], Uint128::new(7));
+        assert_eq!(tally.ballots, make_ballots(&[(&[0, 1], 17), (&[1], 5)]));
+    }
+
+    #[test]
+    fn test_stv_surplus_transfer() {
+        // quota = 100 / 3 + 1 = 34. candidate 0 is elected with 60
+        // and its surplus of 26 moves to candidate 2, who then beats
+        // candidate 1.
+        let ballots = make_ballots(&[(&[0, 2], 60), (&[1], 25), (&[2], 15)]);
+        let winners = ElectionMethod::SingleTransferableVote.elect(3, 2, &ballots);
+        assert_eq!(winners, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_stv_elimination() {
+        // quota = 100 / 3 + 1 = 34. after 0 is elected no one
+        // reaches the quota so 3 is eliminated. their votes are not
+        // enough for 2 to overtake 1, so 2 is eliminated next.
+        let ballots = make_ballots(&[(&[0], 40), (&[1], 32), (&[2], 18), (&[3, 2], 10)]);
+        let winners = ElectionMethod::SingleTransferableVote.elect(4, 2, &ballots);
+        assert_eq!(winners, vec![0, 1]);
+
+        // with more of 3's votes, 2 reaches the quota.
+        let ballots = make_ballots(&[(&[0], 40), (&[1], 25), (&[2], 20), (&[3, 2], 15)]);
+        let winners = ElectionMethod::SingleTransferableVote.elect(4, 2, &ballots);
+        assert_eq!(winners, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_stv_fills_seats() {
+        // all ballots are exhausted but both seats are filled.
+        let ballots = make_ballots(&[(&[1], 10)]);
+        let winners = ElectionMethod::SingleTransferableVote.elect(3, 2, &ballots);
+        assert_eq!(winners, vec![1, 0]);
+    }
+
+    #[test]
+    fn test_pav_proportionality() {
+        // a 60% majority approving of 0, 1, and 2 does not take all
+        // three seats from a 40% minority approving of 3.
+        let ballots = make_ballots(&[(&[0, 1, 2], 60), (&[3], 40)]);
+        let winners = ElectionMethod::ProportionalApproval.elect(4, 3, &ballots);
+        assert_eq!(winners, vec![0, 3, 1]);
+    }
+}
@@ -0,0 +1,59 @@
+use cosmwasm_std::StdError;
+use dao_voting::{error::VotingError, reply::error::TagError, threshold::ThresholdError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+    #[error(transparent)]
+    Threshold(#[from] ThresholdError),
+    #[error(transparent)]
+    Voting(#[from] VotingError),
+    #[error(transparent)]
+    Tag(#[from] TagError),
+
+    #[error("non-zero voting power required to perform this action")]
+    ZeroVotingPower {},
+
+    #[error("only proposals that are in the passed state may be executed")]
+    Unexecutable {},
+
+    #[error("only rejected proposals may be closed")]
+    Unclosable {},
+
+    #[error("only the DAO my perform this action")]
+    NotDao {},
+
+    #[error("already voted")]
+    Voted {},
+
+    #[error("only open proposals may be voted on")]
+    Expired {},
+
+    #[error(
+        "must elect between one and the number of candidates ({candidates}) seats, got ({seats})"
+    )]
+    InvalidSeats { seats: u32, candidates: u32 },
+
+    #[error("({address}) may only be nominated once")]
+    DuplicateNominee { address: String },
+
+    #[error("seats must have a non-zero weight")]
+    ZeroWeight {},
+
+    #[error("no such election target ({target})")]
+    InvalidTarget { target: u32 },
+
+    #[error("ballot must include at least one candidate")]
+    EmptyBallot {},
+
+    #[error("no such candidate ({candidate})")]
+    InvalidCandidate { candidate: u32 },
+
+    #[error("candidate ({candidate}) appears in ballot more than once")]
+    DuplicateCandidate { candidate: u32 },
+
+    #[error("received reply for an unknown ID ({id})")]
+    InvalidReplyID { id: u64 },
+}
@@ -0,0 +1,14 @@
+#![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
+
+pub mod config;
+pub mod contract;
+pub mod election;
+mod error;
+pub mod msg;
+pub mod proposal;
+pub mod state;
+
+#[cfg(test)]
+mod testing;
+
+pub use crate::error::ContractError;
@@ -0,0 +1,63 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+
+use dao_dao_macros::proposal_module_query;
+
+use crate::config::UncheckedConfig;
+
+pub type InstantiateMsg = UncheckedConfig;
+
+#[cw_serde]
+pub struct ProposeMsg {
+    /// The title of the election.
+    pub title: String,
+    /// A description of the election.
+    pub description: String,
+    /// The addresses standing for election. Candidates that are not
+    /// elected lose any seat they hold in the target. Seat holders
+    /// that are not candidates keep their seats.
+    pub candidates: Vec<String>,
+    /// The number of candidates to elect.
+    pub seats: u32,
+    /// The index of the configured target that the result of the
+    /// election is applied to when it is executed.
+    pub target: u32,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    Propose(ProposeMsg),
+    /// Casts a ballot. Under single transferable vote, `vote` ranks
+    /// candidates by index from most to least preferred. Under
+    /// proportional approval, `vote` lists the indexes of the
+    /// candidates the voter approves of.
+    Vote {
+        proposal_id: u32,
+        vote: Vec<u32>,
+    },
+    Execute {
+        proposal_id: u32,
+    },
+    Close {
+        proposal_id: u32,
+    },
+    SetConfig(UncheckedConfig),
+}
+
+#[proposal_module_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(crate::proposal::Proposal)]
+    Proposal { id: u32 },
+    /// Lists the proposals in this module in ascending order of ID.
+    #[returns(Vec<crate::proposal::Proposal>)]
+    ListProposals {
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+    /// Gets the ballot cast by VOTER on a proposal, if any.
+    #[returns(Option<crate::election::Ballot>)]
+    Ballot { proposal_id: u32, voter: String },
+    #[returns(crate::config::Config)]
+    Config {},
+}
@@ -0,0 +1,192 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, Api, BlockInfo, CosmosMsg, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw_utils::Expiration;
+use dao_voting::{
+    reply::mask_proposal_execution_proposal_id, threshold::PercentageThreshold,
+    voting::does_vote_count_pass,
+};
+
+use crate::{election::ElectionMethod, ContractError};
+
+/// Where the result of an election is applied.
+#[cw_serde]
+pub enum ElectionTarget {
+    /// Winners are added to a cw4-group with WEIGHT and losing
+    /// candidates are removed from it. The DAO must be the group's
+    /// admin.
+    Cw4Group { address: String, weight: u64 },
+    /// A cw721-roles token with ROLE and WEIGHT is minted to each
+    /// winner that does not already hold a seat from an earlier
+    /// election, and seats held by losing candidates are burned. The
+    /// DAO must be the collection's minter.
+    Cw721Roles {
+        address: String,
+        role: Option<String>,
+        weight: u64,
+    },
+}
+
+impl ElectionTarget {
+    pub(crate) fn into_checked(self, api: &dyn Api) -> Result<Self, ContractError> {
+        let target = match self {
+            ElectionTarget::Cw4Group { address, weight } => ElectionTarget::Cw4Group {
+                address: api.addr_validate(&address)?.into_string(),
+                weight,
+            },
+            ElectionTarget::Cw721Roles {
+                address,
+                role,
+                weight,
+            } => ElectionTarget::Cw721Roles {
+                address: api.addr_validate(&address)?.into_string(),
+                role,
+                weight,
+            },
+        };
+        if let ElectionTarget::Cw4Group { weight: 0, .. }
+        | ElectionTarget::Cw721Roles { weight: 0, .. } = target
+        {
+            return Err(ContractError::ZeroWeight {});
+        }
+        Ok(target)
+    }
+}
+
+#[cw_serde]
+pub struct Proposal {
+    last_status: Status,
+
+    pub id: u32,
+    pub title: String,
+    pub description: String,
+    pub proposer: Addr,
+
+    pub candidates: Vec<Addr>,
+    pub seats: u32,
+    pub target: ElectionTarget,
+    pub method: ElectionMethod,
+
+    pub quorum: PercentageThreshold,
+    pub start_height: u64,
+    pub expiration: Expiration,
+    pub total_power: Uint128,
+    /// The voting power that has cast a ballot.
+    pub power_cast: Uint128,
+
+    /// The elected candidates in the order they were elected. Set
+    /// when the proposal is executed.
+    pub winners: Option<Vec<Addr>>,
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum Status {
+    /// The proposal is open for voting.
+    Open,
+    /// Voting has ended without reaching quorum.
+    Rejected,
+    /// Voting has ended and the election may be counted and
+    /// executed.
+    Passed,
+    /// The election has been counted and its result applied.
+    Executed,
+    /// The proposal has been rejected and closed.
+    Closed,
+    /// Applying the election's result failed. Seats are left as they
+    /// were before the proposal was executed.
+    ExecutionFailed,
+}
+
+impl Proposal {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        block: &BlockInfo,
+        id: u32,
+        title: String,
+        description: String,
+        proposer: Addr,
+        candidates: Vec<Addr>,
+        seats: u32,
+        target: ElectionTarget,
+        config: &crate::config::Config,
+        total_power: Uint128,
+    ) -> Self {
+        Self {
+            last_status: Status::Open,
+            id,
+            title,
+            description,
+            proposer,
+            candidates,
+            seats,
+            target,
+            method: config.method,
+            quorum: config.quorum,
+            start_height: block.height,
+            expiration: config.voting_period.after(block),
+            total_power,
+            power_cast: Uint128::zero(),
+            winners: None,
+        }
+    }
+
+    /// Elections are only decided once voting ends as, unlike single
+    /// winner elections, the outcome may change with every ballot.
+    pub fn status(&self, block: &BlockInfo) -> Status {
+        match self.last_status {
+            Status::Open => {
+                if !self.expiration.is_expired(block) {
+                    Status::Open
+                } else if does_vote_count_pass(self.power_cast, self.total_power, self.quorum) {
+                    Status::Passed
+                } else {
+                    Status::Rejected
+                }
+            }
+            status => status,
+        }
+    }
+
+    pub(crate) fn update_status(&mut self, block: &BlockInfo) -> Status {
+        self.last_status = self.status(block);
+        self.last_status
+    }
+
+    pub fn last_status(&self) -> Status {
+        self.last_status
+    }
+
+    pub(crate) fn set_executed(
+        &mut self,
+        dao: Addr,
+        winners: Vec<Addr>,
+        msgs: Vec<CosmosMsg>,
+    ) -> StdResult<SubMsg> {
+        debug_assert_eq!(self.last_status, Status::Passed);
+
+        self.last_status = Status::Executed;
+        self.winners = Some(winners);
+
+        let core_exec = WasmMsg::Execute {
+            contract_addr: dao.into_string(),
+            msg: to_json_binary(&dao_interface::msg::ExecuteMsg::ExecuteProposalHook { msgs })?,
+            funds: vec![],
+        };
+        let masked_id = mask_proposal_execution_proposal_id(self.id as u64);
+        Ok(SubMsg::reply_on_error(core_exec, masked_id))
+    }
+
+    pub(crate) fn set_execution_failed(&mut self) {
+        debug_assert_eq!(self.last_status, Status::Executed);
+
+        self.last_status = Status::ExecutionFailed;
+    }
+
+    pub(crate) fn set_closed(&mut self) {
+        debug_assert_eq!(self.last_status, Status::Rejected);
+
+        self.last_status = Status::Closed;
+    }
+}
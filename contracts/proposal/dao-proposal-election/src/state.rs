@@ -0,0 +1,42 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+use cw_storage_plus::{Item, Map};
+
+use crate::{
+    config::Config,
+    election::{Ballot, Tally},
+    proposal::Proposal,
+};
+
+pub(crate) const DAO: Item<Addr> = Item::new("dao");
+pub(crate) const CONFIG: Item<Config> = Item::new("config");
+
+pub(crate) const PROPOSAL: Map<u32, Proposal> = Map::new("proposals");
+/// The ballot cast by each voter.
+pub(crate) const VOTE: Map<(u32, &Addr), Ballot> = Map::new("votes");
+/// Each proposal's ballots, tallied as votes are cast.
+pub(crate) const TALLY: Map<u32, Tally> = Map::new("tallies");
+/// The cw721-roles token held by each seat holder, keyed by
+/// collection and holder. Tracked so that losing candidates' seats
+/// may be burned.
+pub(crate) const SEATS: Map<(&Addr, &Addr), String> = Map::new("seats");
+/// The changes an executed proposal made to `SEATS`, kept so that
+/// they may be undone if applying the proposal's result fails.
+pub(crate) const SEAT_CHANGES: Map<u32, SeatChanges> = Map::new("seat_changes");
+
+#[cw_serde]
+pub(crate) struct SeatChanges {
+    pub collection: Addr,
+    /// The holders whose seats were burned and their token IDs.
+    pub burned: Vec<(Addr, String)>,
+    /// The holders that were minted a seat.
+    pub minted: Vec<Addr>,
+}
+
+pub(crate) fn next_proposal_id(storage: &dyn Storage) -> StdResult<u32> {
+    PROPOSAL
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()
+        .map(|id| id.unwrap_or(0) + 1)
+}
@@ -0,0 +1,280 @@
+use cosmwasm_std::{Addr, Uint128};
+use dao_voting::threshold::PercentageThreshold;
+
+use crate::{
+    config::UncheckedConfig,
+    election::{Ballot, ElectionMethod},
+    proposal::{ElectionTarget, Status},
+    ContractError,
+};
+
+use super::{is_error, suite::SuiteBuilder};
+
+#[test]
+fn test_council_election() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("a", 30), ("b", 30), ("c", 20), ("d", 20)])
+        .with_council(&[("carol", 1), ("dave", 1)])
+        .build();
+    suite.set_targets(vec![suite.council_target(5)]).unwrap();
+    let id = suite
+        .propose("a", &["alice", "bob", "carol"], 2, 0)
+        .unwrap();
+
+    suite.vote("a", id, vec![0, 1]).unwrap();
+    suite.vote("b", id, vec![1, 0]).unwrap();
+    suite.vote("c", id, vec![2]).unwrap();
+    suite.vote("d", id, vec![0, 2]).unwrap();
+
+    // elections are decided when voting ends.
+    assert_eq!(suite.query_proposal(id).last_status(), Status::Open);
+    is_error!(
+        suite.execute("a", id),
+        &ContractError::Unexecutable {}.to_string()
+    );
+
+    suite.a_week_passes();
+    assert_eq!(suite.query_proposal(id).last_status(), Status::Passed);
+
+    // alice reaches the quota of 34 on first preferences and her
+    // surplus of 16 carries bob from 30 to 39.6.
+    suite.execute("b", id).unwrap();
+    let proposal = suite.query_proposal(id);
+    assert_eq!(proposal.last_status(), Status::Executed);
+    assert_eq!(
+        proposal.winners,
+        Some(vec![Addr::unchecked("alice"), Addr::unchecked("bob")])
+    );
+
+    // carol stood and lost so is removed. dave did not stand and
+    // keeps his seat.
+    assert_eq!(
+        suite.query_council(),
+        vec![
+            ("alice".to_string(), 5),
+            ("bob".to_string(), 5),
+            ("dave".to_string(), 1),
+        ]
+    );
+
+    is_error!(
+        suite.execute("b", id),
+        &ContractError::Unexecutable {}.to_string()
+    );
+}
+
+#[test]
+fn test_roles_election() {
+    let mut suite = SuiteBuilder::default()
+        .with_method(ElectionMethod::ProportionalApproval)
+        .with_voters(&[("a", 35), ("b", 25), ("c", 40)])
+        .build();
+    suite
+        .set_targets(vec![suite.roles_target("councilor", 1)])
+        .unwrap();
+    let id = suite
+        .propose("a", &["alice", "bob", "carol"], 2, 0)
+        .unwrap();
+
+    suite.vote("a", id, vec![1, 0]).unwrap();
+    suite.vote("b", id, vec![0, 1]).unwrap();
+    suite.vote("c", id, vec![2]).unwrap();
+    // approvals are stored sorted.
+    assert_eq!(
+        suite.query_ballot(id, "a"),
+        Some(Ballot {
+            vote: vec![0, 1],
+            power: Uint128::new(35)
+        })
+    );
+
+    suite.a_week_passes();
+    // alice and bob are tied at 60. alice is elected and bob's score
+    // is halved to 30, below carol's 40.
+    suite.execute("a", id).unwrap();
+    assert_eq!(
+        suite.query_proposal(id).winners,
+        Some(vec![Addr::unchecked("alice"), Addr::unchecked("carol")])
+    );
+    assert_eq!(suite.query_seats("alice").len(), 1);
+    assert_eq!(suite.query_seats("bob").len(), 0);
+    assert_eq!(suite.query_seats("carol").len(), 1);
+
+    // alice loses her seat to bob. carol does not stand and keeps
+    // hers.
+    let id = suite.propose("a", &["alice", "bob"], 1, 0).unwrap();
+    suite.vote("b", id, vec![1]).unwrap();
+    suite.vote("c", id, vec![1]).unwrap();
+    suite.a_week_passes();
+    suite.execute("c", id).unwrap();
+
+    assert_eq!(suite.query_seats("alice").len(), 0);
+    assert_eq!(suite.query_seats("bob").len(), 1);
+    assert_eq!(suite.query_seats("carol").len(), 1);
+}
+
+#[test]
+fn test_election_rejected() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("a", 10), ("b", 90)])
+        .build();
+    suite.set_targets(vec![suite.council_target(1)]).unwrap();
+    let id = suite.propose("a", &["alice", "bob"], 1, 0).unwrap();
+    suite.vote("a", id, vec![0]).unwrap();
+
+    is_error!(
+        suite.close("a", id),
+        &ContractError::Unclosable {}.to_string()
+    );
+
+    suite.a_week_passes();
+    assert_eq!(suite.query_proposal(id).last_status(), Status::Rejected);
+    is_error!(
+        suite.vote("b", id, vec![1]),
+        &ContractError::Expired {}.to_string()
+    );
+    is_error!(
+        suite.execute("a", id),
+        &ContractError::Unexecutable {}.to_string()
+    );
+
+    suite.close("b", id).unwrap();
+    assert_eq!(suite.query_proposal(id).last_status(), Status::Closed);
+    assert_eq!(suite.query_council(), vec![]);
+}
+
+#[test]
+fn test_propose_validation() {
+    let mut suite = SuiteBuilder::default().build();
+    // proposals may only use targets configured by the DAO.
+    is_error!(
+        suite.propose("sender", &["alice"], 1, 0),
+        &ContractError::InvalidTarget { target: 0 }.to_string()
+    );
+    is_error!(
+        suite.set_targets(vec![suite.council_target(0)]),
+        &ContractError::ZeroWeight {}.to_string()
+    );
+    suite.set_targets(vec![suite.council_target(1)]).unwrap();
+    is_error!(
+        suite.propose("sender", &["alice"], 1, 1),
+        &ContractError::InvalidTarget { target: 1 }.to_string()
+    );
+
+    is_error!(
+        suite.propose("sender", &["alice"], 0, 0),
+        &ContractError::InvalidSeats {
+            seats: 0,
+            candidates: 1
+        }
+        .to_string()
+    );
+    is_error!(
+        suite.propose("sender", &["alice"], 2, 0),
+        &ContractError::InvalidSeats {
+            seats: 2,
+            candidates: 1
+        }
+        .to_string()
+    );
+    is_error!(
+        suite.propose("sender", &["alice", "bob", "alice"], 1, 0),
+        &ContractError::DuplicateNominee {
+            address: "alice".to_string()
+        }
+        .to_string()
+    );
+    is_error!(
+        suite.propose("nobody", &["alice"], 1, 0),
+        &ContractError::ZeroVotingPower {}.to_string()
+    );
+
+    suite.propose("sender", &["alice"], 1, 0).unwrap();
+    assert_eq!(suite.query_list_proposals(None, None).len(), 1);
+    assert_eq!(suite.query_list_proposals(Some(1), None).len(), 0);
+}
+
+#[test]
+fn test_vote_validation() {
+    let mut suite = SuiteBuilder::default().build();
+    suite.set_targets(vec![suite.council_target(1)]).unwrap();
+    let id = suite.propose("sender", &["alice", "bob"], 1, 0).unwrap();
+
+    is_error!(
+        suite.vote("sender", id, vec![]),
+        &ContractError::EmptyBallot {}.to_string()
+    );
+    is_error!(
+        suite.vote("sender", id, vec![2]),
+        &ContractError::InvalidCandidate { candidate: 2 }.to_string()
+    );
+    is_error!(
+        suite.vote("sender", id, vec![1, 0, 1]),
+        &ContractError::DuplicateCandidate { candidate: 1 }.to_string()
+    );
+    is_error!(
+        suite.vote("nobody", id, vec![0]),
+        &ContractError::ZeroVotingPower {}.to_string()
+    );
+
+    assert_eq!(suite.query_ballot(id, "sender"), None);
+    suite.vote("sender", id, vec![1, 0]).unwrap();
+    is_error!(
+        suite.vote("sender", id, vec![0]),
+        &ContractError::Voted {}.to_string()
+    );
+    assert_eq!(suite.query_proposal(id).power_cast, Uint128::new(10));
+}
+
+#[test]
+fn test_set_config() {
+    let mut suite = SuiteBuilder::default().build();
+    let config = UncheckedConfig {
+        quorum: PercentageThreshold::Majority {},
+        voting_period: cw_utils::Duration::Height(10),
+        method: ElectionMethod::ProportionalApproval,
+        targets: vec![suite.council_target(1)],
+    };
+
+    is_error!(
+        suite.set_config("sender", config.clone()),
+        &ContractError::NotDao {}.to_string()
+    );
+    let core = suite.core.clone();
+    suite.set_config(core, config.clone()).unwrap();
+
+    let updated = suite.query_config();
+    assert_eq!(updated.quorum, config.quorum);
+    assert_eq!(updated.method, config.method);
+    assert_eq!(updated.targets, config.targets);
+}
+
+#[test]
+fn test_execution_failed() {
+    let mut suite = SuiteBuilder::default()
+        .with_council(&[("carol", 1)])
+        .build();
+    // the roles collection is not a cw4-group so applying the
+    // result fails.
+    let roles = suite.roles.to_string();
+    suite
+        .set_targets(vec![ElectionTarget::Cw4Group {
+            address: roles,
+            weight: 1,
+        }])
+        .unwrap();
+    let id = suite.propose("sender", &["alice", "carol"], 1, 0).unwrap();
+    suite.vote("sender", id, vec![0]).unwrap();
+    suite.a_week_passes();
+
+    suite.execute("sender", id).unwrap();
+    let proposal = suite.query_proposal(id);
+    assert_eq!(proposal.last_status(), Status::ExecutionFailed);
+    assert_eq!(proposal.winners, Some(vec![Addr::unchecked("alice")]));
+    assert_eq!(suite.query_council(), vec![("carol".to_string(), 1)]);
+
+    is_error!(
+        suite.execute("sender", id),
+        &ContractError::Unexecutable {}.to_string()
+    );
+}
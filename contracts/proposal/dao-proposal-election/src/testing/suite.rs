@@ -0,0 +1,425 @@
+use cosmwasm_std::{to_json_binary, Addr, Decimal, Empty};
+use cw_multi_test::{next_block, App, Executor};
+use cw_utils::Duration;
+use dao_interface::{
+    state::{Admin, ModuleInstantiateInfo},
+    voting::InfoResponse,
+};
+use dao_testing::contracts::{
+    cw4_group_contract, cw721_roles_contract, dao_dao_core_contract,
+    dao_proposal_election_contract, dao_voting_cw4_contract,
+};
+use dao_voting::threshold::PercentageThreshold;
+use dao_voting_cw4::msg::GroupContract;
+
+use crate::{
+    config::{Config, UncheckedConfig},
+    contract::{CONTRACT_NAME, CONTRACT_VERSION},
+    election::{Ballot, ElectionMethod},
+    msg::{ExecuteMsg, InstantiateMsg, ProposeMsg, QueryMsg},
+    proposal::{ElectionTarget, Proposal},
+};
+
+pub(crate) struct Suite {
+    app: App,
+    sender: Addr,
+    pub election: Addr,
+    pub core: Addr,
+    /// A cw4-group administered by the DAO whose membership is
+    /// decided by elections.
+    pub council: Addr,
+    /// A cw721-roles collection the DAO may mint seats in.
+    pub roles: Addr,
+}
+
+pub(crate) struct SuiteBuilder {
+    pub instantiate: UncheckedConfig,
+    with_voters: Vec<(String, u64)>,
+    with_council: Vec<(String, u64)>,
+}
+
+impl Default for SuiteBuilder {
+    fn default() -> Self {
+        Self {
+            instantiate: UncheckedConfig {
+                quorum: PercentageThreshold::Percent(Decimal::percent(15)),
+                voting_period: Duration::Time(60 * 60 * 24 * 7),
+                method: ElectionMethod::SingleTransferableVote,
+                targets: vec![],
+            },
+            with_voters: vec![("sender".to_string(), 10)],
+            with_council: vec![],
+        }
+    }
+}
+
+impl SuiteBuilder {
+    pub fn with_method(mut self, method: ElectionMethod) -> Self {
+        self.instantiate.method = method;
+        self
+    }
+
+    pub fn with_voters(mut self, voters: &[(&str, u64)]) -> Self {
+        self.with_voters = voters.iter().map(|(a, p)| (a.to_string(), *p)).collect();
+        self
+    }
+
+    pub fn with_council(mut self, members: &[(&str, u64)]) -> Self {
+        self.with_council = members.iter().map(|(a, p)| (a.to_string(), *p)).collect();
+        self
+    }
+
+    pub fn build(self) -> Suite {
+        let initial_members: Vec<_> = self
+            .with_voters
+            .into_iter()
+            .map(|(addr, weight)| cw4::Member { addr, weight })
+            .collect();
+        let sender = Addr::unchecked(&initial_members[0].addr);
+
+        let mut app = App::default();
+        let election_id = app.store_code(dao_proposal_election_contract());
+        let core_id = app.store_code(dao_dao_core_contract());
+        let cw4_id = app.store_code(cw4_group_contract());
+        let cw4_voting_id = app.store_code(dao_voting_cw4_contract());
+        let roles_id = app.store_code(cw721_roles_contract());
+
+        let core_instantiate = dao_interface::msg::InstantiateMsg {
+            admin: None,
+            name: "core module".to_string(),
+            description: "core module".to_string(),
+            image_url: None,
+            automatically_add_cw20s: false,
+            automatically_add_cw721s: false,
+            voting_module_instantiate_info: ModuleInstantiateInfo {
+                code_id: cw4_voting_id,
+                msg: to_json_binary(&dao_voting_cw4::msg::InstantiateMsg {
+                    group_contract: GroupContract::New {
+                        cw4_group_code_id: cw4_id,
+                        initial_members,
+                    },
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "voting module".to_string(),
+            },
+            proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
+                code_id: election_id,
+                msg: to_json_binary(&InstantiateMsg {
+                    quorum: self.instantiate.quorum,
+                    voting_period: self.instantiate.voting_period,
+                    method: self.instantiate.method,
+                    targets: self.instantiate.targets,
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "election module".to_string(),
+            }],
+            initial_items: None,
+            dao_uri: None,
+        };
+        let core = app
+            .instantiate_contract(
+                core_id,
+                sender.clone(),
+                &core_instantiate,
+                &[],
+                "core module".to_string(),
+                None,
+            )
+            .unwrap();
+        let election: Vec<dao_interface::state::ProposalModule> = app
+            .wrap()
+            .query_wasm_smart(
+                &core,
+                &dao_interface::msg::QueryMsg::ProposalModules {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        let election = election.into_iter().next().unwrap().address;
+
+        let council = app
+            .instantiate_contract(
+                cw4_id,
+                sender.clone(),
+                &cw4_group::msg::InstantiateMsg {
+                    admin: Some(core.to_string()),
+                    members: self
+                        .with_council
+                        .into_iter()
+                        .map(|(addr, weight)| cw4::Member { addr, weight })
+                        .collect(),
+                },
+                &[],
+                "council".to_string(),
+                None,
+            )
+            .unwrap();
+        let roles = app
+            .instantiate_contract(
+                roles_id,
+                sender.clone(),
+                &cw721_base::InstantiateMsg {
+                    name: "council".to_string(),
+                    symbol: "COUNCIL".to_string(),
+                    minter: core.to_string(),
+                },
+                &[],
+                "roles".to_string(),
+                None,
+            )
+            .unwrap();
+
+        app.update_block(next_block);
+
+        let suite = Suite {
+            app,
+            sender,
+            election,
+            core,
+            council,
+            roles,
+        };
+
+        let next_id = suite.query_next_proposal_id();
+        assert_eq!(next_id, 1);
+        let dao = suite.query_dao();
+        assert_eq!(dao, suite.core);
+        let info = suite.query_info();
+        assert_eq!(info.info.version, CONTRACT_VERSION);
+        assert_eq!(info.info.contract, CONTRACT_NAME);
+
+        suite
+    }
+}
+
+impl Suite {
+    pub fn a_week_passes(&mut self) {
+        self.app
+            .update_block(|b| b.time = b.time.plus_seconds(60 * 60 * 24 * 7))
+    }
+
+    pub fn sender(&self) -> Addr {
+        self.sender.clone()
+    }
+
+    pub fn council_target(&self, weight: u64) -> ElectionTarget {
+        ElectionTarget::Cw4Group {
+            address: self.council.to_string(),
+            weight,
+        }
+    }
+
+    pub fn roles_target(&self, role: &str, weight: u64) -> ElectionTarget {
+        ElectionTarget::Cw721Roles {
+            address: self.roles.to_string(),
+            role: Some(role.to_string()),
+            weight,
+        }
+    }
+}
+
+// query
+impl Suite {
+    pub fn query_config(&self) -> Config {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.election, &QueryMsg::Config {})
+            .unwrap()
+    }
+
+    pub fn query_proposal(&self, id: u32) -> Proposal {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.election, &QueryMsg::Proposal { id })
+            .unwrap()
+    }
+
+    pub fn query_list_proposals(
+        &self,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<Proposal> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.election,
+                &QueryMsg::ListProposals { start_after, limit },
+            )
+            .unwrap()
+    }
+
+    pub fn query_ballot(&self, proposal_id: u32, voter: &str) -> Option<Ballot> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.election,
+                &QueryMsg::Ballot {
+                    proposal_id,
+                    voter: voter.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_next_proposal_id(&self) -> u32 {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.election, &QueryMsg::NextProposalId {})
+            .unwrap()
+    }
+
+    pub fn query_dao(&self) -> Addr {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.election, &QueryMsg::Dao {})
+            .unwrap()
+    }
+
+    pub fn query_info(&self) -> InfoResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.election, &QueryMsg::Info {})
+            .unwrap()
+    }
+
+    /// The council's members and their weights, sorted by address.
+    pub fn query_council(&self) -> Vec<(String, u64)> {
+        let members: cw4::MemberListResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.council,
+                &cw4::Cw4QueryMsg::ListMembers {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        members
+            .members
+            .into_iter()
+            .map(|m| (m.addr, m.weight))
+            .collect()
+    }
+
+    /// The roles tokens held by OWNER.
+    pub fn query_seats(&self, owner: &str) -> Vec<String> {
+        let tokens: cw721::TokensResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.roles,
+                &cw721_base::QueryMsg::<Empty>::Tokens {
+                    owner: owner.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        tokens.tokens
+    }
+}
+
+// execute
+impl Suite {
+    pub fn propose<S: Into<String>>(
+        &mut self,
+        sender: S,
+        candidates: &[&str],
+        seats: u32,
+        target: u32,
+    ) -> anyhow::Result<u32> {
+        let id = self.query_next_proposal_id();
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.election.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                candidates: candidates.iter().map(|c| c.to_string()).collect(),
+                seats,
+                target,
+            }),
+            &[],
+        )?;
+        Ok(id)
+    }
+
+    pub fn vote<S: Into<String>>(
+        &mut self,
+        sender: S,
+        proposal_id: u32,
+        vote: Vec<u32>,
+    ) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.election.clone(),
+                &ExecuteMsg::Vote { proposal_id, vote },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn execute<S: Into<String>>(&mut self, sender: S, proposal_id: u32) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.election.clone(),
+                &ExecuteMsg::Execute { proposal_id },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn close<S: Into<String>>(&mut self, sender: S, proposal_id: u32) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.election.clone(),
+                &ExecuteMsg::Close { proposal_id },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn set_config<S: Into<String>>(
+        &mut self,
+        sender: S,
+        config: UncheckedConfig,
+    ) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.election.clone(),
+                &ExecuteMsg::SetConfig(config),
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    /// Has the DAO replace the targets elections may apply their
+    /// results to.
+    pub fn set_targets(&mut self, targets: Vec<ElectionTarget>) -> anyhow::Result<()> {
+        let Config {
+            quorum,
+            voting_period,
+            method,
+            ..
+        } = self.query_config();
+        self.set_config(
+            self.core.clone(),
+            UncheckedConfig {
+                quorum,
+                voting_period,
+                method,
+                targets,
+            },
+        )
+    }
+}
@@ -21,6 +21,7 @@ use dao_voting::{
     reply::{
         failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id, TaggedReplyId,
     },
+    simulation::{simulate_execution, SimulateExecutionResponse},
     status::Status,
     veto::{VetoConfig, VetoError},
     voting::{get_total_power, get_voting_power, validate_voting_period},
@@ -865,6 +866,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ProposalHooks {} => to_json_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_json_binary(&VOTE_HOOKS.query_hooks(deps)?),
         QueryMsg::Dao {} => query_dao(deps),
+        QueryMsg::SimulateExecution { proposal_id } => query_simulate_execution(deps, proposal_id),
     }
 }
 
@@ -883,6 +885,21 @@ pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
     to_json_binary(&proposal.into_response(&env.block, id)?)
 }
 
+pub fn query_simulate_execution(deps: Deps, id: u64) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    let mut warnings = vec![];
+    for choice in proposal.choices {
+        warnings.extend(simulate_execution(
+            deps,
+            &config.dao,
+            Some(choice.index),
+            &choice.msgs,
+        )?);
+    }
+    to_json_binary(&SimulateExecutionResponse { warnings })
+}
+
 pub fn query_creation_policy(deps: Deps) -> StdResult<Binary> {
     let policy = CREATION_POLICY.load(deps.storage)?;
     to_json_binary(&policy)
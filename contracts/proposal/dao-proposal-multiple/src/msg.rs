@@ -190,6 +190,12 @@ pub enum QueryMsg {
     /// Lists all of the consumers of vote hooks for this module.
     #[returns(::cw_hooks::HooksResponse)]
     VoteHooks {},
+    /// Statically checks the messages of each of a proposal's choices
+    /// against the DAO's current balances and validates the addresses
+    /// they contain, returning a warning for each message that may
+    /// fail. Does not execute the messages.
+    #[returns(::dao_voting::simulation::SimulateExecutionResponse)]
+    SimulateExecution { proposal_id: u64 },
 }
 
 #[cw_serde]
@@ -278,6 +278,8 @@ pub fn test_allow_voting_after_proposal_execution_pre_expiration_cw20() {
                 },
                 amount: Uint128::new(10_000_000),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                formula: None,
+                alternatives: vec![],
             }),
             false,
         ),
@@ -42,6 +42,9 @@ pub fn make_proposal(
                     denom,
                     amount,
                     refund_policy: _,
+                    formula: None,
+                    alternatives: vec![],
+                    escrowed_nft: None,
                 }) => match denom {
                     CheckedDenom::Native(denom) => coins(amount.u128(), denom),
                     CheckedDenom::Cw20(addr) => {
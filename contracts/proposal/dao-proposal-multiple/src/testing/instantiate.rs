@@ -48,6 +48,10 @@ fn get_pre_propose_info(
             msg: to_json_binary(&cppm::InstantiateMsg {
                 deposit_info,
                 submission_policy,
+                sponsorship: None,
+                forfeiture: None,
+                rate_limit: None,
+                conditions: None,
                 extension: Empty::default(),
             })
             .unwrap(),
@@ -76,6 +80,8 @@ pub fn _get_default_token_dao_proposal_module_instantiate(app: &mut App) -> Inst
                 },
                 amount: Uint128::new(10_000_000),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                formula: None,
+                alternatives: vec![],
             }),
             false,
         ),
@@ -283,6 +289,7 @@ pub fn instantiate_with_native_staked_balances_governance(
                 },
                 unstaking_duration: None,
                 active_threshold: None,
+                receipt_token: None,
             })
             .unwrap(),
             admin: None,
@@ -1,5 +1,6 @@
 use cosmwasm_std::{
-    to_json_binary, Addr, Coin, CosmosMsg, Decimal, Empty, Timestamp, Uint128, WasmMsg,
+    coins, to_json_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Empty, Timestamp, Uint128,
+    WasmMsg,
 };
 use cw20::Cw20Coin;
 use cw_denom::{CheckedDenom, UncheckedDenom};
@@ -23,6 +24,7 @@ use dao_voting::{
     },
     pre_propose::PreProposeInfo,
     proposal::MultipleChoiceProposeMsg as ProposeMsg,
+    simulation::{ExecutionWarning, SimulateExecutionResponse},
     status::Status,
     threshold::{ActiveThreshold, PercentageThreshold, Threshold},
 };
@@ -58,6 +60,7 @@ use dao_testing::{
         cw20_base_contract, dao_pre_propose_multiple_contract, dao_proposal_multiple_contract,
         dao_voting_cw20_balance_contract,
     },
+    helpers::simulate_proposal_multiple,
     ShouldExecute,
 };
 
@@ -98,6 +101,10 @@ pub fn get_pre_propose_info(
             msg: to_json_binary(&cppm::InstantiateMsg {
                 deposit_info,
                 submission_policy,
+                sponsorship: None,
+                forfeiture: None,
+                rate_limit: None,
+                conditions: None,
                 extension: Empty::default(),
             })
             .unwrap(),
@@ -773,6 +780,87 @@ fn test_propose_with_messages() {
     assert_eq!(config.max_voting_period, Duration::Height(20))
 }
 
+#[test]
+fn test_simulate_execution() {
+    let mut app = App::default();
+    let msg = InstantiateMsg {
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(10),
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        only_members_execute: true,
+        allow_revoting: false,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        veto: None,
+    };
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, msg, None);
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let options = vec![
+        MultipleChoiceOption {
+            description: "send".to_string(),
+            msgs: vec![BankMsg::Send {
+                to_address: "blue".to_string(),
+                amount: coins(10, "ujuno"),
+            }
+            .into()],
+            title: "title".to_string(),
+        },
+        MultipleChoiceOption {
+            description: "do nothing".to_string(),
+            msgs: vec![],
+            title: "title".to_string(),
+        },
+    ];
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod.clone(),
+        &ExecuteMsg::Propose(ProposeMsg {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            choices: MultipleChoiceOptions { options },
+            proposer: None,
+            vote: None,
+        }),
+        &[],
+    )
+    .unwrap();
+
+    let simulate = |app: &App| -> SimulateExecutionResponse {
+        app.wrap()
+            .query_wasm_smart(&govmod, &QueryMsg::SimulateExecution { proposal_id: 1 })
+            .unwrap()
+    };
+
+    // The DAO has no funds, so the first choice can not be executed.
+    assert_eq!(
+        simulate(&app).warnings,
+        vec![ExecutionWarning {
+            option_id: Some(0),
+            msg_index: 0,
+            warning: "insufficient balance: spends 10ujuno but the DAO holds 0ujuno".to_string(),
+        }]
+    );
+    simulate_proposal_multiple(&mut app, &govmod, 1, 0).unwrap_err();
+
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_addr.to_string(),
+        amount: coins(10, "ujuno"),
+    }))
+    .unwrap();
+    assert_eq!(simulate(&app).warnings, vec![]);
+
+    // Dry running the choice does not move any funds.
+    simulate_proposal_multiple(&mut app, &govmod, 1, 0).unwrap();
+    assert_eq!(query_balance_native(&app, "blue", "ujuno"), Uint128::zero());
+    assert_eq!(
+        query_balance_native(&app, core_addr.as_str(), "ujuno"),
+        Uint128::new(10)
+    );
+}
+
 #[test]
 #[should_panic(
     expected = "min_voting_period and max_voting_period must have the same units (height or time)"
@@ -976,6 +1064,8 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                formula: None,
+                alternatives: vec![],
             }),
             false,
         ),
@@ -1004,7 +1094,10 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
         Some(CheckedDepositInfo {
             denom: CheckedDenom::Cw20(token),
             amount: Uint128::new(1),
-            refund_policy: DepositRefundPolicy::OnlyPassed
+            refund_policy: DepositRefundPolicy::OnlyPassed,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: None,
         })
     )
 }
@@ -1052,6 +1145,8 @@ fn test_different_token_proposal_deposit() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                formula: None,
+                alternatives: vec![],
             }),
             false,
         ),
@@ -1114,6 +1209,8 @@ fn test_bad_token_proposal_deposit() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                formula: None,
+                alternatives: vec![],
             }),
             false,
         ),
@@ -1147,6 +1244,8 @@ fn test_take_proposal_deposit() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                formula: None,
+                alternatives: vec![],
             }),
             false,
         ),
@@ -1255,6 +1354,8 @@ fn test_take_native_proposal_deposit() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                formula: None,
+                alternatives: vec![],
             }),
             false,
         ),
@@ -1349,6 +1450,8 @@ fn test_native_proposal_deposit() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::Always,
+                formula: None,
+                alternatives: vec![],
             }),
             false,
         ),
@@ -1493,6 +1596,8 @@ fn test_deposit_return_on_execute() {
             },
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::OnlyPassed,
+            formula: None,
+            alternatives: vec![],
         }),
         true,
     );
@@ -1780,6 +1885,8 @@ fn test_cant_propose_zero_power() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::Always,
+                formula: None,
+                alternatives: vec![],
             }),
             false,
         ),
@@ -1900,6 +2007,8 @@ fn test_cant_vote_not_registered() {
             },
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -2260,6 +2369,8 @@ fn test_close_open_proposal() {
             },
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -2330,6 +2441,8 @@ fn test_no_refund_failed_proposal() {
             },
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::OnlyPassed,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -2410,6 +2523,8 @@ fn test_deposit_return_on_close() {
             },
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -2713,6 +2828,8 @@ fn test_no_return_if_no_refunds() {
             },
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::OnlyPassed,
+            formula: None,
+            alternatives: vec![],
         }),
         true,
     );
@@ -3925,6 +4042,8 @@ fn test_return_deposit_to_dao_on_proposal_failure() {
             },
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::OnlyPassed,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -4236,6 +4355,8 @@ fn test_no_double_refund_on_execute_fail_and_close() {
                 // that we don't get a second refund on close. Refunds on
                 // close only happen if this is true.
                 refund_policy: DepositRefundPolicy::Always,
+                formula: None,
+                alternatives: vec![],
             }),
             false,
         ),
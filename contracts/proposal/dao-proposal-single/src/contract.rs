@@ -20,6 +20,7 @@ use dao_voting::proposal::{
 use dao_voting::reply::{
     failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id, TaggedReplyId,
 };
+use dao_voting::simulation::{simulate_execution, SimulateExecutionResponse};
 use dao_voting::status::Status;
 use dao_voting::threshold::Threshold;
 use dao_voting::veto::{VetoConfig, VetoError};
@@ -845,6 +846,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ProposalCreationPolicy {} => query_creation_policy(deps),
         QueryMsg::ProposalHooks {} => to_json_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_json_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::SimulateExecution { proposal_id } => query_simulate_execution(deps, proposal_id),
     }
 }
 
@@ -863,6 +865,13 @@ pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
     to_json_binary(&proposal.into_response(&env.block, id)?)
 }
 
+pub fn query_simulate_execution(deps: Deps, id: u64) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    let warnings = simulate_execution(deps, &config.dao, None, &proposal.msgs)?;
+    to_json_binary(&SimulateExecutionResponse { warnings })
+}
+
 pub fn query_creation_policy(deps: Deps) -> StdResult<Binary> {
     let policy = CREATION_POLICY.load(deps.storage)?;
     to_json_binary(&policy)
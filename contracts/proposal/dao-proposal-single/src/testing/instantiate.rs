@@ -46,6 +46,10 @@ pub(crate) fn get_pre_propose_info(
             msg: to_json_binary(&cppbps::InstantiateMsg {
                 deposit_info,
                 submission_policy,
+                sponsorship: None,
+                forfeiture: None,
+                rate_limit: None,
+                conditions: None,
                 extension: Empty::default(),
             })
             .unwrap(),
@@ -75,6 +79,8 @@ pub(crate) fn get_default_token_dao_proposal_module_instantiate(app: &mut App) -
                 },
                 amount: Uint128::new(10_000_000),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                formula: None,
+                alternatives: vec![],
             }),
             false,
         ),
@@ -284,6 +290,7 @@ pub(crate) fn instantiate_with_native_staked_balances_governance(
                 },
                 unstaking_duration: None,
                 active_threshold: None,
+                receipt_token: None,
             })
             .unwrap(),
             admin: None,
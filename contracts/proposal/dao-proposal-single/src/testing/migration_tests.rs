@@ -312,6 +312,8 @@ fn test_v1_v2_full_migration() {
             },
             amount: Uint128::new(1),
             refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -18,6 +18,7 @@ use dao_interface::{
 };
 use dao_testing::{
     contracts::{dao_pre_propose_single_contract, dao_proposal_single_contract},
+    helpers::simulate_proposal_single,
     ShouldExecute, TestSingleChoiceVote,
 };
 use dao_voting::{
@@ -28,6 +29,7 @@ use dao_voting::{
         failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id,
         mask_proposal_hook_index, mask_vote_hook_index,
     },
+    simulation::{ExecutionWarning, SimulateExecutionResponse},
     status::Status,
     threshold::{ActiveThreshold, PercentageThreshold, Threshold},
     veto::{VetoConfig, VetoError},
@@ -148,7 +150,10 @@ fn test_simple_propose_staked_balances() {
         Some(CheckedDepositInfo {
             denom: cw_denom::CheckedDenom::Cw20(gov_token),
             amount: Uint128::new(10_000_000),
-            refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed
+            refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: None,
         })
     );
 }
@@ -356,6 +361,8 @@ fn test_instantiate_with_non_voting_module_cw20_deposit() {
             },
             amount: Uint128::new(10_000_000),
             refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed,
+            formula: None,
+            alternatives: vec![],
         }),
         false,
     );
@@ -401,7 +408,10 @@ fn test_instantiate_with_non_voting_module_cw20_deposit() {
         Some(CheckedDepositInfo {
             denom: cw_denom::CheckedDenom::Cw20(alt_cw20),
             amount: Uint128::new(10_000_000),
-            refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed
+            refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: None,
         })
     );
 }
@@ -3959,12 +3969,18 @@ fn test_update_pre_propose_module() {
                                 },
                                 amount: Uint128::new(1),
                                 refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed,
+                                formula: None,
+                                alternatives: vec![],
                             }),
                             submission_policy: PreProposeSubmissionPolicy::Specific {
                                 dao_members: true,
                                 allowlist: vec![],
                                 denylist: vec![],
                             },
+                            sponsorship: None,
+                            forfeiture: None,
+                            rate_limit: None,
+                            conditions: None,
                             extension: Empty::default(),
                         })
                         .unwrap(),
@@ -4014,12 +4030,19 @@ fn test_update_pre_propose_module() {
                 denom: CheckedDenom::Cw20(gov_token.clone()),
                 amount: Uint128::new(1),
                 refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed,
+                formula: None,
+                alternatives: vec![],
+                escrowed_nft: None,
             }),
             submission_policy: PreProposeSubmissionPolicy::Specific {
                 dao_members: true,
                 allowlist: vec![],
                 denylist: vec![]
             },
+            sponsorship: None,
+            forfeiture: None,
+            rate_limit: None,
+            conditions: None,
         }
     );
 
@@ -4251,3 +4274,74 @@ fn test_proposal_count_goes_up() {
     let next = query_next_proposal_id(&app, &proposal_module);
     assert_eq!(next, 3);
 }
+
+#[test]
+fn test_simulate_execution() {
+    let CommonTest {
+        mut app,
+        core_addr,
+        proposal_module,
+        gov_token,
+        proposal_id,
+    } = setup_test(vec![BankMsg::Send {
+        to_address: "recipient".to_string(),
+        amount: coins(10, "ujuno"),
+    }
+    .into()]);
+
+    let simulate = |app: &App, proposal_id| -> SimulateExecutionResponse {
+        app.wrap()
+            .query_wasm_smart(
+                &proposal_module,
+                &QueryMsg::SimulateExecution { proposal_id },
+            )
+            .unwrap()
+    };
+
+    // The DAO has no funds, so the proposal can not be executed.
+    assert_eq!(
+        simulate(&app, proposal_id).warnings,
+        vec![ExecutionWarning {
+            option_id: None,
+            msg_index: 0,
+            warning: "insufficient balance: spends 10ujuno but the DAO holds 0ujuno".to_string(),
+        }]
+    );
+    simulate_proposal_single(&mut app, &proposal_module, proposal_id).unwrap_err();
+
+    mint_natives(&mut app, core_addr.as_str(), coins(10, "ujuno"));
+    assert_eq!(simulate(&app, proposal_id).warnings, vec![]);
+
+    // Dry running the proposal does not move any funds.
+    let res = simulate_proposal_single(&mut app, &proposal_module, proposal_id).unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(
+        query_balance_native(&app, "recipient", "ujuno"),
+        Uint128::zero()
+    );
+    assert_eq!(
+        query_balance_native(&app, core_addr.as_str(), "ujuno"),
+        Uint128::new(10)
+    );
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        vec![BankMsg::Send {
+            to_address: "Recipient".to_string(),
+            amount: coins(10, "ujuno"),
+        }
+        .into()],
+        None,
+    );
+    assert_eq!(
+        simulate(&app, proposal_id).warnings,
+        vec![ExecutionWarning {
+            option_id: None,
+            msg_index: 0,
+            warning: "invalid address (Recipient)".to_string(),
+        }]
+    );
+}
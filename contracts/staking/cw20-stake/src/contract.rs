@@ -2,8 +2,8 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
-    StdError, StdResult, Uint128,
+    from_json, to_json_binary, Addr, Binary, BlockInfo, Deps, DepsMut, Empty, Env, MessageInfo,
+    Response, StdError, StdResult, Storage, SubMsg, Timestamp, Uint128, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw20::{Cw20ReceiveMsg, TokenInfoResponse};
@@ -21,6 +21,7 @@ use cw_controllers::ClaimsResponse;
 use cw_utils::Duration;
 use dao_hooks::stake::{stake_hook_msgs, unstake_hook_msgs};
 use dao_voting::duration::validate_duration;
+use dao_voting::early_unstake::{EarlyUnstakeConfig, EarlyUnstakeUpdate, PenaltyDestination};
 
 use crate::math;
 use crate::msg::{
@@ -29,7 +30,8 @@ use crate::msg::{
     TotalStakedAtHeightResponse, TotalValueResponse,
 };
 use crate::state::{
-    Config, BALANCE, CLAIMS, CONFIG, HOOKS, MAX_CLAIMS, STAKED_BALANCES, STAKED_TOTAL,
+    Config, BALANCE, CLAIMS, CLAIM_DURATIONS, CONFIG, HOOKS, MAX_CLAIMS, STAKED_BALANCES,
+    STAKED_TOTAL,
 };
 use crate::ContractError;
 
@@ -59,6 +61,8 @@ pub fn instantiate(
     let config = Config {
         token_address,
         unstaking_duration: msg.unstaking_duration,
+        stake_transfers_enabled: false,
+        early_unstake: None,
     };
     CONFIG.save(deps.storage, &config)?;
 
@@ -84,8 +88,17 @@ pub fn execute(
     match msg {
         ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
         ExecuteMsg::Unstake { amount } => execute_unstake(deps, env, info, amount),
+        ExecuteMsg::InstantUnstake { amount } => execute_instant_unstake(deps, env, info, amount),
         ExecuteMsg::Claim {} => execute_claim(deps, env, info),
-        ExecuteMsg::UpdateConfig { duration } => execute_update_config(info, deps, duration),
+        ExecuteMsg::AccelerateClaim {} => execute_accelerate_claim(deps, env, info),
+        ExecuteMsg::TransferStake { to, amount } => {
+            execute_transfer_stake(deps, env, info, to, amount)
+        }
+        ExecuteMsg::UpdateConfig {
+            duration,
+            stake_transfers_enabled,
+            early_unstake,
+        } => execute_update_config(info, deps, duration, stake_transfers_enabled, early_unstake),
         ExecuteMsg::AddHook { addr } => execute_add_hook(deps, env, info, addr),
         ExecuteMsg::RemoveHook { addr } => execute_remove_hook(deps, env, info, addr),
         ExecuteMsg::UpdateOwnership(action) => execute_update_owner(deps, info, env, action),
@@ -96,13 +109,24 @@ pub fn execute_update_config(
     info: MessageInfo,
     deps: DepsMut,
     duration: Option<Duration>,
+    stake_transfers_enabled: Option<bool>,
+    early_unstake: Option<EarlyUnstakeUpdate>,
 ) -> Result<Response, ContractError> {
     cw_ownable::assert_owner(deps.storage, &info.sender)?;
 
     validate_duration(duration)?;
+    let early_unstake = early_unstake
+        .map(EarlyUnstakeUpdate::into_config)
+        .transpose()?;
 
-    CONFIG.update(deps.storage, |mut config| -> Result<Config, StdError> {
+    let config = CONFIG.update(deps.storage, |mut config| -> Result<Config, StdError> {
         config.unstaking_duration = duration;
+        if let Some(stake_transfers_enabled) = stake_transfers_enabled {
+            config.stake_transfers_enabled = stake_transfers_enabled;
+        }
+        if let Some(early_unstake) = early_unstake {
+            config.early_unstake = early_unstake;
+        }
         Ok(config)
     })?;
 
@@ -113,6 +137,10 @@ pub fn execute_update_config(
             duration
                 .map(|d| format!("{d}"))
                 .unwrap_or_else(|| "none".to_string()),
+        )
+        .add_attribute(
+            "stake_transfers_enabled",
+            config.stake_transfers_enabled.to_string(),
         ))
 }
 
@@ -133,6 +161,10 @@ pub fn execute_receive(
     let sender = deps.api.addr_validate(&wrapper.sender)?;
     match msg {
         ReceiveMsg::Stake {} => execute_stake(deps, env, sender, wrapper.amount),
+        ReceiveMsg::StakeFor { recipient } => {
+            let recipient = deps.api.addr_validate(&recipient)?;
+            execute_stake(deps, env, recipient, wrapper.amount)
+        }
         ReceiveMsg::Fund {} => execute_fund(deps, env, &sender, wrapper.amount),
     }
 }
@@ -179,51 +211,10 @@ pub fn execute_unstake(
     amount: Uint128,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let balance = BALANCE.load(deps.storage)?;
-    let staked_total = STAKED_TOTAL.load(deps.storage)?;
-    // invariant checks for amount_to_claim
-    if staked_total.is_zero() {
-        return Err(ContractError::NothingStaked {});
-    }
-    if amount.saturating_add(balance) == Uint128::MAX {
-        return Err(ContractError::Cw20InvaraintViolation {});
-    }
-    if amount > staked_total {
-        return Err(ContractError::ImpossibleUnstake {});
-    }
-    let amount_to_claim = math::amount_to_claim(staked_total, balance, amount);
-    STAKED_BALANCES.update(
-        deps.storage,
-        &info.sender,
-        env.block.height,
-        |bal| -> StdResult<Uint128> { Ok(bal.unwrap_or_default().checked_sub(amount)?) },
-    )?;
-    STAKED_TOTAL.update(
-        deps.storage,
-        env.block.height,
-        |total| -> StdResult<Uint128> {
-            // Initialized during instantiate - OK to unwrap.
-            Ok(total.unwrap().checked_sub(amount)?)
-        },
-    )?;
-    BALANCE.save(
-        deps.storage,
-        &balance
-            .checked_sub(amount_to_claim)
-            .map_err(StdError::overflow)?,
-    )?;
-    let hook_msgs = unstake_hook_msgs(HOOKS, deps.storage, info.sender.clone(), amount)?;
+    let (amount_to_claim, hook_msgs) = remove_stake(deps.storage, &env, &info.sender, amount)?;
     match config.unstaking_duration {
         None => {
-            let cw_send_msg = cw20::Cw20ExecuteMsg::Transfer {
-                recipient: info.sender.to_string(),
-                amount: amount_to_claim,
-            };
-            let wasm_msg = cosmwasm_std::WasmMsg::Execute {
-                contract_addr: config.token_address.to_string(),
-                msg: to_json_binary(&cw_send_msg)?,
-                funds: vec![],
-            };
+            let wasm_msg = transfer_msg(&config, &info.sender, amount_to_claim)?;
             Ok(Response::new()
                 .add_message(wasm_msg)
                 .add_submessages(hook_msgs)
@@ -238,12 +229,9 @@ pub fn execute_unstake(
                 return Err(ContractError::TooManyClaims {});
             }
 
-            CLAIMS.create_claim(
-                deps.storage,
-                &info.sender,
-                amount_to_claim,
-                duration.after(&env.block),
-            )?;
+            let release_at = duration.after(&env.block);
+            CLAIMS.create_claim(deps.storage, &info.sender, amount_to_claim, release_at)?;
+            CLAIM_DURATIONS.save(deps.storage, &info.sender, &release_at, duration)?;
             Ok(Response::new()
                 .add_attribute("action", "unstake")
                 .add_submessages(hook_msgs)
@@ -254,11 +242,189 @@ pub fn execute_unstake(
     }
 }
 
+pub fn execute_instant_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let early_unstake = config
+        .early_unstake
+        .clone()
+        .ok_or(ContractError::EarlyUnstakeDisabled {})?;
+
+    let (amount_to_claim, hook_msgs) = remove_stake(deps.storage, &env, &info.sender, amount)?;
+
+    // Without an unstaking duration there is nothing to skip.
+    let penalty = match config.unstaking_duration {
+        Some(_) => early_unstake.unstake_penalty(amount_to_claim),
+        None => Uint128::zero(),
+    };
+    let payout_msgs = payout_msgs(
+        deps.as_ref(),
+        &config,
+        &early_unstake,
+        &info.sender,
+        amount_to_claim,
+        penalty,
+    )?;
+
+    Ok(Response::new()
+        .add_messages(payout_msgs)
+        .add_submessages(hook_msgs)
+        .add_attribute("action", "instant_unstake")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("penalty", penalty))
+}
+
+/// Removes AMOUNT of STAKER's staked balance, returning the amount of
+/// tokens it is worth and the unstake hook messages.
+fn remove_stake(
+    storage: &mut dyn Storage,
+    env: &Env,
+    staker: &Addr,
+    amount: Uint128,
+) -> Result<(Uint128, Vec<SubMsg>), ContractError> {
+    let balance = BALANCE.load(storage)?;
+    let staked_total = STAKED_TOTAL.load(storage)?;
+    // invariant checks for amount_to_claim
+    if staked_total.is_zero() {
+        return Err(ContractError::NothingStaked {});
+    }
+    if amount.saturating_add(balance) == Uint128::MAX {
+        return Err(ContractError::Cw20InvaraintViolation {});
+    }
+    if amount > staked_total {
+        return Err(ContractError::ImpossibleUnstake {});
+    }
+    let amount_to_claim = math::amount_to_claim(staked_total, balance, amount);
+    STAKED_BALANCES.update(
+        storage,
+        staker,
+        env.block.height,
+        |bal| -> StdResult<Uint128> { Ok(bal.unwrap_or_default().checked_sub(amount)?) },
+    )?;
+    STAKED_TOTAL.update(storage, env.block.height, |total| -> StdResult<Uint128> {
+        // Initialized during instantiate - OK to unwrap.
+        Ok(total.unwrap().checked_sub(amount)?)
+    })?;
+    BALANCE.save(
+        storage,
+        &balance
+            .checked_sub(amount_to_claim)
+            .map_err(StdError::overflow)?,
+    )?;
+    let hook_msgs = unstake_hook_msgs(HOOKS, storage, staker.clone(), amount)?;
+    Ok((amount_to_claim, hook_msgs))
+}
+
+fn transfer_msg(config: &Config, recipient: &Addr, amount: Uint128) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: config.token_address.to_string(),
+        msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    })
+}
+
+/// Pays AMOUNT less PENALTY to STAKER, and sends PENALTY to the owner
+/// or burns it. If there is no owner, the penalty is burned.
+fn payout_msgs(
+    deps: Deps,
+    config: &Config,
+    early_unstake: &EarlyUnstakeConfig,
+    staker: &Addr,
+    amount: Uint128,
+    penalty: Uint128,
+) -> StdResult<Vec<WasmMsg>> {
+    let mut msgs = vec![];
+    let payout = amount.checked_sub(penalty)?;
+    if !payout.is_zero() {
+        msgs.push(transfer_msg(config, staker, payout)?);
+    }
+    if !penalty.is_zero() {
+        let owner = match early_unstake.destination {
+            PenaltyDestination::Dao => cw_ownable::get_ownership(deps.storage)?.owner,
+            PenaltyDestination::Burn => None,
+        };
+        msgs.push(match owner {
+            Some(owner) => transfer_msg(config, &owner, penalty)?,
+            None => WasmMsg::Execute {
+                contract_addr: config.token_address.to_string(),
+                msg: to_json_binary(&cw20::Cw20ExecuteMsg::Burn { amount: penalty })?,
+                funds: vec![],
+            },
+        });
+    }
+    Ok(msgs)
+}
+
+pub fn execute_transfer_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    to: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.stake_transfers_enabled {
+        return Err(ContractError::StakeTransfersDisabled {});
+    }
+    if amount.is_zero() {
+        return Err(ContractError::ZeroTransfer {});
+    }
+    let to = deps.api.addr_validate(&to)?;
+    if to == info.sender {
+        return Err(ContractError::SelfTransfer {});
+    }
+
+    // Staked balances are shares of the contract's balance, so moving
+    // them leaves the value of every other stake unchanged.
+    STAKED_BALANCES.update(
+        deps.storage,
+        &info.sender,
+        env.block.height,
+        |bal| -> Result<Uint128, ContractError> {
+            bal.unwrap_or_default()
+                .checked_sub(amount)
+                .map_err(|_| ContractError::ImpossibleUnstake {})
+        },
+    )?;
+    STAKED_BALANCES.update(
+        deps.storage,
+        &to,
+        env.block.height,
+        |bal| -> StdResult<Uint128> { Ok(bal.unwrap_or_default().checked_add(amount)?) },
+    )?;
+
+    // The transfer unstakes from the sender and stakes for the
+    // recipient as far as hooks are concerned.
+    let unstake_msgs = unstake_hook_msgs(HOOKS, deps.storage, info.sender.clone(), amount)?;
+    let stake_msgs = stake_hook_msgs(HOOKS, deps.storage, to.clone(), amount)?;
+
+    Ok(Response::new()
+        .add_submessages(unstake_msgs)
+        .add_submessages(stake_msgs)
+        .add_attribute("action", "transfer_stake")
+        .add_attribute("from", info.sender)
+        .add_attribute("to", to)
+        .add_attribute("amount", amount))
+}
+
 pub fn execute_claim(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
+    for claim in CLAIMS.query_claims(deps.as_ref(), &info.sender)?.claims {
+        if claim.release_at.is_expired(&_env.block) {
+            CLAIM_DURATIONS.remove(deps.storage, &info.sender, &claim.release_at);
+        }
+    }
     let release = CLAIMS.claim_tokens(deps.storage, &info.sender, &_env.block, None)?;
     if release.is_zero() {
         return Err(ContractError::NothingToClaim {});
@@ -280,6 +446,69 @@ pub fn execute_claim(
         .add_attribute("amount", release))
 }
 
+pub fn execute_accelerate_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let early_unstake = config
+        .early_unstake
+        .clone()
+        .ok_or(ContractError::EarlyUnstakeDisabled {})?;
+
+    let claims = CLAIMS.query_claims(deps.as_ref(), &info.sender)?.claims;
+    let mut penalty = Uint128::zero();
+    for claim in claims.iter() {
+        let claim_duration =
+            CLAIM_DURATIONS.may_load(deps.storage, &info.sender, &claim.release_at)?;
+        penalty = penalty
+            .checked_add(early_unstake.claim_penalty(
+                claim.amount,
+                &claim.release_at,
+                claim_duration,
+                config.unstaking_duration,
+                &env.block,
+            ))
+            .map_err(StdError::overflow)?;
+    }
+    for claim in claims {
+        CLAIM_DURATIONS.remove(deps.storage, &info.sender, &claim.release_at);
+    }
+
+    // cw-controllers only releases expired claims, so release them as
+    // of a block after every claim has expired.
+    let release = CLAIMS.claim_tokens(
+        deps.storage,
+        &info.sender,
+        &BlockInfo {
+            height: u64::MAX,
+            time: Timestamp::from_nanos(u64::MAX),
+            chain_id: env.block.chain_id.clone(),
+        },
+        None,
+    )?;
+    if release.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    let payout_msgs = payout_msgs(
+        deps.as_ref(),
+        &config,
+        &early_unstake,
+        &info.sender,
+        release,
+        penalty,
+    )?;
+
+    Ok(Response::new()
+        .add_messages(payout_msgs)
+        .add_attribute("action", "accelerate_claim")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", release)
+        .add_attribute("penalty", penalty))
+}
+
 pub fn execute_fund(
     deps: DepsMut,
     _env: Env,
@@ -480,6 +709,8 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
                     cw_utils_v1::Duration::Time(t) => Duration::Time(t),
                     cw_utils_v1::Duration::Height(h) => Duration::Height(h),
                 }),
+                stake_transfers_enabled: false,
+                early_unstake: None,
             };
             CONFIG.save(deps.storage, &config)?;
 
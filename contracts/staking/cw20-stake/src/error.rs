@@ -18,6 +18,9 @@ pub enum ContractError {
     #[error(transparent)]
     UnstakingDurationError(#[from] dao_voting::duration::UnstakingDurationError),
 
+    #[error(transparent)]
+    EarlyUnstakeError(#[from] dao_voting::early_unstake::EarlyUnstakeError),
+
     #[error("can not migrate. current version is up to date")]
     AlreadyMigrated {},
 
@@ -30,6 +33,9 @@ pub enum ContractError {
     #[error("Provided cw20 errored in response to TokenInfo query")]
     InvalidCw20 {},
 
+    #[error("Early unstaking is not enabled")]
+    EarlyUnstakeDisabled {},
+
     #[error("Invalid token")]
     InvalidToken { received: Addr, expected: Addr },
 
@@ -39,6 +45,15 @@ pub enum ContractError {
     #[error("Nothing to unstake")]
     NothingStaked {},
 
+    #[error("Can not transfer stake to yourself")]
+    SelfTransfer {},
+
+    #[error("Stake transfers are not enabled")]
+    StakeTransfersDisabled {},
+
     #[error("Too many outstanding claims. Claim some tokens before unstaking more.")]
     TooManyClaims {},
+
+    #[error("Amount being transferred must be non-zero")]
+    ZeroTransfer {},
 }
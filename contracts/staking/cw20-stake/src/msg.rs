@@ -3,6 +3,7 @@ use cosmwasm_std::Uint128;
 use cw20::Cw20ReceiveMsg;
 
 use cw_utils::Duration;
+use dao_voting::early_unstake::EarlyUnstakeUpdate;
 
 use cw_ownable::cw_ownable_execute;
 
@@ -23,16 +24,50 @@ pub struct InstantiateMsg {
 #[cw_serde]
 pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
-    Unstake { amount: Uint128 },
+    Unstake {
+        amount: Uint128,
+    },
+    /// Unstakes AMOUNT without waiting for the unstaking duration by
+    /// paying the early unstake penalty. Only allowed if the owner has
+    /// configured `early_unstake`.
+    InstantUnstake {
+        amount: Uint128,
+    },
     Claim {},
-    UpdateConfig { duration: Option<Duration> },
-    AddHook { addr: String },
-    RemoveHook { addr: String },
+    /// Claims all of the sender's unbonding tokens now. Each claim
+    /// pays the early unstake penalty scaled by how much of the
+    /// unstaking duration it has left.
+    AccelerateClaim {},
+    /// Moves AMOUNT of the sender's staked balance to TO without
+    /// unstaking it. Only allowed if the owner has enabled stake
+    /// transfers.
+    TransferStake {
+        to: String,
+        amount: Uint128,
+    },
+    /// Only callable by the owner. If STAKE_TRANSFERS_ENABLED or
+    /// EARLY_UNSTAKE is `None`, it is left unchanged.
+    UpdateConfig {
+        duration: Option<Duration>,
+        stake_transfers_enabled: Option<bool>,
+        early_unstake: Option<EarlyUnstakeUpdate>,
+    },
+    AddHook {
+        addr: String,
+    },
+    RemoveHook {
+        addr: String,
+    },
 }
 
 #[cw_serde]
 pub enum ReceiveMsg {
     Stake {},
+    /// Stakes the sent tokens on behalf of RECIPIENT, who may unstake
+    /// them.
+    StakeFor {
+        recipient: String,
+    },
     Fund {},
 }
 
@@ -4,11 +4,20 @@ use cw_controllers::Claims;
 use cw_hooks::Hooks;
 use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
 use cw_utils::Duration;
+use dao_voting::early_unstake::{ClaimDurations, EarlyUnstakeConfig};
 
 #[cw_serde]
 pub struct Config {
     pub token_address: Addr,
     pub unstaking_duration: Option<Duration>,
+    /// Whether stakers may move their staked balance to another
+    /// address with `TransferStake`.
+    #[serde(default)]
+    pub stake_transfers_enabled: bool,
+    /// If set, stakers may skip the unstaking duration by paying a
+    /// penalty with `InstantUnstake` and `AccelerateClaim`.
+    #[serde(default)]
+    pub early_unstake: Option<EarlyUnstakeConfig>,
 }
 
 // `"config"` key stores v1 configuration.
@@ -33,6 +42,9 @@ pub const MAX_CLAIMS: u64 = 100;
 
 pub const CLAIMS: Claims = Claims::new("claims");
 
+/// The unstaking duration each claim was created with.
+pub const CLAIM_DURATIONS: ClaimDurations = ClaimDurations::new();
+
 pub const BALANCE: Item<Uint128> = Item::new("balance");
 
 // Hooks to contracts that will receive staking and unstaking messages
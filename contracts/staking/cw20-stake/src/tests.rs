@@ -1,6 +1,6 @@
 use anyhow::Result as AnyResult;
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{to_json_binary, Addr, MessageInfo, Uint128, WasmMsg};
+use cosmwasm_std::{to_json_binary, Addr, Decimal, MessageInfo, Uint128, WasmMsg};
 use cw20::Cw20Coin;
 use cw_controllers::{Claim, ClaimsResponse};
 use cw_multi_test::{next_block, App, AppResponse, Executor};
@@ -9,6 +9,9 @@ use cw_utils::Duration;
 use cw_utils::Expiration::AtHeight;
 use dao_testing::contracts::{cw20_base_contract, cw20_stake_contract, v1::cw20_stake_v1_contract};
 use dao_voting::duration::UnstakingDurationError;
+use dao_voting::early_unstake::{
+    EarlyUnstakeConfig, EarlyUnstakeError, EarlyUnstakeUpdate, PenaltyDestination,
+};
 use std::borrow::BorrowMut;
 
 use crate::msg::{
@@ -173,7 +176,11 @@ fn update_config(
     info: MessageInfo,
     duration: Option<Duration>,
 ) -> AnyResult<AppResponse> {
-    let msg = ExecuteMsg::UpdateConfig { duration };
+    let msg = ExecuteMsg::UpdateConfig {
+        duration,
+        stake_transfers_enabled: None,
+        early_unstake: None,
+    };
     app.execute_contract(info.sender, staking_addr.clone(), &msg, &[])
 }
 
@@ -1088,6 +1095,401 @@ fn test_ownership_transfer() {
     );
 }
 
+#[test]
+fn test_stake_for() {
+    let mut app = mock_app();
+    let initial_balances = vec![Cw20Coin {
+        address: ADDR1.to_string(),
+        amount: Uint128::new(100),
+    }];
+    let (staking_addr, cw20_addr) = setup_test_case(&mut app, initial_balances, None);
+
+    let msg = cw20::Cw20ExecuteMsg::Send {
+        contract: staking_addr.to_string(),
+        amount: Uint128::new(100),
+        msg: to_json_binary(&ReceiveMsg::StakeFor {
+            recipient: ADDR2.to_string(),
+        })
+        .unwrap(),
+    };
+    app.execute_contract(Addr::unchecked(ADDR1), cw20_addr.clone(), &msg, &[])
+        .unwrap();
+    app.update_block(next_block);
+
+    // The recipient has the stake and may unstake it.
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR1),
+        Uint128::zero()
+    );
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR2),
+        Uint128::new(100)
+    );
+    unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(100),
+    )
+    .unwrap_err();
+    unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR2, &[]),
+        Uint128::new(100),
+    )
+    .unwrap();
+    assert_eq!(get_balance(&app, &cw20_addr, ADDR2), Uint128::new(100));
+}
+
+#[test]
+fn test_transfer_stake() {
+    let mut app = mock_app();
+    let initial_balances = vec![Cw20Coin {
+        address: ADDR1.to_string(),
+        amount: Uint128::new(200),
+    }];
+    let (staking_addr, cw20_addr) =
+        setup_test_case(&mut app, initial_balances, Some(Duration::Height(10)));
+
+    stake_tokens(
+        &mut app,
+        &staking_addr,
+        &cw20_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(100),
+    )
+    .unwrap();
+    // Rewards double the value of each staked token.
+    let msg = cw20::Cw20ExecuteMsg::Send {
+        contract: staking_addr.to_string(),
+        amount: Uint128::new(100),
+        msg: to_json_binary(&ReceiveMsg::Fund {}).unwrap(),
+    };
+    app.execute_contract(Addr::unchecked(ADDR1), cw20_addr, &msg, &[])
+        .unwrap();
+
+    let transfer = |app: &mut App, to: &str, amount: u128| {
+        app.execute_contract(
+            Addr::unchecked(ADDR1),
+            staking_addr.clone(),
+            &ExecuteMsg::TransferStake {
+                to: to.to_string(),
+                amount: Uint128::new(amount),
+            },
+            &[],
+        )
+    };
+
+    // Transfers are disabled by default, and only the owner may
+    // enable them.
+    let err: ContractError = transfer(&mut app, ADDR2, 40)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::StakeTransfersDisabled {});
+
+    let enable = ExecuteMsg::UpdateConfig {
+        duration: Some(Duration::Height(10)),
+        stake_transfers_enabled: Some(true),
+        early_unstake: None,
+    };
+    let err: ContractError = app
+        .execute_contract(Addr::unchecked(ADDR1), staking_addr.clone(), &enable, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+    app.execute_contract(Addr::unchecked(OWNER), staking_addr.clone(), &enable, &[])
+        .unwrap();
+    assert!(query_config(&app, &staking_addr).stake_transfers_enabled);
+
+    let err: ContractError = transfer(&mut app, ADDR2, 0)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroTransfer {});
+    let err: ContractError = transfer(&mut app, ADDR1, 40)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::SelfTransfer {});
+    let err: ContractError = transfer(&mut app, ADDR2, 101)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ImpossibleUnstake {});
+
+    // The staked balance and its share of rewards move without
+    // unbonding.
+    transfer(&mut app, ADDR2, 40).unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR1),
+        Uint128::new(60)
+    );
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR2),
+        Uint128::new(40)
+    );
+    assert_eq!(query_total_staked(&app, &staking_addr), Uint128::new(100));
+    assert_eq!(
+        query_staked_value(&app, &staking_addr, ADDR2),
+        Uint128::new(80)
+    );
+    assert!(query_claims(&app, &staking_addr, ADDR1).is_empty());
+}
+
+fn update_early_unstake(
+    app: &mut App,
+    staking_addr: &Addr,
+    duration: Option<Duration>,
+    early_unstake: Option<EarlyUnstakeUpdate>,
+) -> AnyResult<AppResponse> {
+    let msg = ExecuteMsg::UpdateConfig {
+        duration,
+        stake_transfers_enabled: None,
+        early_unstake,
+    };
+    app.execute_contract(Addr::unchecked(OWNER), staking_addr.clone(), &msg, &[])
+}
+
+fn query_total_supply(app: &App, cw20_addr: &Addr) -> Uint128 {
+    let info: cw20::TokenInfoResponse = app
+        .wrap()
+        .query_wasm_smart(cw20_addr, &cw20::Cw20QueryMsg::TokenInfo {})
+        .unwrap();
+    info.total_supply
+}
+
+#[test]
+fn test_instant_unstake() {
+    let mut app = mock_app();
+    let initial_balances = vec![Cw20Coin {
+        address: ADDR1.to_string(),
+        amount: Uint128::new(1000),
+    }];
+    let duration = Some(Duration::Height(10));
+    let (staking_addr, cw20_addr) = setup_test_case(&mut app, initial_balances, duration);
+
+    stake_tokens(
+        &mut app,
+        &staking_addr,
+        &cw20_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(100),
+    )
+    .unwrap();
+    // Rewards double the value of each staked token.
+    let msg = cw20::Cw20ExecuteMsg::Send {
+        contract: staking_addr.to_string(),
+        amount: Uint128::new(100),
+        msg: to_json_binary(&ReceiveMsg::Fund {}).unwrap(),
+    };
+    app.execute_contract(Addr::unchecked(ADDR1), cw20_addr.clone(), &msg, &[])
+        .unwrap();
+
+    let instant_unstake = |app: &mut App, amount: u128| {
+        app.execute_contract(
+            Addr::unchecked(ADDR1),
+            staking_addr.clone(),
+            &ExecuteMsg::InstantUnstake {
+                amount: Uint128::new(amount),
+            },
+            &[],
+        )
+    };
+
+    // Early unstaking is disabled by default.
+    let err: ContractError = instant_unstake(&mut app, 10)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::EarlyUnstakeDisabled {});
+
+    let err: ContractError = update_early_unstake(
+        &mut app,
+        &staking_addr,
+        duration,
+        Some(EarlyUnstakeUpdate::Set(EarlyUnstakeConfig {
+            penalty: Decimal::percent(101),
+            destination: PenaltyDestination::Dao,
+        })),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(
+        err,
+        ContractError::EarlyUnstakeError(EarlyUnstakeError::InvalidPenalty {})
+    );
+
+    // Penalties are of the tokens the stake is worth, and are sent
+    // to the owner.
+    update_early_unstake(
+        &mut app,
+        &staking_addr,
+        duration,
+        Some(EarlyUnstakeUpdate::Set(EarlyUnstakeConfig {
+            penalty: Decimal::percent(10),
+            destination: PenaltyDestination::Dao,
+        })),
+    )
+    .unwrap();
+    instant_unstake(&mut app, 50).unwrap();
+    assert_eq!(get_balance(&app, &cw20_addr, ADDR1), Uint128::new(890));
+    assert_eq!(get_balance(&app, &cw20_addr, OWNER), Uint128::new(10));
+    assert!(query_claims(&app, &staking_addr, ADDR1).is_empty());
+    app.update_block(next_block);
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR1),
+        Uint128::new(50)
+    );
+    assert_eq!(query_total_value(&app, &staking_addr), Uint128::new(100));
+
+    // Penalties burned.
+    update_early_unstake(
+        &mut app,
+        &staking_addr,
+        duration,
+        Some(EarlyUnstakeUpdate::Set(EarlyUnstakeConfig {
+            penalty: Decimal::percent(10),
+            destination: PenaltyDestination::Burn,
+        })),
+    )
+    .unwrap();
+    instant_unstake(&mut app, 15).unwrap();
+    assert_eq!(get_balance(&app, &cw20_addr, ADDR1), Uint128::new(917));
+    assert_eq!(get_balance(&app, &cw20_addr, OWNER), Uint128::new(10));
+    assert_eq!(query_total_supply(&app, &cw20_addr), Uint128::new(997));
+
+    // Config updates that omit early unstaking leave it unchanged.
+    update_early_unstake(&mut app, &staking_addr, duration, None).unwrap();
+    instant_unstake(&mut app, 10).unwrap();
+    assert_eq!(get_balance(&app, &cw20_addr, ADDR1), Uint128::new(935));
+
+    // Disabling early unstaking with a later config update stops it.
+    update_early_unstake(
+        &mut app,
+        &staking_addr,
+        duration,
+        Some(EarlyUnstakeUpdate::Disable {}),
+    )
+    .unwrap();
+    let err: ContractError = instant_unstake(&mut app, 10)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::EarlyUnstakeDisabled {});
+}
+
+#[test]
+fn test_accelerate_claim() {
+    let mut app = mock_app();
+    let initial_balances = vec![Cw20Coin {
+        address: ADDR1.to_string(),
+        amount: Uint128::new(1000),
+    }];
+    let duration = Some(Duration::Height(100));
+    let (staking_addr, cw20_addr) = setup_test_case(&mut app, initial_balances, duration);
+
+    let accelerate_claim = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked(ADDR1),
+            staking_addr.clone(),
+            &ExecuteMsg::AccelerateClaim {},
+            &[],
+        )
+    };
+
+    stake_tokens(
+        &mut app,
+        &staking_addr,
+        &cw20_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(1000),
+    )
+    .unwrap();
+    unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(1000),
+    )
+    .unwrap();
+
+    let err: ContractError = accelerate_claim(&mut app).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::EarlyUnstakeDisabled {});
+
+    update_early_unstake(
+        &mut app,
+        &staking_addr,
+        duration,
+        Some(EarlyUnstakeUpdate::Set(EarlyUnstakeConfig {
+            penalty: Decimal::percent(10),
+            destination: PenaltyDestination::Dao,
+        })),
+    )
+    .unwrap();
+
+    // With three quarters of the unstaking duration elapsed, a
+    // quarter of the penalty is paid.
+    app.update_block(|block| block.height += 75);
+    accelerate_claim(&mut app).unwrap();
+    assert_eq!(get_balance(&app, &cw20_addr, ADDR1), Uint128::new(975));
+    assert_eq!(get_balance(&app, &cw20_addr, OWNER), Uint128::new(25));
+    assert!(query_claims(&app, &staking_addr, ADDR1).is_empty());
+
+    let err: ContractError = accelerate_claim(&mut app).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    // Claims are priced from their own unstaking duration, even if it
+    // has since changed.
+    stake_tokens(
+        &mut app,
+        &staking_addr,
+        &cw20_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(200),
+    )
+    .unwrap();
+    unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(200),
+    )
+    .unwrap();
+    let duration = Some(Duration::Height(10));
+    update_early_unstake(&mut app, &staking_addr, duration, None).unwrap();
+    app.update_block(|block| block.height += 50);
+    accelerate_claim(&mut app).unwrap();
+    assert_eq!(get_balance(&app, &cw20_addr, ADDR1), Uint128::new(965));
+    assert_eq!(get_balance(&app, &cw20_addr, OWNER), Uint128::new(35));
+
+    // Without an unstaking duration, accelerating claims is free.
+    stake_tokens(
+        &mut app,
+        &staking_addr,
+        &cw20_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(100),
+    )
+    .unwrap();
+    unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(100),
+    )
+    .unwrap();
+    update_early_unstake(&mut app, &staking_addr, None, None).unwrap();
+    accelerate_claim(&mut app).unwrap();
+    assert_eq!(get_balance(&app, &cw20_addr, ADDR1), Uint128::new(965));
+    assert_eq!(get_balance(&app, &cw20_addr, OWNER), Uint128::new(35));
+}
+
 #[test]
 fn test_migrate_from_v1() {
     let mut app = App::default();
@@ -1164,6 +1566,8 @@ fn test_migrate_from_v1() {
         Config {
             token_address: cw20_addr,
             unstaking_duration: None,
+            stake_transfers_enabled: false,
+            early_unstake: None,
         }
     );
 }
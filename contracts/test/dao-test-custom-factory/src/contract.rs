@@ -24,9 +24,11 @@ use dao_voting::threshold::{
     ActiveThresholdResponse,
 };
 
+use omniflix_std::types::omniflix::onft::v1beta1::{MsgCreateDenom, MsgMintOnft, MsgTransferDenom};
+
 use crate::{
     error::ContractError,
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, OnftFactoryNft, QueryMsg},
 };
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
@@ -89,6 +91,9 @@ pub fn execute(
         ExecuteMsg::NftFactoryWrongCallback {} => {
             execute_nft_factory_wrong_callback(deps, env, info)
         }
+        ExecuteMsg::OnftFactory { id, initial_nfts } => {
+            execute_onft_factory(deps, env, info, id, initial_nfts)
+        }
         ExecuteMsg::TokenFactoryFactory(token) => {
             execute_token_factory_factory(deps, env, info, token)
         }
@@ -199,6 +204,74 @@ pub fn execute_nft_factory_wrong_callback(
     )
 }
 
+/// An example factory that creates a new x/onft collection, mints the
+/// initial ONFTs, and transfers ownership of the collection to the DAO.
+pub fn execute_onft_factory(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: String,
+    initial_nfts: Vec<OnftFactoryNft>,
+) -> Result<Response, ContractError> {
+    if initial_nfts.is_empty() {
+        return Err(ContractError::NoInitialNfts {});
+    }
+
+    // Query for DAO
+    let dao: Addr = deps
+        .querier
+        .query_wasm_smart(info.sender, &VotingModuleQueryMsg::Dao {})?;
+
+    let factory = env.contract.address.to_string();
+    let mut msgs: Vec<CosmosMsg> = vec![MsgCreateDenom {
+        id: id.clone(),
+        symbol: id.clone(),
+        name: id.clone(),
+        description: "".to_string(),
+        preview_uri: "".to_string(),
+        schema: "".to_string(),
+        sender: factory.clone(),
+        creation_fee: None,
+        uri: "".to_string(),
+        uri_hash: "".to_string(),
+        data: "".to_string(),
+        royalty_receivers: vec![],
+    }
+    .into()];
+    msgs.extend(initial_nfts.into_iter().map(|nft| {
+        MsgMintOnft {
+            id: nft.token_id,
+            denom_id: id.clone(),
+            metadata: None,
+            data: "".to_string(),
+            transferable: true,
+            extensible: false,
+            nsfw: false,
+            royalty_share: "0".to_string(),
+            sender: factory.clone(),
+            recipient: nft.owner,
+        }
+        .into()
+    }));
+    msgs.push(
+        MsgTransferDenom {
+            id: id.clone(),
+            sender: factory,
+            recipient: dao.to_string(),
+        }
+        .into(),
+    );
+
+    // Responses for `dao-voting-onft-staked` MUST include a
+    // NftFactoryCallback with the collection ID.
+    Ok(Response::new()
+        .add_messages(msgs)
+        .set_data(to_json_binary(&NftFactoryCallback {
+            nft_contract: id,
+            module_instantiate_callback: None,
+        })?))
+}
+
 /// An example factory that instantiates a cw_tokenfactory_issuer contract
 /// A more realistic example would be something like a DeFi Pool or Augmented
 /// bonding curve.
@@ -24,6 +24,12 @@ pub enum ExecuteMsg {
     NftFactoryNoCallback {},
     /// Used for testing wrong callback
     NftFactoryWrongCallback {},
+    /// Example x/onft factory implementation that creates a new collection,
+    /// mints the initial ONFTs, and transfers the collection to the DAO.
+    OnftFactory {
+        id: String,
+        initial_nfts: Vec<OnftFactoryNft>,
+    },
     /// Example Factory Implementation
     TokenFactoryFactory(NewTokenInfo),
     /// Example Factory Implementation that accepts funds
@@ -36,6 +42,12 @@ pub enum ExecuteMsg {
     ValidateNftDao {},
 }
 
+#[cw_serde]
+pub struct OnftFactoryNft {
+    pub token_id: String,
+    pub owner: String,
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -0,0 +1,368 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, Response,
+    StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+use cw_utils::parse_reply_instantiate_data;
+use dao_interface::voting::{
+    InfoResponse, Query as VotingQuery, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+
+use crate::error::ContractError;
+use crate::msg::{ChildModule, ChildMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::state::{
+    Child, PendingChild, CHILDREN, DAO, MAX_CHILDREN, NEXT_REPLY_ID, PENDING_CHILDREN,
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-voting-composite";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    DAO.save(deps.storage, &info.sender)?;
+    NEXT_REPLY_ID.save(deps.storage, &0)?;
+
+    if msg.children.is_empty() {
+        return Err(ContractError::NoChildren {});
+    }
+    if msg.children.len() > MAX_CHILDREN {
+        return Err(ContractError::TooManyChildren { max: MAX_CHILDREN });
+    }
+
+    let mut children = vec![];
+    let mut submessages = vec![];
+    for child in msg.children {
+        if let Some(submessage) = add_child(deps.branch(), &info.sender, &mut children, child)? {
+            submessages.push(submessage);
+        }
+    }
+    CHILDREN.save(deps.storage, &children, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_submessages(submessages))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match msg {
+        ExecuteMsg::AddChild { child } => execute_add_child(deps, env, dao, child),
+        ExecuteMsg::RemoveChild { address } => execute_remove_child(deps, env, address),
+        ExecuteMsg::UpdateChild {
+            address,
+            weight,
+            normalize_to,
+        } => execute_update_child(deps, env, address, weight, normalize_to),
+        ExecuteMsg::ExecuteChild { address, msg } => {
+            execute_execute_child(deps, info, address, msg)
+        }
+    }
+}
+
+pub fn execute_add_child(
+    mut deps: DepsMut,
+    env: Env,
+    dao: Addr,
+    child: ChildMsg,
+) -> Result<Response, ContractError> {
+    let mut children = CHILDREN.load(deps.storage)?;
+    if children.len() >= MAX_CHILDREN {
+        return Err(ContractError::TooManyChildren { max: MAX_CHILDREN });
+    }
+    let submessage = add_child(deps.branch(), &dao, &mut children, child)?;
+    CHILDREN.save(deps.storage, &children, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_child")
+        .add_submessages(submessage))
+}
+
+pub fn execute_remove_child(
+    deps: DepsMut,
+    env: Env,
+    address: String,
+) -> Result<Response, ContractError> {
+    let mut children = CHILDREN.load(deps.storage)?;
+    let index = children
+        .iter()
+        .position(|child| child.address == address)
+        .ok_or_else(|| ContractError::ChildNotFound {
+            address: address.clone(),
+        })?;
+    children.remove(index);
+    if children.is_empty() {
+        return Err(ContractError::NoChildren {});
+    }
+    CHILDREN.save(deps.storage, &children, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_child")
+        .add_attribute("child", address))
+}
+
+pub fn execute_update_child(
+    deps: DepsMut,
+    env: Env,
+    address: String,
+    weight: Decimal,
+    normalize_to: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    validate_weight(weight, normalize_to)?;
+
+    let mut children = CHILDREN.load(deps.storage)?;
+    let child = children
+        .iter_mut()
+        .find(|child| child.address == address)
+        .ok_or_else(|| ContractError::ChildNotFound {
+            address: address.clone(),
+        })?;
+    child.weight = weight;
+    child.normalize_to = normalize_to;
+    CHILDREN.save(deps.storage, &children, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_child")
+        .add_attribute("child", address)
+        .add_attribute("weight", weight.to_string()))
+}
+
+pub fn execute_execute_child(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let children = CHILDREN.load(deps.storage)?;
+    let child = children
+        .into_iter()
+        .find(|child| child.address == address)
+        .ok_or(ContractError::ChildNotFound { address })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_child")
+        .add_attribute("child", child.address.to_string())
+        .add_message(WasmMsg::Execute {
+            contract_addr: child.address.into_string(),
+            msg,
+            funds: info.funds,
+        }))
+}
+
+/// Adds CHILD to CHILDREN if it is an existing module. If it is a
+/// new module, returns the submessage that instantiates it; the child
+/// is added when the instantiation's reply is received.
+fn add_child(
+    deps: DepsMut,
+    dao: &Addr,
+    children: &mut Vec<Child>,
+    child: ChildMsg,
+) -> Result<Option<SubMsg>, ContractError> {
+    let ChildMsg {
+        module,
+        weight,
+        normalize_to,
+    } = child;
+    validate_weight(weight, normalize_to)?;
+
+    match module {
+        ChildModule::Existing { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            if children.iter().any(|child| child.address == address) {
+                return Err(ContractError::DuplicateChild {
+                    address: address.into_string(),
+                });
+            }
+            // Check that the child is a voting module.
+            let _: TotalPowerAtHeightResponse = deps
+                .querier
+                .query_wasm_smart(&address, &VotingQuery::TotalPowerAtHeight { height: None })?;
+            children.push(Child {
+                address,
+                weight,
+                normalize_to,
+            });
+            Ok(None)
+        }
+        ChildModule::New { info } => {
+            let id = NEXT_REPLY_ID.load(deps.storage)?;
+            NEXT_REPLY_ID.save(deps.storage, &(id + 1))?;
+            PENDING_CHILDREN.save(
+                deps.storage,
+                id,
+                &PendingChild {
+                    weight,
+                    normalize_to,
+                },
+            )?;
+            Ok(Some(SubMsg::reply_on_success(
+                info.into_wasm_msg(dao.clone()),
+                id,
+            )))
+        }
+    }
+}
+
+fn validate_weight(weight: Decimal, normalize_to: Option<Uint128>) -> Result<(), ContractError> {
+    if weight.is_zero() {
+        return Err(ContractError::ZeroWeight {});
+    }
+    if normalize_to.map_or(false, |n| n.is_zero()) {
+        return Err(ContractError::ZeroNormalization {});
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::VotingPowerAtHeight { address, height } => {
+            query_voting_power_at_height(deps, env, address, height)
+        }
+        QueryMsg::TotalPowerAtHeight { height } => query_total_power_at_height(deps, env, height),
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::Dao {} => to_json_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Children { height } => query_children(deps, height),
+    }
+}
+
+pub fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let height = height.unwrap_or(env.block.height);
+    let children = CHILDREN
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+
+    let mut power = Uint128::zero();
+    for child in children {
+        let res: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+            &child.address,
+            &VotingQuery::VotingPowerAtHeight {
+                address: address.to_string(),
+                height: Some(height),
+            },
+        )?;
+        let child_power = match child.normalize_to {
+            Some(normalize_to) => {
+                let total = query_child_total_power(deps, &child.address, height)?;
+                if total.is_zero() {
+                    Uint128::zero()
+                } else {
+                    res.power.multiply_ratio(normalize_to, total)
+                }
+            }
+            None => res.power,
+        };
+        power = power.checked_add(child_power.mul_floor(child.weight))?;
+    }
+
+    to_json_binary(&VotingPowerAtHeightResponse { power, height })
+}
+
+pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
+    let height = height.unwrap_or(env.block.height);
+    let children = CHILDREN
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+
+    let mut power = Uint128::zero();
+    for child in children {
+        let total = query_child_total_power(deps, &child.address, height)?;
+        let child_power = match child.normalize_to {
+            Some(normalize_to) if !total.is_zero() => normalize_to,
+            _ => total,
+        };
+        power = power.checked_add(child_power.mul_floor(child.weight))?;
+    }
+
+    to_json_binary(&TotalPowerAtHeightResponse { power, height })
+}
+
+fn query_child_total_power(deps: Deps, child: &Addr, height: u64) -> StdResult<Uint128> {
+    let res: TotalPowerAtHeightResponse = deps.querier.query_wasm_smart(
+        child,
+        &VotingQuery::TotalPowerAtHeight {
+            height: Some(height),
+        },
+    )?;
+    Ok(res.power)
+}
+
+pub fn query_children(deps: Deps, height: Option<u64>) -> StdResult<Binary> {
+    let children = match height {
+        Some(height) => CHILDREN
+            .may_load_at_height(deps.storage, height)?
+            .unwrap_or_default(),
+        None => CHILDREN.load(deps.storage)?,
+    };
+    to_json_binary(&children)
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_json_binary(&InfoResponse { info })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let storage_version: ContractVersion = get_contract_version(deps.storage)?;
+
+    // Only migrate if newer
+    if storage_version.version.as_str() < CONTRACT_VERSION {
+        // Set contract to version to latest
+        set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "migrate"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let PendingChild {
+        weight,
+        normalize_to,
+    } = PENDING_CHILDREN
+        .may_load(deps.storage, msg.id)?
+        .ok_or(ContractError::UnknownReplyId { id: msg.id })?;
+    PENDING_CHILDREN.remove(deps.storage, msg.id);
+
+    let res =
+        parse_reply_instantiate_data(msg).map_err(|_| ContractError::ChildInstantiateError {})?;
+    let address = deps.api.addr_validate(&res.contract_address)?;
+
+    let mut children = CHILDREN.load(deps.storage)?;
+    children.push(Child {
+        address: address.clone(),
+        weight,
+        normalize_to,
+    });
+    if children.len() > MAX_CHILDREN {
+        return Err(ContractError::TooManyChildren { max: MAX_CHILDREN });
+    }
+    CHILDREN.save(deps.storage, &children, env.block.height)?;
+
+    Ok(Response::new().add_attribute("child", address))
+}
@@ -0,0 +1,35 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Child voting module ({address}) has already been added")]
+    DuplicateChild { address: String },
+
+    #[error("Child voting module ({address}) not found")]
+    ChildNotFound { address: String },
+
+    #[error("Error occured whilst instantiating child voting module")]
+    ChildInstantiateError {},
+
+    #[error("Composite voting module must have at least one child")]
+    NoChildren {},
+
+    #[error("Composite voting module may have at most {max} children")]
+    TooManyChildren { max: usize },
+
+    #[error("Child voting module weights must be non-zero")]
+    ZeroWeight {},
+
+    #[error("Child voting module power may not be normalized to zero")]
+    ZeroNormalization {},
+
+    #[error("Got a submessage reply with unknown id: {id}")]
+    UnknownReplyId { id: u64 },
+}
@@ -0,0 +1,63 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Binary, Decimal, Uint128};
+use dao_dao_macros::voting_module_query;
+use dao_interface::state::ModuleInstantiateInfo;
+
+#[cw_serde]
+pub enum ChildModule {
+    /// Uses an existing voting module.
+    Existing { address: String },
+    /// Instantiates a new voting module. The new module is
+    /// instantiated by this contract, so modules that treat their
+    /// instantiator as their DAO will treat this contract as their
+    /// DAO. The DAO may manage such modules with `ExecuteChild`.
+    New { info: ModuleInstantiateInfo },
+}
+
+#[cw_serde]
+pub struct ChildMsg {
+    pub module: ChildModule,
+    /// The child's (possibly normalized) voting power is multiplied
+    /// by this value.
+    pub weight: Decimal,
+    /// If set, the child's voting power is scaled so that its total
+    /// power equals this value before the weight is applied.
+    pub normalize_to: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub children: Vec<ChildMsg>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Adds a child voting module. Only callable by the DAO.
+    AddChild { child: ChildMsg },
+    /// Removes a child voting module. Only callable by the DAO.
+    RemoveChild { address: String },
+    /// Updates the weight and normalization of a child voting
+    /// module. Only callable by the DAO.
+    UpdateChild {
+        address: String,
+        weight: Decimal,
+        normalize_to: Option<Uint128>,
+    },
+    /// Executes MSG on a child voting module as this contract,
+    /// forwarding any funds sent. Lets the DAO manage children that
+    /// treat this contract as their DAO. Only callable by the DAO.
+    ExecuteChild { address: String, msg: Binary },
+}
+
+#[voting_module_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Lists the child voting modules at HEIGHT, or at the current
+    /// height if none is specified.
+    #[returns(Vec<crate::state::Child>)]
+    Children { height: Option<u64> },
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
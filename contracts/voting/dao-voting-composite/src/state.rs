@@ -0,0 +1,48 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotItem, Strategy};
+
+/// The maximum number of child voting modules. Every voting power
+/// query queries each child, so this bounds the gas those queries
+/// use.
+pub const MAX_CHILDREN: usize = 10;
+
+/// A voting module whose voting power contributes to this module's.
+#[cw_serde]
+pub struct Child {
+    pub address: Addr,
+    /// The child's (possibly normalized) voting power is multiplied
+    /// by this value.
+    pub weight: Decimal,
+    /// If set, the child's voting power is scaled so that its total
+    /// power equals this value before the weight is applied. This
+    /// lets modules with very different supplies, for example a
+    /// token with 10^12 units and a cw4 group with 10 members, be
+    /// compared.
+    pub normalize_to: Option<Uint128>,
+}
+
+/// The weight and normalization of a child that is being
+/// instantiated, keyed by the reply ID of its instantiation.
+#[cw_serde]
+pub struct PendingChild {
+    pub weight: Decimal,
+    pub normalize_to: Option<Uint128>,
+}
+
+/// The address of the DAO this voting contract is connected to.
+pub const DAO: Item<Addr> = Item::new("dao");
+
+/// The child voting modules over time, so that historical voting
+/// power queries use the children that were present at that height.
+pub const CHILDREN: SnapshotItem<Vec<Child>> = SnapshotItem::new(
+    "children",
+    "children__checkpoints",
+    "children__changelog",
+    Strategy::EveryBlock,
+);
+
+pub const PENDING_CHILDREN: Map<u64, PendingChild> = Map::new("pending_children");
+
+/// The next reply ID to use when instantiating a child.
+pub const NEXT_REPLY_ID: Item<u64> = Item::new("next_reply_id");
@@ -0,0 +1,555 @@
+use cosmwasm_std::{to_json_binary, Addr, Decimal, Uint128};
+use cw_multi_test::{next_block, App, Executor};
+use cw_utils::Expiration;
+use dao_interface::{
+    state::{Admin, ModuleInstantiateInfo},
+    voting::{InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse},
+};
+use dao_testing::contracts::{
+    cw4_group_contract, dao_voting_composite_contract, dao_voting_cw4_contract,
+};
+use dao_voting_cw4::msg::{GroupContract, MemberExpiration};
+
+use crate::{
+    msg::{ChildModule, ChildMsg, ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::Child,
+    ContractError,
+};
+
+const DAO_ADDR: &str = "dao";
+const ADDR1: &str = "addr1";
+const ADDR2: &str = "addr2";
+const ADDR3: &str = "addr3";
+
+struct TestCase {
+    app: App,
+    composite_id: u64,
+    /// A cw4 voting module where ADDR1 and ADDR2 have a weight of 1.
+    council: Addr,
+    /// A cw4 voting module where ADDR1 has a weight of 10 and ADDR3
+    /// has a weight of 30.
+    members: Addr,
+}
+
+fn cw4_voting_instantiate(
+    cw4_id: u64,
+    members: Vec<(&str, u64)>,
+) -> dao_voting_cw4::msg::InstantiateMsg {
+    dao_voting_cw4::msg::InstantiateMsg {
+        group_contract: GroupContract::New {
+            cw4_group_code_id: cw4_id,
+            initial_members: members
+                .into_iter()
+                .map(|(addr, weight)| cw4::Member {
+                    addr: addr.to_string(),
+                    weight,
+                })
+                .collect(),
+        },
+    }
+}
+
+fn setup_test_case() -> TestCase {
+    let mut app = App::default();
+    let cw4_id = app.store_code(cw4_group_contract());
+    let cw4_voting_id = app.store_code(dao_voting_cw4_contract());
+    let composite_id = app.store_code(dao_voting_composite_contract());
+
+    let council = app
+        .instantiate_contract(
+            cw4_voting_id,
+            Addr::unchecked(DAO_ADDR),
+            &cw4_voting_instantiate(cw4_id, vec![(ADDR1, 1), (ADDR2, 1)]),
+            &[],
+            "council",
+            None,
+        )
+        .unwrap();
+    let members = app
+        .instantiate_contract(
+            cw4_voting_id,
+            Addr::unchecked(DAO_ADDR),
+            &cw4_voting_instantiate(cw4_id, vec![(ADDR1, 10), (ADDR3, 30)]),
+            &[],
+            "members",
+            None,
+        )
+        .unwrap();
+
+    TestCase {
+        app,
+        composite_id,
+        council,
+        members,
+    }
+}
+
+fn existing(address: &Addr, weight: Decimal, normalize_to: Option<u128>) -> ChildMsg {
+    ChildMsg {
+        module: ChildModule::Existing {
+            address: address.to_string(),
+        },
+        weight,
+        normalize_to: normalize_to.map(Uint128::new),
+    }
+}
+
+fn instantiate_composite(
+    app: &mut App,
+    composite_id: u64,
+    children: Vec<ChildMsg>,
+) -> anyhow::Result<Addr> {
+    app.instantiate_contract(
+        composite_id,
+        Addr::unchecked(DAO_ADDR),
+        &InstantiateMsg { children },
+        &[],
+        "composite",
+        None,
+    )
+}
+
+fn query_power(app: &App, composite: &Addr, address: &str) -> Uint128 {
+    let res: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            composite,
+            &QueryMsg::VotingPowerAtHeight {
+                address: address.to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    res.power
+}
+
+fn query_total_power(app: &App, composite: &Addr, height: Option<u64>) -> Uint128 {
+    let res: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(composite, &QueryMsg::TotalPowerAtHeight { height })
+        .unwrap();
+    res.power
+}
+
+fn query_children(app: &App, composite: &Addr, height: Option<u64>) -> Vec<Child> {
+    app.wrap()
+        .query_wasm_smart(composite, &QueryMsg::Children { height })
+        .unwrap()
+}
+
+#[test]
+fn test_weighted_power() {
+    let TestCase {
+        mut app,
+        composite_id,
+        council,
+        members,
+    } = setup_test_case();
+    let composite = instantiate_composite(
+        &mut app,
+        composite_id,
+        vec![
+            existing(&council, Decimal::percent(200), None),
+            existing(&members, Decimal::percent(50), None),
+        ],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(query_power(&app, &composite, ADDR1), Uint128::new(7));
+    assert_eq!(query_power(&app, &composite, ADDR2), Uint128::new(2));
+    assert_eq!(query_power(&app, &composite, ADDR3), Uint128::new(15));
+    assert_eq!(query_power(&app, &composite, "nobody"), Uint128::zero());
+    assert_eq!(query_total_power(&app, &composite, None), Uint128::new(24));
+
+    let info: InfoResponse = app
+        .wrap()
+        .query_wasm_smart(&composite, &QueryMsg::Info {})
+        .unwrap();
+    assert_eq!(info.info.contract, "crates.io:dao-voting-composite");
+    let dao: Addr = app
+        .wrap()
+        .query_wasm_smart(&composite, &QueryMsg::Dao {})
+        .unwrap();
+    assert_eq!(dao, Addr::unchecked(DAO_ADDR));
+}
+
+#[test]
+fn test_normalized_power() {
+    let TestCase {
+        mut app,
+        composite_id,
+        council,
+        members,
+    } = setup_test_case();
+    // Both modules are worth the same amount of power, even though
+    // their totals differ.
+    let composite = instantiate_composite(
+        &mut app,
+        composite_id,
+        vec![
+            existing(&council, Decimal::one(), Some(100)),
+            existing(&members, Decimal::one(), Some(100)),
+        ],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(query_power(&app, &composite, ADDR1), Uint128::new(75));
+    assert_eq!(query_power(&app, &composite, ADDR2), Uint128::new(50));
+    assert_eq!(query_power(&app, &composite, ADDR3), Uint128::new(75));
+    assert_eq!(query_total_power(&app, &composite, None), Uint128::new(200));
+
+    // Normalization may be mixed with weights.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        composite.clone(),
+        &ExecuteMsg::UpdateChild {
+            address: members.to_string(),
+            weight: Decimal::percent(300),
+            normalize_to: Some(Uint128::new(100)),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(query_power(&app, &composite, ADDR1), Uint128::new(125));
+    assert_eq!(query_power(&app, &composite, ADDR3), Uint128::new(225));
+    assert_eq!(query_total_power(&app, &composite, None), Uint128::new(400));
+}
+
+#[test]
+fn test_add_and_remove_children() {
+    let TestCase {
+        mut app,
+        composite_id,
+        council,
+        members,
+    } = setup_test_case();
+    let composite = instantiate_composite(
+        &mut app,
+        composite_id,
+        vec![existing(&council, Decimal::one(), None)],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(query_total_power(&app, &composite, None), Uint128::new(2));
+
+    // Only the DAO may manage children.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            composite.clone(),
+            &ExecuteMsg::AddChild {
+                child: existing(&members, Decimal::one(), None),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        composite.clone(),
+        &ExecuteMsg::AddChild {
+            child: existing(&members, Decimal::one(), None),
+        },
+        &[],
+    )
+    .unwrap();
+    let added = app.block_info().height;
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            composite.clone(),
+            &ExecuteMsg::AddChild {
+                child: existing(&members, Decimal::one(), None),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::DuplicateChild {
+            address: members.to_string()
+        }
+    );
+    app.update_block(next_block);
+
+    assert_eq!(query_total_power(&app, &composite, None), Uint128::new(42));
+    assert_eq!(
+        query_children(&app, &composite, None),
+        vec![
+            Child {
+                address: council.clone(),
+                weight: Decimal::one(),
+                normalize_to: None,
+            },
+            Child {
+                address: members.clone(),
+                weight: Decimal::one(),
+                normalize_to: None,
+            },
+        ]
+    );
+    // Historical queries use the children present at that height.
+    assert_eq!(
+        query_total_power(&app, &composite, Some(added)),
+        Uint128::new(2)
+    );
+    assert_eq!(query_children(&app, &composite, Some(added)).len(), 1);
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        composite.clone(),
+        &ExecuteMsg::RemoveChild {
+            address: council.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(query_power(&app, &composite, ADDR1), Uint128::new(10));
+    assert_eq!(query_total_power(&app, &composite, None), Uint128::new(40));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            composite.clone(),
+            &ExecuteMsg::RemoveChild {
+                address: council.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ChildNotFound {
+            address: council.to_string()
+        }
+    );
+
+    // The last child may not be removed.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            composite,
+            &ExecuteMsg::RemoveChild {
+                address: members.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoChildren {});
+}
+
+#[test]
+fn test_new_child() {
+    let TestCase {
+        mut app,
+        composite_id,
+        council,
+        ..
+    } = setup_test_case();
+    let cw4_id = app.store_code(cw4_group_contract());
+    let cw4_voting_id = app.store_code(dao_voting_cw4_contract());
+
+    let composite = instantiate_composite(
+        &mut app,
+        composite_id,
+        vec![
+            existing(&council, Decimal::one(), None),
+            ChildMsg {
+                module: ChildModule::New {
+                    info: ModuleInstantiateInfo {
+                        code_id: cw4_voting_id,
+                        msg: to_json_binary(&cw4_voting_instantiate(cw4_id, vec![(ADDR3, 5)]))
+                            .unwrap(),
+                        admin: Some(Admin::CoreModule {}),
+                        funds: vec![],
+                        label: "new child".to_string(),
+                    },
+                },
+                weight: Decimal::one(),
+                normalize_to: None,
+            },
+        ],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let children = query_children(&app, &composite, None);
+    assert_eq!(children.len(), 2);
+    assert_eq!(children[0].address, council);
+    // The new child's admin is the DAO.
+    let child_info = app
+        .wrap()
+        .query_wasm_contract_info(&children[1].address)
+        .unwrap();
+    assert_eq!(child_info.admin, Some(DAO_ADDR.to_string()));
+
+    assert_eq!(query_power(&app, &composite, ADDR3), Uint128::new(5));
+    assert_eq!(query_total_power(&app, &composite, None), Uint128::new(7));
+
+    // The new child treats the composite module as its DAO, so the
+    // DAO manages it through the composite module.
+    let child_dao: Addr = app
+        .wrap()
+        .query_wasm_smart(&children[1].address, &QueryMsg::Dao {})
+        .unwrap();
+    assert_eq!(child_dao, composite);
+    let expire = to_json_binary(&dao_voting_cw4::msg::ExecuteMsg::UpdateExpirations {
+        add: vec![MemberExpiration {
+            addr: ADDR3.to_string(),
+            expiration: Expiration::AtHeight(app.block_info().height + 1),
+        }],
+        remove: vec![],
+    })
+    .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            composite.clone(),
+            &ExecuteMsg::ExecuteChild {
+                address: children[1].address.to_string(),
+                msg: expire.clone(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            composite.clone(),
+            &ExecuteMsg::ExecuteChild {
+                address: ADDR1.to_string(),
+                msg: expire.clone(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ChildNotFound {
+            address: ADDR1.to_string()
+        }
+    );
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        composite.clone(),
+        &ExecuteMsg::ExecuteChild {
+            address: children[1].address.to_string(),
+            msg: expire,
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(query_power(&app, &composite, ADDR3), Uint128::zero());
+}
+
+#[test]
+fn test_instantiate_validation() {
+    let TestCase {
+        mut app,
+        composite_id,
+        council,
+        ..
+    } = setup_test_case();
+
+    let err: ContractError = instantiate_composite(&mut app, composite_id, vec![])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoChildren {});
+
+    let err: ContractError = instantiate_composite(
+        &mut app,
+        composite_id,
+        vec![existing(&council, Decimal::zero(), None)],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::ZeroWeight {});
+
+    let err: ContractError = instantiate_composite(
+        &mut app,
+        composite_id,
+        vec![existing(&council, Decimal::one(), Some(0))],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::ZeroNormalization {});
+
+    let err: ContractError = instantiate_composite(
+        &mut app,
+        composite_id,
+        vec![
+            existing(&council, Decimal::one(), None),
+            existing(&council, Decimal::one(), None),
+        ],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(
+        err,
+        ContractError::DuplicateChild {
+            address: council.to_string()
+        }
+    );
+
+    let err: ContractError = instantiate_composite(
+        &mut app,
+        composite_id,
+        (0..11)
+            .map(|_| existing(&council, Decimal::one(), None))
+            .collect(),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::TooManyChildren { max: 10 });
+
+    // Children must be voting modules.
+    let cw4_id = app.store_code(cw4_group_contract());
+    let not_voting = app
+        .instantiate_contract(
+            cw4_id,
+            Addr::unchecked(DAO_ADDR),
+            &cw4_group::msg::InstantiateMsg {
+                admin: None,
+                members: vec![],
+            },
+            &[],
+            "group",
+            None,
+        )
+        .unwrap();
+    instantiate_composite(
+        &mut app,
+        composite_id,
+        vec![existing(&not_voting, Decimal::one(), None)],
+    )
+    .unwrap_err();
+}
@@ -1,20 +1,23 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, Response,
-    StdResult, SubMsg, Uint128, Uint256, WasmMsg,
+    to_json_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Reply,
+    Response, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw20::{Cw20Coin, TokenInfoResponse};
 use cw_utils::parse_reply_instantiate_data;
+use dao_hooks::stake::StakeChangedHookMsg;
+use dao_interface::state::ModuleInstantiateCallback;
 use dao_interface::voting::IsActiveResponse;
+use dao_voting::power_cap::{PowerCap, PowerCapResponse};
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 use std::convert::TryInto;
 
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StakingInfo, TokenInfo};
 use crate::state::{
-    ACTIVE_THRESHOLD, DAO, STAKING_CONTRACT, STAKING_CONTRACT_CODE_ID,
+    ACTIVE_THRESHOLD, DAO, POWER_CAPS, STAKING_CONTRACT, STAKING_CONTRACT_CODE_ID,
     STAKING_CONTRACT_UNSTAKING_DURATION, TOKEN,
 };
 
@@ -193,6 +196,11 @@ pub fn execute(
         ExecuteMsg::UpdateActiveThreshold { new_threshold } => {
             execute_update_active_threshold(deps, env, info, new_threshold)
         }
+        ExecuteMsg::UpdateMaxPowerPerAddress {
+            max_power_per_address,
+        } => execute_update_max_power_per_address(deps, env, info, max_power_per_address),
+        ExecuteMsg::RefreshPowerCap { address } => execute_refresh_power_cap(deps, env, address),
+        ExecuteMsg::StakeChangeHook(msg) => execute_stake_changed(deps, env, info, msg),
     }
 }
 
@@ -227,6 +235,100 @@ pub fn execute_update_active_threshold(
     Ok(Response::new().add_attribute("action", "update_active_threshold"))
 }
 
+pub fn execute_update_max_power_per_address(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    max_power_per_address: Option<PowerCap>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Caps are kept up to date by the staking contract's hooks, so
+    // they may only be set once this contract receives them.
+    if max_power_per_address.is_some() {
+        let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+        let hooks: cw20_stake::msg::GetHooksResponse = deps
+            .querier
+            .query_wasm_smart(&staking_contract, &cw20_stake::msg::QueryMsg::GetHooks {})?;
+        if !hooks
+            .hooks
+            .iter()
+            .any(|hook| hook == env.contract.address.as_str())
+        {
+            return Err(ContractError::StakeHookNotRegistered {});
+        }
+    }
+
+    POWER_CAPS.set_cap(deps.storage, max_power_per_address)?;
+
+    Ok(Response::new().add_attribute("action", "update_max_power_per_address"))
+}
+
+pub fn execute_refresh_power_cap(
+    deps: DepsMut,
+    env: Env,
+    address: String,
+) -> Result<Response, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+    apply_power_cap(deps, &env, &address)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "refresh_power_cap")
+        .add_attribute("address", address))
+}
+
+pub fn execute_stake_changed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: StakeChangedHookMsg,
+) -> Result<Response, ContractError> {
+    let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+    if info.sender != staking_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let address = match msg {
+        StakeChangedHookMsg::Stake { addr, .. } | StakeChangedHookMsg::Unstake { addr, .. } => addr,
+    };
+    apply_power_cap(deps, &env, &address)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "stake_changed")
+        .add_attribute("address", address))
+}
+
+/// Recomputes how much of ADDRESS's staked balance is above the
+/// voting power cap.
+fn apply_power_cap(deps: DepsMut, env: &Env, address: &Addr) -> StdResult<()> {
+    let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+    // The staking contract's snapshots only reflect changes made in
+    // this block from the next block on, so query the next block to
+    // see the current balances.
+    let height = Some(env.block.height + 1);
+    let balance: cw20_stake::msg::StakedBalanceAtHeightResponse = deps.querier.query_wasm_smart(
+        &staking_contract,
+        &cw20_stake::msg::QueryMsg::StakedBalanceAtHeight {
+            address: address.to_string(),
+            height,
+        },
+    )?;
+    let total: cw20_stake::msg::TotalStakedAtHeightResponse = deps.querier.query_wasm_smart(
+        &staking_contract,
+        &cw20_stake::msg::QueryMsg::TotalStakedAtHeight { height },
+    )?;
+    POWER_CAPS.apply(
+        deps.storage,
+        env.block.height,
+        address,
+        balance.balance,
+        total.total,
+    )
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -240,6 +342,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Dao {} => query_dao(deps),
         QueryMsg::IsActive {} => query_is_active(deps),
         QueryMsg::ActiveThreshold {} => query_active_threshold(deps),
+        QueryMsg::MaxPowerPerAddress {} => to_json_binary(&PowerCapResponse {
+            max_power_per_address: POWER_CAPS.cap(deps.storage)?,
+        }),
     }
 }
 
@@ -268,8 +373,10 @@ pub fn query_voting_power_at_height(
             height,
         },
     )?;
+    let power =
+        POWER_CAPS.capped_power_at_height(deps.storage, &address, res.balance, res.height)?;
     to_json_binary(&dao_interface::voting::VotingPowerAtHeightResponse {
-        power: res.balance,
+        power,
         height: res.height,
     })
 }
@@ -284,8 +391,9 @@ pub fn query_total_power_at_height(
         staking_contract,
         &cw20_stake::msg::QueryMsg::TotalStakedAtHeight { height },
     )?;
+    let power = POWER_CAPS.capped_total_at_height(deps.storage, res.total, res.height)?;
     to_json_binary(&dao_interface::voting::TotalPowerAtHeightResponse {
-        power: res.total,
+        power,
         height: res.height,
     })
 }
@@ -440,7 +548,22 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
 
                     STAKING_CONTRACT.save(deps.storage, &staking_contract_addr)?;
 
-                    Ok(Response::new().add_attribute("staking_contract", staking_contract_addr))
+                    // The DAO owns the staking contract, so have it
+                    // register this contract as a stake hook in a
+                    // ModuleInstantiateCallback.
+                    let callback = to_json_binary(&ModuleInstantiateCallback {
+                        msgs: vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                            contract_addr: staking_contract_addr.to_string(),
+                            msg: to_json_binary(&cw20_stake::msg::ExecuteMsg::AddHook {
+                                addr: env.contract.address.to_string(),
+                            })?,
+                            funds: vec![],
+                        })],
+                    })?;
+
+                    Ok(Response::new()
+                        .add_attribute("staking_contract", staking_contract_addr)
+                        .set_data(callback))
                 }
                 Err(_) => Err(ContractError::StakingInstantiateError {}),
             }
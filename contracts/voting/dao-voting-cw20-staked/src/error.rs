@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use dao_voting::power_cap::PowerCapError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +7,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error(transparent)]
+    PowerCapError(#[from] PowerCapError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -38,4 +42,9 @@ pub enum ContractError {
 
     #[error("Absolute count threshold cannot be greater than the total token supply")]
     InvalidAbsoluteCount {},
+
+    #[error(
+        "This contract must be registered as a hook on the staking contract to cap voting power"
+    )]
+    StakeHookNotRegistered {},
 }
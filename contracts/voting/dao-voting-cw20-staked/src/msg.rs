@@ -5,6 +5,8 @@ use cw20_base::msg::InstantiateMarketingInfo;
 use cw_utils::Duration;
 
 use dao_dao_macros::{active_query, cw20_token_query, voting_module_query};
+use dao_hooks::stake::StakeChangedHookMsg;
+use dao_voting::power_cap::PowerCap;
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 
 /// Information about the staking contract to be used with this voting
@@ -68,6 +70,24 @@ pub enum ExecuteMsg {
     UpdateActiveThreshold {
         new_threshold: Option<ActiveThreshold>,
     },
+    /// Sets the maximum voting power any one address may have. Only
+    /// the DAO may call this method. Addresses' powers are capped
+    /// the next time their stake changes or they are refreshed with
+    /// `RefreshPowerCap`. A percentage cap is measured against the
+    /// total staked when the address was last capped, so the total
+    /// power may differ from the sum of capped powers until stakers
+    /// are refreshed.
+    UpdateMaxPowerPerAddress {
+        max_power_per_address: Option<PowerCap>,
+    },
+    /// Reapplies the voting power cap to an address. Callable by
+    /// anyone.
+    RefreshPowerCap { address: String },
+    /// Called by the staking contract when an address's stake
+    /// changes. The DAO must register this contract as a hook on the
+    /// staking contract for the voting power cap to track stake
+    /// changes.
+    StakeChangeHook(StakeChangedHookMsg),
 }
 
 #[voting_module_query]
@@ -82,6 +102,8 @@ pub enum QueryMsg {
     StakingContract {},
     #[returns(ActiveThresholdResponse)]
     ActiveThreshold {},
+    #[returns(dao_voting::power_cap::PowerCapResponse)]
+    MaxPowerPerAddress {},
 }
 
 #[cw_serde]
@@ -1,19 +1,29 @@
 use cosmwasm_std::{
+    from_json,
     testing::{mock_dependencies, mock_env},
     to_json_binary, Addr, CosmosMsg, Decimal, Uint128, WasmMsg,
 };
 use cw2::ContractVersion;
 use cw20::{BalanceResponse, Cw20Coin, MinterResponse, TokenInfoResponse};
 use cw_multi_test::{next_block, App, Executor};
-use dao_interface::voting::{InfoResponse, IsActiveResponse, VotingPowerAtHeightResponse};
+use cw_utils::parse_instantiate_response_data;
+use dao_hooks::stake::StakeChangedHookMsg;
+use dao_interface::state::ModuleInstantiateCallback;
+use dao_interface::voting::{
+    InfoResponse, IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
 use dao_testing::contracts::{
     cw20_base_contract, cw20_stake_contract, dao_voting_cw20_staked_contract,
 };
-use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
+use dao_voting::{
+    power_cap::{PowerCap, PowerCapResponse},
+    threshold::{ActiveThreshold, ActiveThresholdResponse},
+};
 
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StakingInfo},
+    ContractError,
 };
 
 const DAO_ADDR: &str = "dao";
@@ -1299,6 +1309,274 @@ fn test_active_threshold_absolute_count_invalid() {
     );
 }
 
+#[test]
+fn test_max_power_per_address() {
+    let mut app = App::default();
+    let cw20_id = app.store_code(cw20_base_contract());
+    let voting_id = app.store_code(dao_voting_cw20_staked_contract());
+    let staking_contract_id = app.store_code(cw20_stake_contract());
+
+    let voting_addr = instantiate_voting(
+        &mut app,
+        voting_id,
+        InstantiateMsg {
+            token_info: crate::msg::TokenInfo::New {
+                code_id: cw20_id,
+                label: "DAO DAO voting".to_string(),
+                name: "DAO DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![
+                    Cw20Coin {
+                        address: CREATOR_ADDR.to_string(),
+                        amount: Uint128::from(200u64),
+                    },
+                    Cw20Coin {
+                        address: "addr1".to_string(),
+                        amount: Uint128::from(100u64),
+                    },
+                ],
+                marketing: None,
+                unstaking_duration: None,
+                staking_code_id: staking_contract_id,
+                initial_dao_balance: None,
+            },
+            active_threshold: None,
+        },
+    );
+    let token_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::TokenContract {})
+        .unwrap();
+    let staking_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::StakingContract {})
+        .unwrap();
+
+    // Caps may only be set once the voting module receives the
+    // staking contract's hooks.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            voting_addr.clone(),
+            &ExecuteMsg::UpdateMaxPowerPerAddress {
+                max_power_per_address: Some(PowerCap::AbsoluteCount {
+                    count: Uint128::new(150),
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::StakeHookNotRegistered {}));
+
+    // The voting module tracks stake changes through the staking
+    // contract's hooks.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        staking_addr.clone(),
+        &cw20_stake::msg::ExecuteMsg::AddHook {
+            addr: voting_addr.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::UpdateMaxPowerPerAddress {
+        max_power_per_address: Some(PowerCap::AbsoluteCount {
+            count: Uint128::new(150),
+        }),
+    };
+    // Expect failure as sender is not the DAO
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        voting_addr.clone(),
+        &msg,
+        &[],
+    )
+    .unwrap_err();
+    app.execute_contract(Addr::unchecked(DAO_ADDR), voting_addr.clone(), &msg, &[])
+        .unwrap();
+
+    let resp: PowerCapResponse = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::MaxPowerPerAddress {})
+        .unwrap();
+    assert_eq!(
+        resp.max_power_per_address,
+        Some(PowerCap::AbsoluteCount {
+            count: Uint128::new(150)
+        })
+    );
+
+    // Only the staking contract may call the stake hook.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::StakeChangeHook(StakeChangedHookMsg::Unstake {
+            addr: Addr::unchecked(CREATOR_ADDR),
+            amount: Uint128::new(200),
+        }),
+        &[],
+    )
+    .unwrap_err();
+
+    stake_tokens(
+        &mut app,
+        staking_addr.clone(),
+        token_addr.clone(),
+        CREATOR_ADDR,
+        200,
+    );
+    stake_tokens(&mut app, staking_addr, token_addr, "addr1", 100);
+    app.update_block(next_block);
+
+    let creator_power: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::VotingPowerAtHeight {
+                address: CREATOR_ADDR.to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(creator_power.power, Uint128::new(150));
+    let addr1_power: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::VotingPowerAtHeight {
+                address: "addr1".to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(addr1_power.power, Uint128::new(100));
+    let total_power: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::TotalPowerAtHeight { height: None },
+        )
+        .unwrap();
+    assert_eq!(total_power.power, Uint128::new(250));
+
+    // Removing the cap takes effect once addresses are refreshed.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateMaxPowerPerAddress {
+            max_power_per_address: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("addr1"),
+        voting_addr.clone(),
+        &ExecuteMsg::RefreshPowerCap {
+            address: CREATOR_ADDR.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let total_power: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(voting_addr, &QueryMsg::TotalPowerAtHeight { height: None })
+        .unwrap();
+    assert_eq!(total_power.power, Uint128::new(300));
+}
+
+#[test]
+fn test_new_staking_contract_registers_hook() {
+    let mut app = App::default();
+    let cw20_id = app.store_code(cw20_base_contract());
+    let voting_id = app.store_code(dao_voting_cw20_staked_contract());
+    let staking_contract_id = app.store_code(cw20_stake_contract());
+
+    let res = app
+        .execute(
+            Addr::unchecked(DAO_ADDR),
+            CosmosMsg::Wasm(WasmMsg::Instantiate {
+                admin: None,
+                code_id: voting_id,
+                msg: to_json_binary(&InstantiateMsg {
+                    token_info: crate::msg::TokenInfo::New {
+                        code_id: cw20_id,
+                        label: "DAO DAO voting".to_string(),
+                        name: "DAO DAO".to_string(),
+                        symbol: "DAO".to_string(),
+                        decimals: 6,
+                        initial_balances: vec![Cw20Coin {
+                            address: CREATOR_ADDR.to_string(),
+                            amount: Uint128::from(200u64),
+                        }],
+                        marketing: None,
+                        unstaking_duration: None,
+                        staking_code_id: staking_contract_id,
+                        initial_dao_balance: None,
+                    },
+                    active_threshold: None,
+                })
+                .unwrap(),
+                funds: vec![],
+                label: "voting module".to_string(),
+            }),
+        )
+        .unwrap();
+    let res = parse_instantiate_response_data(&res.data.unwrap()).unwrap();
+    let voting_addr = Addr::unchecked(res.contract_address);
+    let callback: ModuleInstantiateCallback = from_json(res.data.unwrap()).unwrap();
+
+    // The DAO executes the callback, as dao-dao-core does.
+    for msg in callback.msgs {
+        app.execute(Addr::unchecked(DAO_ADDR), msg).unwrap();
+    }
+    let staking_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(&voting_addr, &QueryMsg::StakingContract {})
+        .unwrap();
+    let hooks: cw20_stake::msg::GetHooksResponse = app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &cw20_stake::msg::QueryMsg::GetHooks {})
+        .unwrap();
+    assert_eq!(hooks.hooks, vec![voting_addr.to_string()]);
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateMaxPowerPerAddress {
+            max_power_per_address: Some(PowerCap::AbsoluteCount {
+                count: Uint128::new(150),
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+    let token_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(&voting_addr, &QueryMsg::TokenContract {})
+        .unwrap();
+    stake_tokens(&mut app, staking_addr, token_addr, CREATOR_ADDR, 200);
+    app.update_block(next_block);
+
+    let power: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &voting_addr,
+            &QueryMsg::VotingPowerAtHeight {
+                address: CREATOR_ADDR.to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(power.power, Uint128::new(150));
+}
+
 #[test]
 fn test_migrate() {
     let mut app = App::default();
@@ -1,22 +1,33 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult, SubMsg,
-    Uint128, WasmMsg,
+    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response,
+    StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
-use cw4::{MemberListResponse, MemberResponse, TotalWeightResponse};
-use cw_utils::parse_reply_instantiate_data;
+use cw4::{MemberChangedHookMsg, MemberListResponse, MemberResponse, TotalWeightResponse};
+use cw_controllers::{AdminResponse, HooksResponse};
+use cw_storage_plus::Bound;
+use cw_utils::{parse_reply_instantiate_data, Expiration};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, GroupContract, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{DAO, GROUP_CONTRACT};
+use crate::msg::{
+    ExecuteMsg, ExpiringMember, ExpiringMembersResponse, GroupContract, InstantiateMsg,
+    MemberExpiration, MigrateMsg, QueryMsg,
+};
+use crate::state::{
+    ExpiredWeight, DAO, EXPIRATIONS, EXPIRED_WEIGHT, EXPIRING_WEIGHTS, GROUP_CONTRACT,
+};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-voting-cw4";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const INSTANTIATE_GROUP_REPLY_ID: u64 = 0;
 
+// Settings for query pagination and pruning
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -63,12 +74,14 @@ pub fn instantiate(
 
             // Instantiate group contract, set DAO as admin.
             // Voting module contracts are instantiated by the main dao-dao-core
-            // contract, so the Admin is set to info.sender.
+            // contract, so the Admin is set to info.sender. This contract is
+            // the group's admin until it has registered itself as a member
+            // changed hook, after which the DAO is made the group's admin.
             let msg = WasmMsg::Instantiate {
                 admin: Some(info.sender.to_string()),
                 code_id: cw4_group_code_id,
                 msg: to_json_binary(&cw4_group::msg::InstantiateMsg {
-                    admin: Some(info.sender.to_string()),
+                    admin: Some(env.contract.address.to_string()),
                     members: initial_members,
                 })?,
                 funds: vec![],
@@ -108,12 +121,239 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    _deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    _msg: ExecuteMsg,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateExpirations { add, remove } => {
+            execute_update_expirations(deps, env, info, add, remove)
+        }
+        ExecuteMsg::Prune { limit } => execute_prune(deps, env, limit),
+        ExecuteMsg::UpdateMembers { add, remove } => {
+            execute_update_members(deps, info, add, remove)
+        }
+        ExecuteMsg::MemberChangedHook(msg) => execute_member_changed_hook(deps, env, info, msg),
+    }
+}
+
+pub fn execute_update_expirations(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    add: Vec<MemberExpiration>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // The weight of expiring members is tracked with the group's
+    // member changed hooks.
+    let group_contract = GROUP_CONTRACT.load(deps.storage)?;
+    let hooks: HooksResponse = deps
+        .querier
+        .query_wasm_smart(&group_contract, &cw4_group::msg::QueryMsg::Hooks {})?;
+    if !hooks
+        .hooks
+        .iter()
+        .any(|hook| hook == env.contract.address.as_str())
+    {
+        return Err(ContractError::NotGroupHook {});
+    }
+
+    let height = env.block.height;
+    for MemberExpiration { addr, expiration } in add {
+        let expiration = match expiration {
+            Expiration::AtHeight(expiration) => expiration,
+            Expiration::AtTime(_) => return Err(ContractError::TimeExpiration {}),
+            Expiration::Never {} => return Err(ContractError::NeverExpires {}),
+        };
+        let addr = deps.api.addr_validate(&addr)?;
+        let weight = query_member_weight(deps.as_ref(), &group_contract, addr.to_string(), None)?;
+        if let Some(old) = EXPIRATIONS.may_load(deps.storage, &addr)? {
+            update_expiring_weight(deps.storage, height, old, weight, 0)?;
+        }
+        update_expiring_weight(deps.storage, height, expiration, 0, weight)?;
+        EXPIRATIONS.save(deps.storage, &addr, &expiration, height)?;
+    }
+    for addr in remove {
+        let addr = deps.api.addr_validate(&addr)?;
+        if let Some(old) = EXPIRATIONS.may_load(deps.storage, &addr)? {
+            let weight =
+                query_member_weight(deps.as_ref(), &group_contract, addr.to_string(), None)?;
+            update_expiring_weight(deps.storage, height, old, weight, 0)?;
+            EXPIRATIONS.remove(deps.storage, &addr, height)?;
+        }
+    }
+
+    Ok(Response::new().add_attribute("action", "update_expirations"))
+}
+
+/// Called by the group contract when its members change. Keeps the
+/// weight of expiring members up to date, and removes the expirations
+/// of members that have been removed.
+pub fn execute_member_changed_hook(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: MemberChangedHookMsg,
 ) -> Result<Response, ContractError> {
-    Err(ContractError::NoExecute {})
+    let group_contract = GROUP_CONTRACT.load(deps.storage)?;
+    if info.sender != group_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let height = env.block.height;
+    for diff in msg.diffs {
+        let addr = deps.api.addr_validate(&diff.key)?;
+        let Some(expiration) = EXPIRATIONS.may_load(deps.storage, &addr)? else {
+            continue;
+        };
+        update_expiring_weight(
+            deps.storage,
+            height,
+            expiration,
+            diff.old.unwrap_or_default(),
+            diff.new.unwrap_or_default(),
+        )?;
+        if diff.new.is_none() {
+            EXPIRATIONS.remove(deps.storage, &addr, height)?;
+        }
+    }
+
+    Ok(Response::new().add_attribute("action", "member_changed_hook"))
+}
+
+/// Replaces OLD with NEW in the tracked weight of members whose
+/// membership expires at EXPIRATION, as of HEIGHT.
+fn update_expiring_weight(
+    storage: &mut dyn Storage,
+    height: u64,
+    expiration: u64,
+    old: u64,
+    new: u64,
+) -> StdResult<()> {
+    if old == new {
+        return Ok(());
+    }
+    let update = |weight: Uint128| -> StdResult<Uint128> {
+        Ok(weight
+            .checked_add(Uint128::from(new))?
+            .checked_sub(Uint128::from(old))?)
+    };
+    if expiration > height {
+        // Only weights expiring after the current height are changed,
+        // so past totals are unaffected.
+        EXPIRING_WEIGHTS.update(storage, expiration, |weight| {
+            update(weight.unwrap_or_default())
+        })?;
+    } else {
+        let mut expired = expired_weight(storage, load_expired_weight(storage)?, height)?;
+        expired.weight = update(expired.weight)?;
+        EXPIRED_WEIGHT.save(storage, &expired, height)?;
+    }
+    Ok(())
+}
+
+fn load_expired_weight(storage: &dyn Storage) -> StdResult<ExpiredWeight> {
+    Ok(EXPIRED_WEIGHT.may_load(storage)?.unwrap_or_default())
+}
+
+/// Adds the weight of the members whose membership expires after
+/// `expired.expired_through` and at or before HEIGHT to EXPIRED.
+fn expired_weight(
+    storage: &dyn Storage,
+    mut expired: ExpiredWeight,
+    height: u64,
+) -> StdResult<ExpiredWeight> {
+    if expired.expired_through >= height {
+        return Ok(expired);
+    }
+    for item in EXPIRING_WEIGHTS.range(
+        storage,
+        Some(Bound::exclusive(expired.expired_through)),
+        Some(Bound::inclusive(height)),
+        Order::Ascending,
+    ) {
+        let (_, weight) = item?;
+        expired.weight = expired.weight.checked_add(weight)?;
+    }
+    expired.expired_through = height;
+    Ok(expired)
+}
+
+pub fn execute_prune(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let group_contract = GROUP_CONTRACT.load(deps.storage)?;
+    let admin: AdminResponse = deps
+        .querier
+        .query_wasm_smart(&group_contract, &cw4_group::msg::QueryMsg::Admin {})?;
+    if admin.admin.as_deref() != Some(env.contract.address.as_str()) {
+        return Err(ContractError::NotGroupAdmin {});
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let expired = EXPIRATIONS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| {
+            item.as_ref()
+                .map_or(true, |(_, expiration)| *expiration <= env.block.height)
+        })
+        .map(|item| item.map(|(addr, _)| addr))
+        .take(limit)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    if expired.is_empty() {
+        return Err(ContractError::NothingToPrune {});
+    }
+
+    // The group contract's member changed hook removes the pruned
+    // members' expirations.
+
+    let remove: Vec<String> = expired.into_iter().map(Addr::into_string).collect();
+    let msg = WasmMsg::Execute {
+        contract_addr: group_contract.into_string(),
+        msg: to_json_binary(&cw4_group::msg::ExecuteMsg::UpdateMembers {
+            remove: remove.clone(),
+            add: vec![],
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "prune")
+        .add_attribute("removed", remove.join(","))
+        .add_message(msg))
+}
+
+pub fn execute_update_members(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<cw4::Member>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // The group contract's member changed hook removes the expirations
+    // of removed members.
+    let group_contract = GROUP_CONTRACT.load(deps.storage)?;
+    let msg = WasmMsg::Execute {
+        contract_addr: group_contract.into_string(),
+        msg: to_json_binary(&cw4_group::msg::ExecuteMsg::UpdateMembers { remove, add })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "update_members")
+        .add_message(msg))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -126,6 +366,14 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::GroupContract {} => to_json_binary(&GROUP_CONTRACT.load(deps.storage)?),
         QueryMsg::Dao {} => to_json_binary(&DAO.load(deps.storage)?),
+        QueryMsg::ExpiringMembers { start_after, limit } => {
+            to_json_binary(&query_expiring_members(deps, env, start_after, limit)?)
+        }
+        QueryMsg::MemberExpiration { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            let expiration = EXPIRATIONS.may_load(deps.storage, &address)?;
+            to_json_binary(&expiration.map(Expiration::AtHeight))
+        }
     }
 }
 
@@ -135,18 +383,26 @@ pub fn query_voting_power_at_height(
     address: String,
     height: Option<u64>,
 ) -> StdResult<Binary> {
-    let addr = deps.api.addr_validate(&address)?.to_string();
+    let addr = deps.api.addr_validate(&address)?;
     let group_contract = GROUP_CONTRACT.load(deps.storage)?;
-    let res: MemberResponse = deps.querier.query_wasm_smart(
-        group_contract,
-        &cw4_group::msg::QueryMsg::Member {
-            addr,
-            at_height: height,
-        },
-    )?;
+
+    // Members whose membership has expired at the queried height have
+    // no voting power.
+    let expiration = match height {
+        Some(height) => EXPIRATIONS.may_load_at_height(deps.storage, &addr, height)?,
+        None => EXPIRATIONS.may_load(deps.storage, &addr)?,
+    };
+    let expired = expiration.map_or(false, |expiration| {
+        expiration <= height.unwrap_or(env.block.height)
+    });
+    let power = if expired {
+        0
+    } else {
+        query_member_weight(deps, &group_contract, addr.into_string(), height)?
+    };
 
     to_json_binary(&dao_interface::voting::VotingPowerAtHeightResponse {
-        power: res.weight.unwrap_or(0).into(),
+        power: power.into(),
         height: height.unwrap_or(env.block.height),
     })
 }
@@ -154,15 +410,71 @@ pub fn query_voting_power_at_height(
 pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
     let group_contract = GROUP_CONTRACT.load(deps.storage)?;
     let res: TotalWeightResponse = deps.querier.query_wasm_smart(
-        group_contract,
+        group_contract.clone(),
         &cw4_group::msg::QueryMsg::TotalWeight { at_height: height },
     )?;
+
+    // Exclude the weight of members whose membership has expired but
+    // who have not been pruned from the group contract.
+    let expired = match height {
+        Some(height) => EXPIRED_WEIGHT
+            .may_load_at_height(deps.storage, height)?
+            .unwrap_or_default(),
+        None => load_expired_weight(deps.storage)?,
+    };
+    let expired = expired_weight(deps.storage, expired, height.unwrap_or(env.block.height))?;
+
     to_json_binary(&dao_interface::voting::TotalPowerAtHeightResponse {
-        power: res.weight.into(),
+        power: Uint128::from(res.weight).saturating_sub(expired.weight),
         height: height.unwrap_or(env.block.height),
     })
 }
 
+fn query_member_weight(
+    deps: Deps,
+    group_contract: &Addr,
+    addr: String,
+    height: Option<u64>,
+) -> StdResult<u64> {
+    let res: MemberResponse = deps.querier.query_wasm_smart(
+        group_contract,
+        &cw4_group::msg::QueryMsg::Member {
+            addr,
+            at_height: height,
+        },
+    )?;
+    Ok(res.weight.unwrap_or(0))
+}
+
+pub fn query_expiring_members(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ExpiringMembersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let members = EXPIRATIONS
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| {
+            item.map(|(addr, expiration)| ExpiringMember {
+                addr: addr.into_string(),
+                expiration: Expiration::AtHeight(expiration),
+                expired: expiration <= env.block.height,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(ExpiringMembersResponse { members })
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_json_binary(&dao_interface::voting::InfoResponse { info })
@@ -182,7 +494,7 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
         INSTANTIATE_GROUP_REPLY_ID => {
             let res = parse_reply_instantiate_data(msg);
@@ -194,7 +506,31 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
                     }
                     let group_contract = deps.api.addr_validate(&res.contract_address)?;
                     GROUP_CONTRACT.save(deps.storage, &group_contract)?;
-                    Ok(Response::default().add_attribute("group_contract", group_contract))
+
+                    // Register this contract as a member changed hook
+                    // and hand the group over to the DAO.
+                    let dao = DAO.load(deps.storage)?;
+                    let msgs = [
+                        cw4_group::msg::ExecuteMsg::AddHook {
+                            addr: env.contract.address.to_string(),
+                        },
+                        cw4_group::msg::ExecuteMsg::UpdateAdmin {
+                            admin: Some(dao.into_string()),
+                        },
+                    ]
+                    .iter()
+                    .map(|msg| {
+                        Ok(WasmMsg::Execute {
+                            contract_addr: group_contract.to_string(),
+                            msg: to_json_binary(msg)?,
+                            funds: vec![],
+                        })
+                    })
+                    .collect::<StdResult<Vec<_>>>()?;
+
+                    Ok(Response::default()
+                        .add_attribute("group_contract", group_contract)
+                        .add_messages(msgs))
                 }
                 Err(_) => Err(ContractError::GroupContractInstantiateError {}),
             }
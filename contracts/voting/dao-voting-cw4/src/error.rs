@@ -18,12 +18,26 @@ pub enum ContractError {
     #[error("Error occured whilst instantiating group contract")]
     GroupContractInstantiateError {},
 
-    #[error("Contract only supports queries")]
-    NoExecute {},
+    #[error("Expiration must not be never")]
+    NeverExpires {},
 
     #[error("Cannot instantiate or use a group contract with no initial members")]
     NoMembers {},
 
+    #[error("This contract must be the group contract's admin to prune members")]
+    NotGroupAdmin {},
+
+    #[error(
+        "This contract must be registered as a hook on the group contract to track expirations"
+    )]
+    NotGroupHook {},
+
+    #[error("No expired members to prune")]
+    NothingToPrune {},
+
+    #[error("Expirations must be block heights")]
+    TimeExpiration {},
+
     #[error("Got a submessage reply with unknown id: {id}")]
     UnknownReplyId { id: u64 },
 
@@ -1,4 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw_utils::Expiration;
 use dao_dao_macros::voting_module_query;
 
 #[cw_serde]
@@ -18,7 +19,39 @@ pub struct InstantiateMsg {
 }
 
 #[cw_serde]
-pub enum ExecuteMsg {}
+pub struct MemberExpiration {
+    pub addr: String,
+    pub expiration: Expiration,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Sets the block height at which the membership of the addresses
+    /// in ADD expires, and removes the expirations of the addresses in
+    /// REMOVE. Once a member's membership expires they have no voting
+    /// power, even if the group contract still lists them. Only
+    /// callable by the DAO, and only once this contract is registered
+    /// as a hook on the group contract.
+    UpdateExpirations {
+        add: Vec<MemberExpiration>,
+        remove: Vec<String>,
+    },
+    /// Removes up to LIMIT members whose membership has expired from
+    /// the group contract. Callable by anyone. This contract must be
+    /// the group contract's admin.
+    Prune { limit: Option<u32> },
+    /// Updates the members of the group contract. Members that are
+    /// removed also have their expirations removed. Only callable by
+    /// the DAO, and only useful once this contract is the group
+    /// contract's admin.
+    UpdateMembers {
+        add: Vec<cw4::Member>,
+        remove: Vec<String>,
+    },
+    /// Called by the group contract when its members change. Keeps the
+    /// weight of expiring members up to date.
+    MemberChangedHook(cw4::MemberChangedHookMsg),
+}
 
 #[voting_module_query]
 #[cw_serde]
@@ -26,6 +59,26 @@ pub enum ExecuteMsg {}
 pub enum QueryMsg {
     #[returns(cosmwasm_std::Addr)]
     GroupContract {},
+    /// Lists members with an expiration, ordered by address.
+    #[returns(ExpiringMembersResponse)]
+    ExpiringMembers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Option<Expiration>)]
+    MemberExpiration { address: String },
+}
+
+#[cw_serde]
+pub struct ExpiringMember {
+    pub addr: String,
+    pub expiration: Expiration,
+    pub expired: bool,
+}
+
+#[cw_serde]
+pub struct ExpiringMembersResponse {
+    pub members: Vec<ExpiringMember>,
 }
 
 #[cw_serde]
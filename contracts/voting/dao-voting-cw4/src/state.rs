@@ -1,5 +1,42 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 
 pub const GROUP_CONTRACT: Item<Addr> = Item::new("group_contract");
 pub const DAO: Item<Addr> = Item::new("dao_address");
+
+/// The heights at which the membership of members with a term limit
+/// expires, over time, so that past voting power queries use the
+/// expirations that were in place at that height.
+pub const EXPIRATIONS: SnapshotMap<&Addr, u64> = SnapshotMap::new(
+    "expirations",
+    "expirations__checkpoints",
+    "expirations__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The total weight of members whose membership has expired but who
+/// are still members of the group contract.
+#[cw_serde]
+#[derive(Default)]
+pub struct ExpiredWeight {
+    pub weight: Uint128,
+    /// Members whose membership expires at or before this height are
+    /// included in `weight`. The weight of members expiring later is
+    /// added from `EXPIRING_WEIGHTS` when the total is next computed.
+    pub expired_through: u64,
+}
+
+/// Keeps track of the weight of expired members over time, so that
+/// total power queries do not need to iterate over members.
+pub const EXPIRED_WEIGHT: SnapshotItem<ExpiredWeight> = SnapshotItem::new(
+    "expired_weight",
+    "expired_weight__checkpoints",
+    "expired_weight__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The total weight of the members whose membership expires at each
+/// height. Kept after that height so that past totals may be
+/// computed.
+pub const EXPIRING_WEIGHTS: Map<u64, Uint128> = Map::new("expiring_weights");
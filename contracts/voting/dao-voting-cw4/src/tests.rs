@@ -3,7 +3,8 @@ use cosmwasm_std::{
     to_json_binary, Addr, CosmosMsg, Uint128, WasmMsg,
 };
 use cw2::ContractVersion;
-use cw_multi_test::{next_block, App, Executor};
+use cw_multi_test::{next_block, App, AppResponse, Executor};
+use cw_utils::Expiration;
 use dao_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
@@ -11,7 +12,10 @@ use dao_testing::contracts::{cw4_group_contract, dao_voting_cw4_contract};
 
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
-    msg::{GroupContract, InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::{
+        ExecuteMsg, ExpiringMember, ExpiringMembersResponse, GroupContract, InstantiateMsg,
+        MemberExpiration, MigrateMsg, QueryMsg,
+    },
 };
 use dao_voting_cw4::ContractError;
 
@@ -713,6 +717,471 @@ fn test_zero_voting_power() {
     assert_eq!(total_voting_power.height, app.block_info().height);
 }
 
+fn update_expirations(
+    app: &mut App,
+    voting_addr: &Addr,
+    sender: &str,
+    add: Vec<MemberExpiration>,
+    remove: Vec<String>,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateExpirations { add, remove },
+        &[],
+    )
+}
+
+fn query_power(app: &App, voting_addr: &Addr, address: &str) -> Uint128 {
+    let res: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr,
+            &QueryMsg::VotingPowerAtHeight {
+                address: address.to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    res.power
+}
+
+fn query_total_power(app: &App, voting_addr: &Addr) -> Uint128 {
+    let res: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(voting_addr, &QueryMsg::TotalPowerAtHeight { height: None })
+        .unwrap();
+    res.power
+}
+
+#[test]
+fn test_expirations() {
+    let mut app = App::default();
+    let voting_addr = setup_test_case(&mut app);
+    app.update_block(next_block);
+    let expiration = Expiration::AtHeight(app.block_info().height + 5);
+
+    // Only the DAO may set expirations
+    let err: ContractError = update_expirations(
+        &mut app,
+        &voting_addr,
+        ADDR1,
+        vec![MemberExpiration {
+            addr: ADDR1.to_string(),
+            expiration,
+        }],
+        vec![],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Expirations must expire
+    let err: ContractError = update_expirations(
+        &mut app,
+        &voting_addr,
+        DAO_ADDR,
+        vec![MemberExpiration {
+            addr: ADDR1.to_string(),
+            expiration: Expiration::Never {},
+        }],
+        vec![],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::NeverExpires {});
+
+    // Expirations must be block heights
+    let err: ContractError = update_expirations(
+        &mut app,
+        &voting_addr,
+        DAO_ADDR,
+        vec![MemberExpiration {
+            addr: ADDR1.to_string(),
+            expiration: Expiration::AtTime(app.block_info().time.plus_seconds(60)),
+        }],
+        vec![],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::TimeExpiration {});
+
+    update_expirations(
+        &mut app,
+        &voting_addr,
+        DAO_ADDR,
+        vec![
+            MemberExpiration {
+                addr: ADDR1.to_string(),
+                expiration,
+            },
+            MemberExpiration {
+                addr: ADDR2.to_string(),
+                expiration,
+            },
+        ],
+        vec![],
+    )
+    .unwrap();
+    let member_expiration: Option<Expiration> = app
+        .wrap()
+        .query_wasm_smart(
+            &voting_addr,
+            &QueryMsg::MemberExpiration {
+                address: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(member_expiration, Some(expiration));
+
+    // Members have voting power until their membership expires
+    assert_eq!(query_power(&app, &voting_addr, ADDR1), Uint128::new(1));
+    assert_eq!(query_total_power(&app, &voting_addr), Uint128::new(3));
+
+    app.update_block(|block| block.height += 5);
+    assert_eq!(query_power(&app, &voting_addr, ADDR1), Uint128::zero());
+    assert_eq!(query_power(&app, &voting_addr, ADDR2), Uint128::zero());
+    assert_eq!(query_power(&app, &voting_addr, ADDR3), Uint128::new(1));
+    assert_eq!(query_total_power(&app, &voting_addr), Uint128::new(1));
+
+    let expiring: ExpiringMembersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &voting_addr,
+            &QueryMsg::ExpiringMembers {
+                start_after: Some(ADDR1.to_string()),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        expiring.members,
+        vec![ExpiringMember {
+            addr: ADDR2.to_string(),
+            expiration,
+            expired: true,
+        }]
+    );
+
+    // Removing an expiration restores voting power
+    update_expirations(
+        &mut app,
+        &voting_addr,
+        DAO_ADDR,
+        vec![],
+        vec![ADDR2.to_string()],
+    )
+    .unwrap();
+    assert_eq!(query_power(&app, &voting_addr, ADDR2), Uint128::new(1));
+    assert_eq!(query_total_power(&app, &voting_addr), Uint128::new(2));
+}
+
+fn query_total_power_at_height(app: &App, voting_addr: &Addr, height: u64) -> Uint128 {
+    let res: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr,
+            &QueryMsg::TotalPowerAtHeight {
+                height: Some(height),
+            },
+        )
+        .unwrap();
+    res.power
+}
+
+#[test]
+fn test_expired_power_at_height() {
+    let mut app = App::default();
+    let voting_addr = setup_test_case(&mut app);
+    app.update_block(next_block);
+    let cw4_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(&voting_addr, &QueryMsg::GroupContract {})
+        .unwrap();
+
+    let start = app.block_info().height;
+    update_expirations(
+        &mut app,
+        &voting_addr,
+        DAO_ADDR,
+        vec![MemberExpiration {
+            addr: ADDR1.to_string(),
+            expiration: Expiration::AtHeight(start + 2),
+        }],
+        vec![],
+    )
+    .unwrap();
+    app.update_block(|block| block.height += 3);
+    assert_eq!(query_total_power(&app, &voting_addr), Uint128::new(2));
+
+    // The DAO changes the weight of the expired member directly on the
+    // group contract.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        cw4_addr,
+        &cw4_group::msg::ExecuteMsg::UpdateMembers {
+            add: vec![cw4::Member {
+                addr: ADDR1.to_string(),
+                weight: 5,
+            }],
+            remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_power(&app, &voting_addr, ADDR1), Uint128::zero());
+    assert_eq!(query_total_power(&app, &voting_addr), Uint128::new(2));
+    app.update_block(next_block);
+    assert_eq!(query_total_power(&app, &voting_addr), Uint128::new(2));
+
+    // Expiration is evaluated at the queried height.
+    assert_eq!(
+        query_total_power_at_height(&app, &voting_addr, start + 1),
+        Uint128::new(3)
+    );
+    assert_eq!(
+        query_total_power_at_height(&app, &voting_addr, start + 2),
+        Uint128::new(2)
+    );
+    let res: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &voting_addr,
+            &QueryMsg::VotingPowerAtHeight {
+                address: ADDR1.to_string(),
+                height: Some(start + 1),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.power, Uint128::new(1));
+
+    // Removing the expiration restores the member's current weight
+    // without changing past totals.
+    update_expirations(
+        &mut app,
+        &voting_addr,
+        DAO_ADDR,
+        vec![],
+        vec![ADDR1.to_string()],
+    )
+    .unwrap();
+    assert_eq!(query_total_power(&app, &voting_addr), Uint128::new(7));
+    app.update_block(next_block);
+    assert_eq!(
+        query_total_power_at_height(&app, &voting_addr, start + 2),
+        Uint128::new(2)
+    );
+    assert_eq!(
+        query_total_power_at_height(&app, &voting_addr, app.block_info().height),
+        Uint128::new(7)
+    );
+}
+
+#[test]
+fn test_expirations_require_group_hook() {
+    let mut app = App::default();
+    let voting_id = app.store_code(dao_voting_cw4_contract());
+    let cw4_id = app.store_code(cw4_group_contract());
+    let cw4_addr = app
+        .instantiate_contract(
+            cw4_id,
+            Addr::unchecked(DAO_ADDR),
+            &cw4_group::msg::InstantiateMsg {
+                admin: Some(DAO_ADDR.to_string()),
+                members: vec![cw4::Member {
+                    addr: ADDR1.to_string(),
+                    weight: 1,
+                }],
+            },
+            &[],
+            "cw4 group",
+            None,
+        )
+        .unwrap();
+    let voting_addr = instantiate_voting(
+        &mut app,
+        voting_id,
+        InstantiateMsg {
+            group_contract: GroupContract::Existing {
+                address: cw4_addr.to_string(),
+            },
+        },
+    );
+    let expiration = MemberExpiration {
+        addr: ADDR1.to_string(),
+        expiration: Expiration::AtHeight(app.block_info().height + 1),
+    };
+
+    let err: ContractError = update_expirations(
+        &mut app,
+        &voting_addr,
+        DAO_ADDR,
+        vec![expiration.clone()],
+        vec![],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::NotGroupHook {});
+
+    // Only the group contract may call the hook.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            voting_addr.clone(),
+            &ExecuteMsg::MemberChangedHook(cw4::MemberChangedHookMsg { diffs: vec![] }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        cw4_addr,
+        &cw4_group::msg::ExecuteMsg::AddHook {
+            addr: voting_addr.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    update_expirations(&mut app, &voting_addr, DAO_ADDR, vec![expiration], vec![]).unwrap();
+    app.update_block(next_block);
+    assert_eq!(query_power(&app, &voting_addr, ADDR1), Uint128::zero());
+    assert_eq!(query_total_power(&app, &voting_addr), Uint128::zero());
+}
+
+#[test]
+fn test_prune() {
+    let mut app = App::default();
+    let voting_addr = setup_test_case(&mut app);
+    app.update_block(next_block);
+    let cw4_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(&voting_addr, &QueryMsg::GroupContract {})
+        .unwrap();
+
+    update_expirations(
+        &mut app,
+        &voting_addr,
+        DAO_ADDR,
+        vec![
+            MemberExpiration {
+                addr: ADDR1.to_string(),
+                expiration: Expiration::AtHeight(app.block_info().height + 1),
+            },
+            MemberExpiration {
+                addr: ADDR2.to_string(),
+                expiration: Expiration::AtHeight(app.block_info().height + 10),
+            },
+        ],
+        vec![],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    // The voting module must be the group's admin to prune
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR3),
+            voting_addr.clone(),
+            &ExecuteMsg::Prune { limit: None },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotGroupAdmin {});
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        cw4_addr.clone(),
+        &cw4_group::msg::ExecuteMsg::UpdateAdmin {
+            admin: Some(voting_addr.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Anyone may prune expired members
+    app.execute_contract(
+        Addr::unchecked(ADDR3),
+        voting_addr.clone(),
+        &ExecuteMsg::Prune { limit: None },
+        &[],
+    )
+    .unwrap();
+    let member: cw4::MemberResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &cw4_addr,
+            &cw4_group::msg::QueryMsg::Member {
+                addr: ADDR1.to_string(),
+                at_height: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(member.weight, None);
+    assert_eq!(query_total_power(&app, &voting_addr), Uint128::new(2));
+
+    let expiring: ExpiringMembersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &voting_addr,
+            &QueryMsg::ExpiringMembers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(expiring.members.len(), 1);
+    assert_eq!(expiring.members[0].addr, ADDR2);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR3),
+            voting_addr.clone(),
+            &ExecuteMsg::Prune { limit: None },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NothingToPrune {});
+
+    // The DAO updates members through the voting module, which
+    // removes the expirations of removed members.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateMembers {
+            add: vec![cw4::Member {
+                addr: ADDR1.to_string(),
+                weight: 3,
+            }],
+            remove: vec![ADDR2.to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_power(&app, &voting_addr, ADDR1), Uint128::new(3));
+    assert_eq!(query_total_power(&app, &voting_addr), Uint128::new(4));
+    let member_expiration: Option<Expiration> = app
+        .wrap()
+        .query_wasm_smart(
+            &voting_addr,
+            &QueryMsg::MemberExpiration {
+                address: ADDR2.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(member_expiration, None);
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
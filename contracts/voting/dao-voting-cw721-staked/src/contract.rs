@@ -1,17 +1,19 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
-    Reply, Response, StdError, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
+    from_json, to_json_binary, to_json_vec, Addr, Binary, ContractResult, CosmosMsg, Deps, DepsMut,
+    Empty, Env, MessageInfo, QueryRequest, Reply, Response, StdError, StdResult, Storage, SubMsg,
+    SystemResult, Uint128, Uint256, WasmMsg, WasmQuery,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
-use cw721::{Cw721QueryMsg, Cw721ReceiveMsg, NumTokensResponse};
+use cw721::{Cw721QueryMsg, Cw721ReceiveMsg, NftInfoResponse, NumTokensResponse};
 use cw_storage_plus::Bound;
 use cw_utils::{parse_reply_execute_data, parse_reply_instantiate_data, Duration};
 use dao_hooks::nft_stake::{stake_nft_hook_msgs, unstake_nft_hook_msgs};
 use dao_interface::state::ModuleInstantiateCallback;
 use dao_interface::{nft::NftFactoryCallback, voting::IsActiveResponse};
 use dao_voting::duration::validate_duration;
+use dao_voting::power_cap::{PowerCap, PowerCapResponse};
 use dao_voting::threshold::{
     assert_valid_absolute_count_threshold, assert_valid_percentage_threshold, ActiveThreshold,
     ActiveThresholdResponse,
@@ -19,9 +21,12 @@ use dao_voting::threshold::{
 
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, NftContract, QueryMsg};
 use crate::state::{
-    register_staked_nft, register_unstaked_nfts, Config, ACTIVE_THRESHOLD, CONFIG, DAO, HOOKS,
-    INITIAL_NFTS, MAX_CLAIMS, NFT_BALANCES, NFT_CLAIMS, STAKED_NFTS_PER_OWNER, TOTAL_STAKED_NFTS,
+    register_staked_nft, register_unstaked_nfts, Config, NftWeights, ACTIVE_THRESHOLD, CONFIG, DAO,
+    HOOKS, INITIAL_NFTS, MAX_CLAIMS, NFT_BALANCES, NFT_CLAIMS, NFT_WEIGHTS, POWER_CAPS,
+    STAKED_NFTS_PER_OWNER, STAKED_NFT_WEIGHTS, TOTAL_STAKED_NFTS, TOTAL_STAKED_POWER,
 };
+use serde::Deserialize;
+
 use crate::ContractError;
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-voting-cw721-staked";
@@ -105,6 +110,7 @@ pub fn instantiate(
     }
 
     TOTAL_STAKED_NFTS.save(deps.storage, &Uint128::zero(), env.block.height)?;
+    TOTAL_STAKED_POWER.save(deps.storage, &Uint128::zero(), env.block.height)?;
 
     match msg.nft_contract {
         NftContract::Existing { address } => {
@@ -211,6 +217,11 @@ pub fn execute(
         ExecuteMsg::UpdateActiveThreshold { new_threshold } => {
             execute_update_active_threshold(deps, env, info, new_threshold)
         }
+        ExecuteMsg::UpdateWeights { weights } => execute_update_weights(deps, info, weights),
+        ExecuteMsg::UpdateMaxPowerPerAddress {
+            max_power_per_address,
+        } => execute_update_max_power_per_address(deps, info, max_power_per_address),
+        ExecuteMsg::RefreshPowerCap { address } => execute_refresh_power_cap(deps, env, address),
     }
 }
 
@@ -228,7 +239,15 @@ pub fn execute_stake(
         });
     }
     let staker = deps.api.addr_validate(&wrapper.sender)?;
-    register_staked_nft(deps.storage, env.block.height, &staker, &wrapper.token_id)?;
+    let weight = query_nft_weight(deps.as_ref(), &config.nft_address, &wrapper.token_id)?;
+    register_staked_nft(
+        deps.storage,
+        env.block.height,
+        &staker,
+        &wrapper.token_id,
+        weight,
+    )?;
+    apply_power_cap(deps.storage, env.block.height, &staker)?;
     let hook_msgs = stake_nft_hook_msgs(
         HOOKS,
         deps.storage,
@@ -239,7 +258,86 @@ pub fn execute_stake(
         .add_submessages(hook_msgs)
         .add_attribute("action", "stake")
         .add_attribute("from", staker)
-        .add_attribute("token_id", wrapper.token_id))
+        .add_attribute("token_id", wrapper.token_id)
+        .add_attribute("weight", weight))
+}
+
+/// The parts of a NFT's metadata extension used to look up its trait
+/// weight. This matches the cw721-metadata-onchain and sg721 metadata
+/// formats; other fields are ignored, and NFTs whose metadata does not
+/// match have the default weight.
+#[derive(Deserialize)]
+struct TraitMetadata {
+    #[serde(default)]
+    attributes: Option<Vec<NftTrait>>,
+}
+
+#[derive(Deserialize)]
+struct NftTrait {
+    trait_type: String,
+    value: String,
+}
+
+/// Computes the weight of TOKEN_ID according to the current
+/// NFT_WEIGHTS.
+fn query_nft_weight(deps: Deps, nft_address: &Addr, token_id: &str) -> StdResult<Uint128> {
+    let Some(weights) = NFT_WEIGHTS.may_load(deps.storage)? else {
+        return Ok(Uint128::one());
+    };
+    match weights {
+        NftWeights::TokenIdRanges {
+            ranges,
+            default_weight,
+        } => Ok(token_id
+            .parse::<u64>()
+            .ok()
+            .and_then(|id| {
+                ranges
+                    .into_iter()
+                    .find(|range| range.start <= id && id <= range.end)
+            })
+            .map_or(default_weight, |range| range.weight)),
+        NftWeights::Trait {
+            trait_type,
+            values,
+            default_weight,
+        } => {
+            // Metadata formats vary between collections, so the
+            // response is only parsed once it has been received.
+            let info = query_nft_info(deps, nft_address, token_id)?;
+            let value = from_json::<NftInfoResponse<Option<TraitMetadata>>>(&info)
+                .ok()
+                .and_then(|info| info.extension)
+                .and_then(|metadata| metadata.attributes)
+                .unwrap_or_default()
+                .into_iter()
+                .find(|attribute| attribute.trait_type == trait_type)
+                .map(|attribute| attribute.value);
+            Ok(value
+                .and_then(|value| values.into_iter().find(|v| v.value == value))
+                .map_or(default_weight, |v| v.weight))
+        }
+    }
+}
+
+/// Queries the unparsed `NftInfo` response for TOKEN_ID.
+fn query_nft_info(deps: Deps, nft_address: &Addr, token_id: &str) -> StdResult<Binary> {
+    let request: QueryRequest<Empty> = WasmQuery::Smart {
+        contract_addr: nft_address.to_string(),
+        msg: to_json_binary(&Cw721QueryMsg::NftInfo {
+            token_id: token_id.to_string(),
+        })?,
+    }
+    .into();
+    match deps.querier.raw_query(&to_json_vec(&request)?) {
+        SystemResult::Ok(ContractResult::Ok(info)) => Ok(info),
+        SystemResult::Ok(ContractResult::Err(err)) => Err(StdError::generic_err(format!(
+            "Querier contract error: {err}"
+        ))),
+        SystemResult::Err(err) => Err(StdError::generic_err(format!(
+            "Querier system error: {err}"
+        ))),
+    }
 }
 
 pub fn execute_unstake(
@@ -253,6 +351,7 @@ pub fn execute_unstake(
     }
 
     register_unstaked_nfts(deps.storage, env.block.height, &info.sender, &token_ids)?;
+    apply_power_cap(deps.storage, env.block.height, &info.sender)?;
 
     // Provided that the backing cw721 contract is non-malicious:
     //
@@ -477,6 +576,66 @@ pub fn execute_update_active_threshold(
     Ok(Response::new().add_attribute("action", "update_active_threshold"))
 }
 
+pub fn execute_update_weights(
+    deps: DepsMut,
+    info: MessageInfo,
+    weights: Option<NftWeights>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match weights {
+        Some(weights) => {
+            weights.validate()?;
+            NFT_WEIGHTS.save(deps.storage, &weights)?;
+        }
+        None => NFT_WEIGHTS.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_weights"))
+}
+
+/// Recomputes how much of ADDRESS's voting power is above the voting
+/// power cap. Called whenever ADDRESS stakes or unstakes.
+fn apply_power_cap(storage: &mut dyn Storage, height: u64, address: &Addr) -> StdResult<()> {
+    let power = NFT_BALANCES.may_load(storage, address)?.unwrap_or_default();
+    let total = match TOTAL_STAKED_POWER.may_load(storage)? {
+        Some(total) => total,
+        None => TOTAL_STAKED_NFTS.may_load(storage)?.unwrap_or_default(),
+    };
+    POWER_CAPS.apply(storage, height, address, power, total)
+}
+
+pub fn execute_update_max_power_per_address(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_power_per_address: Option<PowerCap>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    POWER_CAPS.set_cap(deps.storage, max_power_per_address)?;
+
+    Ok(Response::new().add_attribute("action", "update_max_power_per_address"))
+}
+
+pub fn execute_refresh_power_cap(
+    deps: DepsMut,
+    env: Env,
+    address: String,
+) -> Result<Response, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+    apply_power_cap(deps.storage, env.block.height, &address)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "refresh_power_cap")
+        .add_attribute("address", address))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -496,6 +655,13 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::VotingPowerAtHeight { address, height } => {
             query_voting_power_at_height(deps, env, address, height)
         }
+        QueryMsg::Weights {} => to_json_binary(&NFT_WEIGHTS.may_load(deps.storage)?),
+        QueryMsg::StakedNftWeight { token_id } => {
+            to_json_binary(&STAKED_NFT_WEIGHTS.may_load(deps.storage, &token_id)?)
+        }
+        QueryMsg::MaxPowerPerAddress {} => to_json_binary(&PowerCapResponse {
+            max_power_per_address: POWER_CAPS.cap(deps.storage)?,
+        }),
     }
 }
 
@@ -585,14 +751,19 @@ pub fn query_voting_power_at_height(
     let power = NFT_BALANCES
         .may_load_at_height(deps.storage, &address, height)?
         .unwrap_or_default();
+    let power = POWER_CAPS.capped_power_at_height(deps.storage, &address, power, height)?;
     to_json_binary(&dao_interface::voting::VotingPowerAtHeightResponse { power, height })
 }
 
 pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
     let height = height.unwrap_or(env.block.height);
-    let power = TOTAL_STAKED_NFTS
-        .may_load_at_height(deps.storage, height)?
-        .unwrap_or_default();
+    let power = match TOTAL_STAKED_POWER.may_load_at_height(deps.storage, height)? {
+        Some(power) => power,
+        None => TOTAL_STAKED_NFTS
+            .may_load_at_height(deps.storage, height)?
+            .unwrap_or_default(),
+    };
+    let power = POWER_CAPS.capped_total_at_height(deps.storage, power, height)?;
     to_json_binary(&dao_interface::voting::TotalPowerAtHeightResponse { power, height })
 }
 
@@ -1,6 +1,6 @@
 use cosmwasm_std::{Addr, StdError};
 use cw_utils::ParseReplyError;
-use dao_voting::threshold::ActiveThresholdError;
+use dao_voting::{power_cap::PowerCapError, threshold::ActiveThresholdError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -11,6 +11,9 @@ pub enum ContractError {
     #[error(transparent)]
     ActiveThresholdError(#[from] ActiveThresholdError),
 
+    #[error(transparent)]
+    PowerCapError(#[from] PowerCapError),
+
     #[error(transparent)]
     HookError(#[from] cw_hooks::HookError),
 
@@ -26,6 +29,9 @@ pub enum ContractError {
     #[error("Invalid token. Got ({received}), expected ({expected})")]
     InvalidToken { received: Addr, expected: Addr },
 
+    #[error("Invalid NFT weights: {reason}")]
+    InvalidWeights { reason: String },
+
     #[error("Error instantiating NFT contract")]
     NftInstantiateError {},
 
@@ -3,8 +3,11 @@ use cosmwasm_std::Binary;
 use cw721::Cw721ReceiveMsg;
 use cw_utils::Duration;
 use dao_dao_macros::{active_query, voting_module_query};
+use dao_voting::power_cap::PowerCap;
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 
+use crate::state::NftWeights;
+
 #[cw_serde]
 #[allow(clippy::large_enum_variant)]
 pub enum NftContract {
@@ -70,6 +73,24 @@ pub enum ExecuteMsg {
     UpdateActiveThreshold {
         new_threshold: Option<ActiveThreshold>,
     },
+    /// Sets how the voting power of NFTs is determined when they are
+    /// staked. If `None`, every NFT has a weight of one. Staked NFTs
+    /// keep the weight they were staked with.
+    /// Only callable by the DAO that initialized this voting contract.
+    UpdateWeights { weights: Option<NftWeights> },
+    /// Sets the maximum voting power any one address may have. Only
+    /// callable by the DAO that initialized this voting contract.
+    /// Addresses' powers are capped the next time they stake or
+    /// unstake, or are refreshed with `RefreshPowerCap`. A percentage
+    /// cap is measured against the total power when the address was
+    /// last capped, so the total power may differ from the sum of
+    /// capped powers until stakers are refreshed.
+    UpdateMaxPowerPerAddress {
+        max_power_per_address: Option<PowerCap>,
+    },
+    /// Reapplies the voting power cap to an address. Callable by
+    /// anyone.
+    RefreshPowerCap { address: String },
 }
 
 #[active_query]
@@ -92,6 +113,15 @@ pub enum QueryMsg {
     },
     #[returns(ActiveThresholdResponse)]
     ActiveThreshold {},
+    #[returns(Option<NftWeights>)]
+    Weights {},
+    #[returns(dao_voting::power_cap::PowerCapResponse)]
+    MaxPowerPerAddress {},
+    /// The weight a staked NFT was staked with. `None` if the NFT is
+    /// not staked, or was staked before weights were supported, in
+    /// which case its weight is one.
+    #[returns(Option<::cosmwasm_std::Uint128>)]
+    StakedNftWeight { token_id: String },
 }
 
 #[cw_serde]
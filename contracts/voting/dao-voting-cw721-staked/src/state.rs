@@ -4,7 +4,7 @@ use cw721_controllers::NftClaims;
 use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 use cw_utils::Duration;
-use dao_voting::threshold::ActiveThreshold;
+use dao_voting::{power_cap::PowerCaps, threshold::ActiveThreshold};
 
 use crate::ContractError;
 
@@ -25,8 +25,8 @@ pub const INITIAL_NFTS: Item<Vec<Binary>> = Item::new("initial_nfts");
 /// an `(address, token_id)` pair implies that `address` has staked
 /// `token_id`.
 pub const STAKED_NFTS_PER_OWNER: Map<(&Addr, &str), Empty> = Map::new("snpw");
-/// The number of NFTs staked by an address as a function of block
-/// height.
+/// The voting power of an address as a function of block height. This
+/// is the sum of the weights of the NFTs it has staked.
 pub const NFT_BALANCES: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
     "nb",
     "nb__checkpoints",
@@ -42,6 +42,104 @@ pub const TOTAL_STAKED_NFTS: SnapshotItem<Uint128> = SnapshotItem::new(
     Strategy::EveryBlock,
 );
 
+/// The total voting power of staked NFTs as a function of block
+/// height. Heights before this was first saved fall back to
+/// `TOTAL_STAKED_NFTS`, as every NFT had a weight of one.
+pub const TOTAL_STAKED_POWER: SnapshotItem<Uint128> = SnapshotItem::new(
+    "tsp",
+    "tsp__checkpoints",
+    "tsp__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The maximum voting power of any one address, and how much of each
+/// address's power is above it.
+pub const POWER_CAPS: PowerCaps = PowerCaps::new();
+
+/// The weight of each staked NFT, fixed when it was staked so that
+/// changing NFT_WEIGHTS does not change the power of staked NFTs.
+/// NFTs staked before weights were supported have no entry and a
+/// weight of one.
+pub const STAKED_NFT_WEIGHTS: Map<&str, Uint128> = Map::new("snw");
+
+/// An inclusive range of numeric token IDs and the weight of NFTs in
+/// it.
+#[cw_serde]
+pub struct TokenIdRange {
+    pub start: u64,
+    pub end: u64,
+    pub weight: Uint128,
+}
+
+/// A value of a NFT trait and the weight of NFTs with it.
+#[cw_serde]
+pub struct TraitWeight {
+    pub value: String,
+    pub weight: Uint128,
+}
+
+/// How the voting power of a staked NFT is determined.
+#[cw_serde]
+pub enum NftWeights {
+    /// Weights NFTs by their token ID. Token IDs that are not numeric
+    /// or not in any range have DEFAULT_WEIGHT.
+    TokenIdRanges {
+        ranges: Vec<TokenIdRange>,
+        default_weight: Uint128,
+    },
+    /// Weights NFTs by the value of the TRAIT_TYPE attribute in the
+    /// metadata extension returned by the collection's `NftInfo`
+    /// query. NFTs without the trait, with a value not in VALUES, or
+    /// whose metadata can not be parsed have DEFAULT_WEIGHT.
+    Trait {
+        trait_type: String,
+        values: Vec<TraitWeight>,
+        default_weight: Uint128,
+    },
+}
+
+impl NftWeights {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        match self {
+            NftWeights::TokenIdRanges { ranges, .. } => {
+                let mut ranges: Vec<&TokenIdRange> = ranges.iter().collect();
+                ranges.sort_by_key(|range| range.start);
+                for range in ranges.iter() {
+                    if range.start > range.end {
+                        return Err(ContractError::InvalidWeights {
+                            reason: format!("range {}-{} is empty", range.start, range.end),
+                        });
+                    }
+                }
+                for pair in ranges.windows(2) {
+                    if pair[1].start <= pair[0].end {
+                        return Err(ContractError::InvalidWeights {
+                            reason: format!(
+                                "ranges {}-{} and {}-{} overlap",
+                                pair[0].start, pair[0].end, pair[1].start, pair[1].end
+                            ),
+                        });
+                    }
+                }
+            }
+            NftWeights::Trait { values, .. } => {
+                let mut seen: Vec<&str> = values.iter().map(|v| v.value.as_str()).collect();
+                seen.sort_unstable();
+                if let Some(pair) = seen.windows(2).find(|pair| pair[0] == pair[1]) {
+                    return Err(ContractError::InvalidWeights {
+                        reason: format!("duplicate trait value ({})", pair[0]),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The weights applied to NFTs when they are staked. If unset, every
+/// NFT has a weight of one.
+pub const NFT_WEIGHTS: Item<NftWeights> = Item::new("nft_weights");
+
 /// The maximum number of claims that may be outstanding.
 pub const MAX_CLAIMS: u64 = 70;
 pub const NFT_CLAIMS: NftClaims = NftClaims::new("nft_claims");
@@ -55,17 +153,23 @@ pub fn register_staked_nft(
     height: u64,
     staker: &Addr,
     token_id: &String,
+    weight: Uint128,
 ) -> StdResult<()> {
-    let add_one = |prev: Option<Uint128>| -> StdResult<Uint128> {
-        prev.unwrap_or_default()
-            .checked_add(Uint128::new(1))
-            .map_err(StdError::overflow)
+    let add = |amount: Uint128| {
+        move |prev: Option<Uint128>| -> StdResult<Uint128> {
+            prev.unwrap_or_default()
+                .checked_add(amount)
+                .map_err(StdError::overflow)
+        }
     };
 
     STAKED_NFTS_PER_OWNER.save(storage, (staker, token_id), &Empty::default())?;
-    NFT_BALANCES.update(storage, staker, height, add_one)?;
+    STAKED_NFT_WEIGHTS.save(storage, token_id, &weight)?;
+    NFT_BALANCES.update(storage, staker, height, add(weight))?;
+    let total_power = load_total_staked_power(storage)?;
+    TOTAL_STAKED_POWER.save(storage, &total_power.checked_add(weight)?, height)?;
     TOTAL_STAKED_NFTS
-        .update(storage, height, add_one)
+        .update(storage, height, add(Uint128::one()))
         .map(|_| ())
 }
 
@@ -79,14 +183,15 @@ pub fn register_unstaked_nfts(
     staker: &Addr,
     token_ids: &[String],
 ) -> Result<(), ContractError> {
-    let subtractor = |amount: u128| {
+    let subtractor = |amount: Uint128| {
         move |prev: Option<Uint128>| -> StdResult<Uint128> {
             prev.expect("unstaking that which was not staked")
-                .checked_sub(Uint128::new(amount))
+                .checked_sub(amount)
                 .map_err(StdError::overflow)
         }
     };
 
+    let mut weight = Uint128::zero();
     for token in token_ids {
         let key = (staker, token.as_str());
         if STAKED_NFTS_PER_OWNER.has(storage, key) {
@@ -96,12 +201,31 @@ pub fn register_unstaked_nfts(
                 token_id: token.clone(),
             });
         }
+        weight += STAKED_NFT_WEIGHTS
+            .may_load(storage, token)?
+            .unwrap_or(Uint128::one());
+        STAKED_NFT_WEIGHTS.remove(storage, token);
     }
 
     // invariant: token_ids has unique values. for loop asserts this.
 
-    let sub_n = subtractor(token_ids.len() as u128);
-    TOTAL_STAKED_NFTS.update(storage, height, sub_n)?;
-    NFT_BALANCES.update(storage, staker, height, sub_n)?;
+    let total_power = load_total_staked_power(storage)?
+        .checked_sub(weight)
+        .map_err(StdError::overflow)?;
+    TOTAL_STAKED_POWER.save(storage, &total_power, height)?;
+    TOTAL_STAKED_NFTS.update(
+        storage,
+        height,
+        subtractor(Uint128::new(token_ids.len() as u128)),
+    )?;
+    NFT_BALANCES.update(storage, staker, height, subtractor(weight))?;
     Ok(())
 }
+
+/// Loads the current total voting power of staked NFTs.
+fn load_total_staked_power(storage: &dyn Storage) -> StdResult<Uint128> {
+    match TOTAL_STAKED_POWER.may_load(storage)? {
+        Some(power) => Ok(power),
+        None => Ok(TOTAL_STAKED_NFTS.may_load(storage)?.unwrap_or_default()),
+    }
+}
@@ -4,8 +4,10 @@ use cw_multi_test::{App, AppResponse, Executor};
 
 use anyhow::Result as AnyResult;
 use cw_utils::Duration;
+use dao_voting::power_cap::PowerCap;
 
 use crate::msg::ExecuteMsg;
+use crate::state::NftWeights;
 
 // Shorthand for an unchecked address.
 macro_rules! addr {
@@ -141,3 +143,49 @@ pub fn remove_hook(
         &[],
     )
 }
+
+pub fn update_weights(
+    app: &mut App,
+    module: &Addr,
+    sender: &str,
+    weights: Option<NftWeights>,
+) -> AnyResult<AppResponse> {
+    app.execute_contract(
+        addr!(sender),
+        module.clone(),
+        &ExecuteMsg::UpdateWeights { weights },
+        &[],
+    )
+}
+
+pub fn update_max_power_per_address(
+    app: &mut App,
+    module: &Addr,
+    sender: &str,
+    max_power_per_address: Option<PowerCap>,
+) -> AnyResult<AppResponse> {
+    app.execute_contract(
+        addr!(sender),
+        module.clone(),
+        &ExecuteMsg::UpdateMaxPowerPerAddress {
+            max_power_per_address,
+        },
+        &[],
+    )
+}
+
+pub fn refresh_power_cap(
+    app: &mut App,
+    module: &Addr,
+    sender: &str,
+    address: &str,
+) -> AnyResult<AppResponse> {
+    app.execute_contract(
+        addr!(sender),
+        module.clone(),
+        &ExecuteMsg::RefreshPowerCap {
+            address: address.to_string(),
+        },
+        &[],
+    )
+}
@@ -1,5 +1,7 @@
-use cosmwasm_std::Addr;
-use cw_multi_test::{App, Executor};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+use cw721_base::Cw721Contract;
+use cw_multi_test::{App, ContractWrapper, Executor};
 use dao_testing::contracts::cw721_base_contract;
 
 pub fn instantiate_cw721_base(app: &mut App, sender: &str, minter: &str) -> Addr {
@@ -19,3 +21,79 @@ pub fn instantiate_cw721_base(app: &mut App, sender: &str, minter: &str) -> Addr
     )
     .unwrap()
 }
+
+/// A trait in the metadata of a NFT, in the cw721-metadata-onchain
+/// format.
+#[cw_serde]
+pub struct Trait {
+    pub display_type: Option<String>,
+    pub trait_type: String,
+    pub value: TraitValue,
+}
+
+/// Some collections use numeric trait values, which the voting module
+/// does not understand.
+#[cw_serde]
+#[serde(untagged)]
+pub enum TraitValue {
+    String(String),
+    Number(u64),
+}
+
+#[cw_serde]
+pub struct Metadata {
+    pub name: Option<String>,
+    pub attributes: Option<Vec<Trait>>,
+}
+
+type Cw721Metadata<'a> = Cw721Contract<'a, Option<Metadata>, Empty, Empty, Empty>;
+
+fn cw721_metadata_instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw721_base::InstantiateMsg,
+) -> Result<Response, cw721_base::ContractError> {
+    Ok(Cw721Metadata::default().instantiate(deps, env, info, msg)?)
+}
+
+fn cw721_metadata_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw721_base::ExecuteMsg<Option<Metadata>, Empty>,
+) -> Result<Response, cw721_base::ContractError> {
+    Cw721Metadata::default().execute(deps, env, info, msg)
+}
+
+fn cw721_metadata_query(
+    deps: Deps,
+    env: Env,
+    msg: cw721_base::QueryMsg<Empty>,
+) -> StdResult<Binary> {
+    Cw721Metadata::default().query(deps, env, msg)
+}
+
+/// Instantiates a cw721 contract whose NFTs have on-chain metadata
+/// with traits.
+pub fn instantiate_cw721_metadata(app: &mut App, sender: &str, minter: &str) -> Addr {
+    let cw721_id = app.store_code(Box::new(ContractWrapper::new(
+        cw721_metadata_execute,
+        cw721_metadata_instantiate,
+        cw721_metadata_query,
+    )));
+
+    app.instantiate_contract(
+        cw721_id,
+        Addr::unchecked(sender),
+        &cw721_base::InstantiateMsg {
+            name: "traits".to_string(),
+            symbol: "traits".to_string(),
+            minter: minter.to_string(),
+        },
+        &[],
+        "cw721_metadata".to_string(),
+        None,
+    )
+    .unwrap()
+}
@@ -8,21 +8,27 @@ use dao_interface::voting::IsActiveResponse;
 use dao_testing::contracts::{
     cw721_base_contract, dao_test_custom_factory_contract, dao_voting_cw721_staked_contract,
 };
-use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
+use dao_voting::{
+    power_cap::{PowerCap, PowerCapResponse},
+    threshold::{ActiveThreshold, ActiveThresholdResponse},
+};
 
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, NftContract, QueryMsg},
-    state::MAX_CLAIMS,
+    state::{NftWeights, TokenIdRange, TraitWeight, MAX_CLAIMS},
     testing::{
         execute::{
-            claim_nfts, mint_and_stake_nft, mint_nft, stake_nft, unstake_nfts, update_config,
+            claim_nfts, mint_and_stake_nft, mint_nft, refresh_power_cap, stake_nft, unstake_nfts,
+            update_config, update_max_power_per_address, update_weights,
         },
         queries::{query_config, query_hooks, query_nft_owner, query_total_and_voting_power},
     },
 };
 
-use super::instantiate::instantiate_cw721_base;
+use super::instantiate::{
+    instantiate_cw721_base, instantiate_cw721_metadata, Metadata, Trait, TraitValue,
+};
 use super::{
     execute::{add_hook, remove_hook},
     is_error,
@@ -1444,6 +1450,257 @@ fn test_factory_no_callback() {
     .unwrap();
 }
 
+#[test]
+fn test_token_id_range_weights() -> anyhow::Result<()> {
+    let CommonTest {
+        mut app,
+        module,
+        nft,
+    } = setup_test(None);
+
+    let weights = NftWeights::TokenIdRanges {
+        ranges: vec![
+            TokenIdRange {
+                start: 1,
+                end: 10,
+                weight: Uint128::new(5),
+            },
+            TokenIdRange {
+                start: 11,
+                end: 20,
+                weight: Uint128::new(2),
+            },
+        ],
+        default_weight: Uint128::one(),
+    };
+
+    // Only the DAO may update weights.
+    let res = update_weights(&mut app, &module, "ekez", Some(weights.clone()));
+    is_error!(res => "Unauthorized");
+
+    let res = update_weights(
+        &mut app,
+        &module,
+        CREATOR_ADDR,
+        Some(NftWeights::TokenIdRanges {
+            ranges: vec![
+                TokenIdRange {
+                    start: 1,
+                    end: 10,
+                    weight: Uint128::new(5),
+                },
+                TokenIdRange {
+                    start: 10,
+                    end: 20,
+                    weight: Uint128::new(2),
+                },
+            ],
+            default_weight: Uint128::one(),
+        }),
+    );
+    is_error!(res => "Invalid NFT weights: ranges 1-10 and 10-20 overlap");
+
+    update_weights(&mut app, &module, CREATOR_ADDR, Some(weights.clone()))?;
+    let stored: Option<NftWeights> = app
+        .wrap()
+        .query_wasm_smart(&module, &QueryMsg::Weights {})?;
+    assert_eq!(stored, Some(weights));
+
+    mint_and_stake_nft(&mut app, &nft, &module, CREATOR_ADDR, "1")?;
+    mint_and_stake_nft(&mut app, &nft, &module, CREATOR_ADDR, "15")?;
+    mint_and_stake_nft(&mut app, &nft, &module, CREATOR_ADDR, "abc")?;
+    app.update_block(next_block);
+
+    let (total, personal) = query_total_and_voting_power(&app, &module, CREATOR_ADDR, None)?;
+    assert_eq!(total, Uint128::new(8));
+    assert_eq!(personal, Uint128::new(8));
+
+    // Staked NFTs keep the weight they were staked with.
+    update_weights(&mut app, &module, CREATOR_ADDR, None)?;
+    mint_and_stake_nft(&mut app, &nft, &module, CREATOR_ADDR, "2")?;
+    app.update_block(next_block);
+
+    let (total, personal) = query_total_and_voting_power(&app, &module, CREATOR_ADDR, None)?;
+    assert_eq!(total, Uint128::new(9));
+    assert_eq!(personal, Uint128::new(9));
+    let weight: Option<Uint128> = app.wrap().query_wasm_smart(
+        &module,
+        &QueryMsg::StakedNftWeight {
+            token_id: "1".to_string(),
+        },
+    )?;
+    assert_eq!(weight, Some(Uint128::new(5)));
+
+    // Unstaking removes the weight the NFT was staked with.
+    unstake_nfts(&mut app, &module, CREATOR_ADDR, &["1"])?;
+    app.update_block(next_block);
+
+    let (total, personal) = query_total_and_voting_power(&app, &module, CREATOR_ADDR, None)?;
+    assert_eq!(total, Uint128::new(4));
+    assert_eq!(personal, Uint128::new(4));
+    let weight: Option<Uint128> = app.wrap().query_wasm_smart(
+        &module,
+        &QueryMsg::StakedNftWeight {
+            token_id: "1".to_string(),
+        },
+    )?;
+    assert_eq!(weight, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_trait_weights() -> anyhow::Result<()> {
+    let mut app = App::default();
+    let module_id = app.store_code(dao_voting_cw721_staked_contract());
+    let nft = instantiate_cw721_metadata(&mut app, CREATOR_ADDR, CREATOR_ADDR);
+    let module = app.instantiate_contract(
+        module_id,
+        Addr::unchecked(CREATOR_ADDR),
+        &InstantiateMsg {
+            nft_contract: NftContract::Existing {
+                address: nft.to_string(),
+            },
+            unstaking_duration: None,
+            active_threshold: None,
+        },
+        &[],
+        "cw721_voting",
+        None,
+    )?;
+
+    update_weights(
+        &mut app,
+        &module,
+        CREATOR_ADDR,
+        Some(NftWeights::Trait {
+            trait_type: "rarity".to_string(),
+            values: vec![
+                TraitWeight {
+                    value: "legendary".to_string(),
+                    weight: Uint128::new(10),
+                },
+                TraitWeight {
+                    value: "rare".to_string(),
+                    weight: Uint128::new(3),
+                },
+            ],
+            default_weight: Uint128::one(),
+        }),
+    )?;
+
+    let mint = |app: &mut App, token_id: &str, rarity: Option<TraitValue>| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            nft.clone(),
+            &Cw721ExecuteMsg::<Option<Metadata>, Empty>::Mint {
+                token_id: token_id.to_string(),
+                owner: CREATOR_ADDR.to_string(),
+                token_uri: None,
+                extension: Some(Metadata {
+                    name: Some(token_id.to_string()),
+                    attributes: rarity.map(|rarity| {
+                        vec![Trait {
+                            display_type: None,
+                            trait_type: "rarity".to_string(),
+                            value: rarity,
+                        }]
+                    }),
+                }),
+            },
+            &[],
+        )
+    };
+    let rarity = |rarity: &str| Some(TraitValue::String(rarity.to_string()));
+    mint(&mut app, "1", rarity("legendary"))?;
+    mint(&mut app, "2", rarity("rare"))?;
+    mint(&mut app, "3", rarity("common"))?;
+    mint(&mut app, "4", None)?;
+    for token_id in ["1", "2", "3", "4"] {
+        stake_nft(&mut app, &nft, &module, CREATOR_ADDR, token_id)?;
+    }
+    app.update_block(next_block);
+
+    let (total, personal) = query_total_and_voting_power(&app, &module, CREATOR_ADDR, None)?;
+    assert_eq!(total, Uint128::new(15));
+    assert_eq!(personal, Uint128::new(15));
+
+    // NFTs whose metadata can not be parsed have the default weight.
+    mint(&mut app, "5", Some(TraitValue::Number(10)))?;
+    stake_nft(&mut app, &nft, &module, CREATOR_ADDR, "5")?;
+    app.update_block(next_block);
+
+    let (total, personal) = query_total_and_voting_power(&app, &module, CREATOR_ADDR, None)?;
+    assert_eq!(total, Uint128::new(16));
+    assert_eq!(personal, Uint128::new(16));
+
+    Ok(())
+}
+
+#[test]
+fn test_max_power_per_address() -> anyhow::Result<()> {
+    let CommonTest {
+        mut app,
+        module,
+        nft,
+    } = setup_test(None);
+
+    for token_id in ["1", "2", "3", "4"] {
+        mint_and_stake_nft(&mut app, &nft, &module, CREATOR_ADDR, token_id)?;
+    }
+    mint_and_stake_nft(&mut app, &nft, &module, "ekez", "5")?;
+
+    let cap = PowerCap::AbsoluteCount {
+        count: Uint128::new(2),
+    };
+
+    // Only the DAO may set the cap, and it must be valid.
+    let res = update_max_power_per_address(&mut app, &module, "ekez", Some(cap.clone()));
+    is_error!(res => "Unauthorized");
+    let res = update_max_power_per_address(
+        &mut app,
+        &module,
+        CREATOR_ADDR,
+        Some(PowerCap::Percentage {
+            percent: Decimal::percent(150),
+        }),
+    );
+    is_error!(res => "Voting power cap percentage must be greater than 0 and not greater than 1");
+
+    update_max_power_per_address(&mut app, &module, CREATOR_ADDR, Some(cap.clone()))?;
+    let res: PowerCapResponse = app
+        .wrap()
+        .query_wasm_smart(&module, &QueryMsg::MaxPowerPerAddress {})?;
+    assert_eq!(res.max_power_per_address, Some(cap));
+
+    // The cap applies once the staker is refreshed.
+    refresh_power_cap(&mut app, &module, "ekez", CREATOR_ADDR)?;
+    app.update_block(next_block);
+
+    let (total, personal) = query_total_and_voting_power(&app, &module, CREATOR_ADDR, None)?;
+    assert_eq!(total, Uint128::new(3));
+    assert_eq!(personal, Uint128::new(2));
+
+    // Staking past the cap does not add voting power.
+    mint_and_stake_nft(&mut app, &nft, &module, "ekez", "6")?;
+    mint_and_stake_nft(&mut app, &nft, &module, "ekez", "7")?;
+    app.update_block(next_block);
+
+    let (total, personal) = query_total_and_voting_power(&app, &module, "ekez", None)?;
+    assert_eq!(total, Uint128::new(4));
+    assert_eq!(personal, Uint128::new(2));
+
+    // Unstaking below the cap removes the excess.
+    unstake_nfts(&mut app, &module, CREATOR_ADDR, &["1", "2", "3"])?;
+    app.update_block(next_block);
+
+    let (total, personal) = query_total_and_voting_power(&app, &module, CREATOR_ADDR, None)?;
+    assert_eq!(total, Uint128::new(3));
+    assert_eq!(personal, Uint128::new(1));
+
+    Ok(())
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
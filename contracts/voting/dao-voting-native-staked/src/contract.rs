@@ -0,0 +1,249 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError,
+    StdResult, Uint128,
+};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+use cw_storage_plus::Bound;
+use cw_utils::maybe_addr;
+use dao_hooks::stake::{stake_hook_msgs, unstake_hook_msgs};
+use dao_interface::voting::{
+    DenomResponse, InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, MigrateMsg, QueryMsg,
+    StakerBalanceResponse,
+};
+use crate::state::{DAO, DENOM, HOOKS, MAX_CHECKPOINT_ADDRESSES, STAKED_BALANCES, STAKED_TOTAL};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-voting-native-staked";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Settings for query pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    DAO.save(deps.storage, &info.sender)?;
+
+    let denom = deps.querier.query_bonded_denom()?;
+    DENOM.save(deps.storage, &denom)?;
+    STAKED_TOTAL.save(deps.storage, &Uint128::zero(), env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("denom", denom))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Checkpoint { addresses } => execute_checkpoint(deps, env, addresses),
+        ExecuteMsg::AddHook { addr } => execute_add_hook(deps, info, addr),
+        ExecuteMsg::RemoveHook { addr } => execute_remove_hook(deps, info, addr),
+    }
+}
+
+pub fn execute_checkpoint(
+    deps: DepsMut,
+    env: Env,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    if addresses.is_empty() {
+        return Err(ContractError::NoAddresses {});
+    }
+    if addresses.len() > MAX_CHECKPOINT_ADDRESSES {
+        return Err(ContractError::TooManyAddresses {
+            max: MAX_CHECKPOINT_ADDRESSES,
+        });
+    }
+
+    let denom = DENOM.load(deps.storage)?;
+    let mut total = STAKED_TOTAL.load(deps.storage)?;
+    let mut hook_msgs = vec![];
+
+    for address in addresses {
+        let address = deps.api.addr_validate(&address)?;
+        let delegated = query_delegated(deps.as_ref(), &denom, &address)?;
+        let previous = STAKED_BALANCES
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default();
+
+        if delegated > previous {
+            let diff = delegated - previous;
+            total = total.checked_add(diff).map_err(StdError::overflow)?;
+            hook_msgs.extend(stake_hook_msgs(HOOKS, deps.storage, address.clone(), diff)?);
+        } else if delegated < previous {
+            let diff = previous - delegated;
+            total = total.checked_sub(diff).map_err(StdError::overflow)?;
+            hook_msgs.extend(unstake_hook_msgs(
+                HOOKS,
+                deps.storage,
+                address.clone(),
+                diff,
+            )?);
+        } else {
+            continue;
+        }
+
+        if delegated.is_zero() {
+            STAKED_BALANCES.remove(deps.storage, &address, env.block.height)?;
+        } else {
+            STAKED_BALANCES.save(deps.storage, &address, &delegated, env.block.height)?;
+        }
+    }
+
+    STAKED_TOTAL.save(deps.storage, &total, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "checkpoint")
+        .add_attribute("total", total)
+        .add_submessages(hook_msgs))
+}
+
+/// Sums ADDRESS's delegations of DENOM across all validators.
+fn query_delegated(deps: Deps, denom: &str, address: &Addr) -> StdResult<Uint128> {
+    deps.querier
+        .query_all_delegations(address)?
+        .into_iter()
+        .filter(|delegation| delegation.amount.denom == denom)
+        .try_fold(Uint128::zero(), |total, delegation| {
+            total
+                .checked_add(delegation.amount.amount)
+                .map_err(StdError::overflow)
+        })
+}
+
+pub fn execute_add_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let hook = deps.api.addr_validate(&addr)?;
+    HOOKS.add_hook(deps.storage, hook)?;
+    Ok(Response::new()
+        .add_attribute("action", "add_hook")
+        .add_attribute("hook", addr))
+}
+
+pub fn execute_remove_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let hook = deps.api.addr_validate(&addr)?;
+    HOOKS.remove_hook(deps.storage, hook)?;
+    Ok(Response::new()
+        .add_attribute("action", "remove_hook")
+        .add_attribute("hook", addr))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::VotingPowerAtHeight { address, height } => {
+            query_voting_power_at_height(deps, env, address, height)
+        }
+        QueryMsg::TotalPowerAtHeight { height } => query_total_power_at_height(deps, env, height),
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::Dao {} => to_json_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Denom {} => to_json_binary(&DenomResponse {
+            denom: DENOM.load(deps.storage)?,
+        }),
+        QueryMsg::ListStakers { start_after, limit } => {
+            query_list_stakers(deps, start_after, limit)
+        }
+        QueryMsg::GetHooks {} => to_json_binary(&GetHooksResponse {
+            hooks: HOOKS.query_hooks(deps)?.hooks,
+        }),
+    }
+}
+
+pub fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let height = height.unwrap_or(env.block.height);
+    let address = deps.api.addr_validate(&address)?;
+    let power = STAKED_BALANCES
+        .may_load_at_height(deps.storage, &address, height)?
+        .unwrap_or_default();
+    to_json_binary(&VotingPowerAtHeightResponse { power, height })
+}
+
+pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
+    let height = height.unwrap_or(env.block.height);
+    let power = STAKED_TOTAL
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+    to_json_binary(&TotalPowerAtHeightResponse { power, height })
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_json_binary(&InfoResponse { info })
+}
+
+pub fn query_list_stakers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    let stakers = STAKED_BALANCES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(address, balance)| StakerBalanceResponse {
+                address: address.into_string(),
+                balance,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    to_json_binary(&ListStakersResponse { stakers })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let storage_version: ContractVersion = get_contract_version(deps.storage)?;
+
+    // Only migrate if newer
+    if storage_version.version.as_str() < CONTRACT_VERSION {
+        // Set contract to version to latest
+        set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "migrate"))
+}
@@ -0,0 +1,20 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    HookError(#[from] cw_hooks::HookError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Must checkpoint at least one address")]
+    NoAddresses {},
+
+    #[error("May checkpoint at most {max} addresses at once")]
+    TooManyAddresses { max: usize },
+}
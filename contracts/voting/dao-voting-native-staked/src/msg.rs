@@ -0,0 +1,57 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+use dao_dao_macros::{native_token_query, voting_module_query};
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Updates the voting power of ADDRESSES to the amount of the
+    /// chain's bonding denom they currently have delegated. Voting
+    /// power only changes when an address is checkpointed, so this
+    /// should be called after delegating, undelegating, or being
+    /// slashed. Callable by anyone.
+    Checkpoint { addresses: Vec<String> },
+    /// Adds a hook which is called when a checkpoint changes an
+    /// address's voting power. Only callable by the DAO.
+    AddHook { addr: String },
+    /// Removes a hook which is called when a checkpoint changes an
+    /// address's voting power. Only callable by the DAO.
+    RemoveHook { addr: String },
+}
+
+#[native_token_query]
+#[voting_module_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Lists addresses with non-zero voting power as of their last
+    /// checkpoint.
+    #[returns(ListStakersResponse)]
+    ListStakers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(GetHooksResponse)]
+    GetHooks {},
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_serde]
+pub struct ListStakersResponse {
+    pub stakers: Vec<StakerBalanceResponse>,
+}
+
+#[cw_serde]
+pub struct StakerBalanceResponse {
+    pub address: String,
+    pub balance: Uint128,
+}
+
+#[cw_serde]
+pub struct GetHooksResponse {
+    pub hooks: Vec<String>,
+}
@@ -0,0 +1,33 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_hooks::Hooks;
+use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+
+/// The maximum number of addresses that may be checkpointed in one
+/// message. Each address requires a delegations query.
+pub const MAX_CHECKPOINT_ADDRESSES: usize = 30;
+
+/// The address of the DAO this voting contract is connected to
+pub const DAO: Item<Addr> = Item::new("dao");
+
+/// The chain's bonding denom, queried on instantiation
+pub const DENOM: Item<String> = Item::new("denom");
+
+/// Keeps track of checkpointed delegations by address over time
+pub const STAKED_BALANCES: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "staked_balances",
+    "staked_balance__checkpoints",
+    "staked_balance__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Keeps track of the checkpointed total over time
+pub const STAKED_TOTAL: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_staked",
+    "total_staked__checkpoints",
+    "total_staked__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Hooks to contracts that will receive staking and unstaking
+/// messages when a checkpoint changes an address's voting power
+pub const HOOKS: Hooks = Hooks::new("hooks");
@@ -0,0 +1,341 @@
+use cosmwasm_std::{
+    coin, testing::mock_env, Addr, CosmosMsg, Decimal, StakingMsg, Uint128, Validator,
+};
+use cw_multi_test::{next_block, App, AppBuilder, AppResponse, Executor, StakingInfo};
+use dao_interface::voting::{
+    DenomResponse, InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use dao_testing::contracts::{
+    dao_proposal_hook_counter_contract, dao_voting_native_staked_contract,
+};
+
+use crate::{
+    msg::{
+        ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, QueryMsg,
+        StakerBalanceResponse,
+    },
+    state::MAX_CHECKPOINT_ADDRESSES,
+    ContractError,
+};
+
+const DAO_ADDR: &str = "dao";
+const ADDR1: &str = "addr1";
+const ADDR2: &str = "addr2";
+const VALIDATOR1: &str = "validator1";
+const VALIDATOR2: &str = "validator2";
+const DENOM: &str = "ujuno";
+
+fn setup_test_case() -> (App, Addr) {
+    let mut app = AppBuilder::default().build(|router, api, storage| {
+        router
+            .staking
+            .setup(
+                storage,
+                StakingInfo {
+                    bonded_denom: DENOM.to_string(),
+                    unbonding_time: 60,
+                    apr: Decimal::percent(10),
+                },
+            )
+            .unwrap();
+        for validator in [VALIDATOR1, VALIDATOR2] {
+            router
+                .staking
+                .add_validator(
+                    api,
+                    storage,
+                    &mock_env().block,
+                    Validator {
+                        address: validator.to_string(),
+                        commission: Decimal::percent(1),
+                        max_commission: Decimal::percent(100),
+                        max_change_rate: Decimal::percent(1),
+                    },
+                )
+                .unwrap();
+        }
+        for addr in [ADDR1, ADDR2] {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked(addr), vec![coin(1000, DENOM)])
+                .unwrap();
+        }
+    });
+
+    let code_id = app.store_code(dao_voting_native_staked_contract());
+    let module = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {},
+            &[],
+            "native staked voting",
+            None,
+        )
+        .unwrap();
+
+    (app, module)
+}
+
+fn delegate(app: &mut App, delegator: &str, validator: &str, amount: u128) {
+    app.execute(
+        Addr::unchecked(delegator),
+        CosmosMsg::Staking(StakingMsg::Delegate {
+            validator: validator.to_string(),
+            amount: coin(amount, DENOM),
+        }),
+    )
+    .unwrap();
+}
+
+fn undelegate(app: &mut App, delegator: &str, validator: &str, amount: u128) {
+    app.execute(
+        Addr::unchecked(delegator),
+        CosmosMsg::Staking(StakingMsg::Undelegate {
+            validator: validator.to_string(),
+            amount: coin(amount, DENOM),
+        }),
+    )
+    .unwrap();
+}
+
+fn checkpoint(
+    app: &mut App,
+    module: &Addr,
+    sender: &str,
+    addresses: &[&str],
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        module.clone(),
+        &ExecuteMsg::Checkpoint {
+            addresses: addresses.iter().map(|a| a.to_string()).collect(),
+        },
+        &[],
+    )
+}
+
+fn voting_power(app: &App, module: &Addr, address: &str, height: Option<u64>) -> Uint128 {
+    let res: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            module,
+            &QueryMsg::VotingPowerAtHeight {
+                address: address.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    res.power
+}
+
+fn total_power(app: &App, module: &Addr, height: Option<u64>) -> Uint128 {
+    let res: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(module, &QueryMsg::TotalPowerAtHeight { height })
+        .unwrap();
+    res.power
+}
+
+#[test]
+fn test_instantiate() {
+    let (app, module) = setup_test_case();
+
+    let denom: DenomResponse = app
+        .wrap()
+        .query_wasm_smart(&module, &QueryMsg::Denom {})
+        .unwrap();
+    assert_eq!(denom.denom, DENOM);
+
+    let dao: Addr = app
+        .wrap()
+        .query_wasm_smart(&module, &QueryMsg::Dao {})
+        .unwrap();
+    assert_eq!(dao, Addr::unchecked(DAO_ADDR));
+
+    let info: InfoResponse = app
+        .wrap()
+        .query_wasm_smart(&module, &QueryMsg::Info {})
+        .unwrap();
+    assert_eq!(info.info.contract, "crates.io:dao-voting-native-staked");
+
+    assert_eq!(total_power(&app, &module, None), Uint128::zero());
+}
+
+#[test]
+fn test_checkpoint() {
+    let (mut app, module) = setup_test_case();
+
+    delegate(&mut app, ADDR1, VALIDATOR1, 100);
+    delegate(&mut app, ADDR1, VALIDATOR2, 50);
+    delegate(&mut app, ADDR2, VALIDATOR1, 200);
+
+    // Delegations have no voting power until checkpointed.
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &module, ADDR1, None), Uint128::zero());
+
+    // Anyone may checkpoint. Powers update one block later.
+    checkpoint(&mut app, &module, "anyone", &[ADDR1, ADDR2]).unwrap();
+    let checkpoint_height = app.block_info().height;
+    assert_eq!(voting_power(&app, &module, ADDR1, None), Uint128::zero());
+
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &module, ADDR1, None), Uint128::new(150));
+    assert_eq!(voting_power(&app, &module, ADDR2, None), Uint128::new(200));
+    assert_eq!(total_power(&app, &module, None), Uint128::new(350));
+
+    // Undelegating lowers power once checkpointed.
+    undelegate(&mut app, ADDR1, VALIDATOR2, 50);
+    checkpoint(&mut app, &module, ADDR2, &[ADDR1]).unwrap();
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &module, ADDR1, None), Uint128::new(100));
+    assert_eq!(total_power(&app, &module, None), Uint128::new(300));
+
+    // Undelegating everything removes the staker.
+    undelegate(&mut app, ADDR2, VALIDATOR1, 200);
+    checkpoint(&mut app, &module, ADDR2, &[ADDR2, ADDR2]).unwrap();
+    app.update_block(next_block);
+    assert_eq!(voting_power(&app, &module, ADDR2, None), Uint128::zero());
+    assert_eq!(total_power(&app, &module, None), Uint128::new(100));
+
+    let stakers: ListStakersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &module,
+            &QueryMsg::ListStakers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        stakers.stakers,
+        vec![StakerBalanceResponse {
+            address: ADDR1.to_string(),
+            balance: Uint128::new(100),
+        }]
+    );
+
+    // Historical queries are unaffected.
+    assert_eq!(
+        voting_power(&app, &module, ADDR1, Some(checkpoint_height)),
+        Uint128::zero()
+    );
+    assert_eq!(
+        voting_power(&app, &module, ADDR2, Some(checkpoint_height + 1)),
+        Uint128::new(200)
+    );
+    assert_eq!(
+        total_power(&app, &module, Some(checkpoint_height + 1)),
+        Uint128::new(350)
+    );
+}
+
+#[test]
+fn test_checkpoint_validation() {
+    let (mut app, module) = setup_test_case();
+
+    let err: ContractError = checkpoint(&mut app, &module, ADDR1, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoAddresses {});
+
+    let addresses = vec![ADDR1; MAX_CHECKPOINT_ADDRESSES + 1];
+    let err: ContractError = checkpoint(&mut app, &module, ADDR1, &addresses)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::TooManyAddresses {
+            max: MAX_CHECKPOINT_ADDRESSES
+        }
+    );
+}
+
+#[test]
+fn test_hooks() {
+    let (mut app, module) = setup_test_case();
+
+    let counter_id = app.store_code(dao_proposal_hook_counter_contract());
+    let counter = app
+        .instantiate_contract(
+            counter_id,
+            Addr::unchecked(DAO_ADDR),
+            &dao_proposal_hook_counter::msg::InstantiateMsg {
+                should_error: false,
+            },
+            &[],
+            "hook counter",
+            None,
+        )
+        .unwrap();
+
+    // Only the DAO may add hooks.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            module.clone(),
+            &ExecuteMsg::AddHook {
+                addr: counter.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        module.clone(),
+        &ExecuteMsg::AddHook {
+            addr: counter.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let hooks: GetHooksResponse = app
+        .wrap()
+        .query_wasm_smart(&module, &QueryMsg::GetHooks {})
+        .unwrap();
+    assert_eq!(hooks.hooks, vec![counter.to_string()]);
+
+    let stake_count = |app: &App| -> Uint128 {
+        app.wrap()
+            .query_wasm_smart(
+                &counter,
+                &dao_proposal_hook_counter::msg::QueryMsg::StakeCounter {},
+            )
+            .unwrap()
+    };
+
+    // A checkpoint that changes power fires a hook.
+    delegate(&mut app, ADDR1, VALIDATOR1, 100);
+    checkpoint(&mut app, &module, ADDR1, &[ADDR1]).unwrap();
+    assert_eq!(stake_count(&app), Uint128::new(1));
+
+    // A checkpoint that does not change power does not.
+    checkpoint(&mut app, &module, ADDR1, &[ADDR1]).unwrap();
+    assert_eq!(stake_count(&app), Uint128::new(1));
+
+    undelegate(&mut app, ADDR1, VALIDATOR1, 100);
+    checkpoint(&mut app, &module, ADDR1, &[ADDR1]).unwrap();
+    assert_eq!(stake_count(&app), Uint128::new(2));
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        module.clone(),
+        &ExecuteMsg::RemoveHook {
+            addr: counter.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let hooks: GetHooksResponse = app
+        .wrap()
+        .query_wasm_smart(&module, &QueryMsg::GetHooks {})
+        .unwrap();
+    assert!(hooks.hooks.is_empty());
+}
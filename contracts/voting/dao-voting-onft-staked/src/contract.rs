@@ -1,14 +1,14 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response,
-    StdResult, SubMsg, Uint128, Uint256,
+    from_json, to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
+    Reply, Response, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw_storage_plus::Bound;
-use cw_utils::Duration;
+use cw_utils::{one_coin, parse_reply_execute_data, Duration};
 use dao_hooks::nft_stake::{stake_nft_hook_msgs, unstake_nft_hook_msgs};
-use dao_interface::voting::IsActiveResponse;
+use dao_interface::{nft::NftFactoryCallback, voting::IsActiveResponse};
 use dao_voting::duration::validate_duration;
 use dao_voting::threshold::{
     assert_valid_absolute_count_threshold, assert_valid_percentage_threshold, ActiveThreshold,
@@ -16,7 +16,10 @@ use dao_voting::threshold::{
 };
 
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, OnftCollection, QueryMsg};
-use crate::omniflix::{get_onft_transfer_msg, query_onft_owner, query_onft_supply};
+use crate::omniflix::{
+    get_create_denom_msg, get_denom_transfer_msg, get_onft_mint_msg, get_onft_transfer_msg,
+    query_onft_owner, query_onft_supply,
+};
 use crate::state::{
     register_staked_nfts, register_unstaked_nfts, Config, ACTIVE_THRESHOLD, CONFIG, DAO, HOOKS,
     MAX_CLAIMS, NFT_BALANCES, NFT_CLAIMS, PREPARED_ONFTS, STAKED_NFTS_PER_OWNER, TOTAL_STAKED_NFTS,
@@ -30,6 +33,8 @@ pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 // when using active threshold with percent
 const PRECISION_FACTOR: u128 = 10u128.pow(9);
 
+const FACTORY_EXECUTE_REPLY_ID: u64 = 0;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -52,14 +57,23 @@ pub fn instantiate(
             }
             ActiveThreshold::AbsoluteCount { count } => {
                 // Check absolute count is less than the supply of NFTs for
-                // existing NFT collection.
-
-                let OnftCollection::Existing { ref id } = msg.onft_collection;
-                let nft_supply = query_onft_supply(deps.as_ref(), id)?;
+                // existing and new NFT collections. For factory collections, we
+                // will check this in the reply.
+                let nft_supply = match msg.onft_collection {
+                    OnftCollection::Existing { ref id } => {
+                        Some(query_onft_supply(deps.as_ref(), id)?)
+                    }
+                    OnftCollection::New {
+                        ref initial_nfts, ..
+                    } => Some(initial_nfts.len() as u64),
+                    OnftCollection::Factory(_) => None,
+                };
 
                 // Check the absolute count is less than the supply of NFTs and
                 // greater than zero.
-                assert_valid_absolute_count_threshold(*count, Uint128::new(nft_supply.into()))?;
+                if let Some(nft_supply) = nft_supply {
+                    assert_valid_absolute_count_threshold(*count, Uint128::new(nft_supply.into()))?;
+                }
             }
         }
         ACTIVE_THRESHOLD.save(deps.storage, active_threshold)?;
@@ -79,6 +93,93 @@ pub fn instantiate(
                 .add_attribute("method", "instantiate")
                 .add_attribute("onft_collection_id", id))
         }
+        OnftCollection::New {
+            id,
+            symbol,
+            name,
+            description,
+            preview_uri,
+            schema,
+            uri,
+            uri_hash,
+            data,
+            initial_nfts,
+        } => {
+            // Check there is at least one NFT to initialize
+            if initial_nfts.is_empty() {
+                return Err(ContractError::NoInitialNfts {});
+            }
+
+            // Funds sent on instantiation pay the denom creation fee.
+            let creation_fee = if info.funds.is_empty() {
+                None
+            } else {
+                Some(one_coin(&info)?)
+            };
+
+            let config = Config {
+                onft_collection_id: id.clone(),
+                unstaking_duration: msg.unstaking_duration,
+            };
+            CONFIG.save(deps.storage, &config)?;
+
+            // Create the collection with this contract as its creator so it
+            // can mint the initial NFTs, and then transfer it to the DAO.
+            let contract = env.contract.address.as_str();
+            let mut msgs = vec![get_create_denom_msg(
+                &id,
+                &symbol,
+                &name,
+                &description,
+                &preview_uri,
+                &schema,
+                &uri,
+                &uri_hash,
+                &data,
+                contract,
+                creation_fee,
+            )];
+            msgs.extend(
+                initial_nfts
+                    .into_iter()
+                    .map(|onft| get_onft_mint_msg(&id, onft, contract)),
+            );
+            msgs.push(get_denom_transfer_msg(&id, contract, info.sender.as_str()));
+
+            Ok(Response::default()
+                .add_attribute("method", "instantiate")
+                .add_attribute("onft_collection_id", id)
+                .add_messages(msgs))
+        }
+        OnftCollection::Factory(binary) => match from_json(binary)? {
+            WasmMsg::Execute {
+                msg: wasm_msg,
+                contract_addr,
+                funds,
+            } => {
+                // Save config with empty onft_collection_id
+                let config = Config {
+                    onft_collection_id: "".to_string(),
+                    unstaking_duration: msg.unstaking_duration,
+                };
+                CONFIG.save(deps.storage, &config)?;
+
+                // Call factory contract. Use only a trusted factory contract,
+                // as this is a critical security component and validation of
+                // setup will happen in the factory.
+                Ok(Response::new()
+                    .add_attribute("action", "instantiate")
+                    .add_submessage(SubMsg::reply_on_success(
+                        WasmMsg::Execute {
+                            contract_addr,
+                            msg: wasm_msg,
+                            funds,
+                        },
+                        FACTORY_EXECUTE_REPLY_ID,
+                    )))
+            }
+            _ => Err(ContractError::UnsupportedFactoryMsg {}),
+        },
     }
 }
 
@@ -700,3 +801,55 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
 
     Ok(Response::new().add_attribute("action", "migrate"))
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        FACTORY_EXECUTE_REPLY_ID => {
+            // Parse reply data
+            let res = parse_reply_execute_data(msg)?;
+            match res.data {
+                Some(data) => {
+                    let mut config = CONFIG.load(deps.storage)?;
+
+                    // Parse info from the callback, this will fail
+                    // if incorrectly formatted. The `nft_contract` field
+                    // contains the x/onft denom/collection ID.
+                    let info: NftFactoryCallback = from_json(data)?;
+
+                    // Validate that the collection exists with a supply query
+                    let nft_supply = query_onft_supply(deps.as_ref(), &info.nft_contract)?;
+
+                    // If Active Threshold absolute count is configured,
+                    // check the count is not greater than supply
+                    if let Some(ActiveThreshold::AbsoluteCount { count }) =
+                        ACTIVE_THRESHOLD.may_load(deps.storage)?
+                    {
+                        assert_valid_absolute_count_threshold(
+                            count,
+                            Uint128::new(nft_supply.into()),
+                        )?;
+                    }
+
+                    // Update ONFT collection
+                    config.onft_collection_id = info.nft_contract.clone();
+                    CONFIG.save(deps.storage, &config)?;
+
+                    // Construct the response
+                    let mut res =
+                        Response::new().add_attribute("onft_collection_id", info.nft_contract);
+
+                    // If a callback has been configured, set the module
+                    // instantiate callback data.
+                    if let Some(callback) = info.module_instantiate_callback {
+                        res = res.set_data(to_json_binary(&callback)?);
+                    }
+
+                    Ok(res)
+                }
+                None => Err(ContractError::NoFactoryCallback {}),
+            }
+        }
+        _ => Err(ContractError::UnknownReplyId { id: msg.id }),
+    }
+}
@@ -16,6 +16,18 @@ pub enum ContractError {
     #[error(transparent)]
     UnstakingDurationError(#[from] dao_voting::duration::UnstakingDurationError),
 
+    #[error(transparent)]
+    PaymentError(#[from] cw_utils::PaymentError),
+
+    #[error(transparent)]
+    ParseReplyError(#[from] cw_utils::ParseReplyError),
+
+    #[error("Factory contract did not implment the required NftFactoryCallback interface")]
+    NoFactoryCallback {},
+
+    #[error("New ONFT collections must have at least one initial NFT")]
+    NoInitialNfts {},
+
     #[error("Nothing to claim")]
     NothingToClaim {},
 
@@ -43,6 +55,9 @@ pub enum ContractError {
     #[error("Got a submessage reply with unknown id: {id}")]
     UnknownReplyId { id: u64 },
 
+    #[error("Factory message must serialize to WasmMsg::Execute")]
+    UnsupportedFactoryMsg {},
+
     #[error("Can't unstake zero NFTs.")]
     ZeroUnstake {},
 }
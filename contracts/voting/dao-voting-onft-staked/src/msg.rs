@@ -1,4 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Binary;
 use cw_utils::Duration;
 use dao_dao_macros::{active_query, voting_module_query};
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
@@ -11,6 +12,47 @@ pub enum OnftCollection {
         /// ID of an already created x/onft denom/collection.
         id: String,
     },
+    /// Creates a new x/onft denom/collection owned by the DAO and mints
+    /// initial ONFTs to members. Funds sent on instantiation (at most one
+    /// coin) are used to pay the denom creation fee.
+    New {
+        /// ID of the x/onft denom/collection to create.
+        id: String,
+        symbol: String,
+        name: String,
+        description: String,
+        preview_uri: String,
+        schema: String,
+        uri: String,
+        uri_hash: String,
+        data: String,
+        /// Initial ONFTs to mint when creating the collection. If empty, an
+        /// error is thrown.
+        initial_nfts: Vec<InitialOnft>,
+    },
+    /// Uses a factory contract that must return the ID of the x/onft
+    /// denom/collection in the `nft_contract` field of an
+    /// `NftFactoryCallback`. The binary must serialize to a
+    /// `WasmMsg::Execute` message. Validation happens in the factory contract
+    /// itself, so be sure to use a trusted factory contract.
+    Factory(Binary),
+}
+
+#[cw_serde]
+pub struct InitialOnft {
+    /// ID of the ONFT within the collection.
+    pub id: String,
+    /// Address that receives the ONFT.
+    pub owner: String,
+    pub name: String,
+    pub description: String,
+    pub media_uri: String,
+    pub preview_uri: String,
+    pub uri_hash: String,
+    pub data: String,
+    pub transferable: bool,
+    pub extensible: bool,
+    pub nsfw: bool,
 }
 
 #[cw_serde]
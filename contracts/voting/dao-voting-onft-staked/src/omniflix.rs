@@ -1,5 +1,10 @@
-use cosmwasm_std::{CosmosMsg, Deps, StdError, StdResult};
-use omniflix_std::types::omniflix::onft::v1beta1::{MsgTransferOnft, OnftQuerier};
+use cosmwasm_std::{Coin, CosmosMsg, Deps, StdError, StdResult};
+use omniflix_std::types::cosmos::base::v1beta1::Coin as ProtoCoin;
+use omniflix_std::types::omniflix::onft::v1beta1::{
+    Metadata, MsgCreateDenom, MsgMintOnft, MsgTransferDenom, MsgTransferOnft, OnftQuerier,
+};
+
+use crate::msg::InitialOnft;
 
 pub fn query_onft_owner(deps: Deps, denom_id: &str, token_id: &str) -> StdResult<String> {
     let res = OnftQuerier::new(&deps.querier).onft(denom_id.to_string(), token_id.to_string())?;
@@ -30,3 +35,68 @@ pub fn get_onft_transfer_msg(
     }
     .into()
 }
+
+#[allow(clippy::too_many_arguments)]
+pub fn get_create_denom_msg(
+    id: &str,
+    symbol: &str,
+    name: &str,
+    description: &str,
+    preview_uri: &str,
+    schema: &str,
+    uri: &str,
+    uri_hash: &str,
+    data: &str,
+    sender: &str,
+    creation_fee: Option<Coin>,
+) -> CosmosMsg {
+    MsgCreateDenom {
+        id: id.to_string(),
+        symbol: symbol.to_string(),
+        name: name.to_string(),
+        description: description.to_string(),
+        preview_uri: preview_uri.to_string(),
+        schema: schema.to_string(),
+        sender: sender.to_string(),
+        creation_fee: creation_fee.map(|fee| ProtoCoin {
+            denom: fee.denom,
+            amount: fee.amount.to_string(),
+        }),
+        uri: uri.to_string(),
+        uri_hash: uri_hash.to_string(),
+        data: data.to_string(),
+        royalty_receivers: vec![],
+    }
+    .into()
+}
+
+pub fn get_onft_mint_msg(denom_id: &str, onft: InitialOnft, sender: &str) -> CosmosMsg {
+    MsgMintOnft {
+        id: onft.id,
+        denom_id: denom_id.to_string(),
+        metadata: Some(Metadata {
+            name: onft.name,
+            description: onft.description,
+            media_uri: onft.media_uri,
+            uri_hash: onft.uri_hash,
+            preview_uri: onft.preview_uri,
+        }),
+        data: onft.data,
+        transferable: onft.transferable,
+        extensible: onft.extensible,
+        nsfw: onft.nsfw,
+        royalty_share: "0".to_string(),
+        sender: sender.to_string(),
+        recipient: onft.owner,
+    }
+    .into()
+}
+
+pub fn get_denom_transfer_msg(denom_id: &str, sender: &str, recipient: &str) -> CosmosMsg {
+    MsgTransferDenom {
+        id: denom_id.to_string(),
+        sender: sender.to_string(),
+        recipient: recipient.to_string(),
+    }
+    .into()
+}
@@ -3,7 +3,8 @@ use cosmwasm_std::{from_json, to_json_binary, Addr, Api, Binary, BlockInfo, Quer
 use cw_multi_test::{error::AnyResult, AppResponse, CosmosRouter, Stargate};
 use omniflix_std::types::omniflix::onft::v1beta1::{
     Collection, Denom, MsgCreateDenom, MsgCreateDenomResponse, MsgMintOnft, MsgMintOnftResponse,
-    MsgTransferOnft, MsgTransferOnftResponse, QuerySupplyRequest, QuerySupplyResponse,
+    MsgTransferDenom, MsgTransferDenomResponse, MsgTransferOnft, MsgTransferOnftResponse,
+    QueryDenomRequest, QueryDenomResponse, QuerySupplyRequest, QuerySupplyResponse,
 };
 use omniflix_std::types::omniflix::onft::v1beta1::{Onft, QueryOnftRequest, QueryOnftResponse};
 use prost::{DecodeError, Message};
@@ -101,6 +102,26 @@ impl Stargate for StargateKeeper {
                 });
             };
         }
+        if type_url == *"/OmniFlix.onft.v1beta1.MsgTransferDenom" {
+            let msg: MsgTransferDenom = Message::decode(value.as_slice()).unwrap();
+            let key = format!("collections:{}:{}", COLLECTION_PREFIX, msg.id);
+            let serialized_collection = storage.get(key.as_bytes());
+            let mut collection: Collection = from_json(serialized_collection.unwrap())
+                .expect("Failed to deserialize Collection");
+            let denom = collection.denom.as_mut().unwrap();
+            if denom.creator != sender.as_str() {
+                anyhow::bail!("only the denom creator can transfer the denom");
+            }
+            denom.creator = msg.recipient;
+            let serialized_collection =
+                to_json_binary(&collection).expect("Failed to serialize Collection");
+            storage.set(key.as_bytes(), &serialized_collection);
+
+            return Ok(AppResponse {
+                events: vec![],
+                data: Some(Binary::from(MsgTransferDenomResponse {})),
+            });
+        }
         Ok(AppResponse::default())
     }
 
@@ -127,6 +148,18 @@ impl Stargate for StargateKeeper {
 
             return Ok(to_json_binary(&QueryOnftResponse { onft })?);
         }
+        if path == *"/OmniFlix.onft.v1beta1.Query/Denom" {
+            let request: QueryDenomRequest = Message::decode(data.as_slice()).unwrap();
+
+            let key = format!("collections:{}:{}", COLLECTION_PREFIX, request.denom_id);
+            let serialized_collection = storage.get(key.as_bytes());
+            let collection: Collection = from_json(serialized_collection.unwrap())
+                .expect("Failed to deserialize Collection");
+
+            return Ok(to_json_binary(&QueryDenomResponse {
+                denom: collection.denom,
+            })?);
+        }
         if path == *"/OmniFlix.onft.v1beta1.Query/Supply" {
             let request: QuerySupplyRequest = Message::decode(data.as_slice()).unwrap();
 
@@ -4,7 +4,9 @@ use cw_controllers::HooksResponse;
 use dao_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
-use omniflix_std::types::omniflix::onft::v1beta1::{QueryOnftRequest, QueryOnftResponse};
+use omniflix_std::types::omniflix::onft::v1beta1::{
+    QueryDenomRequest, QueryDenomResponse, QueryOnftRequest, QueryOnftResponse,
+};
 
 use crate::{msg::QueryMsg, state::Config};
 
@@ -114,3 +116,16 @@ pub fn query_nft_owner(
         .unwrap();
     Ok(response.onft.unwrap().owner)
 }
+
+pub fn query_collection_creator(app: &OmniflixApp, collection_id: &str) -> StdResult<String> {
+    let response: QueryDenomResponse = app
+        .wrap()
+        .query(
+            &QueryDenomRequest {
+                denom_id: collection_id.to_string(),
+            }
+            .into(),
+        )
+        .unwrap();
+    Ok(response.denom.unwrap().creator)
+}
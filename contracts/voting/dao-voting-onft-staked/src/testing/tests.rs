@@ -1,12 +1,14 @@
 use cosmwasm_std::testing::{mock_dependencies, mock_env};
-use cosmwasm_std::{Addr, Decimal, Uint128};
+use cosmwasm_std::{to_json_binary, Addr, Decimal, Uint128, WasmMsg};
 use cw721_controllers::{NftClaim, NftClaimsResponse};
 use cw_multi_test::{next_block, Executor};
 use cw_utils::Duration;
 use dao_interface::voting::IsActiveResponse;
+use dao_test_custom_factory::msg::OnftFactoryNft;
+use dao_testing::contracts::dao_test_custom_factory_contract;
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 
-use crate::msg::OnftCollection;
+use crate::msg::{InitialOnft, OnftCollection};
 use crate::testing::execute::{cancel_stake, confirm_stake_nft, prepare_stake_nft, send_nft};
 use crate::testing::queries::query_dao;
 use crate::testing::DAO;
@@ -18,7 +20,10 @@ use crate::{
         execute::{
             claim_nfts, mint_and_stake_nft, mint_nft, stake_nft, unstake_nfts, update_config,
         },
-        queries::{query_config, query_hooks, query_nft_owner, query_total_and_voting_power},
+        queries::{
+            query_collection_creator, query_config, query_hooks, query_nft_owner,
+            query_total_and_voting_power,
+        },
     },
 };
 
@@ -1205,3 +1210,224 @@ fn test_dao_cancel_stake_must_have_recipient() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn initial_onft(id: &str, owner: &str) -> InitialOnft {
+    InitialOnft {
+        id: id.to_string(),
+        owner: owner.to_string(),
+        name: format!("ONFT {id}"),
+        description: "".to_string(),
+        media_uri: "".to_string(),
+        preview_uri: "".to_string(),
+        uri_hash: "".to_string(),
+        data: "".to_string(),
+        transferable: true,
+        extensible: false,
+        nsfw: false,
+    }
+}
+
+fn new_onft_collection(id: &str, initial_nfts: Vec<InitialOnft>) -> OnftCollection {
+    OnftCollection::New {
+        id: id.to_string(),
+        symbol: "NEW".to_string(),
+        name: "New Collection".to_string(),
+        description: "".to_string(),
+        preview_uri: "".to_string(),
+        schema: "".to_string(),
+        uri: "".to_string(),
+        uri_hash: "".to_string(),
+        data: "".to_string(),
+        initial_nfts,
+    }
+}
+
+/// Creating a new collection mints the initial NFTs to members and
+/// transfers the collection to the DAO.
+#[test]
+fn test_new_collection() -> anyhow::Result<()> {
+    let CommonTest {
+        mut app, module_id, ..
+    } = setup_test(None, None);
+
+    let module = app.instantiate_contract(
+        module_id,
+        Addr::unchecked(DAO),
+        &InstantiateMsg {
+            onft_collection: new_onft_collection(
+                "new",
+                vec![initial_onft("1", STAKER), initial_onft("2", "other")],
+            ),
+            unstaking_duration: None,
+            active_threshold: Some(ActiveThreshold::AbsoluteCount {
+                count: Uint128::new(2),
+            }),
+        },
+        &[],
+        "onft_voting",
+        None,
+    )?;
+
+    let config = query_config(&app, &module)?;
+    assert_eq!(config.onft_collection_id, "new");
+
+    assert_eq!(query_nft_owner(&app, "new", "1")?, STAKER);
+    assert_eq!(query_nft_owner(&app, "new", "2")?, "other");
+    assert_eq!(query_collection_creator(&app, "new")?, DAO);
+
+    // Members can stake their initial NFTs.
+    stake_nft(&mut app, "new", &module, STAKER, "1")?;
+    app.update_block(next_block);
+    let (total, personal) = query_total_and_voting_power(&app, &module, STAKER, None)?;
+    assert_eq!(total, Uint128::new(1));
+    assert_eq!(personal, Uint128::new(1));
+
+    Ok(())
+}
+
+#[test]
+fn test_new_collection_validation() {
+    let CommonTest {
+        mut app, module_id, ..
+    } = setup_test(None, None);
+
+    // No initial NFTs.
+    let res = app.instantiate_contract(
+        module_id,
+        Addr::unchecked(DAO),
+        &InstantiateMsg {
+            onft_collection: new_onft_collection("new", vec![]),
+            unstaking_duration: None,
+            active_threshold: None,
+        },
+        &[],
+        "onft_voting",
+        None,
+    );
+    is_error!(res => "New ONFT collections must have at least one initial NFT");
+
+    // Absolute count greater than the initial supply.
+    let res = app.instantiate_contract(
+        module_id,
+        Addr::unchecked(DAO),
+        &InstantiateMsg {
+            onft_collection: new_onft_collection("new", vec![initial_onft("1", STAKER)]),
+            unstaking_duration: None,
+            active_threshold: Some(ActiveThreshold::AbsoluteCount {
+                count: Uint128::new(2),
+            }),
+        },
+        &[],
+        "onft_voting",
+        None,
+    );
+    is_error!(res => "Absolute count threshold cannot be greater than the total token supply");
+}
+
+#[test]
+fn test_factory() -> anyhow::Result<()> {
+    let CommonTest {
+        mut app, module_id, ..
+    } = setup_test(None, None);
+
+    let factory_id = app.store_code(dao_test_custom_factory_contract());
+    let factory_addr = app.instantiate_contract(
+        factory_id,
+        Addr::unchecked(DAO),
+        &dao_test_custom_factory::msg::InstantiateMsg {},
+        &[],
+        "test factory".to_string(),
+        None,
+    )?;
+
+    let factory_msg = |msg: &dao_test_custom_factory::msg::ExecuteMsg| {
+        OnftCollection::Factory(
+            to_json_binary(&WasmMsg::Execute {
+                contract_addr: factory_addr.to_string(),
+                msg: to_json_binary(msg).unwrap(),
+                funds: vec![],
+            })
+            .unwrap(),
+        )
+    };
+
+    // Instantiate using factory succeeds
+    let module = app.instantiate_contract(
+        module_id,
+        Addr::unchecked(DAO),
+        &InstantiateMsg {
+            onft_collection: factory_msg(&dao_test_custom_factory::msg::ExecuteMsg::OnftFactory {
+                id: "factory".to_string(),
+                initial_nfts: vec![OnftFactoryNft {
+                    token_id: "1".to_string(),
+                    owner: STAKER.to_string(),
+                }],
+            }),
+            unstaking_duration: None,
+            active_threshold: Some(ActiveThreshold::AbsoluteCount {
+                count: Uint128::new(1),
+            }),
+        },
+        &[],
+        "onft_voting",
+        None,
+    )?;
+
+    let config = query_config(&app, &module)?;
+    assert_eq!(config.onft_collection_id, "factory");
+    assert_eq!(query_nft_owner(&app, "factory", "1")?, STAKER);
+    assert_eq!(query_collection_creator(&app, "factory")?, DAO);
+
+    // Factory without a callback fails.
+    let res = app.instantiate_contract(
+        module_id,
+        Addr::unchecked(DAO),
+        &InstantiateMsg {
+            onft_collection: factory_msg(
+                &dao_test_custom_factory::msg::ExecuteMsg::NftFactoryNoCallback {},
+            ),
+            unstaking_duration: None,
+            active_threshold: None,
+        },
+        &[],
+        "onft_voting",
+        None,
+    );
+    is_error!(res => "Missing reply data");
+
+    // Factory with the wrong callback fails.
+    let res = app.instantiate_contract(
+        module_id,
+        Addr::unchecked(DAO),
+        &InstantiateMsg {
+            onft_collection: factory_msg(
+                &dao_test_custom_factory::msg::ExecuteMsg::NftFactoryWrongCallback {},
+            ),
+            unstaking_duration: None,
+            active_threshold: None,
+        },
+        &[],
+        "onft_voting",
+        None,
+    );
+    is_error!(res => "Error parsing into type dao_interface::nft::NftFactoryCallback");
+
+    // Factory messages must be `WasmMsg::Execute`.
+    let res = app.instantiate_contract(
+        module_id,
+        Addr::unchecked(DAO),
+        &InstantiateMsg {
+            onft_collection: OnftCollection::Factory(to_json_binary(&WasmMsg::ClearAdmin {
+                contract_addr: factory_addr.to_string(),
+            })?),
+            unstaking_duration: None,
+            active_threshold: None,
+        },
+        &[],
+        "onft_voting",
+        None,
+    );
+    is_error!(res => "Factory message must serialize to WasmMsg::Execute");
+
+    Ok(())
+}
@@ -2,14 +2,16 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    coins, from_json, to_json_binary, BankMsg, BankQuery, Binary, Coin, CosmosMsg, Deps, DepsMut,
-    Env, MessageInfo, Order, Reply, Response, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
+    coins, from_json, to_json_binary, Addr, BankMsg, BankQuery, Binary, BlockInfo, Coin, CosmosMsg,
+    Decimal, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage,
+    SubMsg, Timestamp, Uint128, Uint256, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw_controllers::ClaimsResponse;
 use cw_storage_plus::Bound;
 use cw_tokenfactory_issuer::msg::{
-    ExecuteMsg as IssuerExecuteMsg, InstantiateMsg as IssuerInstantiateMsg,
+    DenomResponse as IssuerDenomResponse, ExecuteMsg as IssuerExecuteMsg,
+    InstantiateMsg as IssuerInstantiateMsg, QueryMsg as IssuerQueryMsg,
 };
 
 #[cfg(any(feature = "osmosis_tokenfactory", feature = "cosmwasm_tokenfactory"))]
@@ -28,6 +30,8 @@ use dao_interface::{
 };
 use dao_voting::{
     duration::validate_duration,
+    early_unstake::{EarlyUnstakeConfig, EarlyUnstakeUpdate, PenaltyDestination},
+    power_cap::{PowerCap, PowerCapResponse},
     threshold::{
         assert_valid_absolute_count_threshold, assert_valid_percentage_threshold, ActiveThreshold,
         ActiveThresholdResponse,
@@ -36,12 +40,15 @@ use dao_voting::{
 
 use crate::error::ContractError;
 use crate::msg::{
-    ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, MigrateMsg, QueryMsg,
-    StakerBalanceResponse, TokenInfo,
+    ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, LocksResponse, MigrateMsg,
+    QueryMsg, ReceiptTokenResponse, StakerBalanceResponse, SudoMsg, TokenInfo,
 };
 use crate::state::{
-    Config, ACTIVE_THRESHOLD, CLAIMS, CONFIG, DAO, DENOM, HOOKS, MAX_CLAIMS, STAKED_BALANCES,
-    STAKED_TOTAL, TOKEN_INSTANTIATION_INFO, TOKEN_ISSUER_CONTRACT,
+    BonusCurve, BonusTotal, Config, Lock, LockupConfig, LockupTier, ACTIVE_THRESHOLD,
+    BONUS_EXPIRATIONS, BONUS_TOTAL, CLAIMS, CLAIM_DURATIONS, CONFIG, DAO, DENOM, HOOKS, LOCKS,
+    LOCKUP_CONFIG, MAX_CLAIMS, MAX_LOCKS, NEXT_LOCK_ID, POWER_CAPS, RECEIPT_DENOM,
+    RECEIPT_ISSUER_CONTRACT, STAKED_BALANCES, STAKED_TOTAL, TOKEN_INSTANTIATION_INFO,
+    TOKEN_ISSUER_CONTRACT,
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-voting-token-staked";
@@ -53,6 +60,7 @@ const DEFAULT_LIMIT: u32 = 10;
 
 const INSTANTIATE_TOKEN_FACTORY_ISSUER_REPLY_ID: u64 = 0;
 const FACTORY_EXECUTE_REPLY_ID: u64 = 2;
+const INSTANTIATE_RECEIPT_ISSUER_REPLY_ID: u64 = 3;
 
 // We multiply by this when calculating needed power for being active
 // when using active threshold with percent
@@ -71,6 +79,8 @@ pub fn instantiate(
 
     let config = Config {
         unstaking_duration: msg.unstaking_duration,
+        stake_transfers_enabled: false,
+        early_unstake: None,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -87,7 +97,7 @@ pub fn instantiate(
         ACTIVE_THRESHOLD.save(deps.storage, active_threshold)?;
     }
 
-    match msg.token_info {
+    let res = match msg.token_info {
         TokenInfo::Existing { denom } => {
             // Validate active threshold absolute count if configured
             if let Some(ActiveThreshold::AbsoluteCount { count }) = msg.active_threshold {
@@ -97,10 +107,10 @@ pub fn instantiate(
 
             DENOM.save(deps.storage, &denom)?;
 
-            Ok(Response::new()
+            Response::new()
                 .add_attribute("action", "instantiate")
                 .add_attribute("token", "existing_token")
-                .add_attribute("denom", denom))
+                .add_attribute("denom", denom)
         }
         TokenInfo::New(ref token) => {
             let NewTokenInfo {
@@ -127,10 +137,10 @@ pub fn instantiate(
                 INSTANTIATE_TOKEN_FACTORY_ISSUER_REPLY_ID,
             );
 
-            Ok(Response::new()
+            Response::new()
                 .add_attribute("action", "instantiate")
                 .add_attribute("token", "new_token")
-                .add_submessage(issuer_instantiate_msg))
+                .add_submessage(issuer_instantiate_msg)
         }
         TokenInfo::Factory(binary) => match from_json(binary)? {
             WasmMsg::Execute {
@@ -141,7 +151,7 @@ pub fn instantiate(
                 // Call factory contract. Use only a trusted factory contract,
                 // as this is a critical security component and valdiation of
                 // setup will happen in the factory.
-                Ok(Response::new()
+                Response::new()
                     .add_attribute("action", "intantiate")
                     .add_attribute("token", "custom_factory")
                     .add_submessage(SubMsg::reply_on_success(
@@ -151,10 +161,40 @@ pub fn instantiate(
                             funds,
                         },
                         FACTORY_EXECUTE_REPLY_ID,
-                    )))
+                    ))
             }
-            _ => Err(ContractError::UnsupportedFactoryMsg {}),
+            _ => return Err(ContractError::UnsupportedFactoryMsg {}),
         },
+    };
+
+    match msg.receipt_token {
+        Some(receipt_token) => {
+            // Receipt tokens rely on the receipt denom's before send
+            // hook to move voting power when they are transferred.
+            if !cfg!(feature = "osmosis_tokenfactory") {
+                return Err(ContractError::ReceiptTokenUnsupported {});
+            }
+
+            // Instantiate a cw-tokenfactory-issuer contract for the
+            // receipt token. This contract remains its owner so that
+            // it may mint and burn receipts. DAO (sender) is set as
+            // contract admin.
+            let receipt_issuer_instantiate_msg = SubMsg::reply_on_success(
+                WasmMsg::Instantiate {
+                    admin: Some(info.sender.to_string()),
+                    code_id: receipt_token.token_issuer_code_id,
+                    msg: to_json_binary(&IssuerInstantiateMsg::NewToken {
+                        subdenom: receipt_token.subdenom,
+                    })?,
+                    funds: vec![],
+                    label: "cw-tokenfactory-issuer (receipt)".to_string(),
+                },
+                INSTANTIATE_RECEIPT_ISSUER_REPLY_ID,
+            );
+
+            Ok(res.add_submessage(receipt_issuer_instantiate_msg))
+        }
+        None => Ok(res),
     }
 }
 
@@ -166,29 +206,57 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Stake {} => execute_stake(deps, env, info),
+        ExecuteMsg::Stake {} => {
+            let staker = info.sender.clone();
+            execute_stake(deps, env, info, staker)
+        }
+        ExecuteMsg::StakeFor { recipient } => {
+            let recipient = deps.api.addr_validate(&recipient)?;
+            execute_stake(deps, env, info, recipient)
+        }
+        ExecuteMsg::TransferStake { to, amount } => {
+            execute_transfer_stake(deps, env, info, to, amount)
+        }
         ExecuteMsg::Unstake { amount } => execute_unstake(deps, env, info, amount),
-        ExecuteMsg::UpdateConfig { duration } => execute_update_config(deps, info, duration),
+        ExecuteMsg::InstantUnstake { amount } => execute_instant_unstake(deps, env, info, amount),
+        ExecuteMsg::UpdateConfig {
+            duration,
+            stake_transfers_enabled,
+            early_unstake,
+        } => execute_update_config(deps, info, duration, stake_transfers_enabled, early_unstake),
         ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::AccelerateClaim {} => execute_accelerate_claim(deps, env, info),
         ExecuteMsg::UpdateActiveThreshold { new_threshold } => {
             execute_update_active_threshold(deps, env, info, new_threshold)
         }
         ExecuteMsg::AddHook { addr } => execute_add_hook(deps, env, info, addr),
         ExecuteMsg::RemoveHook { addr } => execute_remove_hook(deps, env, info, addr),
+        ExecuteMsg::Lock { amount, duration } => execute_lock(deps, env, info, amount, duration),
+        ExecuteMsg::RefreshLocks { address } => execute_refresh_locks(deps, env, address),
+        ExecuteMsg::UpdateLockupConfig { tiers, decay } => {
+            execute_update_lockup_config(deps, info, tiers, decay)
+        }
+        ExecuteMsg::UpdateMaxPowerPerAddress {
+            max_power_per_address,
+        } => execute_update_max_power_per_address(deps, info, max_power_per_address),
+        ExecuteMsg::RefreshPowerCap { address } => execute_refresh_power_cap(deps, env, address),
     }
 }
 
+/// Stakes the tokens sent with INFO for STAKER, who is the sender
+/// unless staking on another address's behalf.
 pub fn execute_stake(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    staker: Addr,
 ) -> Result<Response, ContractError> {
     let denom = DENOM.load(deps.storage)?;
     let amount = must_pay(&info, &denom)?;
 
     STAKED_BALANCES.update(
         deps.storage,
-        &info.sender,
+        &staker,
         env.block.height,
         |balance| -> StdResult<Uint128> { Ok(balance.unwrap_or_default().checked_add(amount)?) },
     )?;
@@ -197,31 +265,91 @@ pub fn execute_stake(
         env.block.height,
         |total| -> StdResult<Uint128> { Ok(total.unwrap_or_default().checked_add(amount)?) },
     )?;
+    apply_power_cap(deps.storage, env.block.height, &staker)?;
 
     // Add stake hook messages
-    let hook_msgs = stake_hook_msgs(HOOKS, deps.storage, info.sender.clone(), amount)?;
+    let hook_msgs = stake_hook_msgs(HOOKS, deps.storage, staker.clone(), amount)?;
+
+    // Issue receipt tokens for the stake if enabled
+    let receipt_msgs = match RECEIPT_ISSUER_CONTRACT.may_load(deps.storage)? {
+        Some(issuer) => vec![WasmMsg::Execute {
+            contract_addr: issuer.into_string(),
+            msg: to_json_binary(&IssuerExecuteMsg::Mint {
+                to_address: staker.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }],
+        None => vec![],
+    };
 
-    Ok(Response::new()
+    let res = Response::new()
+        .add_messages(receipt_msgs)
         .add_submessages(hook_msgs)
         .add_attribute("action", "stake")
         .add_attribute("amount", amount.to_string())
-        .add_attribute("from", info.sender))
+        .add_attribute("from", info.sender.as_str());
+    if staker == info.sender {
+        Ok(res)
+    } else {
+        Ok(res.add_attribute("recipient", staker))
+    }
 }
 
-pub fn execute_unstake(
+pub fn execute_transfer_stake(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    to: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.stake_transfers_enabled {
+        return Err(ContractError::StakeTransfersDisabled {});
+    }
+    // Receipt holders would no longer hold receipts for their stake.
+    if RECEIPT_DENOM.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::ReceiptTokenTransfer {});
+    }
     if amount.is_zero() {
-        return Err(ContractError::ZeroUnstake {});
+        return Err(ContractError::ZeroTransfer {});
+    }
+    let to = deps.api.addr_validate(&to)?;
+    if to == info.sender {
+        return Err(ContractError::SelfTransfer {});
+    }
+
+    let hook_msgs = move_stake(deps.storage, &env.block, &info.sender, &to, amount)?;
+
+    Ok(Response::new()
+        .add_submessages(hook_msgs)
+        .add_attribute("action", "transfer_stake")
+        .add_attribute("from", info.sender)
+        .add_attribute("to", to)
+        .add_attribute("amount", amount))
+}
+
+/// Moves AMOUNT of FROM's unlocked stake to TO, returning the hook
+/// messages for the move. As far as hooks are concerned, FROM unstakes
+/// and TO stakes.
+fn move_stake(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> Result<Vec<SubMsg>, ContractError> {
+    // Locked stake may not be moved.
+    let locked = locked_amount(&prune_locks(storage, block.height, from)?)?;
+    let staked = STAKED_BALANCES.may_load(storage, from)?.unwrap_or_default();
+    if amount <= staked && staked - amount < locked {
+        return Err(ContractError::LockedStake { locked });
     }
 
     STAKED_BALANCES.update(
-        deps.storage,
-        &info.sender,
-        env.block.height,
+        storage,
+        from,
+        block.height,
         |balance| -> Result<Uint128, ContractError> {
             balance
                 .unwrap_or_default()
@@ -229,19 +357,24 @@ pub fn execute_unstake(
                 .map_err(|_e| ContractError::InvalidUnstakeAmount {})
         },
     )?;
-    STAKED_TOTAL.update(
-        deps.storage,
-        env.block.height,
-        |total| -> Result<Uint128, ContractError> {
-            total
-                .unwrap_or_default()
-                .checked_sub(amount)
-                .map_err(|_e| ContractError::InvalidUnstakeAmount {})
-        },
-    )?;
+    STAKED_BALANCES.update(storage, to, block.height, |balance| -> StdResult<Uint128> {
+        Ok(balance.unwrap_or_default().checked_add(amount)?)
+    })?;
+    apply_power_cap(storage, block.height, from)?;
+    apply_power_cap(storage, block.height, to)?;
+
+    let mut hook_msgs = unstake_hook_msgs(HOOKS, storage, from.clone(), amount)?;
+    hook_msgs.extend(stake_hook_msgs(HOOKS, storage, to.clone(), amount)?);
+    Ok(hook_msgs)
+}
 
-    // Add unstake hook messages
-    let hook_msgs = unstake_hook_msgs(HOOKS, deps.storage, info.sender.clone(), amount)?;
+pub fn execute_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let (receipt_msgs, hook_msgs) = remove_stake(deps.storage, &env, &info, amount)?;
 
     let config = CONFIG.load(deps.storage)?;
     let denom = DENOM.load(deps.storage)?;
@@ -253,6 +386,7 @@ pub fn execute_unstake(
             });
             Ok(Response::new()
                 .add_message(msg)
+                .add_messages(receipt_msgs)
                 .add_submessages(hook_msgs)
                 .add_attribute("action", "unstake")
                 .add_attribute("from", info.sender)
@@ -265,13 +399,11 @@ pub fn execute_unstake(
                 return Err(ContractError::TooManyClaims {});
             }
 
-            CLAIMS.create_claim(
-                deps.storage,
-                &info.sender,
-                amount,
-                duration.after(&env.block),
-            )?;
+            let release_at = duration.after(&env.block);
+            CLAIMS.create_claim(deps.storage, &info.sender, amount, release_at)?;
+            CLAIM_DURATIONS.save(deps.storage, &info.sender, &release_at, duration)?;
             Ok(Response::new()
+                .add_messages(receipt_msgs)
                 .add_submessages(hook_msgs)
                 .add_attribute("action", "unstake")
                 .add_attribute("from", info.sender)
@@ -281,10 +413,153 @@ pub fn execute_unstake(
     }
 }
 
+/// Unstakes AMOUNT immediately, paying the early unstake penalty
+/// instead of waiting for the unstaking duration.
+pub fn execute_instant_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let early_unstake = config
+        .early_unstake
+        .ok_or(ContractError::EarlyUnstakeDisabled {})?;
+
+    let (receipt_msgs, hook_msgs) = remove_stake(deps.storage, &env, &info, amount)?;
+
+    // Without an unstaking duration there is nothing to skip.
+    let penalty = match config.unstaking_duration {
+        Some(_) => early_unstake.unstake_penalty(amount),
+        None => Uint128::zero(),
+    };
+    let denom = DENOM.load(deps.storage)?;
+    let payout_msgs = payout_msgs(
+        deps.as_ref(),
+        &early_unstake,
+        &denom,
+        &info.sender,
+        amount,
+        penalty,
+    )?;
+
+    Ok(Response::new()
+        .add_messages(payout_msgs)
+        .add_messages(receipt_msgs)
+        .add_submessages(hook_msgs)
+        .add_attribute("action", "instant_unstake")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("penalty", penalty))
+}
+
+/// Removes AMOUNT of the sender's unlocked stake, returning the
+/// messages that burn its receipts and the unstake hook messages.
+fn remove_stake(
+    storage: &mut dyn Storage,
+    env: &Env,
+    info: &MessageInfo,
+    amount: Uint128,
+) -> Result<(Vec<WasmMsg>, Vec<SubMsg>), ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::ZeroUnstake {});
+    }
+
+    // If receipt tokens are enabled, the receipts for the unstaked
+    // tokens must be returned so they can be burned.
+    let receipt_msgs = match RECEIPT_ISSUER_CONTRACT.may_load(storage)? {
+        Some(issuer) => {
+            let receipt_denom = RECEIPT_DENOM.load(storage)?;
+            if must_pay(info, &receipt_denom)? != amount {
+                return Err(ContractError::InvalidReceiptAmount { expected: amount });
+            }
+            vec![WasmMsg::Execute {
+                contract_addr: issuer.into_string(),
+                msg: to_json_binary(&IssuerExecuteMsg::Burn {
+                    from_address: env.contract.address.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            }]
+        }
+        None => vec![],
+    };
+
+    // Locked tokens may not be unstaked.
+    let locked = locked_amount(&prune_locks(storage, env.block.height, &info.sender)?)?;
+    let staked = STAKED_BALANCES
+        .may_load(storage, &info.sender)?
+        .unwrap_or_default();
+    if amount <= staked && staked - amount < locked {
+        return Err(ContractError::LockedStake { locked });
+    }
+
+    STAKED_BALANCES.update(
+        storage,
+        &info.sender,
+        env.block.height,
+        |balance| -> Result<Uint128, ContractError> {
+            balance
+                .unwrap_or_default()
+                .checked_sub(amount)
+                .map_err(|_e| ContractError::InvalidUnstakeAmount {})
+        },
+    )?;
+    STAKED_TOTAL.update(
+        storage,
+        env.block.height,
+        |total| -> Result<Uint128, ContractError> {
+            total
+                .unwrap_or_default()
+                .checked_sub(amount)
+                .map_err(|_e| ContractError::InvalidUnstakeAmount {})
+        },
+    )?;
+    apply_power_cap(storage, env.block.height, &info.sender)?;
+
+    // Add unstake hook messages
+    let hook_msgs = unstake_hook_msgs(HOOKS, storage, info.sender.clone(), amount)?;
+
+    Ok((receipt_msgs, hook_msgs))
+}
+
+/// Pays AMOUNT less PENALTY to STAKER, and sends PENALTY to the DAO
+/// or burns it.
+fn payout_msgs(
+    deps: Deps,
+    early_unstake: &EarlyUnstakeConfig,
+    denom: &str,
+    staker: &Addr,
+    amount: Uint128,
+    penalty: Uint128,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut msgs = vec![];
+    let payout = amount.checked_sub(penalty)?;
+    if !payout.is_zero() {
+        msgs.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: staker.to_string(),
+            amount: coins(payout.u128(), denom),
+        }));
+    }
+    if !penalty.is_zero() {
+        let amount = coins(penalty.u128(), denom);
+        msgs.push(match early_unstake.destination {
+            PenaltyDestination::Dao => CosmosMsg::Bank(BankMsg::Send {
+                to_address: DAO.load(deps.storage)?.into_string(),
+                amount,
+            }),
+            PenaltyDestination::Burn => CosmosMsg::Bank(BankMsg::Burn { amount }),
+        });
+    }
+    Ok(msgs)
+}
+
 pub fn execute_update_config(
     deps: DepsMut,
     info: MessageInfo,
     duration: Option<Duration>,
+    stake_transfers_enabled: Option<bool>,
+    early_unstake: Option<EarlyUnstakeUpdate>,
 ) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
@@ -297,6 +572,12 @@ pub fn execute_update_config(
     validate_duration(duration)?;
 
     config.unstaking_duration = duration;
+    if let Some(stake_transfers_enabled) = stake_transfers_enabled {
+        config.stake_transfers_enabled = stake_transfers_enabled;
+    }
+    if let Some(early_unstake) = early_unstake {
+        config.early_unstake = early_unstake.into_config()?;
+    }
 
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::new().add_attribute("action", "update_config"))
@@ -307,6 +588,11 @@ pub fn execute_claim(
     env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
+    for claim in CLAIMS.query_claims(deps.as_ref(), &info.sender)?.claims {
+        if claim.release_at.is_expired(&env.block) {
+            CLAIM_DURATIONS.remove(deps.storage, &info.sender, &claim.release_at);
+        }
+    }
     let release = CLAIMS.claim_tokens(deps.storage, &info.sender, &env.block, None)?;
     if release.is_zero() {
         return Err(ContractError::NothingToClaim {});
@@ -325,6 +611,316 @@ pub fn execute_claim(
         .add_attribute("amount", release))
 }
 
+/// Releases all of the sender's outstanding claims immediately. Each
+/// claim pays the early unstake penalty scaled by the fraction of its
+/// own unstaking duration it has left.
+pub fn execute_accelerate_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let early_unstake = config
+        .early_unstake
+        .ok_or(ContractError::EarlyUnstakeDisabled {})?;
+
+    let claims = CLAIMS.query_claims(deps.as_ref(), &info.sender)?.claims;
+    let mut penalty = Uint128::zero();
+    for claim in claims.iter() {
+        let claim_duration =
+            CLAIM_DURATIONS.may_load(deps.storage, &info.sender, &claim.release_at)?;
+        penalty = penalty
+            .checked_add(early_unstake.claim_penalty(
+                claim.amount,
+                &claim.release_at,
+                claim_duration,
+                config.unstaking_duration,
+                &env.block,
+            ))
+            .map_err(StdError::overflow)?;
+    }
+    for claim in claims {
+        CLAIM_DURATIONS.remove(deps.storage, &info.sender, &claim.release_at);
+    }
+
+    // cw-controllers only releases expired claims, so release them as
+    // of a block after every claim has expired.
+    let release = CLAIMS.claim_tokens(
+        deps.storage,
+        &info.sender,
+        &BlockInfo {
+            height: u64::MAX,
+            time: Timestamp::from_nanos(u64::MAX),
+            chain_id: env.block.chain_id.clone(),
+        },
+        None,
+    )?;
+    if release.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    let denom = DENOM.load(deps.storage)?;
+    let payout_msgs = payout_msgs(
+        deps.as_ref(),
+        &early_unstake,
+        &denom,
+        &info.sender,
+        release,
+        penalty,
+    )?;
+
+    Ok(Response::new()
+        .add_messages(payout_msgs)
+        .add_attribute("action", "accelerate_claim")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", release)
+        .add_attribute("penalty", penalty))
+}
+
+pub fn execute_lock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    duration: Duration,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::ZeroLock {});
+    }
+
+    let config = LOCKUP_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+    let tier = config
+        .tiers
+        .into_iter()
+        .find(|tier| tier.duration == duration)
+        .ok_or(ContractError::LockupTierNotFound { duration })?;
+    let Duration::Height(blocks) = tier.duration else {
+        return Err(ContractError::InvalidLockupTiers {
+            reason: "lockup durations must be in blocks".to_string(),
+        });
+    };
+
+    let height = env.block.height;
+    let mut locks = prune_locks(deps.storage, height, &info.sender)?;
+    let locked = locked_amount(&locks)?;
+    let staked = STAKED_BALANCES
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if staked.saturating_sub(locked) < amount {
+        return Err(ContractError::LockedStake { locked });
+    }
+    if locks.len() >= MAX_LOCKS {
+        return Err(ContractError::TooManyLocks { max: MAX_LOCKS });
+    }
+
+    let id = NEXT_LOCK_ID.may_load(deps.storage)?.unwrap_or_default();
+    NEXT_LOCK_ID.save(deps.storage, &(id + 1))?;
+    let lock = Lock {
+        id,
+        amount,
+        duration: blocks,
+        boost: tier.boost,
+        decay: config.decay,
+        expiration: height + blocks,
+    };
+
+    // Add the lock to the total until it expires.
+    let curve = lock.curve()?;
+    let mut total = checkpoint_bonus_total(deps.storage, height)?;
+    total.curve = total.curve.checked_add(&curve)?;
+    BONUS_TOTAL.save(deps.storage, &total, height)?;
+    BONUS_EXPIRATIONS.update(
+        deps.storage,
+        lock.expiration,
+        |expiring| -> StdResult<BonusCurve> { expiring.unwrap_or_default().checked_add(&curve) },
+    )?;
+
+    let expiration = lock.expiration;
+    locks.push(lock);
+    LOCKS.save(deps.storage, &info.sender, &locks, height)?;
+    apply_power_cap(deps.storage, height, &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "lock")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("boost", tier.boost.to_string())
+        .add_attribute("expiration", expiration.to_string()))
+}
+
+pub fn execute_refresh_locks(
+    deps: DepsMut,
+    env: Env,
+    address: String,
+) -> Result<Response, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+    let height = env.block.height;
+    let locks = prune_locks(deps.storage, height, &address)?;
+    let total = checkpoint_bonus_total(deps.storage, height)?;
+    BONUS_TOTAL.save(deps.storage, &total, height)?;
+    apply_power_cap(deps.storage, height, &address)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "refresh_locks")
+        .add_attribute("address", address)
+        .add_attribute("bonus_power", bonus_at_height(&locks, height)?))
+}
+
+/// Removes ADDRESS's locks that have expired at HEIGHT, returning the
+/// locks that remain.
+fn prune_locks(storage: &mut dyn Storage, height: u64, address: &Addr) -> StdResult<Vec<Lock>> {
+    let locks = LOCKS.may_load(storage, address)?.unwrap_or_default();
+    let count = locks.len();
+    let locks: Vec<Lock> = locks
+        .into_iter()
+        .filter(|lock| lock.expiration > height)
+        .collect();
+    if locks.len() != count {
+        if locks.is_empty() {
+            LOCKS.remove(storage, address, height)?;
+        } else {
+            LOCKS.save(storage, address, &locks, height)?;
+        }
+    }
+    Ok(locks)
+}
+
+/// The amount of staked tokens in LOCKS.
+fn locked_amount(locks: &[Lock]) -> StdResult<Uint128> {
+    locks
+        .iter()
+        .try_fold(Uint128::zero(), |locked, lock| -> StdResult<Uint128> {
+            Ok(locked.checked_add(lock.amount)?)
+        })
+}
+
+/// The voting power LOCKS add at HEIGHT.
+fn bonus_at_height(locks: &[Lock], height: u64) -> StdResult<Uint128> {
+    locks
+        .iter()
+        .try_fold(Uint128::zero(), |bonus, lock| -> StdResult<Uint128> {
+            Ok(bonus.checked_add(lock.bonus_at_height(height)?)?)
+        })
+}
+
+/// Removes the locks that expire after `total.expired_through` and at
+/// or before HEIGHT from TOTAL's curve.
+fn remove_expired(
+    storage: &dyn Storage,
+    mut total: BonusTotal,
+    height: u64,
+) -> StdResult<BonusTotal> {
+    if total.expired_through >= height {
+        return Ok(total);
+    }
+    for expiring in BONUS_EXPIRATIONS.range(
+        storage,
+        Some(Bound::exclusive(total.expired_through)),
+        Some(Bound::inclusive(height)),
+        Order::Ascending,
+    ) {
+        let (_, curve) = expiring?;
+        total.curve = total.curve.checked_sub(&curve)?;
+    }
+    total.expired_through = height;
+    Ok(total)
+}
+
+/// Loads the current total bonus with the locks that have expired at
+/// HEIGHT removed. Callers save the result, so later computations of
+/// the total do not need to remove those locks again.
+fn checkpoint_bonus_total(storage: &dyn Storage, height: u64) -> StdResult<BonusTotal> {
+    let total = BONUS_TOTAL.may_load(storage)?.unwrap_or_default();
+    remove_expired(storage, total, height)
+}
+
+/// Recomputes how much of ADDRESS's voting power is above the voting
+/// power cap. Called whenever ADDRESS's staked or bonus power changes.
+fn apply_power_cap(storage: &mut dyn Storage, height: u64, address: &Addr) -> StdResult<()> {
+    let staked = STAKED_BALANCES
+        .may_load(storage, address)?
+        .unwrap_or_default();
+    let locks = LOCKS.may_load(storage, address)?.unwrap_or_default();
+    let bonus = bonus_at_height(&locks, height)?;
+    let total_bonus = checkpoint_bonus_total(storage, height)?
+        .curve
+        .at_height(height)?;
+    let total = STAKED_TOTAL
+        .may_load(storage)?
+        .unwrap_or_default()
+        .checked_add(total_bonus)?;
+    POWER_CAPS.apply(storage, height, address, staked.checked_add(bonus)?, total)
+}
+
+pub fn execute_update_max_power_per_address(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_power_per_address: Option<PowerCap>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    POWER_CAPS.set_cap(deps.storage, max_power_per_address)?;
+
+    Ok(Response::new().add_attribute("action", "update_max_power_per_address"))
+}
+
+pub fn execute_refresh_power_cap(
+    deps: DepsMut,
+    env: Env,
+    address: String,
+) -> Result<Response, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+    apply_power_cap(deps.storage, env.block.height, &address)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "refresh_power_cap")
+        .add_attribute("address", address))
+}
+
+pub fn execute_update_lockup_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    tiers: Vec<LockupTier>,
+    decay: bool,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for (i, tier) in tiers.iter().enumerate() {
+        validate_duration(Some(tier.duration))?;
+        // Voting power is queried by height, so the bonus of a lock
+        // must be computable from a height alone.
+        if matches!(tier.duration, Duration::Time(_)) {
+            return Err(ContractError::InvalidLockupTiers {
+                reason: "lockup durations must be in blocks".to_string(),
+            });
+        }
+        if tier.boost < Decimal::one() {
+            return Err(ContractError::InvalidLockupTiers {
+                reason: "boosts must be at least one".to_string(),
+            });
+        }
+        if tiers[..i]
+            .iter()
+            .any(|other| other.duration == tier.duration)
+        {
+            return Err(ContractError::InvalidLockupTiers {
+                reason: format!("duplicate tier duration ({})", tier.duration),
+            });
+        }
+    }
+
+    LOCKUP_CONFIG.save(deps.storage, &LockupConfig { tiers, decay })?;
+    Ok(Response::new()
+        .add_attribute("action", "update_lockup_config")
+        .add_attribute("decay", decay.to_string()))
+}
+
 pub fn execute_update_active_threshold(
     deps: DepsMut,
     _env: Env,
@@ -391,6 +987,56 @@ pub fn execute_remove_hook(
         .add_attribute("hook", addr))
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::BlockBeforeSend { from, to, amount } => {
+            sudo_block_before_send(deps, env, from, to, amount)
+        }
+    }
+}
+
+/// Called by the chain before receipt tokens are sent. Moves the
+/// voting power of the stake the receipts represent from the sender to
+/// the recipient.
+pub fn sudo_block_before_send(
+    deps: DepsMut,
+    env: Env,
+    from: String,
+    to: String,
+    amount: Coin,
+) -> Result<Response, ContractError> {
+    let Some(receipt_denom) = RECEIPT_DENOM.may_load(deps.storage)? else {
+        return Ok(Response::new());
+    };
+    let issuer = RECEIPT_ISSUER_CONTRACT.load(deps.storage)?;
+
+    // Minting and burning receipts, and returning them to unstake, are
+    // accounted for by staking and unstaking.
+    let is_exempt = |addr: &str| addr == issuer.as_str() || addr == env.contract.address.as_str();
+    if amount.denom != receipt_denom
+        || amount.amount.is_zero()
+        || from == to
+        || is_exempt(&from)
+        || is_exempt(&to)
+    {
+        return Ok(Response::new());
+    }
+
+    let from = deps.api.addr_validate(&from)?;
+    let to = deps.api.addr_validate(&to)?;
+    let amount = amount.amount;
+
+    let hook_msgs = move_stake(deps.storage, &env.block, &from, &to, amount)?;
+
+    Ok(Response::new()
+        .add_submessages(hook_msgs)
+        .add_attribute("action", "transfer_stake")
+        .add_attribute("from", from)
+        .add_attribute("to", to)
+        .add_attribute("amount", amount))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -416,6 +1062,14 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::TokenContract {} => {
             to_json_binary(&TOKEN_ISSUER_CONTRACT.may_load(deps.storage)?)
         }
+        QueryMsg::LockupConfig {} => {
+            to_json_binary(&LOCKUP_CONFIG.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::Locks { address } => to_json_binary(&query_locks(deps, env, address)?),
+        QueryMsg::ReceiptToken {} => to_json_binary(&query_receipt_token(deps)?),
+        QueryMsg::MaxPowerPerAddress {} => to_json_binary(&PowerCapResponse {
+            max_power_per_address: POWER_CAPS.cap(deps.storage)?,
+        }),
     }
 }
 
@@ -427,9 +1081,19 @@ pub fn query_voting_power_at_height(
 ) -> StdResult<VotingPowerAtHeightResponse> {
     let height = height.unwrap_or(env.block.height);
     let address = deps.api.addr_validate(&address)?;
-    let power = STAKED_BALANCES
+    let staked = STAKED_BALANCES
         .may_load_at_height(deps.storage, &address, height)?
         .unwrap_or_default();
+    let locks = LOCKS
+        .may_load_at_height(deps.storage, &address, height)?
+        .unwrap_or_default();
+    let bonus = bonus_at_height(&locks, height)?;
+    let power = POWER_CAPS.capped_power_at_height(
+        deps.storage,
+        &address,
+        staked.checked_add(bonus)?,
+        height,
+    )?;
     Ok(VotingPowerAtHeightResponse { power, height })
 }
 
@@ -439,9 +1103,17 @@ pub fn query_total_power_at_height(
     height: Option<u64>,
 ) -> StdResult<TotalPowerAtHeightResponse> {
     let height = height.unwrap_or(env.block.height);
-    let power = STAKED_TOTAL
+    let staked = STAKED_TOTAL
         .may_load_at_height(deps.storage, height)?
         .unwrap_or_default();
+    let total = BONUS_TOTAL
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+    let bonus = remove_expired(deps.storage, total, height)?
+        .curve
+        .at_height(height)?;
+    let power =
+        POWER_CAPS.capped_total_at_height(deps.storage, staked.checked_add(bonus)?, height)?;
     Ok(TotalPowerAtHeightResponse { power, height })
 }
 
@@ -459,6 +1131,32 @@ pub fn query_claims(deps: Deps, address: String) -> StdResult<ClaimsResponse> {
     CLAIMS.query_claims(deps, &deps.api.addr_validate(&address)?)
 }
 
+pub fn query_locks(deps: Deps, env: Env, address: String) -> StdResult<LocksResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let height = env.block.height;
+    let locks: Vec<Lock> = LOCKS
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|lock| lock.expiration > height)
+        .collect();
+    Ok(LocksResponse {
+        locked: locked_amount(&locks)?,
+        bonus: bonus_at_height(&locks, height)?,
+        locks,
+    })
+}
+
+pub fn query_receipt_token(deps: Deps) -> StdResult<Option<ReceiptTokenResponse>> {
+    let Some(issuer) = RECEIPT_ISSUER_CONTRACT.may_load(deps.storage)? else {
+        return Ok(None);
+    };
+    Ok(Some(ReceiptTokenResponse {
+        denom: RECEIPT_DENOM.load(deps.storage)?,
+        issuer,
+    }))
+}
+
 pub fn query_list_stakers(
     deps: Deps,
     start_after: Option<String>,
@@ -734,6 +1432,50 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
                 _ => unreachable!(),
             }
         }
+        #[cfg(feature = "osmosis_tokenfactory")]
+        INSTANTIATE_RECEIPT_ISSUER_REPLY_ID => {
+            // Parse and save address of the receipt token's
+            // cw-tokenfactory-issuer
+            let issuer_addr = parse_reply_instantiate_data(msg)?.contract_address;
+            let issuer_addr = deps.api.addr_validate(&issuer_addr)?;
+            RECEIPT_ISSUER_CONTRACT.save(deps.storage, &issuer_addr)?;
+
+            let receipt_denom: IssuerDenomResponse = deps
+                .querier
+                .query_wasm_smart(&issuer_addr, &IssuerQueryMsg::Denom {})?;
+            RECEIPT_DENOM.save(deps.storage, &receipt_denom.denom)?;
+
+            // Allow this contract to mint and burn receipts, and have
+            // the chain call this contract before receipts are sent so
+            // that voting power follows them.
+            let msgs = [
+                IssuerExecuteMsg::SetMinterAllowance {
+                    address: env.contract.address.to_string(),
+                    allowance: Uint128::MAX,
+                },
+                IssuerExecuteMsg::SetBurnerAllowance {
+                    address: env.contract.address.to_string(),
+                    allowance: Uint128::MAX,
+                },
+                IssuerExecuteMsg::SetBeforeSendHook {
+                    cosmwasm_address: env.contract.address.to_string(),
+                },
+            ]
+            .into_iter()
+            .map(|msg| -> StdResult<WasmMsg> {
+                Ok(WasmMsg::Execute {
+                    contract_addr: issuer_addr.to_string(),
+                    msg: to_json_binary(&msg)?,
+                    funds: vec![],
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+            Ok(Response::new()
+                .add_attribute("receipt_denom", receipt_denom.denom)
+                .add_attribute("receipt_token_contract", issuer_addr)
+                .add_messages(msgs))
+        }
         FACTORY_EXECUTE_REPLY_ID => {
             // Parse reply
             let res = parse_reply_execute_data(msg)?;
@@ -1,6 +1,8 @@
-use cosmwasm_std::StdError;
-use cw_utils::{ParseReplyError, PaymentError};
-use dao_voting::threshold::ActiveThresholdError;
+use cosmwasm_std::{StdError, Uint128};
+use cw_utils::{Duration, ParseReplyError, PaymentError};
+use dao_voting::{
+    early_unstake::EarlyUnstakeError, power_cap::PowerCapError, threshold::ActiveThresholdError,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -11,6 +13,12 @@ pub enum ContractError {
     #[error(transparent)]
     ActiveThresholdError(#[from] ActiveThresholdError),
 
+    #[error(transparent)]
+    PowerCapError(#[from] PowerCapError),
+
+    #[error(transparent)]
+    EarlyUnstakeError(#[from] EarlyUnstakeError),
+
     #[error(transparent)]
     HookError(#[from] cw_hooks::HookError),
 
@@ -23,21 +31,51 @@ pub enum ContractError {
     #[error(transparent)]
     UnstakingDurationError(#[from] dao_voting::duration::UnstakingDurationError),
 
+    #[error("Early unstaking is not enabled")]
+    EarlyUnstakeDisabled {},
+
     #[error("Initial governance token balances must not be empty")]
     InitialBalancesError {},
 
     #[error("Can only unstake less than or equal to the amount you have staked")]
     InvalidUnstakeAmount {},
 
+    #[error("Invalid lockup tiers: {reason}")]
+    InvalidLockupTiers { reason: String },
+
+    #[error("Must send {expected} receipt tokens to unstake {expected} tokens")]
+    InvalidReceiptAmount { expected: Uint128 },
+
+    #[error("Can only lock or unstake staked tokens that are not locked. {locked} of your staked tokens are locked.")]
+    LockedStake { locked: Uint128 },
+
+    #[error("No lockup tier with a duration of {duration}")]
+    LockupTierNotFound { duration: Duration },
+
     #[error("Factory contract did not implment the required TokenFactoryCallback interface")]
     NoFactoryCallback {},
 
     #[error("Nothing to claim")]
     NothingToClaim {},
 
+    #[error("Stake is transferred by transferring receipt tokens")]
+    ReceiptTokenTransfer {},
+
+    #[error("Stake transfers are not enabled")]
+    StakeTransfersDisabled {},
+
+    #[error("Can not transfer stake to yourself")]
+    SelfTransfer {},
+
+    #[error("Receipt tokens require the osmosis_tokenfactory feature")]
+    ReceiptTokenUnsupported {},
+
     #[error("Too many outstanding claims. Claim some tokens before unstaking more.")]
     TooManyClaims {},
 
+    #[error("An address may have at most {max} locks at once")]
+    TooManyLocks { max: usize },
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -49,4 +87,10 @@ pub enum ContractError {
 
     #[error("Amount being unstaked must be non-zero")]
     ZeroUnstake {},
+
+    #[error("Amount being locked must be non-zero")]
+    ZeroLock {},
+
+    #[error("Amount being transferred must be non-zero")]
+    ZeroTransfer {},
 }
@@ -1,10 +1,14 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Binary, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Uint128};
 use cw_utils::Duration;
 use dao_dao_macros::{active_query, native_token_query, voting_module_query};
 use dao_interface::token::NewTokenInfo;
+use dao_voting::early_unstake::EarlyUnstakeUpdate;
+use dao_voting::power_cap::PowerCap;
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 
+use crate::state::{Lock, LockupTier};
+
 #[cw_serde]
 pub enum TokenInfo {
     /// Uses an existing Token Factory token and creates a new issuer contract.
@@ -24,6 +28,15 @@ pub enum TokenInfo {
     Factory(Binary),
 }
 
+#[cw_serde]
+pub struct ReceiptTokenInfo {
+    /// The code ID of the cw-tokenfactory-issuer contract that will
+    /// issue the receipt token.
+    pub token_issuer_code_id: u64,
+    /// The subdenom of the receipt token.
+    pub subdenom: String,
+}
+
 #[cw_serde]
 pub struct InstantiateMsg {
     /// New or existing native token to use for voting power.
@@ -33,18 +46,48 @@ pub struct InstantiateMsg {
     /// The number or percentage of tokens that must be staked
     /// for the DAO to be active
     pub active_threshold: Option<ActiveThreshold>,
+    /// If set, stakers receive a transferable receipt token for their
+    /// stake. Transferring receipt tokens transfers the voting power
+    /// of the stake they represent, and unstaking requires returning
+    /// them. Requires a chain with Token Factory before send hooks.
+    pub receipt_token: Option<ReceiptTokenInfo>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Stakes tokens with the contract to get voting power in the DAO
     Stake {},
-    /// Unstakes tokens so that they begin unbonding
+    /// Stakes tokens on behalf of RECIPIENT, who gets the voting power
+    /// and may unstake them. Receipt tokens, if enabled, are minted to
+    /// RECIPIENT.
+    StakeFor { recipient: String },
+    /// Moves AMOUNT of the sender's staked tokens, and their voting
+    /// power, to TO without unstaking them. Only allowed if the DAO
+    /// has enabled stake transfers. Locked tokens may not be
+    /// transferred.
+    TransferStake { to: String, amount: Uint128 },
+    /// Unstakes tokens so that they begin unbonding. If receipt tokens
+    /// are enabled, AMOUNT receipt tokens must be sent with this message.
     Unstake { amount: Uint128 },
-    /// Updates the contract configuration
-    UpdateConfig { duration: Option<Duration> },
+    /// Unstakes tokens without waiting for the unbonding period by
+    /// paying the early unstake penalty. Only allowed if the DAO has
+    /// configured `early_unstake`. If receipt tokens are enabled,
+    /// AMOUNT receipt tokens must be sent with this message.
+    InstantUnstake { amount: Uint128 },
+    /// Updates the contract configuration. Only callable by the DAO.
+    /// If STAKE_TRANSFERS_ENABLED or EARLY_UNSTAKE is `None`, it is
+    /// left unchanged.
+    UpdateConfig {
+        duration: Option<Duration>,
+        stake_transfers_enabled: Option<bool>,
+        early_unstake: Option<EarlyUnstakeUpdate>,
+    },
     /// Claims unstaked tokens that have completed the unbonding period
     Claim {},
+    /// Claims all of the sender's unbonding tokens now. Each claim
+    /// pays the early unstake penalty scaled by how much of the
+    /// unbonding period it has left.
+    AccelerateClaim {},
     /// Sets the active threshold to a new value. Only the
     /// instantiator of this contract (a DAO most likely) may call this
     /// method.
@@ -55,6 +98,32 @@ pub enum ExecuteMsg {
     AddHook { addr: String },
     /// Removes a hook that fires on staking / unstaking
     RemoveHook { addr: String },
+    /// Locks staked tokens for the duration of the lockup tier with
+    /// DURATION in exchange for boosted voting power. Locked tokens
+    /// may not be unstaked until the lock expires.
+    Lock { amount: Uint128, duration: Duration },
+    /// Removes an address's expired locks and recomputes its voting
+    /// power cap. The bonus of locks is computed when voting power is
+    /// queried, so refreshing is not needed for decay or expiry to
+    /// take effect. Callable by anyone.
+    RefreshLocks { address: String },
+    /// Sets the lockup tiers stakers may choose from. Tier durations
+    /// must be in blocks. Existing locks are not affected. Only
+    /// callable by the DAO.
+    UpdateLockupConfig { tiers: Vec<LockupTier>, decay: bool },
+    /// Sets the maximum voting power any one address may have. Only
+    /// callable by the DAO. Addresses' powers are capped the next
+    /// time they change or are refreshed with `RefreshPowerCap`.
+    /// Decaying lock bonuses and changes to the total power under a
+    /// percentage cap are not reflected until then, so the total
+    /// power may differ from the sum of capped powers.
+    UpdateMaxPowerPerAddress {
+        max_power_per_address: Option<PowerCap>,
+    },
+    /// Reapplies the voting power cap to an address. Useful after the
+    /// cap changes, or when a percentage cap was applied against a
+    /// different total. Callable by anyone.
+    RefreshPowerCap { address: String },
 }
 
 #[native_token_query]
@@ -78,6 +147,25 @@ pub enum QueryMsg {
     GetHooks {},
     #[returns(Option<cosmwasm_std::Addr>)]
     TokenContract {},
+    #[returns(crate::state::LockupConfig)]
+    LockupConfig {},
+    #[returns(LocksResponse)]
+    Locks { address: String },
+    #[returns(Option<ReceiptTokenResponse>)]
+    ReceiptToken {},
+    #[returns(dao_voting::power_cap::PowerCapResponse)]
+    MaxPowerPerAddress {},
+}
+
+/// Messages the chain sends to this contract when it is the before
+/// send hook of the receipt token.
+#[cw_serde]
+pub enum SudoMsg {
+    BlockBeforeSend {
+        from: String,
+        to: String,
+        amount: Coin,
+    },
 }
 
 #[cw_serde]
@@ -94,6 +182,23 @@ pub struct StakerBalanceResponse {
     pub balance: Uint128,
 }
 
+#[cw_serde]
+pub struct LocksResponse {
+    pub locks: Vec<Lock>,
+    /// The total amount of the address's staked tokens that are
+    /// locked.
+    pub locked: Uint128,
+    /// The voting power the address's locks add on top of its staked
+    /// balance at the current height.
+    pub bonus: Uint128,
+}
+
+#[cw_serde]
+pub struct ReceiptTokenResponse {
+    pub denom: String,
+    pub issuer: Addr,
+}
+
 #[cw_serde]
 pub struct GetHooksResponse {
     pub hooks: Vec<String>,
@@ -1,16 +1,28 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Decimal256, StdResult, Uint128};
 use cw_controllers::Claims;
 use cw_hooks::Hooks;
-use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 use cw_utils::Duration;
-use dao_voting::threshold::ActiveThreshold;
+use dao_voting::{
+    early_unstake::{ClaimDurations, EarlyUnstakeConfig},
+    power_cap::PowerCaps,
+    threshold::ActiveThreshold,
+};
 
 use crate::msg::TokenInfo;
 
 #[cw_serde]
 pub struct Config {
     pub unstaking_duration: Option<Duration>,
+    /// Whether stakers may move staked tokens to another address with
+    /// `TransferStake`.
+    #[serde(default)]
+    pub stake_transfers_enabled: bool,
+    /// If set, stakers may skip the unstaking duration by paying a
+    /// penalty with `InstantUnstake` and `AccelerateClaim`.
+    #[serde(default)]
+    pub early_unstake: Option<EarlyUnstakeConfig>,
 }
 
 /// The configuration of this voting contract
@@ -43,14 +55,182 @@ pub const MAX_CLAIMS: u64 = 100;
 
 pub const CLAIMS: Claims = Claims::new("claims");
 
+/// The unstaking duration each claim was created with.
+pub const CLAIM_DURATIONS: ClaimDurations = ClaimDurations::new();
+
 /// The minimum amount of staked tokens for the DAO to be active
 pub const ACTIVE_THRESHOLD: Item<ActiveThreshold> = Item::new("active_threshold");
 
 /// Hooks to contracts that will receive staking and unstaking messages
 pub const HOOKS: Hooks = Hooks::new("hooks");
 
+/// The maximum voting power of any one address, and how much of each
+/// address's power is above it
+pub const POWER_CAPS: PowerCaps = PowerCaps::new();
+
 /// Temporarily holds token_instantiation_info when creating a new Token Factory denom
 pub const TOKEN_INSTANTIATION_INFO: Item<TokenInfo> = Item::new("token_instantiation_info");
 
 /// The address of the cw-tokenfactory-issuer contract
 pub const TOKEN_ISSUER_CONTRACT: Item<Addr> = Item::new("token_issuer_contract");
+
+/// The address of the cw-tokenfactory-issuer contract that issues
+/// receipt tokens to stakers, if receipt tokens are enabled
+pub const RECEIPT_ISSUER_CONTRACT: Item<Addr> = Item::new("receipt_issuer_contract");
+
+/// The denom of the receipt token, if receipt tokens are enabled
+pub const RECEIPT_DENOM: Item<String> = Item::new("receipt_denom");
+
+/// A lock duration stakers may choose, and the voting power boost
+/// that tokens locked for that duration receive.
+#[cw_serde]
+pub struct LockupTier {
+    /// How long tokens locked in this tier may not be unstaked for.
+    pub duration: Duration,
+    /// The voting power of tokens locked in this tier is multiplied
+    /// by this value. Must be at least one.
+    pub boost: Decimal,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct LockupConfig {
+    /// The lockup tiers stakers may choose from. If empty, tokens may
+    /// not be locked.
+    pub tiers: Vec<LockupTier>,
+    /// If true, the boost of a lock decays linearly to one as the lock
+    /// approaches its expiration.
+    pub decay: bool,
+}
+
+/// Staked tokens that may not be unstaked until `expiration`, in
+/// exchange for boosted voting power.
+#[cw_serde]
+pub struct Lock {
+    pub id: u64,
+    pub amount: Uint128,
+    /// The tier's duration in blocks and boost at the time the lock
+    /// was created. Later changes to the lockup tiers do not affect
+    /// existing locks.
+    pub duration: u64,
+    pub boost: Decimal,
+    pub decay: bool,
+    /// The height at which the lock expires.
+    pub expiration: u64,
+}
+
+impl Lock {
+    /// The voting power this lock adds on top of its staked amount
+    /// before any decay.
+    fn max_bonus(&self) -> Uint128 {
+        self.amount.mul_floor(self.boost - Decimal::one())
+    }
+
+    /// This lock's contribution to the total bonus power while it has
+    /// not expired.
+    pub fn curve(&self) -> StdResult<BonusCurve> {
+        if !self.decay {
+            return Ok(BonusCurve {
+                constant: self.max_bonus(),
+                ..Default::default()
+            });
+        }
+        let slope = Decimal256::from_ratio(self.max_bonus(), self.duration);
+        Ok(BonusCurve {
+            constant: Uint128::zero(),
+            bias: slope.checked_mul(Decimal256::from_ratio(self.expiration, 1u64))?,
+            slope,
+        })
+    }
+
+    /// Computes the voting power this lock adds on top of its staked
+    /// amount at HEIGHT.
+    pub fn bonus_at_height(&self, height: u64) -> StdResult<Uint128> {
+        if height >= self.expiration {
+            return Ok(Uint128::zero());
+        }
+        self.curve()?.at_height(height)
+    }
+}
+
+/// Voting power added by locks as a function of height, computed as
+/// `constant + bias - slope * height`. Locks are removed from the
+/// curve once they expire.
+#[cw_serde]
+#[derive(Default)]
+pub struct BonusCurve {
+    /// The bonus of locks that do not decay.
+    pub constant: Uint128,
+    /// The sum of `slope * expiration` over locks that decay.
+    pub bias: Decimal256,
+    /// The amount the bonus of locks that decay falls by each block.
+    pub slope: Decimal256,
+}
+
+impl BonusCurve {
+    pub fn checked_add(&self, other: &Self) -> StdResult<Self> {
+        Ok(Self {
+            constant: self.constant.checked_add(other.constant)?,
+            bias: self.bias.checked_add(other.bias)?,
+            slope: self.slope.checked_add(other.slope)?,
+        })
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> StdResult<Self> {
+        Ok(Self {
+            constant: self.constant.checked_sub(other.constant)?,
+            bias: self.bias.checked_sub(other.bias)?,
+            slope: self.slope.checked_sub(other.slope)?,
+        })
+    }
+
+    pub fn at_height(&self, height: u64) -> StdResult<Uint128> {
+        let decayed = self
+            .slope
+            .checked_mul(Decimal256::from_ratio(height, 1u64))?;
+        let decaying = Uint128::try_from(self.bias.saturating_sub(decayed).to_uint_floor())?;
+        Ok(self.constant.checked_add(decaying)?)
+    }
+}
+
+/// The total voting power added by locks.
+#[cw_serde]
+#[derive(Default)]
+pub struct BonusTotal {
+    pub curve: BonusCurve,
+    /// Locks that expire at or before this height have been removed
+    /// from `curve`. Locks that expire later are removed when the
+    /// total is next computed.
+    pub expired_through: u64,
+}
+
+/// The lockup tiers stakers may choose from
+pub const LOCKUP_CONFIG: Item<LockupConfig> = Item::new("lockup_config");
+
+/// The maximum number of locks an address may have at once
+pub const MAX_LOCKS: usize = 10;
+
+/// Keeps track of each address's locks over time. An address's voting
+/// power is its staked balance plus the bonus of its locks, which is
+/// computed from these at the queried height.
+pub const LOCKS: SnapshotMap<&Addr, Vec<Lock>> = SnapshotMap::new(
+    "locks",
+    "locks__checkpoints",
+    "locks__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The ID of the next lock
+pub const NEXT_LOCK_ID: Item<u64> = Item::new("next_lock_id");
+
+/// Keeps track of the total voting power added by locks over time
+pub const BONUS_TOTAL: SnapshotItem<BonusTotal> = SnapshotItem::new(
+    "bonus_total",
+    "bonus_total__checkpoints",
+    "bonus_total__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The combined curves of the locks that expire at each height. Kept
+/// after the locks expire so that past totals may be computed.
+pub const BONUS_EXPIRATIONS: Map<u64, BonusCurve> = Map::new("bonus_expirations");
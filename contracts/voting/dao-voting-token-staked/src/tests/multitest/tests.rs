@@ -1,13 +1,20 @@
-use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::contract::{
+    execute, instantiate, migrate, query_total_power_at_height, query_voting_power_at_height, sudo,
+    CONTRACT_NAME, CONTRACT_VERSION,
+};
 use crate::msg::{
-    ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, MigrateMsg, QueryMsg,
-    StakerBalanceResponse, TokenInfo,
+    ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, LocksResponse, MigrateMsg,
+    QueryMsg, StakerBalanceResponse, SudoMsg as ContractSudoMsg, TokenInfo,
+};
+use crate::state::{self, Config, LockupConfig, LockupTier};
+use crate::ContractError;
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, Coin, CosmosMsg, Decimal, Deps, Event, Uint128, WasmMsg,
 };
-use crate::state::Config;
-use cosmwasm_std::testing::{mock_dependencies, mock_env};
-use cosmwasm_std::{coins, Addr, Coin, Decimal, Uint128};
 use cw_controllers::ClaimsResponse;
 use cw_multi_test::{next_block, App, AppResponse, BankSudo, Executor, SudoMsg};
+use cw_tokenfactory_issuer::msg::ExecuteMsg as IssuerExecuteMsg;
 use cw_utils::Duration;
 use dao_interface::voting::{
     DenomResponse, InfoResponse, IsActiveResponse, TotalPowerAtHeightResponse,
@@ -16,6 +23,10 @@ use dao_interface::voting::{
 use dao_testing::contracts::{
     dao_proposal_hook_counter_contract, dao_voting_token_staked_contract,
 };
+use dao_voting::early_unstake::{
+    EarlyUnstakeConfig, EarlyUnstakeError, EarlyUnstakeUpdate, PenaltyDestination,
+};
+use dao_voting::power_cap::{PowerCap, PowerCapError, PowerCapResponse};
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 
 const DAO_ADDR: &str = "dao";
@@ -137,7 +148,11 @@ fn update_config(
     app.execute_contract(
         Addr::unchecked(sender),
         staking_addr,
-        &ExecuteMsg::UpdateConfig { duration },
+        &ExecuteMsg::UpdateConfig {
+            duration,
+            stake_transfers_enabled: None,
+            early_unstake: None,
+        },
         &[],
     )
 }
@@ -197,6 +212,7 @@ fn test_instantiate_existing() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -210,6 +226,7 @@ fn test_instantiate_existing() {
             },
             unstaking_duration: None,
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -244,6 +261,7 @@ fn test_instantiate_invalid_unstaking_duration_height() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::new(1),
             }),
+            receipt_token: None,
         },
     );
 }
@@ -267,6 +285,7 @@ fn test_instantiate_invalid_unstaking_duration_time() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::new(1),
             }),
+            receipt_token: None,
         },
     );
 }
@@ -286,6 +305,7 @@ fn test_stake_invalid_denom() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -307,6 +327,7 @@ fn test_stake_valid_denom() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -330,6 +351,7 @@ fn test_unstake_none_staked() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -351,6 +373,7 @@ fn test_unstake_zero_tokens() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -372,6 +395,7 @@ fn test_unstake_invalid_balance() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -397,6 +421,7 @@ fn test_unstake() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -434,6 +459,7 @@ fn test_unstake_no_unstaking_duration() {
             },
             unstaking_duration: None,
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -473,6 +499,7 @@ fn test_claim_no_claims() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -494,6 +521,7 @@ fn test_claim_claim_not_reached() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -523,6 +551,7 @@ fn test_claim() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -576,6 +605,7 @@ fn test_update_config_invalid_sender() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -597,6 +627,7 @@ fn test_update_config_as_owner() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -607,6 +638,8 @@ fn test_update_config_as_owner() {
     assert_eq!(
         Config {
             unstaking_duration: Some(Duration::Height(10)),
+            stake_transfers_enabled: false,
+            early_unstake: None,
         },
         config
     );
@@ -627,6 +660,7 @@ fn test_update_config_invalid_duration() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -648,6 +682,7 @@ fn test_query_dao() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -670,6 +705,7 @@ fn test_query_info() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -692,6 +728,7 @@ fn test_query_claims() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -730,6 +767,7 @@ fn test_query_get_config() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -738,6 +776,8 @@ fn test_query_get_config() {
         config,
         Config {
             unstaking_duration: Some(Duration::Height(5)),
+            stake_transfers_enabled: false,
+            early_unstake: None,
         }
     )
 }
@@ -756,6 +796,7 @@ fn test_voting_power_queries() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -864,6 +905,7 @@ fn test_query_list_stakers() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -953,6 +995,7 @@ fn test_instantiate_zero_active_threshold_count() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::zero(),
             }),
+            receipt_token: None,
         },
     );
 }
@@ -974,6 +1017,7 @@ fn test_active_threshold_absolute_count() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::new(100),
             }),
+            receipt_token: None,
         },
     );
 
@@ -1012,6 +1056,7 @@ fn test_active_threshold_percent() {
             active_threshold: Some(ActiveThreshold::Percentage {
                 percent: Decimal::percent(20),
             }),
+            receipt_token: None,
         },
     );
 
@@ -1050,6 +1095,7 @@ fn test_active_threshold_percent_rounds_up() {
             active_threshold: Some(ActiveThreshold::Percentage {
                 percent: Decimal::percent(50),
             }),
+            receipt_token: None,
         },
     );
 
@@ -1095,6 +1141,7 @@ fn test_active_threshold_none() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -1120,6 +1167,7 @@ fn test_update_active_threshold() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -1174,6 +1222,7 @@ fn test_active_threshold_percentage_gt_100() {
             active_threshold: Some(ActiveThreshold::Percentage {
                 percent: Decimal::percent(120),
             }),
+            receipt_token: None,
         },
     );
 }
@@ -1197,6 +1246,7 @@ fn test_active_threshold_percentage_lte_0() {
             active_threshold: Some(ActiveThreshold::Percentage {
                 percent: Decimal::percent(0),
             }),
+            receipt_token: None,
         },
     );
 }
@@ -1218,6 +1268,7 @@ fn test_active_threshold_absolute_count_invalid() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::new(30001),
             }),
+            receipt_token: None,
         },
     );
 }
@@ -1237,6 +1288,7 @@ fn test_add_remove_hooks() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -1313,6 +1365,7 @@ fn test_staking_hooks() {
             },
             unstaking_duration: Some(Duration::Height(5)),
             active_threshold: None,
+            receipt_token: None,
         },
     );
 
@@ -1342,6 +1395,1060 @@ fn test_staking_hooks() {
     assert_eq!("stake_hook", res.events.last().unwrap().attributes[1].value);
 }
 
+fn update_lockup_config(
+    app: &mut App,
+    staking_addr: Addr,
+    sender: &str,
+    tiers: Vec<LockupTier>,
+    decay: bool,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        staking_addr,
+        &ExecuteMsg::UpdateLockupConfig { tiers, decay },
+        &[],
+    )
+}
+
+fn lock_tokens(
+    app: &mut App,
+    staking_addr: Addr,
+    sender: &str,
+    amount: u128,
+    duration: Duration,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        staking_addr,
+        &ExecuteMsg::Lock {
+            amount: Uint128::new(amount),
+            duration,
+        },
+        &[],
+    )
+}
+
+fn refresh_locks(app: &mut App, staking_addr: Addr, address: &str) {
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        staking_addr,
+        &ExecuteMsg::RefreshLocks {
+            address: address.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn get_locks(app: &mut App, staking_addr: Addr, address: &str) -> LocksResponse {
+    app.wrap()
+        .query_wasm_smart(
+            staking_addr,
+            &QueryMsg::Locks {
+                address: address.to_string(),
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_update_lockup_config() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(dao_voting_token_staked_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: None,
+            active_threshold: None,
+            receipt_token: None,
+        },
+    );
+
+    let tier = |blocks, boost| LockupTier {
+        duration: Duration::Height(blocks),
+        boost: Decimal::percent(boost),
+    };
+
+    let err: ContractError = update_lockup_config(&mut app, addr.clone(), ADDR1, vec![], false)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err: ContractError =
+        update_lockup_config(&mut app, addr.clone(), DAO_ADDR, vec![tier(10, 50)], false)
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+    assert_eq!(
+        err,
+        ContractError::InvalidLockupTiers {
+            reason: "boosts must be at least one".to_string()
+        }
+    );
+
+    let err: ContractError = update_lockup_config(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        vec![tier(10, 150), tier(10, 200)],
+        false,
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(
+        err,
+        ContractError::InvalidLockupTiers {
+            reason: "duplicate tier duration (height: 10)".to_string()
+        }
+    );
+
+    // Voting power is queried by height, so lock durations must be in
+    // blocks.
+    let err: ContractError = update_lockup_config(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        vec![LockupTier {
+            duration: Duration::Time(10),
+            boost: Decimal::percent(150),
+        }],
+        false,
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(
+        err,
+        ContractError::InvalidLockupTiers {
+            reason: "lockup durations must be in blocks".to_string()
+        }
+    );
+
+    update_lockup_config(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        vec![tier(10, 150), tier(100, 300)],
+        true,
+    )
+    .unwrap();
+    let config: crate::state::LockupConfig = app
+        .wrap()
+        .query_wasm_smart(addr, &QueryMsg::LockupConfig {})
+        .unwrap();
+    assert_eq!(config.tiers, vec![tier(10, 150), tier(100, 300)]);
+    assert!(config.decay);
+}
+
+#[test]
+fn test_lock() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(dao_voting_token_staked_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: None,
+            active_threshold: None,
+            receipt_token: None,
+        },
+    );
+    update_lockup_config(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        vec![LockupTier {
+            duration: Duration::Height(10),
+            boost: Decimal::percent(200),
+        }],
+        false,
+    )
+    .unwrap();
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    stake_tokens(&mut app, addr.clone(), ADDR2, 50, DENOM).unwrap();
+    app.update_block(next_block);
+    let before_lock = app.block_info().height;
+
+    // Only tiers configured by the DAO may be used.
+    let err: ContractError = lock_tokens(&mut app, addr.clone(), ADDR1, 60, Duration::Height(5))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::LockupTierNotFound {
+            duration: Duration::Height(5)
+        }
+    );
+    // Can't lock more than is staked.
+    let err: ContractError = lock_tokens(&mut app, addr.clone(), ADDR1, 101, Duration::Height(10))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::LockedStake {
+            locked: Uint128::zero()
+        }
+    );
+
+    lock_tokens(&mut app, addr.clone(), ADDR1, 60, Duration::Height(10)).unwrap();
+    app.update_block(next_block);
+
+    // Locked tokens count double.
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(power.power, Uint128::new(160));
+    let total = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(total.power, Uint128::new(210));
+    // Historical queries are unaffected.
+    let power =
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), Some(before_lock));
+    assert_eq!(power.power, Uint128::new(100));
+
+    // Locked tokens may not be unstaked or locked again.
+    let err: ContractError = unstake_tokens(&mut app, addr.clone(), ADDR1, 50)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::LockedStake {
+            locked: Uint128::new(60)
+        }
+    );
+    let err: ContractError = lock_tokens(&mut app, addr.clone(), ADDR1, 50, Duration::Height(10))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::LockedStake {
+            locked: Uint128::new(60)
+        }
+    );
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 40).unwrap();
+
+    let locks = get_locks(&mut app, addr.clone(), ADDR1);
+    assert_eq!(locks.locked, Uint128::new(60));
+    assert_eq!(locks.bonus, Uint128::new(60));
+    assert_eq!(locks.locks.len(), 1);
+    let expiration = locks.locks[0].expiration;
+    assert_eq!(expiration, before_lock + 10);
+
+    // The lock's bonus is removed once it expires, without any
+    // transaction.
+    app.update_block(|block| block.height = expiration - 1);
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(power.power, Uint128::new(120));
+    app.update_block(next_block);
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(power.power, Uint128::new(60));
+    let total = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(total.power, Uint128::new(110));
+    assert_eq!(
+        get_locks(&mut app, addr.clone(), ADDR1),
+        LocksResponse {
+            locks: vec![],
+            locked: Uint128::zero(),
+            bonus: Uint128::zero(),
+        }
+    );
+    // Past heights keep the bonus the lock had then.
+    let total = get_total_power_at_height(&mut app, addr.clone(), Some(expiration - 1));
+    assert_eq!(total.power, Uint128::new(170));
+
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 60).unwrap();
+    refresh_locks(&mut app, addr, ADDR1);
+}
+
+#[test]
+fn test_lock_decay() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(dao_voting_token_staked_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: None,
+            active_threshold: None,
+            receipt_token: None,
+        },
+    );
+    update_lockup_config(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        vec![LockupTier {
+            duration: Duration::Height(100),
+            boost: Decimal::percent(300),
+        }],
+        true,
+    )
+    .unwrap();
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    lock_tokens(&mut app, addr.clone(), ADDR1, 100, Duration::Height(100)).unwrap();
+    app.update_block(next_block);
+    let locked_at = app.block_info().height;
+    // The bonus decays from the height the lock was created at.
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(power.power, Uint128::new(298));
+
+    // The boost decays linearly as the lock approaches expiry,
+    // without the lock being refreshed.
+    app.update_block(|block| block.height += 49);
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(power.power, Uint128::new(200));
+    let total = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(total.power, Uint128::new(200));
+
+    // Locks that do not decay are added to the same total.
+    stake_tokens(&mut app, addr.clone(), ADDR2, 100, DENOM).unwrap();
+    update_lockup_config(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        vec![LockupTier {
+            duration: Duration::Height(20),
+            boost: Decimal::percent(200),
+        }],
+        false,
+    )
+    .unwrap();
+    lock_tokens(&mut app, addr.clone(), ADDR2, 100, Duration::Height(20)).unwrap();
+    app.update_block(|block| block.height += 10);
+    let total = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(total.power, Uint128::new(200 + 80 + 100));
+
+    // Both locks have expired.
+    app.update_block(|block| block.height += 50);
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(power.power, Uint128::new(100));
+    let total = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(total.power, Uint128::new(200));
+
+    // Past heights compute the bonus at that height.
+    let power =
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), Some(locked_at));
+    assert_eq!(power.power, Uint128::new(298));
+    let total = get_total_power_at_height(&mut app, addr, Some(locked_at + 39));
+    assert_eq!(total.power, Uint128::new(220));
+}
+
+#[test]
+fn test_receipt_token_transfer() {
+    const RECEIPT_DENOM: &str = "factory/issuer/ustjuno";
+    const ISSUER: &str = "issuer";
+
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(DAO_ADDR, &[]),
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: None,
+            active_threshold: None,
+            receipt_token: None,
+        },
+    )
+    .unwrap();
+    // The receipt issuer can not be instantiated without Token
+    // Factory, so set it up directly.
+    state::RECEIPT_DENOM
+        .save(deps.as_mut().storage, &RECEIPT_DENOM.to_string())
+        .unwrap();
+    state::RECEIPT_ISSUER_CONTRACT
+        .save(deps.as_mut().storage, &Addr::unchecked(ISSUER))
+        .unwrap();
+    state::LOCKUP_CONFIG
+        .save(
+            deps.as_mut().storage,
+            &LockupConfig {
+                tiers: vec![LockupTier {
+                    duration: Duration::Height(10),
+                    boost: Decimal::one(),
+                }],
+                decay: false,
+            },
+        )
+        .unwrap();
+
+    // Staking mints receipts
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADDR1, &coins(100, DENOM)),
+        ExecuteMsg::Stake {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: ISSUER.to_string(),
+            msg: to_json_binary(&IssuerExecuteMsg::Mint {
+                to_address: ADDR1.to_string(),
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADDR1, &[]),
+        ExecuteMsg::Lock {
+            amount: Uint128::new(60),
+            duration: Duration::Height(10),
+        },
+    )
+    .unwrap();
+    env.block.height += 1;
+
+    let transfer = |amount: u128| ContractSudoMsg::BlockBeforeSend {
+        from: ADDR1.to_string(),
+        to: ADDR2.to_string(),
+        amount: Coin::new(amount, RECEIPT_DENOM),
+    };
+
+    // Locked stake may not be transferred
+    let err = sudo(deps.as_mut(), env.clone(), transfer(50)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::LockedStake {
+            locked: Uint128::new(60)
+        }
+    );
+
+    // Transferring receipts moves voting power at the next height
+    sudo(deps.as_mut(), env.clone(), transfer(40)).unwrap();
+    let power = |deps: Deps, address: &str, height: u64| {
+        query_voting_power_at_height(deps, env.clone(), address.to_string(), Some(height))
+            .unwrap()
+            .power
+    };
+    assert_eq!(
+        power(deps.as_ref(), ADDR1, env.block.height),
+        Uint128::new(100)
+    );
+    assert_eq!(
+        power(deps.as_ref(), ADDR2, env.block.height),
+        Uint128::zero()
+    );
+    assert_eq!(
+        power(deps.as_ref(), ADDR1, env.block.height + 1),
+        Uint128::new(60)
+    );
+    assert_eq!(
+        power(deps.as_ref(), ADDR2, env.block.height + 1),
+        Uint128::new(40)
+    );
+    let total = query_total_power_at_height(deps.as_ref(), env.clone(), Some(env.block.height + 1))
+        .unwrap()
+        .power;
+    assert_eq!(total, Uint128::new(100));
+
+    // Sends of other denoms, and minting and returning receipts, do not
+    // move voting power.
+    for msg in [
+        ContractSudoMsg::BlockBeforeSend {
+            from: ADDR1.to_string(),
+            to: ADDR2.to_string(),
+            amount: Coin::new(60, DENOM),
+        },
+        ContractSudoMsg::BlockBeforeSend {
+            from: ISSUER.to_string(),
+            to: ADDR2.to_string(),
+            amount: Coin::new(60, RECEIPT_DENOM),
+        },
+        ContractSudoMsg::BlockBeforeSend {
+            from: ADDR2.to_string(),
+            to: env.contract.address.to_string(),
+            amount: Coin::new(40, RECEIPT_DENOM),
+        },
+    ] {
+        sudo(deps.as_mut(), env.clone(), msg).unwrap();
+    }
+    assert_eq!(
+        power(deps.as_ref(), ADDR1, env.block.height + 1),
+        Uint128::new(60)
+    );
+    assert_eq!(
+        power(deps.as_ref(), ADDR2, env.block.height + 1),
+        Uint128::new(40)
+    );
+
+    // Unstaking requires returning receipts
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADDR2, &coins(20, RECEIPT_DENOM)),
+        ExecuteMsg::Unstake {
+            amount: Uint128::new(40),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidReceiptAmount {
+            expected: Uint128::new(40)
+        }
+    );
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADDR2, &coins(40, RECEIPT_DENOM)),
+        ExecuteMsg::Unstake {
+            amount: Uint128::new(40),
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_stake_for() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(dao_voting_token_staked_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: None,
+            active_threshold: None,
+            receipt_token: None,
+        },
+    );
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            addr.clone(),
+            &ExecuteMsg::StakeFor {
+                recipient: ADDR2.to_string(),
+            },
+            &coins(100, DENOM),
+        )
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("recipient", ADDR2)));
+    app.update_block(next_block);
+
+    // The recipient has the voting power and may unstake.
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(power.power, Uint128::zero());
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None);
+    assert_eq!(power.power, Uint128::new(100));
+
+    let err: ContractError = unstake_tokens(&mut app, addr.clone(), ADDR1, 100)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidUnstakeAmount {});
+    unstake_tokens(&mut app, addr, ADDR2, 100).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR2, DENOM), Uint128::new(10100));
+}
+
+#[test]
+fn test_transfer_stake() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(dao_voting_token_staked_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: Some(Duration::Height(5)),
+            active_threshold: None,
+            receipt_token: None,
+        },
+    );
+
+    let hook_id = app.store_code(dao_proposal_hook_counter_contract());
+    let hook = app
+        .instantiate_contract(
+            hook_id,
+            Addr::unchecked(DAO_ADDR),
+            &dao_proposal_hook_counter::msg::InstantiateMsg {
+                should_error: false,
+            },
+            &[],
+            "hook counter".to_string(),
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        addr.clone(),
+        &ExecuteMsg::AddHook {
+            addr: hook.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+
+    let transfer = |app: &mut App, to: &str, amount: u128| {
+        app.execute_contract(
+            Addr::unchecked(ADDR1),
+            addr.clone(),
+            &ExecuteMsg::TransferStake {
+                to: to.to_string(),
+                amount: Uint128::new(amount),
+            },
+            &[],
+        )
+    };
+
+    // Transfers are disabled by default.
+    let err: ContractError = transfer(&mut app, ADDR2, 40)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::StakeTransfersDisabled {});
+
+    // Only the DAO may enable them.
+    let enable = ExecuteMsg::UpdateConfig {
+        duration: Some(Duration::Height(5)),
+        stake_transfers_enabled: Some(true),
+        early_unstake: None,
+    };
+    let err: ContractError = app
+        .execute_contract(Addr::unchecked(ADDR1), addr.clone(), &enable, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    app.execute_contract(Addr::unchecked(DAO_ADDR), addr.clone(), &enable, &[])
+        .unwrap();
+    assert!(get_config(&mut app, addr.clone()).stake_transfers_enabled);
+
+    let err: ContractError = transfer(&mut app, ADDR2, 0)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroTransfer {});
+    let err: ContractError = transfer(&mut app, ADDR1, 40)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::SelfTransfer {});
+    let err: ContractError = transfer(&mut app, ADDR2, 101)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidUnstakeAmount {});
+
+    let stake_count = |app: &App| -> Uint128 {
+        app.wrap()
+            .query_wasm_smart(
+                &hook,
+                &dao_proposal_hook_counter::msg::QueryMsg::StakeCounter {},
+            )
+            .unwrap()
+    };
+    assert_eq!(stake_count(&app), Uint128::new(1));
+
+    // Stake moves without unbonding, and hooks fire for both addresses.
+    transfer(&mut app, ADDR2, 40).unwrap();
+    assert_eq!(stake_count(&app), Uint128::new(3));
+    app.update_block(next_block);
+
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(power.power, Uint128::new(60));
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None);
+    assert_eq!(power.power, Uint128::new(40));
+    let total = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(total.power, Uint128::new(100));
+    assert!(get_claims(&mut app, addr.clone(), ADDR1.to_string())
+        .claims
+        .is_empty());
+
+    // Disabling transfers with a later config update stops them.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        addr.clone(),
+        &ExecuteMsg::UpdateConfig {
+            duration: Some(Duration::Height(5)),
+            stake_transfers_enabled: Some(false),
+            early_unstake: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = transfer(&mut app, ADDR2, 10)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::StakeTransfersDisabled {});
+}
+
+#[test]
+fn test_max_power_per_address() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(dao_voting_token_staked_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: None,
+            active_threshold: None,
+            receipt_token: None,
+        },
+    );
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    stake_tokens(&mut app, addr.clone(), ADDR2, 50, DENOM).unwrap();
+
+    // Only the DAO may set the cap, and it must be valid.
+    let update_cap = |app: &mut App, sender: &str, cap: Option<PowerCap>| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            addr.clone(),
+            &ExecuteMsg::UpdateMaxPowerPerAddress {
+                max_power_per_address: cap,
+            },
+            &[],
+        )
+    };
+    let cap = PowerCap::AbsoluteCount {
+        count: Uint128::new(60),
+    };
+    let err: ContractError = update_cap(&mut app, ADDR1, Some(cap.clone()))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err: ContractError = update_cap(
+        &mut app,
+        DAO_ADDR,
+        Some(PowerCap::AbsoluteCount {
+            count: Uint128::zero(),
+        }),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(
+        err,
+        ContractError::PowerCapError(PowerCapError::ZeroCount {})
+    );
+    update_cap(&mut app, DAO_ADDR, Some(cap.clone())).unwrap();
+
+    let res: PowerCapResponse = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::MaxPowerPerAddress {})
+        .unwrap();
+    assert_eq!(res.max_power_per_address, Some(cap));
+
+    // Existing stakers are capped once refreshed.
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        addr.clone(),
+        &ExecuteMsg::RefreshPowerCap {
+            address: ADDR1.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(power.power, Uint128::new(60));
+    let total = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(total.power, Uint128::new(110));
+
+    // Stakers are capped when their stake changes.
+    stake_tokens(&mut app, addr.clone(), ADDR2, 20, DENOM).unwrap();
+    app.update_block(next_block);
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None);
+    assert_eq!(power.power, Uint128::new(60));
+    let total = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(total.power, Uint128::new(120));
+
+    // Percentage caps are of the uncapped total, 170.
+    update_cap(
+        &mut app,
+        DAO_ADDR,
+        Some(PowerCap::Percentage {
+            percent: Decimal::percent(50),
+        }),
+    )
+    .unwrap();
+    for address in [ADDR1, ADDR2] {
+        app.execute_contract(
+            Addr::unchecked(address),
+            addr.clone(),
+            &ExecuteMsg::RefreshPowerCap {
+                address: address.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    app.update_block(next_block);
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(power.power, Uint128::new(85));
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None);
+    assert_eq!(power.power, Uint128::new(70));
+    let total = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(total.power, Uint128::new(155));
+
+    // Unstaking below the cap removes it.
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 50).unwrap();
+    app.update_block(next_block);
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(power.power, Uint128::new(50));
+    let total = get_total_power_at_height(&mut app, addr, None);
+    assert_eq!(total.power, Uint128::new(120));
+}
+
+#[test]
+fn test_instant_unstake() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(dao_voting_token_staked_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: Some(Duration::Height(100)),
+            active_threshold: None,
+            receipt_token: None,
+        },
+    );
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+
+    let instant_unstake = |app: &mut App, amount: u128| {
+        app.execute_contract(
+            Addr::unchecked(ADDR1),
+            addr.clone(),
+            &ExecuteMsg::InstantUnstake {
+                amount: Uint128::new(amount),
+            },
+            &[],
+        )
+    };
+    let set_early_unstake = |app: &mut App, early_unstake: Option<EarlyUnstakeUpdate>| {
+        app.execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                duration: Some(Duration::Height(100)),
+                stake_transfers_enabled: None,
+                early_unstake,
+            },
+            &[],
+        )
+    };
+
+    // Early unstaking is disabled by default.
+    let err: ContractError = instant_unstake(&mut app, 10)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::EarlyUnstakeDisabled {});
+
+    let err: ContractError = set_early_unstake(
+        &mut app,
+        Some(EarlyUnstakeUpdate::Set(EarlyUnstakeConfig {
+            penalty: Decimal::zero(),
+            destination: PenaltyDestination::Dao,
+        })),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(
+        err,
+        ContractError::EarlyUnstakeError(EarlyUnstakeError::InvalidPenalty {})
+    );
+
+    // Penalties sent to the DAO.
+    set_early_unstake(
+        &mut app,
+        Some(EarlyUnstakeUpdate::Set(EarlyUnstakeConfig {
+            penalty: Decimal::percent(10),
+            destination: PenaltyDestination::Dao,
+        })),
+    )
+    .unwrap();
+    let err: ContractError = instant_unstake(&mut app, 0)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroUnstake {});
+    let err: ContractError = instant_unstake(&mut app, 101)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidUnstakeAmount {});
+
+    instant_unstake(&mut app, 50).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), Uint128::new(9945));
+    assert_eq!(get_balance(&mut app, DAO_ADDR, DENOM), Uint128::new(10005));
+    assert!(get_claims(&mut app, addr.clone(), ADDR1.to_string())
+        .claims
+        .is_empty());
+    app.update_block(next_block);
+    let power = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(power.power, Uint128::new(50));
+    let total = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(total.power, Uint128::new(50));
+
+    // Penalties burned. Rounds up.
+    set_early_unstake(
+        &mut app,
+        Some(EarlyUnstakeUpdate::Set(EarlyUnstakeConfig {
+            penalty: Decimal::percent(10),
+            destination: PenaltyDestination::Burn,
+        })),
+    )
+    .unwrap();
+    let supply = app.wrap().query_supply(DENOM).unwrap().amount;
+    instant_unstake(&mut app, 15).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), Uint128::new(9958));
+    assert_eq!(get_balance(&mut app, DAO_ADDR, DENOM), Uint128::new(10005));
+    assert_eq!(
+        app.wrap().query_supply(DENOM).unwrap().amount,
+        supply - Uint128::new(2)
+    );
+
+    // Config updates that omit early unstaking leave it unchanged.
+    set_early_unstake(&mut app, None).unwrap();
+    instant_unstake(&mut app, 10).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), Uint128::new(9967));
+
+    // Disabling early unstaking with a later config update stops it.
+    set_early_unstake(&mut app, Some(EarlyUnstakeUpdate::Disable {})).unwrap();
+    let err: ContractError = instant_unstake(&mut app, 10)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::EarlyUnstakeDisabled {});
+}
+
+#[test]
+fn test_accelerate_claim() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(dao_voting_token_staked_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: Some(Duration::Height(100)),
+            active_threshold: None,
+            receipt_token: None,
+        },
+    );
+
+    let accelerate_claim = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked(ADDR1),
+            addr.clone(),
+            &ExecuteMsg::AccelerateClaim {},
+            &[],
+        )
+    };
+    let update_config =
+        |app: &mut App, duration: Option<Duration>, early_unstake: Option<EarlyUnstakeUpdate>| {
+            app.execute_contract(
+                Addr::unchecked(DAO_ADDR),
+                addr.clone(),
+                &ExecuteMsg::UpdateConfig {
+                    duration,
+                    stake_transfers_enabled: None,
+                    early_unstake,
+                },
+                &[],
+            )
+        };
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 1000, DENOM).unwrap();
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 1000).unwrap();
+
+    let err: ContractError = accelerate_claim(&mut app).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::EarlyUnstakeDisabled {});
+
+    update_config(
+        &mut app,
+        Some(Duration::Height(100)),
+        Some(EarlyUnstakeUpdate::Set(EarlyUnstakeConfig {
+            penalty: Decimal::percent(10),
+            destination: PenaltyDestination::Dao,
+        })),
+    )
+    .unwrap();
+
+    // With three quarters of the unstaking duration elapsed, a
+    // quarter of the penalty is paid.
+    app.update_block(|block| block.height += 75);
+    accelerate_claim(&mut app).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), Uint128::new(9975));
+    assert_eq!(get_balance(&mut app, DAO_ADDR, DENOM), Uint128::new(10025));
+    assert!(get_claims(&mut app, addr.clone(), ADDR1.to_string())
+        .claims
+        .is_empty());
+
+    let err: ContractError = accelerate_claim(&mut app).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    // Claims that have already been released are free.
+    stake_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 100).unwrap();
+    app.update_block(|block| block.height += 100);
+    accelerate_claim(&mut app).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), Uint128::new(9975));
+    assert_eq!(get_balance(&mut app, DAO_ADDR, DENOM), Uint128::new(10025));
+
+    // Claims are priced from their own unstaking duration, even if it
+    // has since changed.
+    stake_tokens(&mut app, addr.clone(), ADDR1, 200, DENOM).unwrap();
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 200).unwrap();
+    update_config(&mut app, Some(Duration::Height(10)), None).unwrap();
+    app.update_block(|block| block.height += 50);
+    accelerate_claim(&mut app).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), Uint128::new(9965));
+    assert_eq!(get_balance(&mut app, DAO_ADDR, DENOM), Uint128::new(10035));
+
+    // Without an unstaking duration, accelerating claims is free.
+    stake_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 100).unwrap();
+    update_config(&mut app, None, None).unwrap();
+    accelerate_claim(&mut app).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), Uint128::new(9965));
+    assert_eq!(get_balance(&mut app, DAO_ADDR, DENOM), Uint128::new(10035));
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
@@ -1,5 +1,7 @@
 use crate::{
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg, TokenInfo},
+    msg::{
+        ExecuteMsg, InstantiateMsg, QueryMsg, ReceiptTokenInfo, ReceiptTokenResponse, TokenInfo,
+    },
     tests::test_tube::test_env::TokenVotingContract,
     ContractError,
 };
@@ -11,6 +13,7 @@ use dao_interface::{
     msg::QueryMsg as DaoQueryMsg,
     state::{Admin, ModuleInstantiateInfo},
     token::{InitialBalance, NewDenomMetadata, NewTokenInfo},
+    voting::TotalPowerAtHeightResponse,
 };
 use dao_testing::test_tube::{cw_tokenfactory_issuer::TokenfactoryIssuer, dao_dao_core::DaoCore};
 use dao_voting::{
@@ -18,8 +21,11 @@ use dao_voting::{
     threshold::{ActiveThreshold, ActiveThresholdError, PercentageThreshold, Threshold},
 };
 use osmosis_test_tube::{
-    osmosis_std::types::cosmos::bank::v1beta1::QueryBalanceRequest, Account, OsmosisTestApp,
-    RunnerError,
+    osmosis_std::types::cosmos::{
+        bank::v1beta1::{MsgSend, QueryBalanceRequest},
+        base::v1beta1::Coin as BaseCoin,
+    },
+    Account, OsmosisTestApp, RunnerError,
 };
 
 use super::test_env::{TestEnv, TestEnvBuilder, DENOM};
@@ -132,6 +138,7 @@ fn test_instantiate_no_dao_balance() {
                 }),
                 unstaking_duration: None,
                 active_threshold: None,
+                receipt_token: None,
             },
             dao,
         )
@@ -185,6 +192,7 @@ fn test_instantiate_no_metadata() {
             }),
             unstaking_duration: None,
             active_threshold: None,
+            receipt_token: None,
         },
         dao,
     )
@@ -226,6 +234,7 @@ fn test_instantiate_invalid_metadata_fails() {
             }),
             unstaking_duration: None,
             active_threshold: None,
+            receipt_token: None,
         },
         dao,
     )
@@ -270,6 +279,7 @@ fn test_instantiate_invalid_active_threshold_count_fails() {
                 active_threshold: Some(ActiveThreshold::AbsoluteCount {
                     count: Uint128::new(1000),
                 }),
+                receipt_token: None,
             },
             dao,
         )
@@ -314,6 +324,7 @@ fn test_instantiate_no_initial_balances_fails() {
                 }),
                 unstaking_duration: None,
                 active_threshold: None,
+                receipt_token: None,
             },
             dao,
         )
@@ -377,6 +388,7 @@ fn test_factory() {
                 active_threshold: Some(ActiveThreshold::AbsoluteCount {
                     count: Uint128::new(75),
                 }),
+                receipt_token: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -487,6 +499,7 @@ fn test_factory_funds_pass_through() {
                 active_threshold: Some(ActiveThreshold::AbsoluteCount {
                     count: Uint128::new(75),
                 }),
+                receipt_token: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -561,6 +574,7 @@ fn test_factory_funds_pass_through() {
             active_threshold: Some(ActiveThreshold::AbsoluteCount {
                 count: Uint128::new(75),
             }),
+            receipt_token: None,
         })
         .unwrap(),
         admin: Some(Admin::CoreModule {}),
@@ -613,6 +627,7 @@ fn test_factory_no_callback() {
                 active_threshold: Some(ActiveThreshold::AbsoluteCount {
                     count: Uint128::new(75),
                 }),
+                receipt_token: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -694,6 +709,7 @@ fn test_factory_wrong_callback() {
                 active_threshold: Some(ActiveThreshold::AbsoluteCount {
                     count: Uint128::new(75),
                 }),
+                receipt_token: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -734,3 +750,141 @@ fn test_factory_wrong_callback() {
         }
     );
 }
+
+#[test]
+fn test_receipt_token() {
+    let app = OsmosisTestApp::new();
+    let env = TestEnvBuilder::new().default_setup(&app);
+    let tf_issuer_id = env.get_tf_issuer_code_id();
+
+    let dao = app
+        .init_account(&[Coin::new(100000000000, "uosmo")])
+        .unwrap();
+
+    let vp_contract = env
+        .instantiate(
+            &InstantiateMsg {
+                token_info: TokenInfo::New(NewTokenInfo {
+                    token_issuer_code_id: tf_issuer_id,
+                    subdenom: "ucat".to_string(),
+                    metadata: None,
+                    initial_balances: vec![InitialBalance {
+                        amount: Uint128::new(100),
+                        address: env.accounts[0].address(),
+                    }],
+                    initial_dao_balance: None,
+                }),
+                unstaking_duration: None,
+                active_threshold: None,
+                receipt_token: Some(ReceiptTokenInfo {
+                    token_issuer_code_id: tf_issuer_id,
+                    subdenom: "stcat".to_string(),
+                }),
+            },
+            dao,
+        )
+        .unwrap();
+
+    let denom = vp_contract.query_denom().unwrap().denom;
+    let receipt_denom = vp_contract
+        .query::<Option<ReceiptTokenResponse>>(&QueryMsg::ReceiptToken {})
+        .unwrap()
+        .unwrap()
+        .denom;
+
+    // Staking issues receipts
+    vp_contract
+        .execute(
+            &ExecuteMsg::Stake {},
+            &[Coin::new(100, denom.clone())],
+            &env.accounts[0],
+        )
+        .unwrap();
+    let bal = env
+        .bank()
+        .query_balance(&QueryBalanceRequest {
+            address: env.accounts[0].address(),
+            denom: receipt_denom.clone(),
+        })
+        .unwrap();
+    assert_eq!(bal.balance.unwrap().amount, Uint128::new(100).to_string());
+
+    // Transferring receipts transfers voting power
+    env.bank()
+        .send(
+            MsgSend {
+                from_address: env.accounts[0].address(),
+                to_address: env.accounts[1].address(),
+                amount: vec![BaseCoin {
+                    denom: receipt_denom.clone(),
+                    amount: "40".to_string(),
+                }],
+            },
+            &env.accounts[0],
+        )
+        .unwrap();
+    app.increase_time(1);
+
+    let vp = vp_contract
+        .query_vp(&env.accounts[0].address(), None)
+        .unwrap();
+    assert_eq!(vp.power, Uint128::new(60));
+    let vp = vp_contract
+        .query_vp(&env.accounts[1].address(), None)
+        .unwrap();
+    assert_eq!(vp.power, Uint128::new(40));
+    let total: TotalPowerAtHeightResponse = vp_contract
+        .query(&QueryMsg::TotalPowerAtHeight { height: None })
+        .unwrap();
+    assert_eq!(total.power, Uint128::new(100));
+
+    // Unstaking requires returning receipts
+    let err = vp_contract
+        .execute(
+            &ExecuteMsg::Unstake {
+                amount: Uint128::new(40),
+            },
+            &[Coin::new(20, receipt_denom.clone())],
+            &env.accounts[1],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenVotingContract::execute_error(ContractError::InvalidReceiptAmount {
+            expected: Uint128::new(40)
+        })
+    );
+
+    // The recipient of the receipts may unstake them
+    vp_contract
+        .execute(
+            &ExecuteMsg::Unstake {
+                amount: Uint128::new(40),
+            },
+            &[Coin::new(40, receipt_denom.clone())],
+            &env.accounts[1],
+        )
+        .unwrap();
+    app.increase_time(1);
+
+    let vp = vp_contract
+        .query_vp(&env.accounts[1].address(), None)
+        .unwrap();
+    assert_eq!(vp.power, Uint128::zero());
+    let bal = env
+        .bank()
+        .query_balance(&QueryBalanceRequest {
+            address: env.accounts[1].address(),
+            denom: receipt_denom,
+        })
+        .unwrap();
+    assert_eq!(bal.balance.unwrap().amount, Uint128::zero().to_string());
+    let bal = env
+        .bank()
+        .query_balance(&QueryBalanceRequest {
+            address: env.accounts[1].address(),
+            denom,
+        })
+        .unwrap();
+    assert_eq!(bal.balance.unwrap().amount, Uint128::new(40).to_string());
+}
@@ -156,6 +156,7 @@ impl TestEnvBuilder {
                 active_threshold: Some(ActiveThreshold::AbsoluteCount {
                     count: Uint128::new(75),
                 }),
+                receipt_token: None,
             },
             &accounts[0],
         )
@@ -228,6 +229,7 @@ impl TestEnvBuilder {
                     active_threshold: Some(ActiveThreshold::AbsoluteCount {
                         count: Uint128::new(75),
                     }),
+                    receipt_token: None,
                 })
                 .unwrap(),
                 admin: Some(Admin::CoreModule {}),
@@ -0,0 +1,43 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Binary;
+
+/// The query a contract must implement to be used as a condition on
+/// proposal submission by a pre-propose module.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum Query {
+    /// Returns whether PROPOSER may submit a proposal. MSG is the
+    /// serialized proposal message being submitted, or `None` if the
+    /// pre-propose module is checking whether PROPOSER may propose at
+    /// all.
+    #[returns(CanProposeResponse)]
+    CanPropose {
+        proposer: ::std::string::String,
+        msg: ::std::option::Option<Binary>,
+    },
+}
+
+#[cw_serde]
+pub struct CanProposeResponse {
+    /// Whether the proposer may submit the proposal.
+    pub can_propose: bool,
+    /// Why the proposer may not submit the proposal. Empty if they
+    /// may.
+    pub reasons: Vec<String>,
+}
+
+impl CanProposeResponse {
+    pub fn allowed() -> Self {
+        Self {
+            can_propose: true,
+            reasons: vec![],
+        }
+    }
+
+    pub fn denied(reason: impl Into<String>) -> Self {
+        Self {
+            can_propose: false,
+            reasons: vec![reason.into()],
+        }
+    }
+}
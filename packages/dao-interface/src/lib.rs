@@ -1,5 +1,6 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
+pub mod condition;
 pub mod migrate_msg;
 pub mod msg;
 pub mod nft;
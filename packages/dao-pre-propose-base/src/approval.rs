@@ -0,0 +1,469 @@
+//! Approver sets shared by the approval pre-propose modules. A
+//! proposal is created once `threshold` approvers have approved it,
+//! and is rejected once so many approvers have rejected it that the
+//! threshold may no longer be reached.
+
+use cosmwasm_schema::{cw_serde, schemars::JsonSchema};
+use cosmwasm_std::{
+    to_json_binary, Addr, Api, Binary, BlockInfo, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult, Storage, WasmMsg,
+};
+use cw_storage_plus::{Item, Map};
+use cw_utils::{Duration, Expiration};
+use dao_voting::deposit::CheckedDepositInfo;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    error::PreProposeError,
+    state::{OpenProposal, PreProposeContract},
+};
+
+/// The rejection reason recorded for proposals that were not decided
+/// within the approval period.
+pub const EXPIRED_REASON: &str = "approval period expired";
+
+/// The approvers of a module and how many of them must approve a
+/// proposal before it is created.
+#[cw_serde]
+pub struct Approvers {
+    pub approvers: Vec<Addr>,
+    /// The number of approvals required to create a proposal. A
+    /// proposal is rejected once enough approvers have rejected it
+    /// that the threshold may no longer be reached.
+    pub threshold: u64,
+    /// How long proposals may remain pending. If `None`, pending
+    /// proposals never expire.
+    pub approval_period: Option<Duration>,
+}
+
+/// An approver's decision on a proposal.
+#[cw_serde]
+pub enum Decision {
+    Approved {},
+    Rejected { reason: Option<String> },
+}
+
+#[cw_serde]
+pub struct Approval {
+    pub approver: Addr,
+    pub decision: Decision,
+}
+
+/// The state of a proposal after an approver's decision has been
+/// recorded.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// Enough approvers have approved the proposal for it to be
+    /// created.
+    Approved,
+    /// The threshold may no longer be reached. The reason is that
+    /// of the deciding rejection.
+    Rejected { reason: Option<String> },
+    /// The proposal remains pending.
+    Undecided { approvals: u64, rejections: u64 },
+}
+
+pub const APPROVERS: Item<Approvers> = Item::new("approvers");
+/// The decision of each approver on each proposal.
+pub const DECISIONS: Map<(u64, &Addr), Decision> = Map::new("decisions");
+
+/// A proposal held by an approval module until its approvers have
+/// decided on it.
+pub trait ApprovalProposal: Serialize + DeserializeOwned {
+    /// The ID used to identify this proposal while it is pending.
+    fn approval_id(&self) -> u64;
+    /// The address that submitted this proposal.
+    fn proposer(&self) -> &Addr;
+    /// Snapshot of the deposit info at the time of submission.
+    fn deposit(&self) -> Option<&CheckedDepositInfo>;
+    /// When this proposal stops accepting approvals.
+    fn expiration(&self) -> &Expiration;
+    /// The message that creates this proposal in the proposal module.
+    fn propose_msg(&self) -> StdResult<Binary>;
+    /// Marks this proposal as approved and created in the proposal
+    /// module with CREATED_PROPOSAL_ID.
+    fn set_approved(&mut self, created_proposal_id: u64);
+    /// Marks this proposal as rejected for REASON.
+    fn set_rejected(&mut self, reason: Option<String>);
+}
+
+/// The proposals of an approval module.
+pub struct ApprovalProposals<P> {
+    /// Proposals awaiting a decision, by approval ID.
+    pub pending: Map<'static, u64, P>,
+    /// Proposals that have been approved or rejected, by approval ID.
+    pub completed: Map<'static, u64, P>,
+    /// Map between the IDs of proposals created in the proposal
+    /// module and the approval IDs they were created from.
+    pub created_to_completed: Map<'static, u64, u64>,
+}
+
+impl<P> ApprovalProposals<P> {
+    pub const fn new(
+        pending_key: &'static str,
+        completed_key: &'static str,
+        created_to_completed_key: &'static str,
+    ) -> Self {
+        Self {
+            pending: Map::new(pending_key),
+            completed: Map::new(completed_key),
+            created_to_completed: Map::new(created_to_completed_key),
+        }
+    }
+}
+
+impl<P> Default for ApprovalProposals<P> {
+    fn default() -> Self {
+        Self::new(
+            "pending_proposals",
+            "completed_proposals",
+            "created_to_completed_proposal",
+        )
+    }
+}
+
+impl Approvers {
+    pub fn new(
+        api: &dyn Api,
+        approvers: Vec<String>,
+        threshold: u64,
+        approval_period: Option<Duration>,
+    ) -> Result<Self, PreProposeError> {
+        let mut checked: Vec<Addr> = Vec::with_capacity(approvers.len());
+        for approver in approvers {
+            let approver = api.addr_validate(&approver)?;
+            if checked.contains(&approver) {
+                return Err(PreProposeError::InvalidApprovers {
+                    reason: format!("{approver} is listed more than once"),
+                });
+            }
+            checked.push(approver);
+        }
+        if threshold == 0 || threshold > checked.len() as u64 {
+            return Err(PreProposeError::InvalidApprovers {
+                reason: "threshold must be between one and the number of approvers".to_string(),
+            });
+        }
+        Ok(Self {
+            approvers: checked,
+            threshold,
+            approval_period,
+        })
+    }
+
+    /// When a proposal submitted at BLOCK stops accepting approvals.
+    pub fn expiration(&self, block: &BlockInfo) -> Expiration {
+        self.approval_period
+            .map_or(Expiration::Never {}, |period| period.after(block))
+    }
+
+    /// Fails if SENDER is neither an approver nor DAO.
+    pub fn check_approver_or_dao(&self, sender: &Addr, dao: &Addr) -> Result<(), PreProposeError> {
+        if !self.approvers.contains(sender) && dao != sender {
+            return Err(PreProposeError::Unauthorized {});
+        }
+        Ok(())
+    }
+}
+
+/// Loads the approver set, failing if SENDER is not an approver.
+pub fn load_for_approver(
+    storage: &dyn Storage,
+    sender: &Addr,
+) -> Result<Approvers, PreProposeError> {
+    let approvers = APPROVERS.load(storage)?;
+    if !approvers.approvers.contains(sender) {
+        return Err(PreProposeError::Unauthorized {});
+    }
+    Ok(approvers)
+}
+
+/// Records SENDER's decision on proposal ID and returns whether the
+/// proposal has been decided.
+///
+/// An approver may not change their decision, but may repeat it to
+/// have the proposal re-evaluated against the current threshold, for
+/// example after the DAO has lowered it. Only decisions by current
+/// approvers count, so decisions made by approvers that have since
+/// been replaced are ignored.
+pub fn decide(
+    storage: &mut dyn Storage,
+    sender: &Addr,
+    id: u64,
+    decision: Decision,
+) -> Result<Outcome, PreProposeError> {
+    let Approvers {
+        approvers,
+        threshold,
+        ..
+    } = load_for_approver(storage, sender)?;
+
+    let already_decided = DECISIONS.has(storage, (id, sender));
+    let decision = if already_decided {
+        DECISIONS.load(storage, (id, sender))?
+    } else {
+        DECISIONS.save(storage, (id, sender), &decision)?;
+        decision
+    };
+
+    let (approvals, rejections) = tally(storage, id, &approvers)?;
+    if approvals >= threshold {
+        Ok(Outcome::Approved)
+    } else if rejections > approvers.len() as u64 - threshold {
+        let reason = match decision {
+            Decision::Rejected { reason } => reason,
+            Decision::Approved {} => None,
+        };
+        Ok(Outcome::Rejected { reason })
+    } else if already_decided {
+        Err(PreProposeError::AlreadyDecided {})
+    } else {
+        Ok(Outcome::Undecided {
+            approvals,
+            rejections,
+        })
+    }
+}
+
+/// Counts the approvals and rejections of a proposal by APPROVERS.
+fn tally(storage: &dyn Storage, id: u64, approvers: &[Addr]) -> StdResult<(u64, u64)> {
+    let mut approvals = 0;
+    let mut rejections = 0;
+    for approver in approvers {
+        match DECISIONS.may_load(storage, (id, approver))? {
+            Some(Decision::Approved {}) => approvals += 1,
+            Some(Decision::Rejected { .. }) => rejections += 1,
+            None => (),
+        }
+    }
+    Ok((approvals, rejections))
+}
+
+/// Clears the decisions made on proposal ID, for example when it is
+/// revised.
+pub fn clear_decisions(storage: &mut dyn Storage, id: u64) -> StdResult<()> {
+    let decided = DECISIONS
+        .prefix(id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for approver in decided {
+        DECISIONS.remove(storage, (id, &approver));
+    }
+    Ok(())
+}
+
+/// The decisions approvers have made on proposal ID.
+pub fn query_approvals(storage: &dyn Storage, id: u64) -> StdResult<Vec<Approval>> {
+    DECISIONS
+        .prefix(id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(approver, decision)| Approval { approver, decision }))
+        .collect()
+}
+
+/// Replaces SENDER in the approver set with ADDRESS.
+pub fn update_approver(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    sender: &Addr,
+    address: String,
+) -> Result<Approvers, PreProposeError> {
+    let mut approvers = APPROVERS.load(storage)?;
+    let position = approvers
+        .approvers
+        .iter()
+        .position(|a| a == sender)
+        .ok_or(PreProposeError::Unauthorized {})?;
+
+    let addr = api.addr_validate(&address)?;
+    if addr != *sender && approvers.approvers.contains(&addr) {
+        return Err(PreProposeError::InvalidApprovers {
+            reason: format!("{addr} is already an approver"),
+        });
+    }
+    approvers.approvers[position] = addr;
+    APPROVERS.save(storage, &approvers)?;
+    Ok(approvers)
+}
+
+impl<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
+    PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
+where
+    ProposalMessage: Serialize + DeserializeOwned,
+    QueryExt: JsonSchema,
+    MigrateExt: JsonSchema,
+{
+    /// Sends an approved proposal to the proposal module and moves it
+    /// from PROPOSALS' pending to completed proposals.
+    pub fn approve_proposal<P: ApprovalProposal>(
+        &self,
+        deps: DepsMut,
+        proposals: &ApprovalProposals<P>,
+        mut proposal: P,
+    ) -> Result<Response, PreProposeError> {
+        let id = proposal.approval_id();
+        let proposal_module = self.proposal_module.load(deps.storage)?;
+
+        // Snapshot the deposit for the proposal that we're about
+        // to create.
+        let proposal_id: u64 = deps.querier.query_wasm_smart(
+            &proposal_module,
+            &dao_interface::proposal::Query::NextProposalId {},
+        )?;
+        self.deposits.save(
+            deps.storage,
+            proposal_id,
+            &(proposal.deposit().cloned(), proposal.proposer().clone()),
+        )?;
+
+        let propose_messsage = WasmMsg::Execute {
+            contract_addr: proposal_module.into_string(),
+            msg: proposal.propose_msg()?,
+            funds: vec![],
+        };
+
+        proposal.set_approved(proposal_id);
+        proposals.completed.save(deps.storage, id, &proposal)?;
+        proposals
+            .created_to_completed
+            .save(deps.storage, proposal_id, &id)?;
+        proposals.pending.remove(deps.storage, id);
+        // The proposal remains open now that it has been created.
+        self.record_moved(
+            deps.storage,
+            &OpenProposal::Approval { id },
+            OpenProposal::Proposal { id: proposal_id },
+        )?;
+
+        Ok(Response::default()
+            .add_message(propose_messsage)
+            .add_attribute("method", "proposal_approved")
+            .add_attribute("approval_id", id.to_string())
+            .add_attribute("proposal_id", proposal_id.to_string()))
+    }
+
+    /// Rejects a pending proposal, refunding or forfeiting its
+    /// deposit, and moves it from PROPOSALS' pending to completed
+    /// proposals.
+    pub fn reject_proposal<P: ApprovalProposal>(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        proposals: &ApprovalProposals<P>,
+        mut proposal: P,
+        reason: Option<String>,
+    ) -> Result<Response, PreProposeError> {
+        let id = proposal.approval_id();
+        let proposer = proposal.proposer().clone();
+        let deposit = proposal.deposit().cloned();
+
+        proposal.set_rejected(reason.clone());
+        proposals.completed.save(deps.storage, id, &proposal)?;
+        proposals.pending.remove(deps.storage, id);
+
+        let messages = self.reject_pending(deps, env, id, &proposer, deposit.as_ref())?;
+
+        Ok(Response::default()
+            .add_attribute("method", "proposal_rejected")
+            .add_attribute("proposal", id.to_string())
+            .add_attribute("reason", reason.unwrap_or_default())
+            .add_attribute("deposit_info", to_json_binary(&deposit)?.to_string())
+            .add_messages(messages))
+    }
+
+    /// Rejects pending proposal ID once its approval period has
+    /// passed. Anyone may call this method.
+    pub fn execute_expire_pending<P: ApprovalProposal>(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        proposals: &ApprovalProposals<P>,
+        id: u64,
+    ) -> Result<Response, PreProposeError> {
+        let proposal = proposals
+            .pending
+            .may_load(deps.storage, id)?
+            .ok_or(PreProposeError::ProposalNotFound {})?;
+        if !proposal.expiration().is_expired(&env.block) {
+            return Err(PreProposeError::ApprovalNotExpired { id });
+        }
+        self.reject_proposal(
+            deps,
+            &env,
+            proposals,
+            proposal,
+            Some(EXPIRED_REASON.to_string()),
+        )
+    }
+
+    pub fn execute_update_approver(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, PreProposeError> {
+        update_approver(deps.storage, deps.api, &info.sender, address)?;
+
+        Ok(Response::default())
+    }
+
+    pub fn execute_update_approvers(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        approvers: Vec<String>,
+        threshold: u64,
+        approval_period: Option<Duration>,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        let approvers = Approvers::new(deps.api, approvers, threshold, approval_period)?;
+        APPROVERS.save(deps.storage, &approvers)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "update_approvers")
+            .add_attribute("approvers", approvers.approvers.len().to_string())
+            .add_attribute("threshold", approvers.threshold.to_string()))
+    }
+
+    pub fn execute_add_approver_hook(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        APPROVERS
+            .load(deps.storage)?
+            .check_approver_or_dao(&info.sender, &dao)?;
+
+        let addr = deps.api.addr_validate(&address)?;
+        self.proposal_submitted_hooks.add_hook(deps.storage, addr)?;
+
+        Ok(Response::default())
+    }
+
+    pub fn execute_remove_approver_hook(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        APPROVERS
+            .load(deps.storage)?
+            .check_approver_or_dao(&info.sender, &dao)?;
+
+        // Validate address
+        let addr = deps.api.addr_validate(&address)?;
+
+        // remove hook
+        self.proposal_submitted_hooks
+            .remove_hook(deps.storage, addr)?;
+
+        Ok(Response::default())
+    }
+}
@@ -55,6 +55,65 @@ pub enum PreProposeError {
     #[error("Unsupported")]
     Unsupported {},
 
+    #[error("Invalid sponsorship configuration: {reason}")]
+    InvalidSponsorshipConfig { reason: String },
+
+    #[error("No proposal awaiting sponsorship with ID ({id})")]
+    SponsorshipNotFound { id: u64 },
+
+    #[error("The sponsorship window for proposal ({id}) has closed")]
+    SponsorshipExpired { id: u64 },
+
+    #[error("The sponsorship window for proposal ({id}) is still open")]
+    SponsorshipNotExpired { id: u64 },
+
+    #[error("Proposers may not sponsor their own proposals")]
+    SelfSponsorship {},
+
+    #[error("Address has already sponsored this proposal")]
+    AlreadySponsored {},
+
+    #[error("Only addresses with voting power may sponsor proposals")]
+    ZeroSponsorPower {},
+
+    #[error("Invalid rate limit configuration: {reason}")]
+    InvalidRateLimit { reason: String },
+
+    #[error("Proposer already has the maximum of ({max}) open proposals")]
+    TooManyOpenProposals { max: u64 },
+
+    #[error(
+        "Proposer has already submitted the maximum of ({max}) proposals in the rate limit window"
+    )]
+    SubmissionRateLimited { max: u64 },
+
+    #[error("This module already has the maximum of ({max}) open proposals")]
+    ModuleOpenProposalLimit { max: u64 },
+
+    #[error("Invalid conditions: {reason}")]
+    InvalidConditions { reason: String },
+
+    #[error("Proposer does not meet the conditions for proposing: {reasons}")]
+    ConditionsNotMet { reasons: String },
+
+    #[error("Invalid approvers: {reason}")]
+    InvalidApprovers { reason: String },
+
+    #[error("Approver has already approved or rejected this proposal")]
+    AlreadyDecided {},
+
+    #[error("The approval period for proposal ({id}) has ended")]
+    ApprovalExpired { id: u64 },
+
+    #[error("The approval period for proposal ({id}) has not ended")]
+    ApprovalNotExpired { id: u64 },
+
+    #[error("Proposal ({id}) is awaiting changes from its proposer")]
+    AwaitingChanges { id: u64 },
+
+    #[error("Changes have not been requested for proposal ({id})")]
+    ChangesNotRequested { id: u64 },
+
     #[error("Cannot migrate contract version {actual}. Requires: {required}")]
     CannotMigrateVersion { required: String, actual: String },
 }
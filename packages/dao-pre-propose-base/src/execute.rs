@@ -1,27 +1,45 @@
 use cosmwasm_schema::schemars::JsonSchema;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
-    StdResult, SubMsg, WasmMsg,
+    from_json, to_json_binary, Addr, Binary, BlockInfo, CosmosMsg, Decimal, Deps, DepsMut, Empty,
+    Env, MessageInfo, Order, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
+use cw_storage_plus::{Bound, Map};
+use cw_utils::Duration;
 
 use semver::{Version, VersionReq};
 
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 
 use cw_denom::{CheckedDenom, UncheckedDenom};
-use dao_interface::voting::{Query as CwCoreQuery, VotingPowerAtHeightResponse};
+use dao_interface::condition::CanProposeResponse;
+use dao_interface::voting::{
+    Query as CwCoreQuery, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
 use dao_voting::{
-    deposit::{CheckedDepositInfo, DepositRefundPolicy, UncheckedDepositInfo},
+    deposit::{
+        CheckedDepositInfo, DepositError, DepositFormula, DepositFormulaInputs,
+        DepositRefundPolicy, ForfeitureConfig, ForfeitureDestination, UncheckedDepositInfo,
+        VetoedForfeiture,
+    },
     pre_propose::{PreProposeSubmissionPolicy, PreProposeSubmissionPolicyError},
     status::Status,
 };
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     error::PreProposeError,
     helpers::add_and_remove_addresses,
-    msg::{DepositInfoResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-    state::{Config, PreProposeContract},
+    msg::{
+        DepositInfoResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, ProposerStatsResponse,
+        QueryMsg, SponsorResponse,
+    },
+    state::{
+        ActivityRecord, Config, OpenProposal, OpenProposalRecord, PendingSponsorship,
+        PreProposeContract, RateLimitConfig, SponsorshipConfig, SponsorshipThreshold,
+        UncheckedCondition, WindowLimit,
+    },
 };
 
 use cw_denom_v241::CheckedDenom as CheckedDenomV241;
@@ -31,10 +49,13 @@ use dao_voting_v241::deposit::DepositRefundPolicy as DepositRefundPolicyV241;
 const CONTRACT_NAME: &str = "crates.io::dao-pre-propose-base";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 impl<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
     PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
 where
-    ProposalMessage: Serialize,
+    ProposalMessage: Serialize + DeserializeOwned,
     QueryExt: JsonSchema,
     MigrateExt: JsonSchema,
 {
@@ -67,9 +88,30 @@ where
 
         msg.submission_policy.validate()?;
 
+        if let Some(sponsorship) = &msg.sponsorship {
+            sponsorship.validate()?;
+        }
+
+        if let Some(forfeiture) = &msg.forfeiture {
+            forfeiture.validate(deps.as_ref(), deposit_info.as_ref())?;
+        }
+
+        if let Some(rate_limit) = &msg.rate_limit {
+            rate_limit.validate()?;
+        }
+
+        let conditions = msg
+            .conditions
+            .map(|conditions| conditions.into_checked(deps.api))
+            .transpose()?;
+
         let config = Config {
             deposit_info,
             submission_policy: msg.submission_policy,
+            sponsorship: msg.sponsorship,
+            forfeiture: msg.forfeiture,
+            rate_limit: msg.rate_limit,
+            conditions,
         };
 
         self.config.save(deps.storage, &config)?;
@@ -82,6 +124,10 @@ where
                 "submission_policy",
                 config.submission_policy.human_readable(),
             )
+            .add_attribute("sponsorship", format!("{:?}", config.sponsorship))
+            .add_attribute("forfeiture", format!("{:?}", config.forfeiture))
+            .add_attribute("rate_limit", format!("{:?}", config.rate_limit))
+            .add_attribute("conditions", format!("{:?}", config.conditions))
             .add_attribute("dao", dao))
     }
 
@@ -94,6 +140,8 @@ where
     ) -> Result<Response, PreProposeError> {
         match msg {
             ExecuteMsg::Propose { msg } => self.execute_propose(deps, env, info, msg),
+            ExecuteMsg::Receive(msg) => self.execute_receive_cw20(deps, env, info, msg),
+            ExecuteMsg::ReceiveNft(msg) => self.execute_receive_nft(deps, env, info, msg),
             ExecuteMsg::UpdateConfig {
                 deposit_info,
                 submission_policy,
@@ -113,6 +161,20 @@ where
                 allowlist_add,
                 allowlist_remove,
             ),
+            ExecuteMsg::UpdateSponsorship { sponsorship } => {
+                self.execute_update_sponsorship(deps, info, sponsorship)
+            }
+            ExecuteMsg::UpdateForfeiture { forfeiture } => {
+                self.execute_update_forfeiture(deps, info, forfeiture)
+            }
+            ExecuteMsg::UpdateRateLimit { rate_limit } => {
+                self.execute_update_rate_limit(deps, info, rate_limit)
+            }
+            ExecuteMsg::UpdateConditions { conditions } => {
+                self.execute_update_conditions(deps, info, conditions)
+            }
+            ExecuteMsg::Sponsor { id } => self.execute_sponsor(deps, env, info, id),
+            ExecuteMsg::ExpireUnsponsored { id } => self.execute_expire_unsponsored(deps, env, id),
             ExecuteMsg::Withdraw { denom } => {
                 self.execute_withdraw(deps.as_ref(), env, info, denom)
             }
@@ -125,7 +187,7 @@ where
             ExecuteMsg::ProposalCompletedHook {
                 proposal_id,
                 new_status,
-            } => self.execute_proposal_completed_hook(deps.as_ref(), info, proposal_id, new_status),
+            } => self.execute_proposal_completed_hook(deps, env, info, proposal_id, new_status),
 
             ExecuteMsg::Extension { .. } => Ok(Response::default()),
         }
@@ -139,33 +201,176 @@ where
         msg: ProposalMessage,
     ) -> Result<Response, PreProposeError> {
         self.check_can_submit(deps.as_ref(), info.sender.clone())?;
+        let msg = to_json_binary(&msg)?;
+        self.check_conditions(deps.as_ref(), &info.sender, &msg)?;
 
         let config = self.config.load(deps.storage)?;
 
-        let deposit_messages = if let Some(ref deposit_info) = config.deposit_info {
-            deposit_info.check_native_deposit_paid(&info)?;
+        let deposit_info = self
+            .get_deposit_for(deps.as_ref(), &env.block, config.deposit_info, &info.sender)?
+            .map(|deposit_info| deposit_info.resolve_native_payment(&info))
+            .transpose()?;
+
+        let deposit_messages = if let Some(ref deposit_info) = deposit_info {
             deposit_info.get_take_deposit_messages(&info.sender, &env.contract.address)?
         } else {
             vec![]
         };
 
+        self.submit_proposal(deps, env, info.sender, deposit_info, msg, deposit_messages)
+    }
+
+    /// Creates a proposal whose deposit was paid by sending cw20
+    /// tokens to this module. The message attached to the cw20 tokens
+    /// must be a serialized proposal message.
+    pub fn execute_receive_cw20(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        receive_msg: Cw20ReceiveMsg,
+    ) -> Result<Response, PreProposeError> {
+        let proposer = deps.api.addr_validate(&receive_msg.sender)?;
+        let msg: ProposalMessage = from_json(&receive_msg.msg)?;
+
+        self.check_can_submit(deps.as_ref(), proposer.clone())?;
+        let msg = to_json_binary(&msg)?;
+        self.check_conditions(deps.as_ref(), &proposer, &msg)?;
+
+        let config = self.config.load(deps.storage)?;
+        let deposit_info = self
+            .get_deposit_for(deps.as_ref(), &env.block, config.deposit_info, &proposer)?
+            .ok_or(DepositError::UnacceptedDeposit {})?;
+        let deposit_info = deposit_info.resolve_cw20_payment(&info.sender, receive_msg.amount)?;
+
+        // The deposit has already been transferred to us.
+        self.submit_proposal(deps, env, proposer, Some(deposit_info), msg, vec![])
+    }
+
+    /// Creates a proposal whose deposit was paid by sending an NFT to
+    /// this module. The message attached to the NFT must be a
+    /// serialized proposal message.
+    pub fn execute_receive_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        receive_msg: Cw721ReceiveMsg,
+    ) -> Result<Response, PreProposeError> {
+        let proposer = deps.api.addr_validate(&receive_msg.sender)?;
+        let msg: ProposalMessage = from_json(&receive_msg.msg)?;
+
+        self.check_can_submit(deps.as_ref(), proposer.clone())?;
+        let msg = to_json_binary(&msg)?;
+        self.check_conditions(deps.as_ref(), &proposer, &msg)?;
+
+        let config = self.config.load(deps.storage)?;
+        let deposit_info = self
+            .get_deposit_for(deps.as_ref(), &env.block, config.deposit_info, &proposer)?
+            .ok_or(DepositError::UnacceptedDeposit {})?;
+        let deposit_info = deposit_info.resolve_nft_payment(&info.sender, receive_msg.token_id)?;
+
+        // The NFT has already been transferred to us and is held in
+        // escrow until the proposal completes.
+        self.submit_proposal(deps, env, proposer, Some(deposit_info), msg, vec![])
+    }
+
+    /// Creates a proposal for PROPOSER whose deposit has been paid,
+    /// or holds it until it is sponsored if sponsorship is required.
+    fn submit_proposal(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        proposer: Addr,
+        deposit_info: Option<CheckedDepositInfo>,
+        msg: Binary,
+        deposit_messages: Vec<CosmosMsg>,
+    ) -> Result<Response, PreProposeError> {
+        self.check_rate_limit(deps.as_ref(), &env.block, &proposer)?;
+        self.prune_rejections(deps.storage, &env.block, &proposer)?;
+
+        let config = self.config.load(deps.storage)?;
+
+        // If sponsorship is required, hold the proposal until enough
+        // members have sponsored it.
+        if let Some(sponsorship) = config.sponsorship {
+            let id = self.advance_sponsorship_id(deps.storage)?;
+            self.record_submission(
+                deps.storage,
+                &env.block,
+                &proposer,
+                OpenProposal::Sponsorship { id },
+            )?;
+            self.pending_sponsorships.save(
+                deps.storage,
+                id,
+                &PendingSponsorship {
+                    id,
+                    proposer: proposer.clone(),
+                    msg,
+                    deposit: deposit_info,
+                    start_height: env.block.height,
+                    expiration: sponsorship.window.after(&env.block),
+                    threshold: sponsorship.threshold,
+                    sponsor_count: 0,
+                    sponsored_power: Uint128::zero(),
+                },
+            )?;
+
+            return Ok(Response::default()
+                .add_attribute("method", "execute_propose")
+                .add_attribute("sender", proposer)
+                .add_attribute("sponsorship_id", id.to_string())
+                .add_messages(deposit_messages));
+        }
+
+        let (proposal_id, propose_messsage, hooks_msgs) =
+            self.prepare_proposal(deps.branch(), proposer.clone(), deposit_info, msg)?;
+        self.record_submission(
+            deps.storage,
+            &env.block,
+            &proposer,
+            OpenProposal::Proposal { id: proposal_id },
+        )?;
+
+        Ok(Response::default()
+            .add_attribute("method", "execute_propose")
+            .add_attribute("sender", proposer)
+            // It's important that the propose message is
+            // first. Otherwise, a hook receiver could create a
+            // proposal before us and invalidate our `NextProposalId
+            // {}` query.
+            .add_message(propose_messsage)
+            .add_submessages(hooks_msgs)
+            .add_messages(deposit_messages))
+    }
+
+    /// Snapshots the deposit for the proposal that is about to be
+    /// created and returns its proposal ID and the message that
+    /// creates it in the proposal module, along with the proposal
+    /// submitted hook messages. The propose message must be added to
+    /// the response before the hook messages.
+    fn prepare_proposal(
+        &self,
+        deps: DepsMut,
+        proposer: Addr,
+        deposit_info: Option<CheckedDepositInfo>,
+        msg: Binary,
+    ) -> Result<(u64, WasmMsg, Vec<SubMsg>), PreProposeError> {
         let proposal_module = self.proposal_module.load(deps.storage)?;
 
         // Snapshot the deposit using the ID of the proposal that we
         // will create.
-        let next_id = deps.querier.query_wasm_smart(
+        let next_id: u64 = deps.querier.query_wasm_smart(
             &proposal_module,
             &dao_interface::proposal::Query::NextProposalId {},
         )?;
-        self.deposits.save(
-            deps.storage,
-            next_id,
-            &(config.deposit_info, info.sender.clone()),
-        )?;
+        self.deposits
+            .save(deps.storage, next_id, &(deposit_info, proposer))?;
 
         let propose_messsage = WasmMsg::Execute {
             contract_addr: proposal_module.into_string(),
-            msg: to_json_binary(&msg)?,
+            msg: msg.clone(),
             funds: vec![],
         };
 
@@ -174,22 +379,13 @@ where
             .prepare_hooks(deps.storage, |a| {
                 let execute = WasmMsg::Execute {
                     contract_addr: a.into_string(),
-                    msg: to_json_binary(&msg)?,
+                    msg: msg.clone(),
                     funds: vec![],
                 };
                 Ok(SubMsg::new(execute))
             })?;
 
-        Ok(Response::default()
-            .add_attribute("method", "execute_propose")
-            .add_attribute("sender", info.sender)
-            // It's important that the propose message is
-            // first. Otherwise, a hook receiver could create a
-            // proposal before us and invalidate our `NextProposalId
-            // {}` query.
-            .add_message(propose_messsage)
-            .add_submessages(hooks_msgs)
-            .add_messages(deposit_messages))
+        Ok((next_id, propose_messsage, hooks_msgs))
     }
 
     pub fn execute_update_config(
@@ -212,6 +408,12 @@ where
             submision_policy.validate()?
         }
 
+        // The forfeiture config may depend on the deposit's refund
+        // policy.
+        if let Some(forfeiture) = &self.config.load(deps.storage)?.forfeiture {
+            forfeiture.validate(deps.as_ref(), deposit_info.as_ref())?;
+        }
+
         self.config
             .update(deps.storage, |prev| -> Result<Config, PreProposeError> {
                 let new_submission_policy = if let Some(submission_policy) = submission_policy {
@@ -223,6 +425,10 @@ where
                 Ok(Config {
                     deposit_info,
                     submission_policy: new_submission_policy,
+                    sponsorship: prev.sponsorship,
+                    forfeiture: prev.forfeiture,
+                    rate_limit: prev.rate_limit,
+                    conditions: prev.conditions,
                 })
             })?;
 
@@ -231,6 +437,244 @@ where
             .add_attribute("sender", info.sender))
     }
 
+    pub fn execute_update_sponsorship(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        sponsorship: Option<SponsorshipConfig>,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        if let Some(sponsorship) = &sponsorship {
+            sponsorship.validate()?;
+        }
+
+        let mut config = self.config.load(deps.storage)?;
+        config.sponsorship = sponsorship;
+        self.config.save(deps.storage, &config)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "update_sponsorship")
+            .add_attribute("sender", info.sender)
+            .add_attribute("sponsorship", format!("{:?}", config.sponsorship)))
+    }
+
+    pub fn execute_update_forfeiture(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        forfeiture: Option<ForfeitureConfig>,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        let mut config = self.config.load(deps.storage)?;
+        if let Some(forfeiture) = &forfeiture {
+            forfeiture.validate(deps.as_ref(), config.deposit_info.as_ref())?;
+        }
+
+        config.forfeiture = forfeiture;
+        self.config.save(deps.storage, &config)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "update_forfeiture")
+            .add_attribute("sender", info.sender)
+            .add_attribute("forfeiture", format!("{:?}", config.forfeiture)))
+    }
+
+    pub fn execute_update_rate_limit(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        rate_limit: Option<RateLimitConfig>,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        if let Some(rate_limit) = &rate_limit {
+            rate_limit.validate()?;
+        }
+
+        let mut config = self.config.load(deps.storage)?;
+        config.rate_limit = rate_limit;
+        self.config.save(deps.storage, &config)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "update_rate_limit")
+            .add_attribute("sender", info.sender)
+            .add_attribute("rate_limit", format!("{:?}", config.rate_limit)))
+    }
+
+    pub fn execute_update_conditions(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        conditions: Option<UncheckedCondition>,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        let conditions = conditions
+            .map(|conditions| conditions.into_checked(deps.api))
+            .transpose()?;
+
+        let mut config = self.config.load(deps.storage)?;
+        config.conditions = conditions;
+        self.config.save(deps.storage, &config)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "update_conditions")
+            .add_attribute("sender", info.sender)
+            .add_attribute("conditions", format!("{:?}", config.conditions)))
+    }
+
+    pub fn execute_sponsor(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        id: u64,
+    ) -> Result<Response, PreProposeError> {
+        let mut pending = self
+            .pending_sponsorships
+            .may_load(deps.storage, id)?
+            .ok_or(PreProposeError::SponsorshipNotFound { id })?;
+
+        if pending.expiration.is_expired(&env.block) {
+            return Err(PreProposeError::SponsorshipExpired { id });
+        }
+        if pending.proposer == info.sender {
+            return Err(PreProposeError::SelfSponsorship {});
+        }
+        if self.sponsors.has(deps.storage, (id, &info.sender)) {
+            return Err(PreProposeError::AlreadySponsored {});
+        }
+
+        // Voting power is measured at the height the proposal was
+        // submitted so that tokens can't be moved between accounts to
+        // sponsor a proposal more than once.
+        let dao = self.dao.load(deps.storage)?;
+        let voting_power: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+            &dao,
+            &CwCoreQuery::VotingPowerAtHeight {
+                address: info.sender.to_string(),
+                height: Some(pending.start_height),
+            },
+        )?;
+        if voting_power.power.is_zero() {
+            return Err(PreProposeError::ZeroSponsorPower {});
+        }
+
+        self.sponsors
+            .save(deps.storage, (id, &info.sender), &voting_power.power)?;
+        pending.sponsor_count += 1;
+        pending.sponsored_power = pending
+            .sponsored_power
+            .checked_add(voting_power.power)
+            .map_err(StdError::overflow)?;
+
+        let sponsored = match pending.threshold {
+            SponsorshipThreshold::Members { count } => pending.sponsor_count >= count,
+            SponsorshipThreshold::Percentage { percent } => {
+                let total_power: TotalPowerAtHeightResponse = deps.querier.query_wasm_smart(
+                    &dao,
+                    &CwCoreQuery::TotalPowerAtHeight {
+                        height: Some(pending.start_height),
+                    },
+                )?;
+                // sponsored_power / total_power >= percent, without
+                // dividing.
+                !total_power.power.is_zero()
+                    && pending.sponsored_power.full_mul(Decimal::one().atomics())
+                        >= total_power.power.full_mul(percent.atomics())
+            }
+        };
+
+        let response = Response::default()
+            .add_attribute("method", "sponsor")
+            .add_attribute("sponsorship_id", id.to_string())
+            .add_attribute("sponsor", info.sender)
+            .add_attribute("sponsored", sponsored.to_string());
+
+        if sponsored {
+            self.pending_sponsorships.remove(deps.storage, id);
+            let (proposal_id, propose_messsage, hooks_msgs) = self.prepare_proposal(
+                deps.branch(),
+                pending.proposer,
+                pending.deposit,
+                pending.msg,
+            )?;
+            // The proposal remains open now that it has been created.
+            self.record_moved(
+                deps.storage,
+                &OpenProposal::Sponsorship { id },
+                OpenProposal::Proposal { id: proposal_id },
+            )?;
+            Ok(response
+                .add_message(propose_messsage)
+                .add_submessages(hooks_msgs))
+        } else {
+            self.pending_sponsorships.save(deps.storage, id, &pending)?;
+            Ok(response)
+        }
+    }
+
+    pub fn execute_expire_unsponsored(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        id: u64,
+    ) -> Result<Response, PreProposeError> {
+        let PendingSponsorship {
+            proposer,
+            deposit,
+            expiration,
+            ..
+        } = self
+            .pending_sponsorships
+            .may_load(deps.storage, id)?
+            .ok_or(PreProposeError::SponsorshipNotFound { id })?;
+
+        if !expiration.is_expired(&env.block) {
+            return Err(PreProposeError::SponsorshipNotExpired { id });
+        }
+
+        self.pending_sponsorships.remove(deps.storage, id);
+        self.record_closed(deps.storage, &OpenProposal::Sponsorship { id })?;
+
+        let messages: Vec<CosmosMsg> = if let Some(ref deposit_info) = deposit {
+            // An unsponsored proposal never passed, so only the
+            // `Always` refund policy returns its deposit.
+            if deposit_info.refund_policy == DepositRefundPolicy::Always {
+                deposit_info.get_return_deposit_message(&proposer)?
+            } else {
+                self.get_forfeit_deposit_messages(
+                    deps.as_ref(),
+                    &env.contract.address,
+                    deposit_info,
+                    false,
+                )?
+            }
+        } else {
+            vec![]
+        };
+
+        Ok(Response::default()
+            .add_attribute("method", "expire_unsponsored")
+            .add_attribute("sponsorship_id", id.to_string())
+            .add_attribute("deposit_info", to_json_binary(&deposit)?.to_string())
+            .add_messages(messages))
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn execute_update_submission_policy(
         &self,
@@ -387,7 +831,8 @@ where
 
     pub fn execute_proposal_completed_hook(
         &self,
-        deps: Deps,
+        deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         id: u64,
         new_status: Status,
@@ -411,32 +856,67 @@ where
 
         match self.deposits.may_load(deps.storage, id)? {
             Some((deposit_info, proposer)) => {
-                let messages = if let Some(ref deposit_info) = deposit_info {
-                    // Determine if refund can be issued
-                    let should_refund_to_proposer =
-                        match (new_status, deposit_info.clone().refund_policy) {
-                            // If policy is refund only passed props, refund for executed status
-                            (Status::Executed, DepositRefundPolicy::OnlyPassed) => true,
-                            // Don't refund other statuses for OnlyPassed policy
-                            (_, DepositRefundPolicy::OnlyPassed) => false,
-                            // Refund if the refund policy is always refund
-                            (_, DepositRefundPolicy::Always) => true,
-                            // Don't refund if the refund is never refund
-                            (_, DepositRefundPolicy::Never) => false,
-                        };
-
-                    if should_refund_to_proposer {
-                        deposit_info.get_return_deposit_message(&proposer)?
-                    } else {
-                        // If the proposer doesn't get the deposit, the DAO does.
-                        let dao = self.dao.load(deps.storage)?;
-                        deposit_info.get_return_deposit_message(&dao)?
-                    }
-                } else {
-                    // No deposit info for this proposal. Nothing to do.
-                    vec![]
+                // Keep track of rejected proposals so deposit formulas
+                // can take a proposer's history into account.
+                if new_status == Status::Closed || new_status == Status::Vetoed {
+                    self.record_rejection(deps.storage, &env.block, &proposer)?;
+                }
+                self.record_closed(deps.storage, &OpenProposal::Proposal { id })?;
+
+                let config = self.config.load(deps.storage)?;
+                let vetoed_slash = match (new_status, config.forfeiture) {
+                    (
+                        Status::Vetoed,
+                        Some(ForfeitureConfig {
+                            vetoed: Some(VetoedForfeiture { slash, .. }),
+                            ..
+                        }),
+                    ) => Some(slash),
+                    _ => None,
                 };
 
+                let messages =
+                    if let (Some(deposit_info), Some(slash)) = (&deposit_info, vetoed_slash) {
+                        // Vetoed proposals are slashed regardless of the
+                        // refund policy, and the remainder is refunded.
+                        let (slashed, refunded) = deposit_info.split(slash)?;
+                        let mut messages = refunded.get_return_deposit_message(&proposer)?;
+                        messages.extend(self.get_forfeit_deposit_messages(
+                            deps.as_ref(),
+                            &env.contract.address,
+                            &slashed,
+                            true,
+                        )?);
+                        messages
+                    } else if let Some(ref deposit_info) = deposit_info {
+                        // Determine if refund can be issued
+                        let should_refund_to_proposer =
+                            match (new_status, deposit_info.clone().refund_policy) {
+                                // If policy is refund only passed props, refund for executed status
+                                (Status::Executed, DepositRefundPolicy::OnlyPassed) => true,
+                                // Don't refund other statuses for OnlyPassed policy
+                                (_, DepositRefundPolicy::OnlyPassed) => false,
+                                // Refund if the refund policy is always refund
+                                (_, DepositRefundPolicy::Always) => true,
+                                // Don't refund if the refund is never refund
+                                (_, DepositRefundPolicy::Never) => false,
+                            };
+
+                        if should_refund_to_proposer {
+                            deposit_info.get_return_deposit_message(&proposer)?
+                        } else {
+                            self.get_forfeit_deposit_messages(
+                                deps.as_ref(),
+                                &env.contract.address,
+                                deposit_info,
+                                new_status == Status::Vetoed,
+                            )?
+                        }
+                    } else {
+                        // No deposit info for this proposal. Nothing to do.
+                        vec![]
+                    };
+
                 Ok(Response::default()
                     .add_attribute("method", "execute_proposal_completed_hook")
                     .add_attribute("proposal", id.to_string())
@@ -454,6 +934,380 @@ where
         }
     }
 
+    /// Determines the deposit PROPOSER must pay to create a proposal,
+    /// evaluating DEPOSIT_INFO's formula if it has one. Returns `None`
+    /// if no deposit is required.
+    pub fn get_deposit_for(
+        &self,
+        deps: Deps,
+        block: &BlockInfo,
+        deposit_info: Option<CheckedDepositInfo>,
+        proposer: &Addr,
+    ) -> Result<Option<CheckedDepositInfo>, PreProposeError> {
+        let Some(deposit_info) = deposit_info else {
+            return Ok(None);
+        };
+        let Some(formula) = &deposit_info.formula else {
+            return Ok(Some(deposit_info));
+        };
+
+        let inputs = match formula {
+            DepositFormula::VotingPower { .. } => {
+                // Price the deposit from voting power at the previous
+                // height so the proposer can not move into a cheaper
+                // tier earlier in the same transaction or block.
+                let height = Some(block.height.saturating_sub(1));
+                let dao = self.dao.load(deps.storage)?;
+                let voting_power: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+                    &dao,
+                    &CwCoreQuery::VotingPowerAtHeight {
+                        address: proposer.to_string(),
+                        height,
+                    },
+                )?;
+                let total_power: TotalPowerAtHeightResponse = deps
+                    .querier
+                    .query_wasm_smart(&dao, &CwCoreQuery::TotalPowerAtHeight { height })?;
+                DepositFormulaInputs {
+                    voting_power: voting_power.power,
+                    total_power: total_power.power,
+                    recent_rejections: 0,
+                }
+            }
+            DepositFormula::RecentRejections { window, .. } => DepositFormulaInputs {
+                voting_power: Uint128::zero(),
+                total_power: Uint128::zero(),
+                recent_rejections: self.count_recent_rejections(deps, block, proposer, window)?,
+            },
+        };
+
+        let deposit_info = deposit_info.scaled_for(&inputs)?;
+
+        // A formula may waive the deposit entirely.
+        Ok(if deposit_info.amount.is_zero() {
+            None
+        } else {
+            Some(deposit_info)
+        })
+    }
+
+    /// Gets the messages that send a forfeited deposit, held by
+    /// CONTRACT, to the configured forfeiture destination. If VETOED,
+    /// the destination for vetoed proposals is used if one is
+    /// set. Without a forfeiture config, the DAO receives the deposit.
+    pub fn get_forfeit_deposit_messages(
+        &self,
+        deps: Deps,
+        contract: &Addr,
+        deposit_info: &CheckedDepositInfo,
+        vetoed: bool,
+    ) -> Result<Vec<CosmosMsg>, PreProposeError> {
+        let config = self.config.load(deps.storage)?;
+        let dao = self.dao.load(deps.storage)?;
+        let destination = match config.forfeiture {
+            Some(ForfeitureConfig {
+                vetoed:
+                    Some(VetoedForfeiture {
+                        destination: Some(destination),
+                        ..
+                    }),
+                ..
+            }) if vetoed => destination,
+            Some(ForfeitureConfig { destination, .. }) => destination,
+            None => ForfeitureDestination::Dao {},
+        };
+        Ok(deposit_info.get_forfeit_deposit_messages(deps, &destination, contract, &dao)?)
+    }
+
+    /// Records the rejection of PROPOSER's proposal APPROVAL_ID by an
+    /// approval module before it was created, and gets the messages
+    /// that settle its deposit. Deposits are returned if they are
+    /// always refunded and are otherwise forfeited, as `OnlyPassed`
+    /// and `Never` refund policies do not apply to proposals that
+    /// were never created.
+    pub fn reject_pending(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        approval_id: u64,
+        proposer: &Addr,
+        deposit_info: Option<&CheckedDepositInfo>,
+    ) -> Result<Vec<CosmosMsg>, PreProposeError> {
+        // Rejections by the approvers count towards the proposer's
+        // rejection history used by deposit formulas.
+        self.record_rejection(deps.storage, &env.block, proposer)?;
+        // The proposal will not be created, so it is no longer open.
+        self.record_closed(deps.storage, &OpenProposal::Approval { id: approval_id })?;
+
+        match deposit_info {
+            Some(deposit_info) if deposit_info.refund_policy == DepositRefundPolicy::Always => {
+                Ok(deposit_info.get_return_deposit_message(proposer)?)
+            }
+            Some(deposit_info) => self.get_forfeit_deposit_messages(
+                deps.as_ref(),
+                &env.contract.address,
+                deposit_info,
+                false,
+            ),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Records that one of PROPOSER's proposals was rejected or
+    /// vetoed in the current block. Rejections are only kept while a
+    /// `RecentRejections` deposit formula is configured, and only for
+    /// its window, so the history does not grow without bound.
+    pub fn record_rejection(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        proposer: &Addr,
+    ) -> StdResult<()> {
+        self.prune_rejections(storage, block, proposer)?;
+        if self.rejection_window(storage)?.is_some() {
+            record_activity(&self.rejections, storage, block, proposer)?;
+        }
+        Ok(())
+    }
+
+    /// Removes PROPOSER's rejections that are no longer within the
+    /// `RecentRejections` deposit formula's window.
+    pub fn prune_rejections(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        proposer: &Addr,
+    ) -> StdResult<()> {
+        let window = self.rejection_window(storage)?;
+        prune_activity(&self.rejections, storage, block, proposer, window.as_ref())
+    }
+
+    /// The window of the configured `RecentRejections` deposit
+    /// formula, if there is one.
+    fn rejection_window(&self, storage: &dyn Storage) -> StdResult<Option<Duration>> {
+        Ok(
+            match self
+                .config
+                .load(storage)?
+                .deposit_info
+                .and_then(|deposit_info| deposit_info.formula)
+            {
+                Some(DepositFormula::RecentRejections { window, .. }) => Some(window),
+                _ => None,
+            },
+        )
+    }
+
+    /// Counts the PROPOSER's proposals that were rejected or vetoed
+    /// within WINDOW of the current block.
+    pub fn count_recent_rejections(
+        &self,
+        deps: Deps,
+        block: &BlockInfo,
+        proposer: &Addr,
+        window: &Duration,
+    ) -> StdResult<u64> {
+        count_recent_activity(&self.rejections, deps.storage, block, proposer, window)
+    }
+
+    /// Checks that PROPOSER may submit another proposal under the
+    /// configured rate limits.
+    pub fn check_rate_limit(
+        &self,
+        deps: Deps,
+        block: &BlockInfo,
+        proposer: &Addr,
+    ) -> Result<(), PreProposeError> {
+        let Some(RateLimitConfig {
+            max_open_per_proposer,
+            max_per_window,
+            max_open,
+            ..
+        }) = self.config.load(deps.storage)?.rate_limit
+        else {
+            return Ok(());
+        };
+
+        if let Some(max) = max_open_per_proposer {
+            let open = self.count_open_proposals(deps.storage, block, Some(proposer))?;
+            if open >= max {
+                return Err(PreProposeError::TooManyOpenProposals { max });
+            }
+        }
+
+        if let Some(WindowLimit { count, window }) = max_per_window {
+            let recent =
+                count_recent_activity(&self.submissions, deps.storage, block, proposer, &window)?;
+            if recent >= count {
+                return Err(PreProposeError::SubmissionRateLimited { max: count });
+            }
+        }
+
+        if let Some(max) = max_open {
+            let open = self.count_open_proposals(deps.storage, block, None)?;
+            if open >= max {
+                return Err(PreProposeError::ModuleOpenProposalLimit { max });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records that PROPOSER submitted PROPOSAL, which is open until
+    /// `record_closed` is called for it or it expires. Nothing is
+    /// recorded for limits that are not configured.
+    pub fn record_submission(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        proposer: &Addr,
+        proposal: OpenProposal,
+    ) -> StdResult<()> {
+        let Some(RateLimitConfig {
+            max_open_per_proposer,
+            max_per_window,
+            max_open,
+            open_expiration,
+        }) = self.config.load(storage)?.rate_limit
+        else {
+            return Ok(());
+        };
+
+        if let Some(WindowLimit { window, .. }) = max_per_window {
+            prune_activity(&self.submissions, storage, block, proposer, Some(&window))?;
+            record_activity(&self.submissions, storage, block, proposer)?;
+        }
+
+        if max_open_per_proposer.is_some() || max_open.is_some() {
+            self.prune_open_proposals(storage, block)?;
+            let id = self.open_proposal_id.may_load(storage)?.unwrap_or_default() + 1;
+            self.open_proposal_id.save(storage, &id)?;
+            self.open_proposals
+                .save(storage, (proposer, id), &open_expiration.after(block))?;
+            self.open_proposal_record_ids
+                .save(storage, proposal.key(), &id)?;
+            self.open_proposal_records.save(
+                storage,
+                id,
+                &OpenProposalRecord {
+                    proposer: proposer.clone(),
+                    proposal,
+                },
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Records that the open proposal FROM has moved on to a later
+    /// stage, where it is identified by TO. It remains open with its
+    /// original expiration. Proposals that were not recorded as open
+    /// are ignored.
+    pub fn record_moved(
+        &self,
+        storage: &mut dyn Storage,
+        from: &OpenProposal,
+        to: OpenProposal,
+    ) -> StdResult<()> {
+        let Some(id) = self
+            .open_proposal_record_ids
+            .may_load(storage, from.key())?
+        else {
+            return Ok(());
+        };
+        self.open_proposal_record_ids.remove(storage, from.key());
+        self.open_proposal_record_ids.save(storage, to.key(), &id)?;
+        self.open_proposal_records
+            .update(storage, id, |record| -> StdResult<_> {
+                let mut record =
+                    record.ok_or_else(|| StdError::not_found("open proposal record"))?;
+                record.proposal = to;
+                Ok(record)
+            })?;
+        Ok(())
+    }
+
+    /// Records that PROPOSAL was completed or discarded, so it no
+    /// longer counts as open. Proposals that were not recorded as
+    /// open, such as those submitted while no open proposal limit was
+    /// configured, are ignored.
+    pub fn record_closed(
+        &self,
+        storage: &mut dyn Storage,
+        proposal: &OpenProposal,
+    ) -> StdResult<()> {
+        if let Some(id) = self
+            .open_proposal_record_ids
+            .may_load(storage, proposal.key())?
+        {
+            let record = self.open_proposal_records.load(storage, id)?;
+            self.remove_open_proposal(storage, id, &record);
+        }
+        Ok(())
+    }
+
+    /// Removes the open proposal record ID.
+    fn remove_open_proposal(
+        &self,
+        storage: &mut dyn Storage,
+        id: u64,
+        record: &OpenProposalRecord,
+    ) {
+        self.open_proposals.remove(storage, (&record.proposer, id));
+        self.open_proposal_records.remove(storage, id);
+        self.open_proposal_record_ids
+            .remove(storage, record.proposal.key());
+    }
+
+    /// Counts PROPOSER's unexpired open proposals, or those of all
+    /// proposers if PROPOSER is `None`.
+    pub fn count_open_proposals(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        proposer: Option<&Addr>,
+    ) -> StdResult<u64> {
+        let expirations = match proposer {
+            Some(proposer) => self
+                .open_proposals
+                .prefix(proposer)
+                .range(storage, None, None, Order::Ascending)
+                .map(|entry| entry.map(|(_, expiration)| expiration))
+                .collect::<StdResult<Vec<_>>>()?,
+            None => self
+                .open_proposals
+                .range(storage, None, None, Order::Ascending)
+                .map(|entry| entry.map(|(_, expiration)| expiration))
+                .collect::<StdResult<Vec<_>>>()?,
+        };
+        Ok(expirations
+            .iter()
+            .filter(|expiration| !expiration.is_expired(block))
+            .count() as u64)
+    }
+
+    /// Removes open proposals that have expired, visiting them in the
+    /// order they were submitted until one has not expired.
+    fn prune_open_proposals(&self, storage: &mut dyn Storage, block: &BlockInfo) -> StdResult<()> {
+        let expired = self
+            .open_proposal_records
+            .range(storage, None, None, Order::Ascending)
+            .map(|entry| {
+                let (id, record) = entry?;
+                let expiration = self.open_proposals.load(storage, (&record.proposer, id))?;
+                Ok((id, record, expiration))
+            })
+            .take_while(|entry: &StdResult<_>| {
+                entry
+                    .as_ref()
+                    .map_or(true, |(_, _, expiration)| expiration.is_expired(block))
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        for (id, record, _) in expired {
+            self.remove_open_proposal(storage, id, &record);
+        }
+        Ok(())
+    }
+
     pub fn check_can_submit(&self, deps: Deps, who: Addr) -> Result<(), PreProposeError> {
         let config = self.config.load(deps.storage)?;
 
@@ -500,7 +1354,61 @@ where
         ))
     }
 
-    pub fn query(&self, deps: Deps, _env: Env, msg: QueryMsg<QueryExt>) -> StdResult<Binary> {
+    /// Checks whether PROPOSER meets this module's conditions for
+    /// submitting MSG. Returns the reasons they do not if they may
+    /// not.
+    pub fn query_conditions(
+        &self,
+        deps: Deps,
+        proposer: &Addr,
+        msg: Option<&Binary>,
+    ) -> StdResult<CanProposeResponse> {
+        match self.config.load(deps.storage)?.conditions {
+            Some(conditions) => conditions.check(&deps.querier, proposer, msg),
+            None => Ok(CanProposeResponse::allowed()),
+        }
+    }
+
+    /// Checks whether PROPOSER may submit MSG under both the
+    /// submission policy and this module's conditions.
+    pub fn query_can_propose(
+        &self,
+        deps: Deps,
+        proposer: &Addr,
+        msg: Option<&Binary>,
+    ) -> StdResult<CanProposeResponse> {
+        match self.check_can_submit(deps, proposer.clone()) {
+            Ok(_) => self.query_conditions(deps, proposer, msg),
+            Err(PreProposeError::SubmissionPolicy(
+                PreProposeSubmissionPolicyError::Unauthorized {},
+            )) => Ok(CanProposeResponse::denied(
+                "not permitted by the submission policy",
+            )),
+            Err(PreProposeError::Std(err)) => Err(err),
+            Err(err) => Err(StdError::generic_err(format!(
+                "unexpected error: {:?}",
+                err
+            ))),
+        }
+    }
+
+    pub fn check_conditions(
+        &self,
+        deps: Deps,
+        proposer: &Addr,
+        msg: &Binary,
+    ) -> Result<(), PreProposeError> {
+        let res = self.query_conditions(deps, proposer, Some(msg))?;
+        if res.can_propose {
+            Ok(())
+        } else {
+            Err(PreProposeError::ConditionsNotMet {
+                reasons: res.reasons.join("; "),
+            })
+        }
+    }
+
+    pub fn query(&self, deps: Deps, env: Env, msg: QueryMsg<QueryExt>) -> StdResult<Binary> {
         match msg {
             QueryMsg::ProposalModule {} => {
                 to_json_binary(&self.proposal_module.load(deps.storage)?)
@@ -517,21 +1425,92 @@ where
                     proposer,
                 })
             }
+            QueryMsg::ProposalDeposit { proposer } => {
+                let proposer = deps.api.addr_validate(&proposer)?;
+                let config = self.config.load(deps.storage)?;
+                let deposit_info = self
+                    .get_deposit_for(deps, &env.block, config.deposit_info, &proposer)
+                    .map_err(|err| match err {
+                        PreProposeError::Std(err) => err,
+                        err => StdError::generic_err(err.to_string()),
+                    })?;
+                to_json_binary(&deposit_info)
+            }
+            QueryMsg::ProposerStats { address } => {
+                let address = deps.api.addr_validate(&address)?;
+                let config = self.config.load(deps.storage)?;
+                let recent_submissions = match config.rate_limit.and_then(|r| r.max_per_window) {
+                    Some(WindowLimit { window, .. }) => count_recent_activity(
+                        &self.submissions,
+                        deps.storage,
+                        &env.block,
+                        &address,
+                        &window,
+                    )?,
+                    None => 0,
+                };
+                to_json_binary(&ProposerStatsResponse {
+                    open_proposals: self.count_open_proposals(
+                        deps.storage,
+                        &env.block,
+                        Some(&address),
+                    )?,
+                    recent_submissions,
+                    total_open_proposals: self.count_open_proposals(
+                        deps.storage,
+                        &env.block,
+                        None,
+                    )?,
+                })
+            }
             QueryMsg::CanPropose { address } => {
                 let addr = deps.api.addr_validate(&address)?;
-                match self.check_can_submit(deps, addr) {
-                    Ok(_) => to_json_binary(&true),
-                    Err(err) => match err {
-                        PreProposeError::SubmissionPolicy(
-                            PreProposeSubmissionPolicyError::Unauthorized {},
-                        ) => to_json_binary(&false),
-                        PreProposeError::Std(err) => Err(err),
-                        _ => Err(StdError::generic_err(format!(
-                            "unexpected error: {:?}",
-                            err
-                        ))),
-                    },
-                }
+                to_json_binary(&self.query_can_propose(deps, &addr, None)?.can_propose)
+            }
+            QueryMsg::CanProposeWithReasons { address, msg } => {
+                let addr = deps.api.addr_validate(&address)?;
+                to_json_binary(&self.query_can_propose(deps, &addr, msg.as_ref())?)
+            }
+            QueryMsg::PendingSponsorship { id } => {
+                to_json_binary(&self.pending_sponsorships.load(deps.storage, id)?)
+            }
+            QueryMsg::PendingSponsorships { start_after, limit } => {
+                let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+                let pending = self
+                    .pending_sponsorships
+                    .range(
+                        deps.storage,
+                        start_after.map(Bound::exclusive),
+                        None,
+                        Order::Ascending,
+                    )
+                    .take(limit)
+                    .map(|item| item.map(|(_, pending)| pending))
+                    .collect::<StdResult<Vec<_>>>()?;
+                to_json_binary(&pending)
+            }
+            QueryMsg::Sponsors {
+                id,
+                start_after,
+                limit,
+            } => {
+                let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+                let start_after = start_after
+                    .map(|addr| deps.api.addr_validate(&addr))
+                    .transpose()?;
+                let sponsors = self
+                    .sponsors
+                    .prefix(id)
+                    .range(
+                        deps.storage,
+                        start_after.as_ref().map(Bound::exclusive),
+                        None,
+                        Order::Ascending,
+                    )
+                    .take(limit)
+                    .map(|item| item.map(|(address, power)| SponsorResponse { address, power }))
+                    .collect::<StdResult<Vec<_>>>()?;
+                to_json_binary(&sponsors)
             }
             QueryMsg::ProposalSubmittedHooks {} => {
                 to_json_binary(&self.proposal_submitted_hooks.query_hooks(deps)?)
@@ -595,6 +1574,9 @@ where
                             DepositRefundPolicyV241::Never => DepositRefundPolicy::Never,
                             DepositRefundPolicyV241::OnlyPassed => DepositRefundPolicy::OnlyPassed,
                         },
+                        formula: None,
+                        alternatives: vec![],
+                        escrowed_nft: None,
                     });
 
                 self.config.save(
@@ -602,6 +1584,10 @@ where
                     &Config {
                         deposit_info,
                         submission_policy,
+                        sponsorship: None,
+                        forfeiture: None,
+                        rate_limit: None,
+                        conditions: None,
                     },
                 )?;
 
@@ -618,3 +1604,83 @@ where
         }
     }
 }
+
+/// Adds one to PROPOSER's count in RECORDS for the current block.
+fn record_activity(
+    records: &Map<'static, (&'static Addr, u64), ActivityRecord>,
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    proposer: &Addr,
+) -> StdResult<()> {
+    records.update(
+        storage,
+        (proposer, block.height),
+        |record| -> StdResult<_> {
+            Ok(ActivityRecord {
+                time: block.time,
+                count: record.map_or(0, |r| r.count) + 1,
+            })
+        },
+    )?;
+    Ok(())
+}
+
+/// Counts PROPOSER's proposals in RECORDS within WINDOW of the
+/// current block.
+fn count_recent_activity(
+    records: &Map<'static, (&'static Addr, u64), ActivityRecord>,
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    proposer: &Addr,
+    window: &Duration,
+) -> StdResult<u64> {
+    let mut count = 0;
+    // Records are keyed by height, so iterating in descending order
+    // visits the most recent records first.
+    for record in records
+        .prefix(proposer)
+        .range(storage, None, None, Order::Descending)
+    {
+        let (height, record) = record?;
+        if !is_recent(height, &record, block, window) {
+            break;
+        }
+        count += record.count;
+    }
+    Ok(count)
+}
+
+/// Removes PROPOSER's records in RECORDS that are not within WINDOW
+/// of the current block, or all of them if WINDOW is `None`.
+fn prune_activity(
+    records: &Map<'static, (&'static Addr, u64), ActivityRecord>,
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    proposer: &Addr,
+    window: Option<&Duration>,
+) -> StdResult<()> {
+    // Records are keyed by height, so iterating in ascending order
+    // visits the oldest records first.
+    let stale = records
+        .prefix(proposer)
+        .range(storage, None, None, Order::Ascending)
+        .take_while(|record| match (record, window) {
+            (Ok((height, record)), Some(window)) => !is_recent(*height, record, block, window),
+            _ => true,
+        })
+        .map(|record| record.map(|(height, _)| height))
+        .collect::<StdResult<Vec<_>>>()?;
+    for height in stale {
+        records.remove(storage, (proposer, height));
+    }
+    Ok(())
+}
+
+/// Whether RECORD, made at HEIGHT, is within WINDOW of the current
+/// block.
+fn is_recent(height: u64, record: &ActivityRecord, block: &BlockInfo, window: &Duration) -> bool {
+    match window {
+        Duration::Height(blocks) => height + blocks > block.height,
+        Duration::Time(seconds) => record.time.plus_seconds(*seconds) > block.time,
+    }
+}
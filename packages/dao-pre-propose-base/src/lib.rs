@@ -1,5 +1,6 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
+pub mod approval;
 pub mod error;
 pub mod execute;
 pub mod helpers;
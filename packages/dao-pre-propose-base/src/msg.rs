@@ -1,12 +1,17 @@
 use cosmwasm_schema::{cw_serde, schemars::JsonSchema, QueryResponses};
+use cosmwasm_std::{Addr, Binary, Uint128};
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
 use cw_denom::UncheckedDenom;
 use dao_interface::proposal::InfoResponse;
 use dao_voting::{
-    deposit::{CheckedDepositInfo, UncheckedDepositInfo},
+    deposit::{CheckedDepositInfo, ForfeitureConfig, UncheckedDepositInfo},
     pre_propose::PreProposeSubmissionPolicy,
     status::Status,
 };
 
+use crate::state::{PendingSponsorship, RateLimitConfig, SponsorshipConfig, UncheckedCondition};
+
 #[cw_serde]
 pub struct InstantiateMsg<InstantiateExt> {
     /// Information about the deposit requirements for this
@@ -14,6 +19,21 @@ pub struct InstantiateMsg<InstantiateExt> {
     pub deposit_info: Option<UncheckedDepositInfo>,
     /// The policy dictating who is allowed to submit proposals.
     pub submission_policy: PreProposeSubmissionPolicy,
+    /// If set, proposals must be co-sponsored by DAO members before
+    /// they are created in the proposal module. None if proposals
+    /// are created immediately.
+    pub sponsorship: Option<SponsorshipConfig>,
+    /// Where deposits that are not refunded are sent, and how
+    /// deposits of vetoed proposals are slashed. None if forfeited
+    /// deposits are sent to the DAO.
+    pub forfeiture: Option<ForfeitureConfig>,
+    /// Limits on how many proposals may be submitted. None if
+    /// proposals are not rate limited.
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Conditions proposers must meet, in addition to the submission
+    /// policy, to submit proposals. None if there are no additional
+    /// conditions.
+    pub conditions: Option<UncheckedCondition>,
     /// Extension for instantiation. The default implementation will
     /// do nothing with this data.
     pub extension: InstantiateExt,
@@ -25,6 +45,19 @@ pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
     /// serialized and used as the proposal creation message.
     Propose { msg: ProposalMessage },
 
+    /// Creates a new proposal, paying its deposit with cw20 tokens
+    /// sent to this module with a cw20 `Send` message. The message
+    /// attached to the tokens must be a serialized proposal message,
+    /// as would be passed to `Propose`.
+    Receive(Cw20ReceiveMsg),
+
+    /// Creates a new proposal, paying its deposit with an NFT sent to
+    /// this module with a cw721 `SendNft` message. The message
+    /// attached to the NFT must be a serialized proposal message, as
+    /// would be passed to `Propose`. The NFT is held in escrow until
+    /// the proposal completes.
+    ReceiveNft(Cw721ReceiveMsg),
+
     /// Updates the configuration of this module. This will completely
     /// override the existing configuration. This new configuration
     /// will only apply to proposals created after the config is
@@ -51,6 +84,48 @@ pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
         allowlist_remove: Option<Vec<String>>,
     },
 
+    /// Updates the sponsorship requirement for newly submitted
+    /// proposals. Proposals already awaiting sponsorship keep the
+    /// requirement they were submitted under. If None, proposals will
+    /// be created without a sponsorship phase. Only the DAO may
+    /// execute this message.
+    UpdateSponsorship {
+        sponsorship: Option<SponsorshipConfig>,
+    },
+
+    /// Updates where forfeited deposits are sent and how deposits of
+    /// vetoed proposals are slashed. Applies to proposals that are
+    /// already open. If None, forfeited deposits are sent to the
+    /// DAO. Only the DAO may execute this message.
+    UpdateForfeiture {
+        forfeiture: Option<ForfeitureConfig>,
+    },
+
+    /// Updates the limits on proposal submissions. Proposals already
+    /// submitted count towards the new limits. If None, proposals are
+    /// not rate limited. Only the DAO may execute this message.
+    UpdateRateLimit { rate_limit: Option<RateLimitConfig> },
+
+    /// Updates the conditions proposers must meet to submit
+    /// proposals. If None, only the submission policy is checked.
+    /// Only the DAO may execute this message.
+    UpdateConditions {
+        conditions: Option<UncheckedCondition>,
+    },
+
+    /// Sponsors a proposal that is awaiting sponsorship. The sender
+    /// must have had voting power in the DAO when the proposal was
+    /// submitted. Once the proposal's sponsorship threshold is met it
+    /// is created in the proposal module.
+    Sponsor { id: u64 },
+
+    /// Removes a proposal whose sponsorship window has passed without
+    /// it receiving enough sponsorship. Its deposit is returned to
+    /// the proposer only if the refund policy is `Always`, otherwise
+    /// it is sent to the configured forfeiture destination (the DAO
+    /// if no forfeiture config is set). Anyone may call this method.
+    ExpireUnsponsored { id: u64 },
+
     /// Withdraws funds inside of this contract to the message
     /// sender. The contracts entire balance for the specifed DENOM is
     /// withdrawn to the message sender. Only the DAO may call this
@@ -129,9 +204,43 @@ where
     /// PROPOSAL_ID.
     #[returns(DepositInfoResponse)]
     DepositInfo { proposal_id: u64 },
+    /// Gets the deposit PROPOSER would need to pay to create a
+    /// proposal now, after evaluating the deposit formula, if any.
+    /// Returns `None` if no deposit is required.
+    #[returns(Option<CheckedDepositInfo>)]
+    ProposalDeposit { proposer: String },
+    /// Gets the number of proposals ADDRESS has open and has recently
+    /// submitted, along with the number open across all proposers.
+    #[returns(ProposerStatsResponse)]
+    ProposerStats { address: String },
     /// Returns whether or not the address can submit proposals.
     #[returns(bool)]
     CanPropose { address: String },
+    /// Returns whether or not the address can submit proposals, and
+    /// if not, why. MSG is a serialized proposal message that is
+    /// passed to the module's conditions, if any are configured.
+    #[returns(dao_interface::condition::CanProposeResponse)]
+    CanProposeWithReasons {
+        address: String,
+        msg: Option<Binary>,
+    },
+    /// Gets a proposal awaiting sponsorship.
+    #[returns(PendingSponsorship)]
+    PendingSponsorship { id: u64 },
+    /// Lists proposals awaiting sponsorship.
+    #[returns(Vec<PendingSponsorship>)]
+    PendingSponsorships {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Lists the sponsors of the proposal identified by ID and the
+    /// voting power each contributed.
+    #[returns(Vec<SponsorResponse>)]
+    Sponsors {
+        id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Returns list of proposal submitted hooks.
     #[returns(cw_hooks::HooksResponse)]
     ProposalSubmittedHooks {},
@@ -143,12 +252,34 @@ where
 
 #[cw_serde]
 pub struct DepositInfoResponse {
-    /// The deposit that has been paid for the specified proposal.
+    /// The deposit that has been paid for the specified proposal. If
+    /// the deposit has a formula, `amount` is the amount the proposer
+    /// paid after evaluating it.
     pub deposit_info: Option<CheckedDepositInfo>,
     /// The address that created the proposal.
     pub proposer: cosmwasm_std::Addr,
 }
 
+#[cw_serde]
+pub struct ProposerStatsResponse {
+    /// The number of the proposer's proposals that are open.
+    pub open_proposals: u64,
+    /// The number of proposals the proposer has submitted within the
+    /// rate limit window. Zero if no window is configured.
+    pub recent_submissions: u64,
+    /// The number of open proposals across all proposers.
+    pub total_open_proposals: u64,
+}
+
+#[cw_serde]
+pub struct SponsorResponse {
+    /// The sponsor's address.
+    pub address: Addr,
+    /// The voting power the sponsor held when the proposal was
+    /// submitted.
+    pub power: Uint128,
+}
+
 #[cw_serde]
 pub enum MigrateMsg<MigrateExt>
 where
@@ -1,11 +1,20 @@
 use std::marker::PhantomData;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{
+    Addr, Api, Binary, Decimal, QuerierWrapper, StdResult, Storage, Timestamp, Uint128,
+};
 use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map};
+use cw_utils::{Duration, Expiration};
 
-use dao_voting::{deposit::CheckedDepositInfo, pre_propose::PreProposeSubmissionPolicy};
+use dao_interface::condition::{CanProposeResponse, Query as ConditionQuery};
+use dao_voting::{
+    deposit::{CheckedDepositInfo, ForfeitureConfig},
+    pre_propose::PreProposeSubmissionPolicy,
+};
+
+use crate::error::PreProposeError;
 
 #[cw_serde]
 pub struct Config {
@@ -14,6 +23,324 @@ pub struct Config {
     pub deposit_info: Option<CheckedDepositInfo>,
     /// The policy dictating who is allowed to submit proposals.
     pub submission_policy: PreProposeSubmissionPolicy,
+    /// If set, submitted proposals must be co-sponsored by DAO
+    /// members before they are created in the proposal module.
+    pub sponsorship: Option<SponsorshipConfig>,
+    /// Where forfeited deposits are sent. If None, they are sent to
+    /// the DAO.
+    pub forfeiture: Option<ForfeitureConfig>,
+    /// Limits on proposal submissions. If None, proposals are not
+    /// rate limited.
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Conditions proposers must meet, in addition to the submission
+    /// policy, to submit proposals. If None, there are no additional
+    /// conditions.
+    pub conditions: Option<Condition>,
+}
+
+/// The maximum number of contracts a condition may query.
+pub const MAX_CONDITION_CONTRACTS: usize = 10;
+
+/// A condition on proposal submission, checked by querying contracts
+/// that implement `dao_interface::condition::Query`.
+#[cw_serde]
+pub enum UncheckedCondition {
+    /// Met if the contract at ADDRESS allows the proposal.
+    Contract { address: String },
+    /// Met if every one of CONDITIONS is met.
+    All { conditions: Vec<UncheckedCondition> },
+    /// Met if any one of CONDITIONS is met.
+    Any { conditions: Vec<UncheckedCondition> },
+}
+
+/// A validated `UncheckedCondition`.
+#[cw_serde]
+pub enum Condition {
+    /// Met if the contract at ADDRESS allows the proposal.
+    Contract { address: Addr },
+    /// Met if every one of CONDITIONS is met.
+    All { conditions: Vec<Condition> },
+    /// Met if any one of CONDITIONS is met.
+    Any { conditions: Vec<Condition> },
+}
+
+/// The amount of support a proposal must receive from DAO members
+/// during its sponsorship phase.
+#[cw_serde]
+pub enum SponsorshipThreshold {
+    /// At least COUNT distinct members must sponsor the proposal.
+    Members { count: u64 },
+    /// Sponsors must together hold at least PERCENT of the DAO's
+    /// total voting power.
+    Percentage { percent: Decimal },
+}
+
+#[cw_serde]
+pub struct SponsorshipConfig {
+    /// The support required before a proposal is forwarded to the
+    /// proposal module.
+    pub threshold: SponsorshipThreshold,
+    /// How long a submitted proposal may wait for sponsors. Once
+    /// this window has passed the proposal may no longer be
+    /// sponsored and its deposit is handled as if it were rejected.
+    pub window: Duration,
+}
+
+/// The number of a proposer's proposals that were submitted, or
+/// rejected or vetoed, in a block.
+#[cw_serde]
+pub struct ActivityRecord {
+    /// The time of the block.
+    pub time: Timestamp,
+    /// The number of proposals.
+    pub count: u64,
+}
+
+/// Limits on how many proposals may be submitted to this module. A
+/// proposal is open from when it is submitted, including any time
+/// spent awaiting sponsorship or approval, until it is completed or
+/// `open_expiration` passes. Proposals submitted while a limit is not
+/// configured do not count towards it.
+#[cw_serde]
+pub struct RateLimitConfig {
+    /// The maximum number of open proposals a single proposer may
+    /// have.
+    pub max_open_per_proposer: Option<u64>,
+    /// The maximum number of proposals a single proposer may submit
+    /// within a rolling window.
+    pub max_per_window: Option<WindowLimit>,
+    /// The maximum number of proposals that may be open in this
+    /// module at once, across all proposers.
+    pub max_open: Option<u64>,
+    /// How long after submission a proposal stops counting as open,
+    /// even if this module is never told that it completed. Should be
+    /// longer than a proposal may spend awaiting sponsorship, approval
+    /// and votes.
+    pub open_expiration: Duration,
+}
+
+#[cw_serde]
+pub struct WindowLimit {
+    /// The maximum number of proposals submitted within `window`.
+    pub count: u64,
+    /// The length of the rolling window.
+    pub window: Duration,
+}
+
+impl RateLimitConfig {
+    pub fn validate(&self) -> Result<(), PreProposeError> {
+        if self.max_open_per_proposer == Some(0) || self.max_open == Some(0) {
+            return Err(PreProposeError::InvalidRateLimit {
+                reason: "open proposal limits must be non-zero".to_string(),
+            });
+        }
+        if matches!(
+            self.open_expiration,
+            Duration::Height(0) | Duration::Time(0)
+        ) {
+            return Err(PreProposeError::InvalidRateLimit {
+                reason: "open expiration must be non-zero".to_string(),
+            });
+        }
+        if let Some(WindowLimit { count, window }) = &self.max_per_window {
+            if *count == 0 {
+                return Err(PreProposeError::InvalidRateLimit {
+                    reason: "window limit must be non-zero".to_string(),
+                });
+            }
+            if matches!(window, Duration::Height(0) | Duration::Time(0)) {
+                return Err(PreProposeError::InvalidRateLimit {
+                    reason: "window must be non-zero".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl UncheckedCondition {
+    pub fn into_checked(self, api: &dyn Api) -> Result<Condition, PreProposeError> {
+        let mut count = 0;
+        let condition = self.check_with_count(api, &mut count)?;
+        if count > MAX_CONDITION_CONTRACTS {
+            return Err(PreProposeError::InvalidConditions {
+                reason: format!("conditions may query at most {MAX_CONDITION_CONTRACTS} contracts"),
+            });
+        }
+        Ok(condition)
+    }
+
+    /// Validates this condition, adding the number of contracts it
+    /// queries to COUNT.
+    fn check_with_count(
+        self,
+        api: &dyn Api,
+        count: &mut usize,
+    ) -> Result<Condition, PreProposeError> {
+        let check_group = |conditions: Vec<UncheckedCondition>, count: &mut usize| {
+            if conditions.is_empty() {
+                return Err(PreProposeError::InvalidConditions {
+                    reason: "condition groups must not be empty".to_string(),
+                });
+            }
+            conditions
+                .into_iter()
+                .map(|condition| condition.check_with_count(api, count))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        match self {
+            UncheckedCondition::Contract { address } => {
+                *count += 1;
+                Ok(Condition::Contract {
+                    address: api.addr_validate(&address)?,
+                })
+            }
+            UncheckedCondition::All { conditions } => Ok(Condition::All {
+                conditions: check_group(conditions, count)?,
+            }),
+            UncheckedCondition::Any { conditions } => Ok(Condition::Any {
+                conditions: check_group(conditions, count)?,
+            }),
+        }
+    }
+}
+
+impl Condition {
+    /// Checks whether PROPOSER meets this condition. MSG is the
+    /// serialized proposal message being submitted, if any. Reasons
+    /// are only returned for conditions that were not met.
+    pub fn check(
+        &self,
+        querier: &QuerierWrapper,
+        proposer: &Addr,
+        msg: Option<&Binary>,
+    ) -> StdResult<CanProposeResponse> {
+        match self {
+            Condition::Contract { address } => querier.query_wasm_smart(
+                address,
+                &ConditionQuery::CanPropose {
+                    proposer: proposer.to_string(),
+                    msg: msg.cloned(),
+                },
+            ),
+            Condition::All { conditions } => {
+                // Every condition is checked so that all of the
+                // reasons the proposer may not propose are reported.
+                let mut reasons = vec![];
+                for condition in conditions {
+                    let res = condition.check(querier, proposer, msg)?;
+                    if !res.can_propose {
+                        reasons.extend(res.reasons);
+                    }
+                }
+                Ok(CanProposeResponse {
+                    can_propose: reasons.is_empty(),
+                    reasons,
+                })
+            }
+            Condition::Any { conditions } => {
+                let mut reasons = vec![];
+                for condition in conditions {
+                    let res = condition.check(querier, proposer, msg)?;
+                    if res.can_propose {
+                        return Ok(CanProposeResponse::allowed());
+                    }
+                    reasons.extend(res.reasons);
+                }
+                Ok(CanProposeResponse {
+                    can_propose: false,
+                    reasons,
+                })
+            }
+        }
+    }
+}
+
+impl SponsorshipConfig {
+    pub fn validate(&self) -> Result<(), PreProposeError> {
+        match self.threshold {
+            SponsorshipThreshold::Members { count } => {
+                if count == 0 {
+                    return Err(PreProposeError::InvalidSponsorshipConfig {
+                        reason: "member count must be non-zero".to_string(),
+                    });
+                }
+            }
+            SponsorshipThreshold::Percentage { percent } => {
+                if percent.is_zero() || percent > Decimal::one() {
+                    return Err(PreProposeError::InvalidSponsorshipConfig {
+                        reason: "percentage must be in the range (0, 1]".to_string(),
+                    });
+                }
+            }
+        }
+        if matches!(self.window, Duration::Height(0) | Duration::Time(0)) {
+            return Err(PreProposeError::InvalidSponsorshipConfig {
+                reason: "window must be non-zero".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A proposal that has been submitted but has not yet received
+/// enough sponsorship to be created in the proposal module.
+#[cw_serde]
+pub struct PendingSponsorship {
+    /// The ID used to identify this pending proposal.
+    pub id: u64,
+    /// The address that submitted the proposal.
+    pub proposer: Addr,
+    /// The serialized propose message that will be sent to the
+    /// proposal module once the proposal is sponsored.
+    pub msg: Binary,
+    /// Snapshot of the deposit info at the time of proposal
+    /// submission.
+    pub deposit: Option<CheckedDepositInfo>,
+    /// The block height at which the proposal was submitted. Sponsor
+    /// voting power is measured at this height.
+    pub start_height: u64,
+    /// When the sponsorship window closes.
+    pub expiration: Expiration,
+    /// Snapshot of the sponsorship threshold at the time of proposal
+    /// submission.
+    pub threshold: SponsorshipThreshold,
+    /// The number of members that have sponsored this proposal.
+    pub sponsor_count: u64,
+    /// The combined voting power of this proposal's sponsors.
+    pub sponsored_power: Uint128,
+}
+
+/// A proposal that counts towards the open proposal limits,
+/// identified by the stage it is in and its ID in that stage.
+#[cw_serde]
+pub enum OpenProposal {
+    /// A proposal awaiting sponsorship, by its sponsorship ID.
+    Sponsorship { id: u64 },
+    /// A proposal awaiting approval in an approval module, by its
+    /// approval ID.
+    Approval { id: u64 },
+    /// A proposal in the proposal module, by its proposal ID.
+    Proposal { id: u64 },
+}
+
+impl OpenProposal {
+    /// The key this proposal's record ID is stored under.
+    pub fn key(&self) -> (&'static str, u64) {
+        match self {
+            OpenProposal::Sponsorship { id } => ("sponsorship", *id),
+            OpenProposal::Approval { id } => ("approval", *id),
+            OpenProposal::Proposal { id } => ("proposal", *id),
+        }
+    }
+}
+
+/// The proposer of an open proposal and the proposal it currently
+/// identifies.
+#[cw_serde]
+pub struct OpenProposalRecord {
+    pub proposer: Addr,
+    pub proposal: OpenProposal,
 }
 
 pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage> {
@@ -28,6 +355,30 @@ pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt,
     pub deposits: Map<'static, u64, (Option<CheckedDepositInfo>, Addr)>,
     /// Consumers of proposal submitted hooks.
     pub proposal_submitted_hooks: Hooks<'static>,
+    /// Proposals awaiting sponsorship, keyed by sponsorship ID.
+    pub pending_sponsorships: Map<'static, u64, PendingSponsorship>,
+    /// Map between (sponsorship ID, sponsor) pairs and the voting
+    /// power the sponsor contributed.
+    pub sponsors: Map<'static, (u64, &'static Addr), Uint128>,
+    /// The most recently assigned sponsorship ID.
+    pub sponsorship_id: Item<'static, u64>,
+    /// Map between (proposer, block height) pairs and the proposer's
+    /// proposals that were rejected or vetoed at that height.
+    pub rejections: Map<'static, (&'static Addr, u64), ActivityRecord>,
+    /// Map between (proposer, block height) pairs and the proposals
+    /// the proposer submitted at that height.
+    pub submissions: Map<'static, (&'static Addr, u64), ActivityRecord>,
+    /// Map between (proposer, open proposal record ID) pairs and when
+    /// the open proposal stops counting towards the rate limits.
+    pub open_proposals: Map<'static, (&'static Addr, u64), Expiration>,
+    /// Map between open proposal record IDs and the proposals they
+    /// record, so expired records can be pruned oldest first.
+    pub open_proposal_records: Map<'static, u64, OpenProposalRecord>,
+    /// Map between open proposals, keyed by `OpenProposal::key`, and
+    /// their record IDs.
+    pub open_proposal_record_ids: Map<'static, (&'static str, u64), u64>,
+    /// The most recently assigned open proposal record ID.
+    pub open_proposal_id: Item<'static, u64>,
 
     // These types are used in associated functions, but not
     // assocaited data. To stop the compiler complaining about unused
@@ -42,12 +393,22 @@ pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt,
 impl<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
     PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
 {
+    #[allow(clippy::too_many_arguments)]
     const fn new(
         proposal_key: &'static str,
         dao_key: &'static str,
         config_key: &'static str,
         deposits_key: &'static str,
         proposal_submitted_hooks_key: &'static str,
+        pending_sponsorships_key: &'static str,
+        sponsors_key: &'static str,
+        sponsorship_id_key: &'static str,
+        rejections_key: &'static str,
+        submissions_key: &'static str,
+        open_proposals_key: &'static str,
+        open_proposal_records_key: &'static str,
+        open_proposal_record_ids_key: &'static str,
+        open_proposal_id_key: &'static str,
     ) -> Self {
         Self {
             proposal_module: Item::new(proposal_key),
@@ -55,6 +416,15 @@ impl<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
             config: Item::new(config_key),
             deposits: Map::new(deposits_key),
             proposal_submitted_hooks: Hooks::new(proposal_submitted_hooks_key),
+            pending_sponsorships: Map::new(pending_sponsorships_key),
+            sponsors: Map::new(sponsors_key),
+            sponsorship_id: Item::new(sponsorship_id_key),
+            rejections: Map::new(rejections_key),
+            submissions: Map::new(submissions_key),
+            open_proposals: Map::new(open_proposals_key),
+            open_proposal_records: Map::new(open_proposal_records_key),
+            open_proposal_record_ids: Map::new(open_proposal_record_ids_key),
+            open_proposal_id: Item::new(open_proposal_id_key),
             execute_type: PhantomData,
             instantiate_type: PhantomData,
             query_type: PhantomData,
@@ -62,6 +432,14 @@ impl<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
             proposal_type: PhantomData,
         }
     }
+
+    /// Increments and returns the ID assigned to the next proposal
+    /// entering its sponsorship phase.
+    pub fn advance_sponsorship_id(&self, store: &mut dyn Storage) -> StdResult<u64> {
+        let id = self.sponsorship_id.may_load(store)?.unwrap_or_default() + 1;
+        self.sponsorship_id.save(store, &id)?;
+        Ok(id)
+    }
 }
 
 impl<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage> Default
@@ -77,6 +455,15 @@ impl<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage> Default
             "config",
             "deposits",
             "proposal_submitted_hooks",
+            "pending_sponsorships",
+            "sponsors",
+            "sponsorship_id",
+            "rejections",
+            "submissions",
+            "open_proposals",
+            "open_proposal_records",
+            "open_proposal_record_ids",
+            "open_proposal_id",
         )
     }
 }
@@ -1,15 +1,35 @@
 use cosmwasm_std::{
-    from_json,
-    testing::{mock_dependencies, mock_env, mock_info},
-    to_json_binary, Addr, Binary, ContractResult, Empty, Response, SubMsg, WasmMsg,
+    coins, from_json,
+    testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+    to_json_binary, Addr, BankMsg, Binary, ContractResult, Decimal, Empty, Order, OwnedDeps,
+    Response, StdResult, SubMsg, SystemResult, Uint128, WasmMsg, WasmQuery,
 };
+use cw721::Cw721ReceiveMsg;
+use cw_denom::{CheckedDenom, UncheckedDenom};
 use cw_hooks::HooksResponse;
-use dao_voting::{pre_propose::PreProposeSubmissionPolicy, status::Status};
+use cw_utils::Duration;
+use dao_interface::condition::{CanProposeResponse, Query as ConditionQuery};
+use dao_interface::voting::{
+    Query as CwCoreQuery, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use dao_voting::{
+    deposit::{
+        CheckedDepositInfo, DepositAlternative, DepositError, DepositFormula, DepositRefundPolicy,
+        DepositToken, EscrowedNft, ForfeitureConfig, ForfeitureDestination, UncheckedDepositInfo,
+        VetoedForfeiture, VotingPowerTier,
+    },
+    pre_propose::PreProposeSubmissionPolicy,
+    status::Status,
+};
 
 use crate::{
+    approval::{self, Approval, Approvers, Decision, Outcome, APPROVERS},
     error::PreProposeError,
-    msg::{ExecuteMsg, QueryMsg},
-    state::{Config, PreProposeContract},
+    msg::{ExecuteMsg, ProposerStatsResponse, QueryMsg, SponsorResponse},
+    state::{
+        Condition, Config, PendingSponsorship, PreProposeContract, RateLimitConfig,
+        SponsorshipConfig, SponsorshipThreshold, UncheckedCondition, WindowLimit,
+    },
 };
 
 type Contract = PreProposeContract<Empty, Empty, Empty, Empty, Empty>;
@@ -88,6 +108,10 @@ fn test_proposal_submitted_hooks() {
             &Config {
                 deposit_info: None,
                 submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+                sponsorship: None,
+                forfeiture: None,
+                rate_limit: None,
+                conditions: None,
             },
         )
         .unwrap();
@@ -200,3 +224,1398 @@ fn test_execute_ext_does_nothing() {
         .unwrap();
     assert_eq!(res, Response::default())
 }
+
+/// Sets up a module with the given sponsorship config. The DAO ("d")
+/// reports 10 voting power for "a", "b", and "c", and no voting power
+/// for anyone else.
+fn setup_sponsorship(
+    sponsorship: SponsorshipConfig,
+) -> (OwnedDeps<MockStorage, MockApi, MockQuerier>, Contract) {
+    let mut deps = mock_dependencies();
+    let module = Contract::default();
+
+    module
+        .dao
+        .save(&mut deps.storage, &Addr::unchecked("d"))
+        .unwrap();
+    module
+        .proposal_module
+        .save(&mut deps.storage, &Addr::unchecked("pm"))
+        .unwrap();
+    module
+        .config
+        .save(
+            &mut deps.storage,
+            &Config {
+                deposit_info: None,
+                submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+                sponsorship: Some(sponsorship),
+                forfeiture: None,
+                rate_limit: None,
+                conditions: None,
+            },
+        )
+        .unwrap();
+
+    deps.querier.update_wasm(|query| {
+        let WasmQuery::Smart { contract_addr, msg } = query else {
+            panic!("unexpected query: {query:?}");
+        };
+        let res = if contract_addr == "pm" {
+            // next proposal ID
+            to_json_binary(&1u64)
+        } else {
+            match from_json(msg).unwrap() {
+                CwCoreQuery::VotingPowerAtHeight { address, height } => {
+                    let power = if ["a", "b", "c"].contains(&address.as_str()) {
+                        10u128
+                    } else {
+                        0
+                    };
+                    to_json_binary(&VotingPowerAtHeightResponse {
+                        power: Uint128::new(power),
+                        height: height.unwrap(),
+                    })
+                }
+                CwCoreQuery::TotalPowerAtHeight { height } => {
+                    to_json_binary(&TotalPowerAtHeightResponse {
+                        power: Uint128::new(30),
+                        height: height.unwrap(),
+                    })
+                }
+                _ => panic!("unexpected query"),
+            }
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+
+    (deps, module)
+}
+
+#[test]
+fn test_sponsorship_members_threshold() {
+    let (mut deps, module) = setup_sponsorship(SponsorshipConfig {
+        threshold: SponsorshipThreshold::Members { count: 2 },
+        window: Duration::Height(10),
+    });
+
+    // Proposing does not create a proposal in the proposal module.
+    let res = module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("a", &[]),
+            ExecuteMsg::Propose {
+                msg: Empty::default(),
+            },
+        )
+        .unwrap();
+    assert!(res.messages.is_empty());
+
+    let pending: PendingSponsorship = from_json(
+        module
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::PendingSponsorship { id: 1 },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(pending.proposer, Addr::unchecked("a"));
+    assert_eq!(pending.sponsor_count, 0);
+
+    let sponsor = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, who: &str| {
+        module.execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(who, &[]),
+            ExecuteMsg::Sponsor { id: 1 },
+        )
+    };
+
+    // The proposer can't sponsor their own proposal.
+    let err = sponsor(&mut deps, "a").unwrap_err();
+    assert_eq!(err, PreProposeError::SelfSponsorship {});
+
+    // Non-members can't sponsor.
+    let err = sponsor(&mut deps, "z").unwrap_err();
+    assert_eq!(err, PreProposeError::ZeroSponsorPower {});
+
+    let res = sponsor(&mut deps, "b").unwrap();
+    assert!(res.messages.is_empty());
+
+    // Sponsoring twice does not count twice.
+    let err = sponsor(&mut deps, "b").unwrap_err();
+    assert_eq!(err, PreProposeError::AlreadySponsored {});
+
+    // The second sponsor creates the proposal.
+    let res = sponsor(&mut deps, "c").unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "pm".to_string(),
+            msg: to_json_binary(&Empty::default()).unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    // The proposal is no longer pending and its deposit has been
+    // snapshotted for the created proposal.
+    assert!(!module.pending_sponsorships.has(&deps.storage, 1));
+    let (_, proposer) = module.deposits.load(&deps.storage, 1).unwrap();
+    assert_eq!(proposer, Addr::unchecked("a"));
+
+    let sponsors: Vec<SponsorResponse> = from_json(
+        module
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Sponsors {
+                    id: 1,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        sponsors,
+        vec![
+            SponsorResponse {
+                address: Addr::unchecked("b"),
+                power: Uint128::new(10),
+            },
+            SponsorResponse {
+                address: Addr::unchecked("c"),
+                power: Uint128::new(10),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_sponsorship_percentage_threshold_expires() {
+    let (mut deps, module) = setup_sponsorship(SponsorshipConfig {
+        threshold: SponsorshipThreshold::Percentage {
+            percent: Decimal::percent(50),
+        },
+        window: Duration::Height(10),
+    });
+
+    module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("a", &[]),
+            ExecuteMsg::Propose {
+                msg: Empty::default(),
+            },
+        )
+        .unwrap();
+
+    // 10 / 30 voting power is not enough.
+    let res = module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("b", &[]),
+            ExecuteMsg::Sponsor { id: 1 },
+        )
+        .unwrap();
+    assert!(res.messages.is_empty());
+
+    // Can't expire the proposal while the window is open.
+    let err = module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ExpireUnsponsored { id: 1 },
+        )
+        .unwrap_err();
+    assert_eq!(err, PreProposeError::SponsorshipNotExpired { id: 1 });
+
+    let mut env = mock_env();
+    env.block.height += 10;
+
+    // Can't sponsor once the window has closed.
+    let err = module
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("c", &[]),
+            ExecuteMsg::Sponsor { id: 1 },
+        )
+        .unwrap_err();
+    assert_eq!(err, PreProposeError::SponsorshipExpired { id: 1 });
+
+    module
+        .execute(
+            deps.as_mut(),
+            env,
+            mock_info("anyone", &[]),
+            ExecuteMsg::ExpireUnsponsored { id: 1 },
+        )
+        .unwrap();
+    assert!(!module.pending_sponsorships.has(&deps.storage, 1));
+}
+
+#[test]
+fn test_update_sponsorship() {
+    let (mut deps, module) = setup_sponsorship(SponsorshipConfig {
+        threshold: SponsorshipThreshold::Members { count: 2 },
+        window: Duration::Height(10),
+    });
+
+    let err = module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("a", &[]),
+            ExecuteMsg::UpdateSponsorship { sponsorship: None },
+        )
+        .unwrap_err();
+    assert_eq!(err, PreProposeError::NotDao {});
+
+    let err = module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("d", &[]),
+            ExecuteMsg::UpdateSponsorship {
+                sponsorship: Some(SponsorshipConfig {
+                    threshold: SponsorshipThreshold::Members { count: 0 },
+                    window: Duration::Height(10),
+                }),
+            },
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        PreProposeError::InvalidSponsorshipConfig { .. }
+    ));
+
+    module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("d", &[]),
+            ExecuteMsg::UpdateSponsorship { sponsorship: None },
+        )
+        .unwrap();
+
+    // Without sponsorship, proposals are created immediately.
+    let res = module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("a", &[]),
+            ExecuteMsg::Propose {
+                msg: Empty::default(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.messages.len(), 1);
+}
+
+#[test]
+fn test_recent_rejections_deposit_formula() {
+    let mut deps = mock_dependencies();
+    let module = Contract::default();
+
+    let deposit_info = CheckedDepositInfo {
+        denom: CheckedDenom::Native("ujuno".to_string()),
+        amount: Uint128::new(100),
+        refund_policy: DepositRefundPolicy::OnlyPassed,
+        formula: Some(DepositFormula::RecentRejections {
+            increment: Decimal::one(),
+            window: Duration::Height(10),
+            max_multiplier: None,
+        }),
+        alternatives: vec![],
+        escrowed_nft: None,
+    };
+
+    module
+        .dao
+        .save(&mut deps.storage, &Addr::unchecked("d"))
+        .unwrap();
+    module
+        .proposal_module
+        .save(&mut deps.storage, &Addr::unchecked("pm"))
+        .unwrap();
+    module
+        .config
+        .save(
+            &mut deps.storage,
+            &Config {
+                deposit_info: Some(deposit_info.clone()),
+                submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+                sponsorship: None,
+                forfeiture: None,
+                rate_limit: None,
+                conditions: None,
+            },
+        )
+        .unwrap();
+    for id in 1..=2 {
+        module
+            .deposits
+            .save(
+                &mut deps.storage,
+                id,
+                &(Some(deposit_info.clone()), Addr::unchecked("a")),
+            )
+            .unwrap();
+    }
+
+    let query_deposit = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                         env: cosmwasm_std::Env,
+                         proposer: &str| {
+        let deposit: Option<CheckedDepositInfo> = from_json(
+            module
+                .query(
+                    deps.as_ref(),
+                    env,
+                    QueryMsg::ProposalDeposit {
+                        proposer: proposer.to_string(),
+                    },
+                )
+                .unwrap(),
+        )
+        .unwrap();
+        deposit.unwrap().amount
+    };
+
+    assert_eq!(query_deposit(&deps, mock_env(), "a"), Uint128::new(100));
+
+    // Proposal 1 is rejected.
+    module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("pm", &[]),
+            ExecuteMsg::ProposalCompletedHook {
+                proposal_id: 1,
+                new_status: Status::Closed,
+            },
+        )
+        .unwrap();
+
+    // The proposer's deposit doubles while the rejection is recent.
+    assert_eq!(query_deposit(&deps, mock_env(), "a"), Uint128::new(200));
+    assert_eq!(query_deposit(&deps, mock_env(), "b"), Uint128::new(100));
+
+    let mut env = mock_env();
+    env.block.height += 10;
+    assert_eq!(query_deposit(&deps, env.clone(), "a"), Uint128::new(100));
+
+    // Rejections outside the window are pruned when the next one is
+    // recorded.
+    module
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("pm", &[]),
+            ExecuteMsg::ProposalCompletedHook {
+                proposal_id: 2,
+                new_status: Status::Vetoed,
+            },
+        )
+        .unwrap();
+    assert_eq!(query_deposit(&deps, env, "a"), Uint128::new(200));
+    assert_eq!(
+        module
+            .rejections
+            .prefix(&Addr::unchecked("a"))
+            .range(&deps.storage, None, None, Order::Ascending)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_voting_power_deposit_formula() {
+    let mut deps = mock_dependencies();
+    let module = Contract::default();
+
+    module
+        .dao
+        .save(&mut deps.storage, &Addr::unchecked("d"))
+        .unwrap();
+    module
+        .config
+        .save(
+            &mut deps.storage,
+            &Config {
+                deposit_info: Some(CheckedDepositInfo {
+                    denom: CheckedDenom::Native("ujuno".to_string()),
+                    amount: Uint128::new(100),
+                    refund_policy: DepositRefundPolicy::OnlyPassed,
+                    formula: Some(DepositFormula::VotingPower {
+                        non_member_multiplier: Decimal::percent(200),
+                        tiers: vec![VotingPowerTier {
+                            min_share: Decimal::percent(50),
+                            multiplier: Decimal::percent(50),
+                        }],
+                    }),
+                    alternatives: vec![],
+                    escrowed_nft: None,
+                }),
+                submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+                sponsorship: None,
+                forfeiture: None,
+                rate_limit: None,
+                conditions: None,
+            },
+        )
+        .unwrap();
+
+    // "a" has 10 of 30 voting power, but stakes another 10 in the
+    // current block.
+    let current_height = mock_env().block.height;
+    deps.querier.update_wasm(move |query| {
+        let WasmQuery::Smart { msg, .. } = query else {
+            panic!("unexpected query: {query:?}");
+        };
+        let res = match from_json(msg).unwrap() {
+            CwCoreQuery::VotingPowerAtHeight { address, height } => {
+                let height = height.unwrap();
+                let power = match (address.as_str(), height >= current_height) {
+                    ("a", false) => 10u128,
+                    ("a", true) => 20,
+                    _ => 0,
+                };
+                to_json_binary(&VotingPowerAtHeightResponse {
+                    power: Uint128::new(power),
+                    height,
+                })
+            }
+            CwCoreQuery::TotalPowerAtHeight { height } => {
+                let height = height.unwrap();
+                let power = if height >= current_height { 40u128 } else { 30 };
+                to_json_binary(&TotalPowerAtHeightResponse {
+                    power: Uint128::new(power),
+                    height,
+                })
+            }
+            _ => panic!("unexpected query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+
+    let query_deposit = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, proposer: &str| {
+        let deposit: Option<CheckedDepositInfo> = from_json(
+            module
+                .query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::ProposalDeposit {
+                        proposer: proposer.to_string(),
+                    },
+                )
+                .unwrap(),
+        )
+        .unwrap();
+        deposit.unwrap().amount
+    };
+
+    // Power gained in the current block does not reach the cheaper
+    // tier.
+    assert_eq!(query_deposit(&deps, "a"), Uint128::new(100));
+    assert_eq!(query_deposit(&deps, "b"), Uint128::new(200));
+}
+
+#[test]
+fn test_nft_deposit() {
+    let mut deps = mock_dependencies();
+    let module = Contract::default();
+
+    module
+        .dao
+        .save(&mut deps.storage, &Addr::unchecked("d"))
+        .unwrap();
+    module
+        .proposal_module
+        .save(&mut deps.storage, &Addr::unchecked("pm"))
+        .unwrap();
+    module
+        .config
+        .save(
+            &mut deps.storage,
+            &Config {
+                deposit_info: Some(CheckedDepositInfo {
+                    denom: CheckedDenom::Native("ujuno".to_string()),
+                    amount: Uint128::new(100),
+                    refund_policy: DepositRefundPolicy::Always,
+                    formula: None,
+                    alternatives: vec![DepositAlternative::Nft {
+                        collection: Addr::unchecked("nft"),
+                    }],
+                    escrowed_nft: None,
+                }),
+                submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+                sponsorship: None,
+                forfeiture: None,
+                rate_limit: None,
+                conditions: None,
+            },
+        )
+        .unwrap();
+    deps.querier.update_wasm(|_| {
+        // next proposal ID
+        SystemResult::Ok(ContractResult::Ok(to_json_binary(&1u64).unwrap()))
+    });
+
+    let receive = || {
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: "a".to_string(),
+            token_id: "1".to_string(),
+            msg: to_json_binary(&Empty::default()).unwrap(),
+        })
+    };
+
+    // NFTs from other collections are not accepted.
+    let err = module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("other", &[]),
+            receive(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::Deposit(DepositError::UnacceptedDeposit {})
+    );
+
+    let res = module
+        .execute(deps.as_mut(), mock_env(), mock_info("nft", &[]), receive())
+        .unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    let (deposit, proposer) = module.deposits.load(&deps.storage, 1).unwrap();
+    assert_eq!(proposer, Addr::unchecked("a"));
+    assert_eq!(
+        deposit.unwrap().escrowed_nft,
+        Some(EscrowedNft {
+            collection: Addr::unchecked("nft"),
+            token_id: "1".to_string(),
+        })
+    );
+
+    // The NFT is returned to the proposer once the proposal completes.
+    let res = module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("pm", &[]),
+            ExecuteMsg::ProposalCompletedHook {
+                proposal_id: 1,
+                new_status: Status::Closed,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "nft".to_string(),
+            msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+                recipient: "a".to_string(),
+                token_id: "1".to_string(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+}
+
+#[test]
+fn test_forfeiture_destination() {
+    let mut deps = mock_dependencies();
+    let module = Contract::default();
+
+    let deposit_info = CheckedDepositInfo {
+        denom: CheckedDenom::Native("ujuno".to_string()),
+        amount: Uint128::new(100),
+        refund_policy: DepositRefundPolicy::OnlyPassed,
+        formula: None,
+        alternatives: vec![],
+        escrowed_nft: None,
+    };
+
+    module
+        .dao
+        .save(&mut deps.storage, &Addr::unchecked("d"))
+        .unwrap();
+    module
+        .proposal_module
+        .save(&mut deps.storage, &Addr::unchecked("pm"))
+        .unwrap();
+    module
+        .config
+        .save(
+            &mut deps.storage,
+            &Config {
+                deposit_info: Some(deposit_info.clone()),
+                submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+                sponsorship: None,
+                forfeiture: None,
+                rate_limit: None,
+                conditions: None,
+            },
+        )
+        .unwrap();
+    for id in 1..=2 {
+        module
+            .deposits
+            .save(
+                &mut deps.storage,
+                id,
+                &(Some(deposit_info.clone()), Addr::unchecked("a")),
+            )
+            .unwrap();
+    }
+
+    // Only the DAO may update the forfeiture config.
+    let forfeiture = ForfeitureConfig {
+        destination: ForfeitureDestination::Burn {},
+        vetoed: Some(VetoedForfeiture {
+            slash: Decimal::percent(50),
+            destination: Some(ForfeitureDestination::Dao {}),
+        }),
+    };
+    let err = module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("a", &[]),
+            ExecuteMsg::UpdateForfeiture {
+                forfeiture: Some(forfeiture.clone()),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, PreProposeError::NotDao {});
+    module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("d", &[]),
+            ExecuteMsg::UpdateForfeiture {
+                forfeiture: Some(forfeiture),
+            },
+        )
+        .unwrap();
+
+    // Deposits may not become never refunded while vetoed proposals
+    // are refunded in part.
+    let err = module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("d", &[]),
+            ExecuteMsg::UpdateConfig {
+                deposit_info: Some(UncheckedDepositInfo {
+                    denom: DepositToken::Token {
+                        denom: UncheckedDenom::Native("ujuno".to_string()),
+                    },
+                    amount: Uint128::new(100),
+                    refund_policy: DepositRefundPolicy::Never,
+                    formula: None,
+                    alternatives: vec![],
+                }),
+                submission_policy: None,
+            },
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        PreProposeError::Deposit(DepositError::InvalidForfeiture { .. })
+    ));
+
+    let complete = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                    proposal_id: u64,
+                    new_status: Status| {
+        module
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("pm", &[]),
+                ExecuteMsg::ProposalCompletedHook {
+                    proposal_id,
+                    new_status,
+                },
+            )
+            .unwrap()
+            .messages
+    };
+
+    // Rejected proposals forfeit their deposit to the destination.
+    assert_eq!(
+        complete(&mut deps, 1, Status::Closed),
+        vec![SubMsg::new(BankMsg::Burn {
+            amount: coins(100, "ujuno"),
+        })]
+    );
+
+    // Vetoed proposals are slashed in part, with the slashed portion
+    // sent to the vetoed destination.
+    assert_eq!(
+        complete(&mut deps, 2, Status::Vetoed),
+        vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: "a".to_string(),
+                amount: coins(50, "ujuno"),
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: "d".to_string(),
+                amount: coins(50, "ujuno"),
+            }),
+        ]
+    );
+}
+
+#[test]
+fn test_rate_limit() {
+    let mut deps = mock_dependencies();
+    let module = Contract::default();
+
+    module
+        .dao
+        .save(&mut deps.storage, &Addr::unchecked("d"))
+        .unwrap();
+    module
+        .proposal_module
+        .save(&mut deps.storage, &Addr::unchecked("pm"))
+        .unwrap();
+    module
+        .config
+        .save(
+            &mut deps.storage,
+            &Config {
+                deposit_info: None,
+                submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+                sponsorship: None,
+                forfeiture: None,
+                rate_limit: Some(RateLimitConfig {
+                    max_open_per_proposer: Some(2),
+                    max_per_window: Some(WindowLimit {
+                        count: 3,
+                        window: Duration::Height(10),
+                    }),
+                    max_open: Some(3),
+                    open_expiration: Duration::Height(100),
+                }),
+                conditions: None,
+            },
+        )
+        .unwrap();
+    // Proposes as WHO, with the proposal module reporting ID as the
+    // next proposal ID.
+    let propose = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                   env: cosmwasm_std::Env,
+                   who: &str,
+                   id: u64| {
+        deps.querier.update_wasm(move |_| {
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&id).unwrap()))
+        });
+        module.execute(
+            deps.as_mut(),
+            env,
+            mock_info(who, &[]),
+            ExecuteMsg::Propose {
+                msg: Empty::default(),
+            },
+        )
+    };
+    let complete = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, id: u64| {
+        module
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("pm", &[]),
+                ExecuteMsg::ProposalCompletedHook {
+                    proposal_id: id,
+                    new_status: Status::Executed,
+                },
+            )
+            .unwrap();
+    };
+    let stats = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, who: &str| {
+        let stats: ProposerStatsResponse = from_json(
+            module
+                .query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::ProposerStats {
+                        address: who.to_string(),
+                    },
+                )
+                .unwrap(),
+        )
+        .unwrap();
+        stats
+    };
+
+    propose(&mut deps, mock_env(), "a", 1).unwrap();
+    propose(&mut deps, mock_env(), "a", 2).unwrap();
+    let err = propose(&mut deps, mock_env(), "a", 3).unwrap_err();
+    assert_eq!(err, PreProposeError::TooManyOpenProposals { max: 2 });
+
+    propose(&mut deps, mock_env(), "b", 3).unwrap();
+    let err = propose(&mut deps, mock_env(), "c", 4).unwrap_err();
+    assert_eq!(err, PreProposeError::ModuleOpenProposalLimit { max: 3 });
+
+    complete(&mut deps, 3);
+    assert_eq!(
+        stats(&deps, "b"),
+        ProposerStatsResponse {
+            open_proposals: 0,
+            recent_submissions: 1,
+            total_open_proposals: 2,
+        }
+    );
+
+    propose(&mut deps, mock_env(), "b", 4).unwrap();
+    complete(&mut deps, 4);
+    propose(&mut deps, mock_env(), "b", 5).unwrap();
+    complete(&mut deps, 5);
+    let err = propose(&mut deps, mock_env(), "b", 6).unwrap_err();
+    assert_eq!(err, PreProposeError::SubmissionRateLimited { max: 3 });
+
+    // Once the window has passed, b may propose again.
+    let mut env = mock_env();
+    env.block.height += 10;
+    propose(&mut deps, env, "b", 6).unwrap();
+    assert_eq!(
+        stats(&deps, "a"),
+        ProposerStatsResponse {
+            open_proposals: 2,
+            recent_submissions: 2,
+            total_open_proposals: 3,
+        }
+    );
+
+    // Open proposals stop counting once they expire, even if the
+    // module is never told that they completed.
+    let mut env = mock_env();
+    env.block.height += 100;
+    propose(&mut deps, env.clone(), "a", 7).unwrap();
+    propose(&mut deps, env.clone(), "a", 8).unwrap();
+    let err = propose(&mut deps, env.clone(), "a", 9).unwrap_err();
+    assert_eq!(err, PreProposeError::TooManyOpenProposals { max: 2 });
+    let err = propose(&mut deps, env, "c", 9).unwrap_err();
+    assert_eq!(err, PreProposeError::ModuleOpenProposalLimit { max: 3 });
+    // The expired proposals were pruned.
+    assert_eq!(
+        module
+            .open_proposals
+            .range(&deps.storage, None, None, Order::Ascending)
+            .count(),
+        3
+    );
+
+    // Nothing is recorded for limits that are not configured.
+    module
+        .config
+        .update(&mut deps.storage, |mut config| -> StdResult<_> {
+            config.rate_limit = None;
+            Ok(config)
+        })
+        .unwrap();
+    propose(&mut deps, mock_env(), "c", 9).unwrap();
+    assert_eq!(
+        module
+            .submissions
+            .prefix(&Addr::unchecked("c"))
+            .range(&deps.storage, None, None, Order::Ascending)
+            .count(),
+        0
+    );
+    assert_eq!(
+        module
+            .open_proposals
+            .prefix(&Addr::unchecked("c"))
+            .range(&deps.storage, None, None, Order::Ascending)
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn test_rate_limit_closes_completed_proposal() {
+    let mut deps = mock_dependencies();
+    let module = Contract::default();
+
+    module
+        .dao
+        .save(&mut deps.storage, &Addr::unchecked("d"))
+        .unwrap();
+    module
+        .proposal_module
+        .save(&mut deps.storage, &Addr::unchecked("pm"))
+        .unwrap();
+    module
+        .config
+        .save(
+            &mut deps.storage,
+            &Config {
+                deposit_info: None,
+                submission_policy: PreProposeSubmissionPolicy::Anyone { denylist: vec![] },
+                sponsorship: None,
+                forfeiture: None,
+                rate_limit: None,
+                conditions: None,
+            },
+        )
+        .unwrap();
+
+    let propose = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, id: u64| {
+        deps.querier.update_wasm(move |_| {
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&id).unwrap()))
+        });
+        module.execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("a", &[]),
+            ExecuteMsg::Propose {
+                msg: Empty::default(),
+            },
+        )
+    };
+    let complete = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, id: u64| {
+        module
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("pm", &[]),
+                ExecuteMsg::ProposalCompletedHook {
+                    proposal_id: id,
+                    new_status: Status::Closed,
+                },
+            )
+            .unwrap();
+    };
+
+    // Proposal 1 is submitted before the limit is configured, so it
+    // is not recorded as open.
+    propose(&mut deps, 1).unwrap();
+    module
+        .config
+        .update(&mut deps.storage, |mut config| -> StdResult<_> {
+            config.rate_limit = Some(RateLimitConfig {
+                max_open_per_proposer: Some(1),
+                max_per_window: None,
+                max_open: None,
+                open_expiration: Duration::Height(100),
+            });
+            Ok(config)
+        })
+        .unwrap();
+    propose(&mut deps, 2).unwrap();
+
+    // Closing proposal 1 does not close proposal 2.
+    complete(&mut deps, 1);
+    let err = propose(&mut deps, 3).unwrap_err();
+    assert_eq!(err, PreProposeError::TooManyOpenProposals { max: 1 });
+
+    complete(&mut deps, 2);
+    propose(&mut deps, 3).unwrap();
+}
+
+#[test]
+fn test_rate_limit_sponsorship() {
+    let (mut deps, module) = setup_sponsorship(SponsorshipConfig {
+        threshold: SponsorshipThreshold::Members { count: 1 },
+        window: Duration::Height(10),
+    });
+    module
+        .config
+        .update(&mut deps.storage, |mut config| -> StdResult<_> {
+            config.rate_limit = Some(RateLimitConfig {
+                max_open_per_proposer: Some(1),
+                max_per_window: None,
+                max_open: None,
+                open_expiration: Duration::Height(100),
+            });
+            Ok(config)
+        })
+        .unwrap();
+
+    let propose = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+        module.execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("a", &[]),
+            ExecuteMsg::Propose {
+                msg: Empty::default(),
+            },
+        )
+    };
+    let open_proposals = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+        module
+            .count_open_proposals(&deps.storage, &mock_env().block, None)
+            .unwrap()
+    };
+
+    // A proposal is open while it awaits sponsorship, and remains
+    // open once it is created in the proposal module.
+    propose(&mut deps).unwrap();
+    assert_eq!(open_proposals(&deps), 1);
+    module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("b", &[]),
+            ExecuteMsg::Sponsor { id: 1 },
+        )
+        .unwrap();
+    assert_eq!(open_proposals(&deps), 1);
+    let err = propose(&mut deps).unwrap_err();
+    assert_eq!(err, PreProposeError::TooManyOpenProposals { max: 1 });
+
+    // The mock proposal module created the proposal with ID one.
+    module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("pm", &[]),
+            ExecuteMsg::ProposalCompletedHook {
+                proposal_id: 1,
+                new_status: Status::Executed,
+            },
+        )
+        .unwrap();
+    assert_eq!(open_proposals(&deps), 0);
+
+    // Expiring an unsponsored proposal closes it.
+    propose(&mut deps).unwrap();
+    assert_eq!(open_proposals(&deps), 1);
+    let mut env = mock_env();
+    env.block.height += 10;
+    module
+        .execute(
+            deps.as_mut(),
+            env,
+            mock_info("anyone", &[]),
+            ExecuteMsg::ExpireUnsponsored { id: 2 },
+        )
+        .unwrap();
+    assert_eq!(open_proposals(&deps), 0);
+}
+
+#[test]
+fn test_conditions() {
+    let mut deps = mock_dependencies();
+    let module = Contract::default();
+
+    module
+        .dao
+        .save(&mut deps.storage, &Addr::unchecked("d"))
+        .unwrap();
+    module
+        .proposal_module
+        .save(&mut deps.storage, &Addr::unchecked("pm"))
+        .unwrap();
+    module
+        .config
+        .save(
+            &mut deps.storage,
+            &Config {
+                deposit_info: None,
+                submission_policy: PreProposeSubmissionPolicy::Anyone {
+                    denylist: vec![Addr::unchecked("denied")],
+                },
+                sponsorship: None,
+                forfeiture: None,
+                rate_limit: None,
+                conditions: None,
+            },
+        )
+        .unwrap();
+    deps.querier.update_wasm(|query| {
+        let WasmQuery::Smart { contract_addr, msg } = query else {
+            panic!("unexpected query");
+        };
+        if contract_addr == "pm" {
+            // next proposal ID
+            return SystemResult::Ok(ContractResult::Ok(to_json_binary(&1u64).unwrap()));
+        }
+        // The proposal message is only passed when one is being
+        // submitted or was given to `CanProposeWithReasons`.
+        let ConditionQuery::CanPropose { proposer, .. } = from_json(msg).unwrap();
+        let (allowed, reason) = match contract_addr.as_str() {
+            "nft" => (["a", "c"].as_slice(), "must hold an NFT"),
+            "staked" => (["b"].as_slice(), "must have staked for 30 days"),
+            "committee" => (["a", "b"].as_slice(), "must be a committee member"),
+            _ => panic!("unexpected contract"),
+        };
+        let res = if allowed.contains(&proposer.as_str()) {
+            CanProposeResponse::allowed()
+        } else {
+            CanProposeResponse::denied(reason)
+        };
+        SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+    });
+
+    let update = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                  sender: &str,
+                  conditions: Option<UncheckedCondition>| {
+        module.execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &[]),
+            ExecuteMsg::UpdateConditions { conditions },
+        )
+    };
+    let contract = |address: &str| UncheckedCondition::Contract {
+        address: address.to_string(),
+    };
+
+    let err = update(&mut deps, "a", None).unwrap_err();
+    assert_eq!(err, PreProposeError::NotDao {});
+    let err = update(
+        &mut deps,
+        "d",
+        Some(UncheckedCondition::Any { conditions: vec![] }),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::InvalidConditions {
+            reason: "condition groups must not be empty".to_string()
+        }
+    );
+    let err = update(
+        &mut deps,
+        "d",
+        Some(UncheckedCondition::All {
+            conditions: vec![contract("nft"); 11],
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::InvalidConditions {
+            reason: "conditions may query at most 10 contracts".to_string()
+        }
+    );
+
+    // Proposers must hold an NFT or have staked, and be a member of
+    // the committee.
+    update(
+        &mut deps,
+        "d",
+        Some(UncheckedCondition::All {
+            conditions: vec![
+                UncheckedCondition::Any {
+                    conditions: vec![contract("nft"), contract("staked")],
+                },
+                contract("committee"),
+            ],
+        }),
+    )
+    .unwrap();
+
+    let propose = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, who: &str| {
+        module.execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(who, &[]),
+            ExecuteMsg::Propose {
+                msg: Empty::default(),
+            },
+        )
+    };
+    let can_propose = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, who: &str| {
+        let res: CanProposeResponse = from_json(
+            module
+                .query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::CanProposeWithReasons {
+                        address: who.to_string(),
+                        msg: Some(to_json_binary(&Empty::default()).unwrap()),
+                    },
+                )
+                .unwrap(),
+        )
+        .unwrap();
+        res
+    };
+
+    propose(&mut deps, "a").unwrap();
+    propose(&mut deps, "b").unwrap();
+    let err = propose(&mut deps, "c").unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::ConditionsNotMet {
+            reasons: "must be a committee member".to_string()
+        }
+    );
+
+    assert_eq!(can_propose(&deps, "a"), CanProposeResponse::allowed());
+    assert_eq!(
+        can_propose(&deps, "e"),
+        CanProposeResponse {
+            can_propose: false,
+            reasons: vec![
+                "must hold an NFT".to_string(),
+                "must have staked for 30 days".to_string(),
+                "must be a committee member".to_string(),
+            ],
+        }
+    );
+    // The submission policy is checked before any conditions.
+    assert_eq!(
+        can_propose(&deps, "denied"),
+        CanProposeResponse::denied("not permitted by the submission policy")
+    );
+
+    // The `CanPropose` query returns only whether the address may
+    // propose.
+    let allowed: bool = from_json(
+        module
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::CanPropose {
+                    address: "a".to_string(),
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert!(allowed);
+
+    // Contract addresses are validated and stored as addresses.
+    let err = update(&mut deps, "d", Some(contract(""))).unwrap_err();
+    assert!(matches!(err, PreProposeError::Std(_)));
+    assert_eq!(
+        module.config.load(&deps.storage).unwrap().conditions,
+        Some(Condition::All {
+            conditions: vec![
+                Condition::Any {
+                    conditions: vec![
+                        Condition::Contract {
+                            address: Addr::unchecked("nft")
+                        },
+                        Condition::Contract {
+                            address: Addr::unchecked("staked")
+                        },
+                    ],
+                },
+                Condition::Contract {
+                    address: Addr::unchecked("committee")
+                },
+            ],
+        })
+    );
+}
+
+#[test]
+fn test_approval_decisions() {
+    let mut deps = mock_dependencies();
+    let approvers = Approvers::new(
+        &deps.api,
+        vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        3,
+        None,
+    )
+    .unwrap();
+    APPROVERS.save(&mut deps.storage, &approvers).unwrap();
+
+    let (a, b, c) = (
+        Addr::unchecked("a"),
+        Addr::unchecked("b"),
+        Addr::unchecked("c"),
+    );
+    let err = approval::decide(
+        &mut deps.storage,
+        &Addr::unchecked("d"),
+        1,
+        Decision::Approved {},
+    )
+    .unwrap_err();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+
+    let outcome = approval::decide(&mut deps.storage, &a, 1, Decision::Approved {}).unwrap();
+    assert_eq!(
+        outcome,
+        Outcome::Undecided {
+            approvals: 1,
+            rejections: 0
+        }
+    );
+    let outcome = approval::decide(&mut deps.storage, &b, 1, Decision::Approved {}).unwrap();
+    assert_eq!(
+        outcome,
+        Outcome::Undecided {
+            approvals: 2,
+            rejections: 0
+        }
+    );
+    let err = approval::decide(&mut deps.storage, &a, 1, Decision::Approved {}).unwrap_err();
+    assert_eq!(err, PreProposeError::AlreadyDecided {});
+
+    // Once the threshold is lowered, repeating a decision decides
+    // the proposal without changing the recorded decision.
+    APPROVERS
+        .save(
+            &mut deps.storage,
+            &Approvers {
+                threshold: 2,
+                ..approvers
+            },
+        )
+        .unwrap();
+    let outcome = approval::decide(
+        &mut deps.storage,
+        &a,
+        1,
+        Decision::Rejected { reason: None },
+    )
+    .unwrap();
+    assert_eq!(outcome, Outcome::Approved);
+    assert_eq!(
+        approval::query_approvals(&deps.storage, 1).unwrap()[0],
+        Approval {
+            approver: a,
+            decision: Decision::Approved {},
+        }
+    );
+
+    // Two rejections of three with a threshold of two decide the
+    // proposal, with the reason of the deciding rejection.
+    approval::decide(
+        &mut deps.storage,
+        &b,
+        2,
+        Decision::Rejected {
+            reason: Some("first".to_string()),
+        },
+    )
+    .unwrap();
+    let outcome = approval::decide(
+        &mut deps.storage,
+        &c,
+        2,
+        Decision::Rejected {
+            reason: Some("second".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        outcome,
+        Outcome::Rejected {
+            reason: Some("second".to_string())
+        }
+    );
+
+    approval::clear_decisions(&mut deps.storage, 2).unwrap();
+    assert!(approval::query_approvals(&deps.storage, 2)
+        .unwrap()
+        .is_empty());
+}
@@ -59,6 +59,16 @@ pub fn dao_proposal_condorcet_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+pub fn dao_proposal_election_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_proposal_election::contract::execute,
+        dao_proposal_election::contract::instantiate,
+        dao_proposal_election::contract::query,
+    )
+    .with_reply(dao_proposal_election::contract::reply);
+    Box::new(contract)
+}
+
 pub fn dao_proposal_single_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         dao_proposal_single::contract::execute,
@@ -120,6 +130,36 @@ pub fn dao_pre_propose_multiple_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+pub fn dao_pre_propose_condorcet_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_pre_propose_condorcet::contract::execute,
+        dao_pre_propose_condorcet::contract::instantiate,
+        dao_pre_propose_condorcet::contract::query,
+    )
+    .with_migrate(dao_pre_propose_condorcet::contract::migrate);
+    Box::new(contract)
+}
+
+pub fn dao_pre_propose_approval_multiple_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_pre_propose_approval_multiple::contract::execute,
+        dao_pre_propose_approval_multiple::contract::instantiate,
+        dao_pre_propose_approval_multiple::contract::query,
+    )
+    .with_migrate(dao_pre_propose_approval_multiple::contract::migrate);
+    Box::new(contract)
+}
+
+pub fn dao_pre_propose_template_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_pre_propose_template::contract::execute,
+        dao_pre_propose_template::contract::instantiate,
+        dao_pre_propose_template::contract::query,
+    )
+    .with_migrate(dao_pre_propose_template::contract::migrate);
+    Box::new(contract)
+}
+
 pub fn dao_pre_propose_approval_single_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         dao_pre_propose_approval_single::contract::execute,
@@ -169,7 +209,8 @@ pub fn dao_voting_token_staked_contract() -> Box<dyn Contract<Empty>> {
         dao_voting_token_staked::contract::query,
     )
     .with_reply(dao_voting_token_staked::contract::reply)
-    .with_migrate(dao_voting_token_staked::contract::migrate);
+    .with_migrate(dao_voting_token_staked::contract::migrate)
+    .with_sudo(dao_voting_token_staked::contract::sudo);
     Box::new(contract)
 }
 
@@ -200,10 +241,32 @@ pub fn dao_voting_onft_staked_contract() -> Box<dyn Contract<Empty>> {
         dao_voting_onft_staked::contract::instantiate,
         dao_voting_onft_staked::contract::query,
     )
+    .with_reply(dao_voting_onft_staked::contract::reply)
     .with_migrate(dao_voting_onft_staked::contract::migrate);
     Box::new(contract)
 }
 
+pub fn dao_voting_composite_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_voting_composite::contract::execute,
+        dao_voting_composite::contract::instantiate,
+        dao_voting_composite::contract::query,
+    )
+    .with_reply(dao_voting_composite::contract::reply)
+    .with_migrate(dao_voting_composite::contract::migrate);
+    Box::new(contract)
+}
+
+pub fn dao_voting_native_staked_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_voting_native_staked::contract::execute,
+        dao_voting_native_staked::contract::instantiate,
+        dao_voting_native_staked::contract::query,
+    )
+    .with_migrate(dao_voting_native_staked::contract::migrate);
+    Box::new(contract)
+}
+
 pub fn dao_dao_core_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         dao_dao_core::contract::execute,
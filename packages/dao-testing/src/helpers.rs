@@ -1,6 +1,6 @@
-use cosmwasm_std::{to_json_binary, Addr, Binary, Uint128};
+use cosmwasm_std::{to_json_binary, Addr, Binary, CosmosMsg, Order, Storage, Uint128};
 use cw20::Cw20Coin;
-use cw_multi_test::{App, Executor};
+use cw_multi_test::{App, AppResponse, Executor};
 use cw_utils::Duration;
 use dao_interface::state::{Admin, ModuleInstantiateInfo};
 use dao_voting::threshold::ActiveThreshold;
@@ -378,3 +378,69 @@ pub fn instantiate_with_cw4_groups_governance(
 
     addr
 }
+
+/// Executes MSGS as SENDER and then reverts every change they made to
+/// the app's storage, returning the responses. Useful for checking
+/// what a proposal's messages would do without executing it.
+pub fn dry_run(
+    app: &mut App,
+    sender: Addr,
+    msgs: Vec<CosmosMsg>,
+) -> anyhow::Result<Vec<AppResponse>> {
+    let snapshot: Vec<_> = app.storage().range(None, None, Order::Ascending).collect();
+
+    let res = app.execute_multi(sender, msgs);
+
+    let storage = app.storage_mut();
+    let keys: Vec<_> = storage.range_keys(None, None, Order::Ascending).collect();
+    for key in keys {
+        storage.remove(&key);
+    }
+    for (key, value) in snapshot {
+        storage.set(&key, &value);
+    }
+
+    res
+}
+
+/// Dry runs the messages of a `dao-proposal-single` proposal as the
+/// DAO. See `dry_run`.
+pub fn simulate_proposal_single(
+    app: &mut App,
+    proposal_module: &Addr,
+    proposal_id: u64,
+) -> anyhow::Result<Vec<AppResponse>> {
+    let dao: Addr = app
+        .wrap()
+        .query_wasm_smart(proposal_module, &dao_proposal_single::msg::QueryMsg::Dao {})?;
+    let proposal: dao_proposal_single::query::ProposalResponse = app.wrap().query_wasm_smart(
+        proposal_module,
+        &dao_proposal_single::msg::QueryMsg::Proposal { proposal_id },
+    )?;
+    dry_run(app, dao, proposal.proposal.msgs)
+}
+
+/// Dry runs the messages of the choice at index OPTION_ID of a
+/// `dao-proposal-multiple` proposal as the DAO. See `dry_run`.
+pub fn simulate_proposal_multiple(
+    app: &mut App,
+    proposal_module: &Addr,
+    proposal_id: u64,
+    option_id: u32,
+) -> anyhow::Result<Vec<AppResponse>> {
+    let dao: Addr = app.wrap().query_wasm_smart(
+        proposal_module,
+        &dao_proposal_multiple::msg::QueryMsg::Dao {},
+    )?;
+    let proposal: dao_proposal_multiple::query::ProposalResponse = app.wrap().query_wasm_smart(
+        proposal_module,
+        &dao_proposal_multiple::msg::QueryMsg::Proposal { proposal_id },
+    )?;
+    let choice = proposal
+        .proposal
+        .choices
+        .into_iter()
+        .find(|choice| choice.index == option_id)
+        .ok_or_else(|| anyhow::anyhow!("no choice with index {option_id}"))?;
+    dry_run(app, dao, choice.msgs)
+}
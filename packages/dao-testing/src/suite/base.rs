@@ -87,6 +87,10 @@ pub trait DaoTestingSuite<Extra = Empty> {
                                     allowlist: vec![],
                                     denylist: vec![],
                                 },
+                            sponsorship: None,
+                            forfeiture: None,
+                            rate_limit: None,
+                            conditions: None,
                             extension: Empty {},
                         })
                         .unwrap(),
@@ -124,6 +128,10 @@ pub trait DaoTestingSuite<Extra = Empty> {
                                     allowlist: vec![],
                                     denylist: vec![],
                                 },
+                            sponsorship: None,
+                            forfeiture: None,
+                            rate_limit: None,
+                            conditions: None,
                             extension: Empty {},
                         })
                         .unwrap(),
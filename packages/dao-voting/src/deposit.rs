@@ -1,13 +1,20 @@
-use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::{
+    cw_serde,
+    serde::{Deserialize, Serialize},
+};
 use cosmwasm_std::{
-    to_json_binary, Addr, CosmosMsg, Deps, MessageInfo, StdError, StdResult, Uint128, WasmMsg,
+    coins, to_json_binary, Addr, BankMsg, CosmosMsg, Decimal, Deps, MessageInfo, OverflowError,
+    StdError, StdResult, Uint128, Uint256, WasmMsg,
 };
-use cw_utils::{must_pay, PaymentError};
+use cw_utils::{must_pay, Duration, PaymentError};
 
 use dao_interface::voting::DenomResponse;
 use thiserror::Error;
 
 use cw_denom::{CheckedDenom, DenomError, UncheckedDenom};
+use osmosis_std::types::cosmos::{
+    base::v1beta1::Coin as ProtoCoin, distribution::v1beta1::MsgFundCommunityPool,
+};
 
 /// Error type for deposit methods.
 #[derive(Error, Debug, PartialEq)]
@@ -21,11 +28,26 @@ pub enum DepositError {
     #[error(transparent)]
     Denom(#[from] DenomError),
 
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+
     #[error("invalid zero deposit. set the deposit to `None` to have no deposit")]
     ZeroDeposit,
 
     #[error("invalid deposit amount. got ({actual}), expected ({expected})")]
     InvalidDeposit { actual: Uint128, expected: Uint128 },
+
+    #[error("invalid deposit formula: {reason}")]
+    InvalidFormula { reason: String },
+
+    #[error("the asset sent is not accepted as a proposal deposit")]
+    UnacceptedDeposit {},
+
+    #[error("invalid deposit forfeiture config: {reason}")]
+    InvalidForfeiture { reason: String },
+
+    #[error("this module only accepts native token deposit alternatives")]
+    UnsupportedAlternative {},
 }
 
 // The voting module token type to expect.
@@ -61,6 +83,171 @@ pub struct UncheckedDepositInfo {
     pub amount: Uint128,
     /// The policy used for refunding deposits on proposal completion.
     pub refund_policy: DepositRefundPolicy,
+    /// An optional formula used to scale `amount` based on the
+    /// proposer. If `None`, every proposer deposits `amount`.
+    pub formula: Option<DepositFormula>,
+    /// Assets that proposers may deposit instead of `amount` of
+    /// `denom`. Token amounts are scaled by `formula` in the same way
+    /// as `amount`.
+    #[serde(default)]
+    pub alternatives: Vec<UncheckedDepositAlternative>,
+}
+
+/// An asset that may be deposited in place of a deposit's primary
+/// token.
+#[cw_serde]
+pub enum UncheckedDepositAlternative {
+    /// AMOUNT of DENOM. Native tokens are sent along with the propose
+    /// message, and cw20 tokens are sent to the module with a cw20
+    /// `Send` message.
+    Token {
+        denom: UncheckedDenom,
+        amount: Uint128,
+    },
+    /// Any single NFT from the cw721 COLLECTION, sent to the module
+    /// with a cw721 `SendNft` message. The NFT is held in escrow until
+    /// the proposal completes.
+    Nft { collection: String },
+}
+
+/// Counterpart to `UncheckedDepositAlternative` which has been
+/// validated.
+#[cw_serde]
+pub enum DepositAlternative {
+    Token {
+        denom: CheckedDenom,
+        amount: Uint128,
+    },
+    Nft {
+        collection: Addr,
+    },
+}
+
+/// An NFT held in escrow as a proposal deposit.
+#[cw_serde]
+pub struct EscrowedNft {
+    /// The cw721 contract the NFT belongs to.
+    pub collection: Addr,
+    /// The ID of the escrowed token.
+    pub token_id: String,
+}
+
+/// A tier in the `VotingPower` deposit formula.
+#[cw_serde]
+pub struct VotingPowerTier {
+    /// The minimum share of the DAO's total voting power a proposer
+    /// must hold for this tier to apply. Must be at most one.
+    pub min_share: Decimal,
+    /// The multiplier applied to the deposit amount for proposers in
+    /// this tier.
+    pub multiplier: Decimal,
+}
+
+/// Formulas for scaling the deposit required of a proposer. Formulas
+/// are evaluated when a proposal is submitted and the resulting
+/// amount is what gets snapshotted for that proposal.
+#[cw_serde]
+pub enum DepositFormula {
+    /// Scales the deposit by the proposer's share of the DAO's total
+    /// voting power at the previous block height. Proposers with no voting power deposit `amount *
+    /// non_member_multiplier`. Members deposit `amount * multiplier`
+    /// for the tier with the largest `min_share` their share meets,
+    /// or `amount` if they meet no tier.
+    VotingPower {
+        non_member_multiplier: Decimal,
+        tiers: Vec<VotingPowerTier>,
+    },
+    /// Increases the deposit with the number of the proposer's
+    /// proposals that were rejected or vetoed within `window`.
+    /// Proposers deposit `amount * (1 + increment * rejections)`,
+    /// capped at `amount * max_multiplier` if one is set.
+    RecentRejections {
+        increment: Decimal,
+        window: Duration,
+        max_multiplier: Option<Decimal>,
+    },
+}
+
+/// What is known about a proposer when evaluating a deposit formula.
+pub struct DepositFormulaInputs {
+    /// The proposer's voting power as of the block before the
+    /// proposal is submitted.
+    pub voting_power: Uint128,
+    /// The DAO's total voting power as of the same block.
+    pub total_power: Uint128,
+    /// The number of the proposer's proposals rejected within the
+    /// formula's window.
+    pub recent_rejections: u64,
+}
+
+impl DepositFormula {
+    pub fn validate(&self) -> Result<(), DepositError> {
+        match self {
+            Self::VotingPower { tiers, .. } => {
+                if tiers.iter().any(|tier| tier.min_share > Decimal::one()) {
+                    return Err(DepositError::InvalidFormula {
+                        reason: "tier min_share must be at most one".to_string(),
+                    });
+                }
+            }
+            Self::RecentRejections {
+                window,
+                max_multiplier,
+                ..
+            } => {
+                if matches!(window, Duration::Height(0) | Duration::Time(0)) {
+                    return Err(DepositError::InvalidFormula {
+                        reason: "window must be non-zero".to_string(),
+                    });
+                }
+                if matches!(max_multiplier, Some(max) if *max < Decimal::one()) {
+                    return Err(DepositError::InvalidFormula {
+                        reason: "max_multiplier must be at least one".to_string(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The multiplier to apply to the deposit amount for a proposer
+    /// described by INPUTS.
+    pub fn multiplier(&self, inputs: &DepositFormulaInputs) -> Result<Decimal, DepositError> {
+        Ok(match self {
+            Self::VotingPower {
+                non_member_multiplier,
+                tiers,
+            } => {
+                if inputs.voting_power.is_zero() {
+                    *non_member_multiplier
+                } else {
+                    // voting_power / total_power >= min_share, without
+                    // dividing.
+                    let share = inputs.voting_power.full_mul(Decimal::one().atomics());
+                    tiers
+                        .iter()
+                        .filter(|tier| {
+                            share >= inputs.total_power.full_mul(tier.min_share.atomics())
+                        })
+                        .max_by_key(|tier| tier.min_share)
+                        .map_or(Decimal::one(), |tier| tier.multiplier)
+                }
+            }
+            Self::RecentRejections {
+                increment,
+                max_multiplier,
+                ..
+            } => {
+                let multiplier = increment
+                    .checked_mul(Decimal::from_ratio(inputs.recent_rejections, 1u64))?
+                    .checked_add(Decimal::one())?;
+                match max_multiplier {
+                    Some(max) => multiplier.min(*max),
+                    None => multiplier,
+                }
+            }
+        })
+    }
 }
 
 #[cw_serde]
@@ -73,6 +260,188 @@ pub enum DepositRefundPolicy {
     Never,
 }
 
+/// Where deposits that are not refunded to their proposer are sent.
+#[cw_serde]
+pub enum ForfeitureDestination {
+    /// Burn forfeited deposits.
+    Burn {},
+    /// Send forfeited deposits to the DAO's treasury.
+    Dao {},
+    /// Send forfeited native deposits to the chain's community
+    /// pool. cw20 and NFT deposits, which can not be sent to the
+    /// community pool, are sent to the DAO.
+    CommunityPool {},
+    /// Fund distribution ID of the dao-rewards-distributor at ADDRESS
+    /// with forfeited deposits. Deposits paid in a token other than
+    /// the one the distribution distributes, and NFT deposits, are
+    /// sent to the DAO.
+    RewardsDistributor { address: String, id: u64 },
+    /// Split forfeited deposits between several destinations. An NFT
+    /// deposit, which can not be split, is sent to the first
+    /// destination.
+    Split { shares: Vec<ForfeitureShare> },
+}
+
+/// A portion of forfeited deposits and where it is sent.
+#[cw_serde]
+pub struct ForfeitureShare {
+    pub destination: ForfeitureDestination,
+    /// The percentage of the deposit sent to `destination`. The
+    /// percentages of all shares in a split must sum to one.
+    pub percent: Decimal,
+}
+
+/// Configures how forfeited deposits are handled.
+#[cw_serde]
+pub struct ForfeitureConfig {
+    /// Where deposits forfeited under the refund policy are sent.
+    pub destination: ForfeitureDestination,
+    /// How deposits of vetoed proposals are handled. If `None`, vetoed
+    /// proposals follow the refund policy like any other proposal.
+    pub vetoed: Option<VetoedForfeiture>,
+}
+
+/// Slashing applied to the deposits of vetoed proposals.
+#[cw_serde]
+pub struct VetoedForfeiture {
+    /// The portion of the deposit forfeited when a proposal is
+    /// vetoed, regardless of the refund policy. The remainder is
+    /// refunded to the proposer. An NFT deposit is forfeited if this
+    /// is non-zero.
+    pub slash: Decimal,
+    /// Where the slashed portion of the deposit is sent. Defaults to
+    /// the config's `destination`.
+    pub destination: Option<ForfeitureDestination>,
+}
+
+impl ForfeitureConfig {
+    /// Validates this config for use alongside DEPOSIT_INFO, the
+    /// module's current deposit configuration.
+    pub fn validate(
+        &self,
+        deps: Deps,
+        deposit_info: Option<&CheckedDepositInfo>,
+    ) -> Result<(), DepositError> {
+        self.destination.validate(deps)?;
+        if let Some(vetoed) = &self.vetoed {
+            if vetoed.slash > Decimal::one() {
+                return Err(DepositError::InvalidForfeiture {
+                    reason: "vetoed slash may not exceed 100%".to_string(),
+                });
+            }
+            // Otherwise a vetoed proposal would get back part of a
+            // deposit that a rejected proposal forfeits in full.
+            if vetoed.slash < Decimal::one()
+                && matches!(
+                    deposit_info,
+                    Some(CheckedDepositInfo {
+                        refund_policy: DepositRefundPolicy::Never,
+                        ..
+                    })
+                )
+            {
+                return Err(DepositError::InvalidForfeiture {
+                    reason: "vetoed slash must be 100% when deposits are never refunded"
+                        .to_string(),
+                });
+            }
+            if let Some(destination) = &vetoed.destination {
+                destination.validate(deps)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ForfeitureDestination {
+    pub fn validate(&self, deps: Deps) -> Result<(), DepositError> {
+        match self {
+            Self::Burn {} | Self::Dao {} | Self::CommunityPool {} => Ok(()),
+            Self::RewardsDistributor { address, id } => {
+                deps.api.addr_validate(address)?;
+                // Errors if the distribution does not exist.
+                query_distribution_denom(deps, address, *id)?;
+                Ok(())
+            }
+            Self::Split { shares } => {
+                if shares.is_empty() {
+                    return Err(DepositError::InvalidForfeiture {
+                        reason: "a split must have at least one share".to_string(),
+                    });
+                }
+                let mut total = Decimal::zero();
+                for ForfeitureShare {
+                    destination,
+                    percent,
+                } in shares
+                {
+                    if matches!(destination, Self::Split { .. }) {
+                        return Err(DepositError::InvalidForfeiture {
+                            reason: "splits may not be nested".to_string(),
+                        });
+                    }
+                    if percent.is_zero() {
+                        return Err(DepositError::InvalidForfeiture {
+                            reason: "split shares must be non-zero".to_string(),
+                        });
+                    }
+                    destination.validate(deps)?;
+                    total = total.checked_add(*percent)?;
+                }
+                if total != Decimal::one() {
+                    return Err(DepositError::InvalidForfeiture {
+                        reason: "split shares must sum to 100%".to_string(),
+                    });
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The subset of dao-rewards-distributor's execute messages used to
+/// fund a distribution with forfeited deposits.
+#[cw_serde]
+enum RewardsDistributorExecuteMsg {
+    Fund(RewardsDistributorFundMsg),
+}
+
+#[cw_serde]
+enum RewardsDistributorReceiveMsg {
+    Fund(RewardsDistributorFundMsg),
+}
+
+#[cw_serde]
+struct RewardsDistributorFundMsg {
+    id: u64,
+}
+
+#[cw_serde]
+enum RewardsDistributorQueryMsg {
+    Distribution { id: u64 },
+}
+
+/// The part of dao-rewards-distributor's `DistributionState` used to
+/// check which token a distribution distributes. Unlike `cw_serde`
+/// types, unknown fields are ignored when deserializing.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "::cosmwasm_schema::serde")]
+struct RewardsDistributorDistribution {
+    denom: cw20::Denom,
+}
+
+/// Queries the token distributed by distribution ID of the
+/// dao-rewards-distributor at ADDRESS.
+fn query_distribution_denom(deps: Deps, address: &str, id: u64) -> StdResult<CheckedDenom> {
+    let distribution: RewardsDistributorDistribution = deps
+        .querier
+        .query_wasm_smart(address, &RewardsDistributorQueryMsg::Distribution { id })?;
+    Ok(match distribution.denom {
+        cw20::Denom::Native(denom) => CheckedDenom::Native(denom),
+        cw20::Denom::Cw20(address) => CheckedDenom::Cw20(address),
+    })
+}
+
 /// Counterpart to the `DepositInfo` struct which has been
 /// processed. This type should never be constructed literally and
 /// should always by built by calling `into_checked` on a
@@ -89,15 +458,50 @@ pub struct CheckedDepositInfo {
     pub amount: Uint128,
     /// The policy used for refunding proposal deposits.
     pub refund_policy: DepositRefundPolicy,
+    /// An optional formula used to scale `amount` based on the
+    /// proposer. In deposits snapshotted for a proposal, `amount` is
+    /// the amount the proposer actually deposited.
+    pub formula: Option<DepositFormula>,
+    /// Assets that proposers may deposit instead of `amount` of
+    /// `denom`. In deposits snapshotted for a proposal this is empty
+    /// and `denom` and `amount` describe the token that was deposited.
+    #[serde(default)]
+    pub alternatives: Vec<DepositAlternative>,
+    /// In deposits snapshotted for a proposal, the NFT the proposer
+    /// deposited, if they paid with an NFT. `amount` is zero in that
+    /// case.
+    pub escrowed_nft: Option<EscrowedNft>,
 }
 
 impl UncheckedDepositInfo {
+    /// Errors if any of this deposit's alternatives are not native
+    /// tokens. For use by modules which only take deposits sent along
+    /// with the propose message and so can not collect cw20 or NFT
+    /// alternatives.
+    pub fn check_native_alternatives(&self) -> Result<(), DepositError> {
+        if self.alternatives.iter().all(|alternative| {
+            matches!(
+                alternative,
+                UncheckedDepositAlternative::Token {
+                    denom: UncheckedDenom::Native(_),
+                    ..
+                }
+            )
+        }) {
+            Ok(())
+        } else {
+            Err(DepositError::UnsupportedAlternative {})
+        }
+    }
+
     /// Converts deposit info into checked deposit info.
     pub fn into_checked(self, deps: Deps, dao: Addr) -> Result<CheckedDepositInfo, DepositError> {
         let Self {
             denom,
             amount,
             refund_policy,
+            formula,
+            alternatives,
         } = self;
         // Check that the deposit is non-zero. Modules should make
         // deposit information optional and consumers should provide
@@ -106,6 +510,28 @@ impl UncheckedDepositInfo {
             return Err(DepositError::ZeroDeposit);
         }
 
+        if let Some(formula) = &formula {
+            formula.validate()?;
+        }
+
+        let alternatives = alternatives
+            .into_iter()
+            .map(|alternative| match alternative {
+                UncheckedDepositAlternative::Token { denom, amount } => {
+                    if amount.is_zero() {
+                        return Err(DepositError::ZeroDeposit);
+                    }
+                    Ok(DepositAlternative::Token {
+                        denom: denom.into_checked(deps)?,
+                        amount,
+                    })
+                }
+                UncheckedDepositAlternative::Nft { collection } => Ok(DepositAlternative::Nft {
+                    collection: deps.api.addr_validate(&collection)?,
+                }),
+            })
+            .collect::<Result<Vec<_>, DepositError>>()?;
+
         let denom = match denom {
             DepositToken::Token { denom } => denom.into_checked(deps),
             DepositToken::VotingModuleToken { token_type } => {
@@ -147,11 +573,141 @@ impl UncheckedDepositInfo {
             denom,
             amount,
             refund_policy,
+            formula,
+            alternatives,
+            escrowed_nft: None,
         })
     }
 }
 
 impl CheckedDepositInfo {
+    /// Evaluates this deposit's formula for a proposer described by
+    /// INPUTS, returning the amount that proposer must deposit.
+    pub fn get_amount_for(&self, inputs: &DepositFormulaInputs) -> Result<Uint128, DepositError> {
+        match &self.formula {
+            Some(formula) => scale_amount(self.amount, formula.multiplier(inputs)?),
+            None => Ok(self.amount),
+        }
+    }
+
+    /// Evaluates this deposit's formula for a proposer described by
+    /// INPUTS, returning this deposit with `amount` and the amounts
+    /// of any token alternatives scaled accordingly.
+    pub fn scaled_for(
+        mut self,
+        inputs: &DepositFormulaInputs,
+    ) -> Result<CheckedDepositInfo, DepositError> {
+        if let Some(formula) = &self.formula {
+            let multiplier = formula.multiplier(inputs)?;
+            self.amount = scale_amount(self.amount, multiplier)?;
+            for alternative in self.alternatives.iter_mut() {
+                if let DepositAlternative::Token { amount, .. } = alternative {
+                    *amount = scale_amount(*amount, multiplier)?;
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Determines which accepted token the native funds sent with
+    /// INFO pay for, returning the deposit to snapshot for the
+    /// proposal. If the funds do not match a native alternative, the
+    /// primary deposit is expected.
+    pub fn resolve_native_payment(
+        &self,
+        info: &MessageInfo,
+    ) -> Result<CheckedDepositInfo, DepositError> {
+        if let [coin] = info.funds.as_slice() {
+            let alternative = self
+                .alternatives
+                .iter()
+                .find_map(|alternative| match alternative {
+                    DepositAlternative::Token {
+                        denom: CheckedDenom::Native(denom),
+                        amount,
+                    } if *denom == coin.denom => Some(*amount),
+                    _ => None,
+                });
+            if let Some(amount) = alternative {
+                if self.denom != CheckedDenom::Native(coin.denom.clone()) {
+                    let paid = self.paid_with(CheckedDenom::Native(coin.denom.clone()), amount);
+                    paid.check_native_deposit_paid(info)?;
+                    return Ok(paid);
+                }
+            }
+        }
+
+        let paid = self.paid_with(self.denom.clone(), self.amount);
+        paid.check_native_deposit_paid(info)?;
+        Ok(paid)
+    }
+
+    /// Determines which accepted token AMOUNT of the cw20 TOKEN sent to
+    /// the module pays for, returning the deposit to snapshot for the
+    /// proposal.
+    pub fn resolve_cw20_payment(
+        &self,
+        token: &Addr,
+        amount: Uint128,
+    ) -> Result<CheckedDepositInfo, DepositError> {
+        let expected = std::iter::once((&self.denom, self.amount))
+            .chain(
+                self.alternatives
+                    .iter()
+                    .filter_map(|alternative| match alternative {
+                        DepositAlternative::Token { denom, amount } => Some((denom, *amount)),
+                        DepositAlternative::Nft { .. } => None,
+                    }),
+            )
+            .find_map(|(denom, amount)| match denom {
+                CheckedDenom::Cw20(address) if address == token => Some(amount),
+                _ => None,
+            })
+            .ok_or(DepositError::UnacceptedDeposit {})?;
+
+        if amount != expected {
+            return Err(DepositError::InvalidDeposit {
+                actual: amount,
+                expected,
+            });
+        }
+        Ok(self.paid_with(CheckedDenom::Cw20(token.clone()), amount))
+    }
+
+    /// Checks that NFTs from COLLECTION are accepted as a deposit,
+    /// returning the deposit to snapshot for the proposal.
+    pub fn resolve_nft_payment(
+        &self,
+        collection: &Addr,
+        token_id: String,
+    ) -> Result<CheckedDepositInfo, DepositError> {
+        let accepted = self.alternatives.iter().any(|alternative| {
+            matches!(alternative, DepositAlternative::Nft { collection: c } if c == collection)
+        });
+        if !accepted {
+            return Err(DepositError::UnacceptedDeposit {});
+        }
+        Ok(CheckedDepositInfo {
+            escrowed_nft: Some(EscrowedNft {
+                collection: collection.clone(),
+                token_id,
+            }),
+            ..self.paid_with(self.denom.clone(), Uint128::zero())
+        })
+    }
+
+    /// The deposit snapshot for a proposal paid with AMOUNT of DENOM.
+    fn paid_with(&self, denom: CheckedDenom, amount: Uint128) -> CheckedDepositInfo {
+        CheckedDepositInfo {
+            denom,
+            amount,
+            refund_policy: self.refund_policy.clone(),
+            formula: self.formula.clone(),
+            alternatives: vec![],
+            escrowed_nft: None,
+        }
+    }
+
     pub fn check_native_deposit_paid(&self, info: &MessageInfo) -> Result<(), DepositError> {
         if let Self {
             amount,
@@ -214,24 +770,243 @@ impl CheckedDepositInfo {
     }
 
     pub fn get_return_deposit_message(&self, depositor: &Addr) -> StdResult<Vec<CosmosMsg>> {
+        let mut messages = vec![];
         // Should get caught in `into_checked()`, but to be pedantic.
-        if self.amount.is_zero() {
-            return Ok(vec![]);
+        if !self.amount.is_zero() {
+            messages.push(self.denom.get_transfer_to_message(depositor, self.amount)?);
+        }
+        if let Some(EscrowedNft {
+            collection,
+            token_id,
+        }) = &self.escrowed_nft
+        {
+            messages.push(
+                WasmMsg::Execute {
+                    contract_addr: collection.to_string(),
+                    msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+                        recipient: depositor.to_string(),
+                        token_id: token_id.clone(),
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            );
         }
-        let message = self.denom.get_transfer_to_message(depositor, self.amount)?;
-        Ok(vec![message])
+        Ok(messages)
     }
+
+    /// Splits off PORTION of this deposit, rounding down, returning
+    /// the portion and the remainder. An escrowed NFT belongs to the
+    /// portion if PORTION is non-zero.
+    pub fn split(
+        &self,
+        portion: Decimal,
+    ) -> Result<(CheckedDepositInfo, CheckedDepositInfo), DepositError> {
+        let amount = scale_amount(self.amount, portion)?;
+        let (nft, rest_nft) = if portion.is_zero() {
+            (None, self.escrowed_nft.clone())
+        } else {
+            (self.escrowed_nft.clone(), None)
+        };
+        Ok((
+            CheckedDepositInfo {
+                amount,
+                escrowed_nft: nft,
+                ..self.clone()
+            },
+            CheckedDepositInfo {
+                amount: self.amount.checked_sub(amount)?,
+                escrowed_nft: rest_nft,
+                ..self.clone()
+            },
+        ))
+    }
+
+    /// Gets the messages that send this deposit, held by CONTRACT, to
+    /// DESTINATION. The DAO receives anything that can not be sent
+    /// there.
+    pub fn get_forfeit_deposit_messages(
+        &self,
+        deps: Deps,
+        destination: &ForfeitureDestination,
+        contract: &Addr,
+        dao: &Addr,
+    ) -> Result<Vec<CosmosMsg>, DepositError> {
+        let mut messages = vec![];
+        match destination {
+            ForfeitureDestination::Dao {} => return Ok(self.get_return_deposit_message(dao)?),
+            ForfeitureDestination::Burn {} => {
+                if !self.amount.is_zero() {
+                    messages.push(match &self.denom {
+                        CheckedDenom::Native(denom) => BankMsg::Burn {
+                            amount: coins(self.amount.u128(), denom),
+                        }
+                        .into(),
+                        CheckedDenom::Cw20(address) => WasmMsg::Execute {
+                            contract_addr: address.to_string(),
+                            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Burn {
+                                amount: self.amount,
+                            })?,
+                            funds: vec![],
+                        }
+                        .into(),
+                    });
+                }
+                if let Some(EscrowedNft {
+                    collection,
+                    token_id,
+                }) = &self.escrowed_nft
+                {
+                    messages.push(
+                        WasmMsg::Execute {
+                            contract_addr: collection.to_string(),
+                            msg: to_json_binary(&cw721::Cw721ExecuteMsg::Burn {
+                                token_id: token_id.clone(),
+                            })?,
+                            funds: vec![],
+                        }
+                        .into(),
+                    );
+                }
+            }
+            ForfeitureDestination::CommunityPool {} => match &self.denom {
+                CheckedDenom::Native(denom) => {
+                    if !self.amount.is_zero() {
+                        messages.push(
+                            MsgFundCommunityPool {
+                                amount: vec![ProtoCoin {
+                                    denom: denom.clone(),
+                                    amount: self.amount.to_string(),
+                                }],
+                                depositor: contract.to_string(),
+                            }
+                            .into(),
+                        );
+                    }
+                    messages.extend(self.nft_only().get_return_deposit_message(dao)?);
+                }
+                CheckedDenom::Cw20(_) => return Ok(self.get_return_deposit_message(dao)?),
+            },
+            ForfeitureDestination::RewardsDistributor { address, id } => {
+                // Funding a distribution with a different token, or
+                // one which no longer exists, would fail and with it
+                // the proposal completed hook, so those deposits go to
+                // the DAO instead.
+                let distributes_deposit = query_distribution_denom(deps, address, *id)
+                    .map_or(false, |denom| denom == self.denom);
+                if !distributes_deposit {
+                    return Ok(self.get_return_deposit_message(dao)?);
+                }
+                let fund = RewardsDistributorFundMsg { id: *id };
+                if !self.amount.is_zero() {
+                    messages.push(match &self.denom {
+                        CheckedDenom::Native(denom) => WasmMsg::Execute {
+                            contract_addr: address.clone(),
+                            msg: to_json_binary(&RewardsDistributorExecuteMsg::Fund(fund))?,
+                            funds: coins(self.amount.u128(), denom),
+                        }
+                        .into(),
+                        CheckedDenom::Cw20(token) => WasmMsg::Execute {
+                            contract_addr: token.to_string(),
+                            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Send {
+                                contract: address.clone(),
+                                amount: self.amount,
+                                msg: to_json_binary(&RewardsDistributorReceiveMsg::Fund(fund))?,
+                            })?,
+                            funds: vec![],
+                        }
+                        .into(),
+                    });
+                }
+                messages.extend(self.nft_only().get_return_deposit_message(dao)?);
+            }
+            ForfeitureDestination::Split { shares } => {
+                let mut remaining = self.amount;
+                for (i, share) in shares.iter().enumerate() {
+                    // The last share receives whatever rounding left
+                    // over.
+                    let amount = if i == shares.len() - 1 {
+                        remaining
+                    } else {
+                        scale_amount(self.amount, share.percent)?
+                    };
+                    remaining = remaining.checked_sub(amount)?;
+                    let portion = CheckedDepositInfo {
+                        amount,
+                        escrowed_nft: if i == 0 {
+                            self.escrowed_nft.clone()
+                        } else {
+                            None
+                        },
+                        ..self.clone()
+                    };
+                    messages.extend(portion.get_forfeit_deposit_messages(
+                        deps,
+                        &share.destination,
+                        contract,
+                        dao,
+                    )?);
+                }
+            }
+        }
+        Ok(messages)
+    }
+
+    /// This deposit's escrowed NFT, if any, without its tokens.
+    fn nft_only(&self) -> CheckedDepositInfo {
+        CheckedDepositInfo {
+            amount: Uint128::zero(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Multiplies AMOUNT by MULTIPLIER, rounding down.
+fn scale_amount(amount: Uint128, multiplier: Decimal) -> Result<Uint128, DepositError> {
+    let scaled = amount.full_mul(multiplier.atomics()) / Uint256::from(Decimal::one().atomics());
+    Ok(Uint128::try_from(scaled).map_err(StdError::from)?)
 }
 
 #[cfg(test)]
 pub mod tests {
-    use cosmwasm_std::{coin, coins, testing::mock_info, BankMsg};
+    use cosmwasm_std::{
+        coin, coins, from_json,
+        testing::{mock_dependencies, mock_info, MockApi, MockQuerier, MockStorage},
+        BankMsg, ContractResult, OwnedDeps, SystemError, SystemResult, WasmQuery,
+    };
 
     use super::*;
 
     const NATIVE_DENOM: &str = "uekez";
     const CW20: &str = "cw20";
 
+    /// Mock dependencies with a dao-rewards-distributor at "rewards"
+    /// whose distribution 1 distributes NATIVE_DENOM.
+    fn mock_rewards_distributor() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "rewards" => {
+                match from_json(msg).unwrap() {
+                    RewardsDistributorQueryMsg::Distribution { id: 1 } => {
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_json_binary(&RewardsDistributorDistribution {
+                                denom: cw20::Denom::Native(NATIVE_DENOM.to_string()),
+                            })
+                            .unwrap(),
+                        ))
+                    }
+                    _ => {
+                        SystemResult::Ok(ContractResult::Err("distribution not found".to_string()))
+                    }
+                }
+            }
+            _ => SystemResult::Err(SystemError::NoSuchContract {
+                addr: "not rewards".to_string(),
+            }),
+        });
+        deps
+    }
+
     #[test]
     fn test_check_native_deposit_paid_yes() {
         let info = mock_info("ekez", &coins(10, NATIVE_DENOM));
@@ -239,6 +1014,9 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: None,
         };
         deposit_info.check_native_deposit_paid(&info).unwrap();
 
@@ -261,6 +1039,9 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: None,
         };
         let err = deposit_info.check_native_deposit_paid(&info).unwrap_err();
         assert_eq!(
@@ -279,6 +1060,9 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: None,
         };
         let err = deposit_info.check_native_deposit_paid(&info).unwrap_err();
         assert_eq!(
@@ -299,6 +1083,9 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: None,
         };
 
         let err = deposit_info.check_native_deposit_paid(&info).unwrap_err();
@@ -312,6 +1099,9 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: None,
         };
         let err = deposit_info.check_native_deposit_paid(&info).unwrap_err();
         assert_eq!(err, DepositError::Payment(PaymentError::NoFunds {}));
@@ -324,6 +1114,9 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: None,
         };
         let messages = deposit_info
             .get_take_deposit_messages(&Addr::unchecked("ekez"), &Addr::unchecked(CW20))
@@ -364,6 +1157,9 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: None,
         };
         let messages = deposit_info
             .get_return_deposit_message(&Addr::unchecked("ekez"))
@@ -390,6 +1186,9 @@ pub mod tests {
             denom: CheckedDenom::Cw20(Addr::unchecked(CW20)),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: None,
         };
         let messages = deposit_info
             .get_return_deposit_message(&Addr::unchecked("ekez"))
@@ -414,4 +1213,438 @@ pub mod tests {
             .unwrap();
         assert_eq!(messages, vec![]);
     }
+
+    #[test]
+    fn test_get_return_deposit_message_nft() {
+        let deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::zero(),
+            refund_policy: DepositRefundPolicy::Always,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: Some(EscrowedNft {
+                collection: Addr::unchecked("nft"),
+                token_id: "1".to_string(),
+            }),
+        };
+        let messages = deposit_info
+            .get_return_deposit_message(&Addr::unchecked("ekez"))
+            .unwrap();
+        assert_eq!(
+            messages,
+            vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "nft".to_string(),
+                msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+                    recipient: "ekez".to_string(),
+                    token_id: "1".to_string(),
+                })
+                .unwrap(),
+                funds: vec![]
+            })]
+        );
+    }
+
+    #[test]
+    fn test_resolve_alternative_payments() {
+        let deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::OnlyPassed,
+            formula: None,
+            alternatives: vec![
+                DepositAlternative::Token {
+                    denom: CheckedDenom::Native("uatom".to_string()),
+                    amount: Uint128::new(5),
+                },
+                DepositAlternative::Token {
+                    denom: CheckedDenom::Cw20(Addr::unchecked(CW20)),
+                    amount: Uint128::new(20),
+                },
+                DepositAlternative::Nft {
+                    collection: Addr::unchecked("nft"),
+                },
+            ],
+            escrowed_nft: None,
+        };
+
+        // Primary deposit.
+        let paid = deposit_info
+            .resolve_native_payment(&mock_info("ekez", &coins(10, NATIVE_DENOM)))
+            .unwrap();
+        assert_eq!(paid.denom, CheckedDenom::Native(NATIVE_DENOM.to_string()));
+        assert_eq!(paid.amount, Uint128::new(10));
+        assert!(paid.alternatives.is_empty());
+
+        // Native alternative.
+        let paid = deposit_info
+            .resolve_native_payment(&mock_info("ekez", &coins(5, "uatom")))
+            .unwrap();
+        assert_eq!(paid.denom, CheckedDenom::Native("uatom".to_string()));
+        assert_eq!(paid.amount, Uint128::new(5));
+        assert_eq!(paid.refund_policy, DepositRefundPolicy::OnlyPassed);
+
+        let err = deposit_info
+            .resolve_native_payment(&mock_info("ekez", &coins(4, "uatom")))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            DepositError::InvalidDeposit {
+                actual: Uint128::new(4),
+                expected: Uint128::new(5)
+            }
+        );
+
+        // cw20 alternative.
+        let paid = deposit_info
+            .resolve_cw20_payment(&Addr::unchecked(CW20), Uint128::new(20))
+            .unwrap();
+        assert_eq!(paid.denom, CheckedDenom::Cw20(Addr::unchecked(CW20)));
+        assert_eq!(paid.amount, Uint128::new(20));
+
+        let err = deposit_info
+            .resolve_cw20_payment(&Addr::unchecked("other"), Uint128::new(20))
+            .unwrap_err();
+        assert_eq!(err, DepositError::UnacceptedDeposit {});
+
+        // NFT alternative.
+        let paid = deposit_info
+            .resolve_nft_payment(&Addr::unchecked("nft"), "1".to_string())
+            .unwrap();
+        assert_eq!(paid.amount, Uint128::zero());
+        assert_eq!(
+            paid.escrowed_nft,
+            Some(EscrowedNft {
+                collection: Addr::unchecked("nft"),
+                token_id: "1".to_string(),
+            })
+        );
+
+        let err = deposit_info
+            .resolve_nft_payment(&Addr::unchecked("other"), "1".to_string())
+            .unwrap_err();
+        assert_eq!(err, DepositError::UnacceptedDeposit {});
+    }
+
+    #[test]
+    fn test_forfeit_deposit_split() {
+        let deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Never,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: None,
+        };
+        let destination = ForfeitureDestination::Split {
+            shares: vec![
+                ForfeitureShare {
+                    destination: ForfeitureDestination::Burn {},
+                    percent: Decimal::percent(33),
+                },
+                ForfeitureShare {
+                    destination: ForfeitureDestination::Dao {},
+                    percent: Decimal::percent(33),
+                },
+                ForfeitureShare {
+                    destination: ForfeitureDestination::RewardsDistributor {
+                        address: "rewards".to_string(),
+                        id: 1,
+                    },
+                    percent: Decimal::percent(34),
+                },
+            ],
+        };
+        let deps = mock_rewards_distributor();
+        let messages = deposit_info
+            .get_forfeit_deposit_messages(
+                deps.as_ref(),
+                &destination,
+                &Addr::unchecked("module"),
+                &Addr::unchecked("dao"),
+            )
+            .unwrap();
+        assert_eq!(
+            messages,
+            vec![
+                CosmosMsg::Bank(BankMsg::Burn {
+                    amount: coins(3, NATIVE_DENOM)
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "dao".to_string(),
+                    amount: coins(3, NATIVE_DENOM)
+                }),
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "rewards".to_string(),
+                    msg: to_json_binary(&RewardsDistributorExecuteMsg::Fund(
+                        RewardsDistributorFundMsg { id: 1 }
+                    ))
+                    .unwrap(),
+                    funds: coins(4, NATIVE_DENOM)
+                }),
+            ]
+        );
+
+        // Vetoed proposals may be slashed in part.
+        let (slashed, refunded) = deposit_info.split(Decimal::percent(75)).unwrap();
+        assert_eq!(slashed.amount, Uint128::new(7));
+        assert_eq!(refunded.amount, Uint128::new(3));
+    }
+
+    #[test]
+    fn test_forfeit_deposit_rewards_distributor_mismatch() {
+        let deps = mock_rewards_distributor();
+        let deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Cw20(Addr::unchecked(CW20)),
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Never,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: None,
+        };
+        let return_to_dao = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: CW20.to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                recipient: "dao".to_string(),
+                amount: Uint128::new(10),
+            })
+            .unwrap(),
+            funds: vec![],
+        })];
+
+        // The distribution distributes a different token, so the
+        // deposit goes to the DAO.
+        let messages = deposit_info
+            .get_forfeit_deposit_messages(
+                deps.as_ref(),
+                &ForfeitureDestination::RewardsDistributor {
+                    address: "rewards".to_string(),
+                    id: 1,
+                },
+                &Addr::unchecked("module"),
+                &Addr::unchecked("dao"),
+            )
+            .unwrap();
+        assert_eq!(messages, return_to_dao);
+
+        // As does a deposit for a distribution that does not exist.
+        let messages = deposit_info
+            .get_forfeit_deposit_messages(
+                deps.as_ref(),
+                &ForfeitureDestination::RewardsDistributor {
+                    address: "rewards".to_string(),
+                    id: 2,
+                },
+                &Addr::unchecked("module"),
+                &Addr::unchecked("dao"),
+            )
+            .unwrap();
+        assert_eq!(messages, return_to_dao);
+    }
+
+    #[test]
+    fn test_validate_forfeiture() {
+        let deps = mock_rewards_distributor();
+        let split = |shares: Vec<(ForfeitureDestination, u64)>| ForfeitureConfig {
+            destination: ForfeitureDestination::Split {
+                shares: shares
+                    .into_iter()
+                    .map(|(destination, percent)| ForfeitureShare {
+                        destination,
+                        percent: Decimal::percent(percent),
+                    })
+                    .collect(),
+            },
+            vetoed: None,
+        };
+
+        split(vec![
+            (ForfeitureDestination::Burn {}, 50),
+            (ForfeitureDestination::CommunityPool {}, 50),
+        ])
+        .validate(deps.as_ref(), None)
+        .unwrap();
+
+        let err = split(vec![
+            (ForfeitureDestination::Burn {}, 50),
+            (ForfeitureDestination::Dao {}, 40),
+        ])
+        .validate(deps.as_ref(), None)
+        .unwrap_err();
+        assert!(matches!(err, DepositError::InvalidForfeiture { .. }));
+
+        let err = split(vec![(split(vec![]).destination, 100)])
+            .validate(deps.as_ref(), None)
+            .unwrap_err();
+        assert!(matches!(err, DepositError::InvalidForfeiture { .. }));
+
+        let err = ForfeitureConfig {
+            destination: ForfeitureDestination::Dao {},
+            vetoed: Some(VetoedForfeiture {
+                slash: Decimal::percent(101),
+                destination: None,
+            }),
+        }
+        .validate(deps.as_ref(), None)
+        .unwrap_err();
+        assert!(matches!(err, DepositError::InvalidForfeiture { .. }));
+
+        // The distribution must exist.
+        ForfeitureConfig {
+            destination: ForfeitureDestination::RewardsDistributor {
+                address: "rewards".to_string(),
+                id: 1,
+            },
+            vetoed: None,
+        }
+        .validate(deps.as_ref(), None)
+        .unwrap();
+        let err = ForfeitureConfig {
+            destination: ForfeitureDestination::RewardsDistributor {
+                address: "rewards".to_string(),
+                id: 2,
+            },
+            vetoed: None,
+        }
+        .validate(deps.as_ref(), None)
+        .unwrap_err();
+        assert!(matches!(err, DepositError::Std(_)));
+
+        // Vetoed proposals may not be refunded in part when other
+        // proposals are never refunded.
+        let partial_slash = ForfeitureConfig {
+            destination: ForfeitureDestination::Dao {},
+            vetoed: Some(VetoedForfeiture {
+                slash: Decimal::percent(50),
+                destination: None,
+            }),
+        };
+        let mut deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Never,
+            formula: None,
+            alternatives: vec![],
+            escrowed_nft: None,
+        };
+        let err = partial_slash
+            .validate(deps.as_ref(), Some(&deposit_info))
+            .unwrap_err();
+        assert!(matches!(err, DepositError::InvalidForfeiture { .. }));
+        deposit_info.refund_policy = DepositRefundPolicy::OnlyPassed;
+        partial_slash
+            .validate(deps.as_ref(), Some(&deposit_info))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_voting_power_formula() {
+        let deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::new(100),
+            refund_policy: DepositRefundPolicy::Always,
+            formula: Some(DepositFormula::VotingPower {
+                non_member_multiplier: Decimal::percent(300),
+                tiers: vec![
+                    VotingPowerTier {
+                        min_share: Decimal::percent(10),
+                        multiplier: Decimal::percent(50),
+                    },
+                    VotingPowerTier {
+                        min_share: Decimal::percent(1),
+                        multiplier: Decimal::percent(80),
+                    },
+                ],
+            }),
+            alternatives: vec![],
+            escrowed_nft: None,
+        };
+        let amount_for = |voting_power: u128| {
+            deposit_info
+                .get_amount_for(&DepositFormulaInputs {
+                    voting_power: Uint128::new(voting_power),
+                    total_power: Uint128::new(1000),
+                    recent_rejections: 0,
+                })
+                .unwrap()
+        };
+
+        // Non-members pay more.
+        assert_eq!(amount_for(0), Uint128::new(300));
+        // Members below every tier pay the base amount.
+        assert_eq!(amount_for(9), Uint128::new(100));
+        // The highest tier met applies.
+        assert_eq!(amount_for(10), Uint128::new(80));
+        assert_eq!(amount_for(99), Uint128::new(80));
+        assert_eq!(amount_for(100), Uint128::new(50));
+    }
+
+    #[test]
+    fn test_recent_rejections_formula() {
+        let mut deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::new(100),
+            refund_policy: DepositRefundPolicy::Always,
+            formula: Some(DepositFormula::RecentRejections {
+                increment: Decimal::percent(50),
+                window: Duration::Height(100),
+                max_multiplier: Some(Decimal::percent(200)),
+            }),
+            alternatives: vec![],
+            escrowed_nft: None,
+        };
+        let amount_for = |deposit_info: &CheckedDepositInfo, recent_rejections: u64| {
+            deposit_info
+                .get_amount_for(&DepositFormulaInputs {
+                    voting_power: Uint128::zero(),
+                    total_power: Uint128::zero(),
+                    recent_rejections,
+                })
+                .unwrap()
+        };
+
+        assert_eq!(amount_for(&deposit_info, 0), Uint128::new(100));
+        assert_eq!(amount_for(&deposit_info, 1), Uint128::new(150));
+        assert_eq!(amount_for(&deposit_info, 2), Uint128::new(200));
+        // Capped by the max multiplier.
+        assert_eq!(amount_for(&deposit_info, 5), Uint128::new(200));
+
+        deposit_info.formula = Some(DepositFormula::RecentRejections {
+            increment: Decimal::percent(50),
+            window: Duration::Height(100),
+            max_multiplier: None,
+        });
+        assert_eq!(amount_for(&deposit_info, 5), Uint128::new(350));
+    }
+
+    #[test]
+    fn test_validate_formula() {
+        let err = DepositFormula::VotingPower {
+            non_member_multiplier: Decimal::one(),
+            tiers: vec![VotingPowerTier {
+                min_share: Decimal::percent(101),
+                multiplier: Decimal::one(),
+            }],
+        }
+        .validate()
+        .unwrap_err();
+        assert!(matches!(err, DepositError::InvalidFormula { .. }));
+
+        let err = DepositFormula::RecentRejections {
+            increment: Decimal::one(),
+            window: Duration::Time(0),
+            max_multiplier: None,
+        }
+        .validate()
+        .unwrap_err();
+        assert!(matches!(err, DepositError::InvalidFormula { .. }));
+
+        let err = DepositFormula::RecentRejections {
+            increment: Decimal::one(),
+            window: Duration::Time(10),
+            max_multiplier: Some(Decimal::percent(50)),
+        }
+        .validate()
+        .unwrap_err();
+        assert!(matches!(err, DepositError::InvalidFormula { .. }));
+    }
 }
@@ -0,0 +1,339 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, BlockInfo, Decimal, StdResult, Storage, Uint128};
+use cw_storage_plus::Map;
+use cw_utils::{Duration, Expiration};
+use thiserror::Error;
+
+/// Where the penalties paid to unstake early are sent.
+#[cw_serde]
+pub enum PenaltyDestination {
+    /// Sent to the DAO. For staking contracts without a DAO, this is
+    /// the contract's owner.
+    Dao,
+    /// Burned.
+    Burn,
+}
+
+/// Allows stakers to skip the unstaking duration by paying a penalty.
+#[cw_serde]
+pub struct EarlyUnstakeConfig {
+    /// The fraction of unstaked tokens paid to receive them
+    /// immediately instead of after the full unstaking duration.
+    pub penalty: Decimal,
+    pub destination: PenaltyDestination,
+}
+
+/// A change to a staking contract's early unstake config.
+#[cw_serde]
+pub enum EarlyUnstakeUpdate {
+    /// Enables early unstaking, or changes its config.
+    Set(EarlyUnstakeConfig),
+    /// Disables early unstaking.
+    Disable {},
+}
+
+impl EarlyUnstakeUpdate {
+    /// Validates the update and returns the config it sets.
+    pub fn into_config(self) -> Result<Option<EarlyUnstakeConfig>, EarlyUnstakeError> {
+        match self {
+            EarlyUnstakeUpdate::Set(config) => {
+                config.validate()?;
+                Ok(Some(config))
+            }
+            EarlyUnstakeUpdate::Disable {} => Ok(None),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum EarlyUnstakeError {
+    #[error("Early unstake penalty must be greater than 0 and not greater than 1")]
+    InvalidPenalty {},
+}
+
+impl EarlyUnstakeConfig {
+    pub fn validate(&self) -> Result<(), EarlyUnstakeError> {
+        if self.penalty.is_zero() || self.penalty > Decimal::one() {
+            return Err(EarlyUnstakeError::InvalidPenalty {});
+        }
+        Ok(())
+    }
+
+    /// The penalty for unstaking AMOUNT without waiting for the
+    /// unstaking duration. Rounds up.
+    pub fn unstake_penalty(&self, amount: Uint128) -> Uint128 {
+        amount.mul_ceil(self.penalty)
+    }
+
+    /// The penalty for releasing a claim of AMOUNT at RELEASE_AT
+    /// now. The unstake penalty is scaled by the fraction of the
+    /// claim's own CLAIM_DURATION remaining, so claims that are nearly
+    /// released are cheap to accelerate. Claims whose duration was not
+    /// recorded are compared to UNSTAKING_DURATION instead, and pay
+    /// the full penalty if they can not be, for example because it
+    /// changed from a height to a time. Without an unstaking duration
+    /// there is nothing to skip, so claims are free. Rounds up.
+    pub fn claim_penalty(
+        &self,
+        amount: Uint128,
+        release_at: &Expiration,
+        claim_duration: Option<Duration>,
+        unstaking_duration: Option<Duration>,
+        block: &BlockInfo,
+    ) -> Uint128 {
+        if unstaking_duration.is_none() || release_at.is_expired(block) {
+            return Uint128::zero();
+        }
+        let remaining = match (release_at, claim_duration.or(unstaking_duration)) {
+            (Expiration::AtHeight(height), Some(Duration::Height(duration))) => {
+                Decimal::from_ratio(height.saturating_sub(block.height).min(duration), duration)
+            }
+            (Expiration::AtTime(time), Some(Duration::Time(duration))) => Decimal::from_ratio(
+                time.seconds()
+                    .saturating_sub(block.time.seconds())
+                    .min(duration),
+                duration,
+            ),
+            _ => Decimal::one(),
+        };
+        amount.mul_ceil(self.penalty * remaining)
+    }
+}
+
+/// Records the unstaking duration each claim was created with, so
+/// that accelerating it is priced from its own remaining time.
+/// Claims are identified by their staker and release time. If two of
+/// a staker's claims are released at the same time, the shorter
+/// duration is kept.
+pub struct ClaimDurations<'a> {
+    durations: Map<'a, (&'a Addr, String), Duration>,
+}
+
+impl<'a> ClaimDurations<'a> {
+    pub const fn new() -> Self {
+        Self {
+            durations: Map::new("claim_durations"),
+        }
+    }
+
+    fn key(release_at: &Expiration) -> String {
+        match release_at {
+            Expiration::AtHeight(height) => format!("height:{height}"),
+            Expiration::AtTime(time) => format!("time:{}", time.nanos()),
+            Expiration::Never {} => "never".to_string(),
+        }
+    }
+
+    /// Records that ADDRESS's claim released at RELEASE_AT was created
+    /// with DURATION.
+    pub fn save(
+        &self,
+        storage: &mut dyn Storage,
+        address: &Addr,
+        release_at: &Expiration,
+        duration: Duration,
+    ) -> StdResult<()> {
+        self.durations
+            .update(storage, (address, Self::key(release_at)), |existing| {
+                StdResult::Ok(match existing {
+                    Some(Duration::Height(existing)) => match duration {
+                        Duration::Height(duration) => Duration::Height(existing.min(duration)),
+                        Duration::Time(_) => duration,
+                    },
+                    Some(Duration::Time(existing)) => match duration {
+                        Duration::Time(duration) => Duration::Time(existing.min(duration)),
+                        Duration::Height(_) => duration,
+                    },
+                    None => duration,
+                })
+            })?;
+        Ok(())
+    }
+
+    pub fn may_load(
+        &self,
+        storage: &dyn Storage,
+        address: &Addr,
+        release_at: &Expiration,
+    ) -> StdResult<Option<Duration>> {
+        self.durations
+            .may_load(storage, (address, Self::key(release_at)))
+    }
+
+    pub fn remove(&self, storage: &mut dyn Storage, address: &Addr, release_at: &Expiration) {
+        self.durations
+            .remove(storage, (address, Self::key(release_at)))
+    }
+}
+
+impl<'a> Default for ClaimDurations<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_env, MockStorage};
+
+    fn config(penalty: u64) -> EarlyUnstakeConfig {
+        EarlyUnstakeConfig {
+            penalty: Decimal::percent(penalty),
+            destination: PenaltyDestination::Burn,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(
+            config(0).validate(),
+            Err(EarlyUnstakeError::InvalidPenalty {})
+        );
+        assert_eq!(
+            config(101).validate(),
+            Err(EarlyUnstakeError::InvalidPenalty {})
+        );
+        config(100).validate().unwrap();
+    }
+
+    #[test]
+    fn test_penalties() {
+        let block = mock_env().block;
+        let config = config(10);
+
+        assert_eq!(config.unstake_penalty(Uint128::new(100)), Uint128::new(10));
+        // Rounds up.
+        assert_eq!(config.unstake_penalty(Uint128::new(15)), Uint128::new(2));
+
+        // A quarter of the duration remains.
+        let release_at = Expiration::AtHeight(block.height + 25);
+        assert_eq!(
+            config.claim_penalty(
+                Uint128::new(1000),
+                &release_at,
+                None,
+                Some(Duration::Height(100)),
+                &block
+            ),
+            Uint128::new(25)
+        );
+        let release_at = Expiration::AtTime(block.time.plus_seconds(50));
+        assert_eq!(
+            config.claim_penalty(
+                Uint128::new(1000),
+                &release_at,
+                None,
+                Some(Duration::Time(100)),
+                &block
+            ),
+            Uint128::new(50)
+        );
+
+        // Released claims are free, and claims that can not be
+        // compared to the unstaking duration pay in full.
+        let release_at = Expiration::AtHeight(block.height);
+        assert_eq!(
+            config.claim_penalty(
+                Uint128::new(1000),
+                &release_at,
+                None,
+                Some(Duration::Height(100)),
+                &block
+            ),
+            Uint128::zero()
+        );
+        let release_at = Expiration::AtHeight(block.height + 25);
+        assert_eq!(
+            config.claim_penalty(
+                Uint128::new(1000),
+                &release_at,
+                None,
+                Some(Duration::Time(100)),
+                &block
+            ),
+            Uint128::new(100)
+        );
+
+        // Claims are priced from their own duration when it is known.
+        assert_eq!(
+            config.claim_penalty(
+                Uint128::new(1000),
+                &release_at,
+                Some(Duration::Height(50)),
+                Some(Duration::Time(100)),
+                &block
+            ),
+            Uint128::new(50)
+        );
+        assert_eq!(
+            config.claim_penalty(
+                Uint128::new(1000),
+                &release_at,
+                Some(Duration::Height(50)),
+                Some(Duration::Height(10)),
+                &block
+            ),
+            Uint128::new(50)
+        );
+
+        // Without an unstaking duration claims are free.
+        assert_eq!(
+            config.claim_penalty(
+                Uint128::new(1000),
+                &release_at,
+                Some(Duration::Height(50)),
+                None,
+                &block
+            ),
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn test_update() {
+        assert_eq!(
+            EarlyUnstakeUpdate::Set(config(10)).into_config(),
+            Ok(Some(config(10)))
+        );
+        assert_eq!(
+            EarlyUnstakeUpdate::Set(config(0)).into_config(),
+            Err(EarlyUnstakeError::InvalidPenalty {})
+        );
+        assert_eq!(EarlyUnstakeUpdate::Disable {}.into_config(), Ok(None));
+    }
+
+    #[test]
+    fn test_claim_durations() {
+        let mut storage = MockStorage::new();
+        let durations = ClaimDurations::new();
+        let alice = Addr::unchecked("alice");
+        let release_at = Expiration::AtHeight(100);
+
+        durations
+            .save(&mut storage, &alice, &release_at, Duration::Height(50))
+            .unwrap();
+        durations
+            .save(&mut storage, &alice, &release_at, Duration::Height(20))
+            .unwrap();
+        durations
+            .save(&mut storage, &alice, &release_at, Duration::Height(30))
+            .unwrap();
+        assert_eq!(
+            durations.may_load(&storage, &alice, &release_at).unwrap(),
+            Some(Duration::Height(20))
+        );
+        assert_eq!(
+            durations
+                .may_load(&storage, &alice, &Expiration::AtHeight(101))
+                .unwrap(),
+            None
+        );
+
+        durations.remove(&mut storage, &alice, &release_at);
+        assert_eq!(
+            durations.may_load(&storage, &alice, &release_at).unwrap(),
+            None
+        );
+    }
+}
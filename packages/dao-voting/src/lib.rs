@@ -2,11 +2,14 @@
 
 pub mod deposit;
 pub mod duration;
+pub mod early_unstake;
 pub mod error;
 pub mod multiple_choice;
+pub mod power_cap;
 pub mod pre_propose;
 pub mod proposal;
 pub mod reply;
+pub mod simulation;
 pub mod status;
 pub mod threshold;
 pub mod veto;
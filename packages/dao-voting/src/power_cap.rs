@@ -0,0 +1,445 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, StdError, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+use thiserror::Error;
+
+/// The maximum voting power any one address may have in a voting
+/// module. Power above the cap is ignored. See `PowerCaps` for when
+/// an address's capped power is recomputed.
+#[cw_serde]
+pub enum PowerCap {
+    /// The absolute amount of voting power an address may have.
+    AbsoluteCount { count: Uint128 },
+    /// The percentage of the module's uncapped total power an address
+    /// may have. Computed as `percent * total` when the address's cap
+    /// is applied, and not updated as the total changes afterwards.
+    Percentage { percent: Decimal },
+}
+
+#[cw_serde]
+pub struct PowerCapResponse {
+    pub max_power_per_address: Option<PowerCap>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PowerCapError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error("Voting power cap count must be greater than zero")]
+    ZeroCount {},
+
+    #[error("Voting power cap percentage must be greater than 0 and not greater than 1")]
+    InvalidPercentage {},
+}
+
+impl PowerCap {
+    pub fn validate(&self) -> Result<(), PowerCapError> {
+        match self {
+            PowerCap::AbsoluteCount { count } => {
+                if count.is_zero() {
+                    return Err(PowerCapError::ZeroCount {});
+                }
+            }
+            PowerCap::Percentage { percent } => {
+                if percent.is_zero() || *percent > Decimal::one() {
+                    return Err(PowerCapError::InvalidPercentage {});
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The most voting power an address may have when the module's
+    /// uncapped total power is TOTAL.
+    pub fn max_power(&self, total: Uint128) -> Uint128 {
+        match self {
+            PowerCap::AbsoluteCount { count } => *count,
+            PowerCap::Percentage { percent } => total.mul_floor(*percent),
+        }
+    }
+}
+
+/// Tracks the voting power each address has above a voting module's
+/// `PowerCap`, and the sum of that excess, as a function of block
+/// height. Voting modules subtract the excess from their uncapped
+/// powers, so capped totals are maintained without iterating over
+/// every address.
+///
+/// The excess of an address is only recomputed when `apply` is
+/// called for it, which voting modules do whenever the address's
+/// power changes. It is stale, and the capped total is not the sum
+/// of addresses' capped powers, until then if:
+///
+/// - the cap is changed,
+/// - the cap is a percentage and the uncapped total changes, or
+/// - the address's power changes without a transaction, such as
+///   decaying lock bonuses.
+pub struct PowerCaps<'a> {
+    cap: Item<'a, PowerCap>,
+    excess: SnapshotMap<'a, &'a Addr, Uint128>,
+    total_excess: SnapshotItem<'a, Uint128>,
+}
+
+impl<'a> PowerCaps<'a> {
+    pub const fn new() -> Self {
+        Self {
+            cap: Item::new("power_cap"),
+            excess: SnapshotMap::new(
+                "power_cap_excess",
+                "power_cap_excess__checkpoints",
+                "power_cap_excess__changelog",
+                Strategy::EveryBlock,
+            ),
+            total_excess: SnapshotItem::new(
+                "power_cap_total_excess",
+                "power_cap_total_excess__checkpoints",
+                "power_cap_total_excess__changelog",
+                Strategy::EveryBlock,
+            ),
+        }
+    }
+
+    pub fn cap(&self, storage: &dyn Storage) -> StdResult<Option<PowerCap>> {
+        self.cap.may_load(storage)
+    }
+
+    /// Sets the cap. Addresses' excess power is not recomputed until
+    /// `apply` is next called for them.
+    pub fn set_cap(
+        &self,
+        storage: &mut dyn Storage,
+        cap: Option<PowerCap>,
+    ) -> Result<(), PowerCapError> {
+        match cap {
+            Some(cap) => {
+                cap.validate()?;
+                self.cap.save(storage, &cap)?;
+            }
+            None => self.cap.remove(storage),
+        }
+        Ok(())
+    }
+
+    /// Recomputes ADDRESS's excess power given its uncapped POWER and
+    /// the module's uncapped TOTAL power.
+    pub fn apply(
+        &self,
+        storage: &mut dyn Storage,
+        height: u64,
+        address: &Addr,
+        power: Uint128,
+        total: Uint128,
+    ) -> StdResult<()> {
+        let excess = match self.cap(storage)? {
+            Some(cap) => power.saturating_sub(cap.max_power(total)),
+            None => Uint128::zero(),
+        };
+        let old_excess = self.excess.may_load(storage, address)?.unwrap_or_default();
+        if old_excess == excess {
+            return Ok(());
+        }
+
+        if excess.is_zero() {
+            self.excess.remove(storage, address, height)?;
+        } else {
+            self.excess.save(storage, address, &excess, height)?;
+        }
+        let total_excess = self.total_excess.may_load(storage)?.unwrap_or_default();
+        self.total_excess.save(
+            storage,
+            &total_excess.checked_sub(old_excess)?.checked_add(excess)?,
+            height,
+        )?;
+        Ok(())
+    }
+
+    /// Caps ADDRESS's uncapped POWER at HEIGHT.
+    pub fn capped_power_at_height(
+        &self,
+        storage: &dyn Storage,
+        address: &Addr,
+        power: Uint128,
+        height: u64,
+    ) -> StdResult<Uint128> {
+        let excess = self
+            .excess
+            .may_load_at_height(storage, address, height)?
+            .unwrap_or_default();
+        Ok(power.saturating_sub(excess))
+    }
+
+    /// Caps the module's uncapped TOTAL power at HEIGHT.
+    pub fn capped_total_at_height(
+        &self,
+        storage: &dyn Storage,
+        total: Uint128,
+        height: u64,
+    ) -> StdResult<Uint128> {
+        // The excess of an address whose power fell without `apply`
+        // being called may be stale, so the total excess is clamped to
+        // the uncapped total. Capped power is not returned below zero.
+        let excess = self
+            .total_excess
+            .may_load_at_height(storage, height)?
+            .unwrap_or_default()
+            .min(total);
+        Ok(total - excess)
+    }
+}
+
+impl<'a> Default for PowerCaps<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    const CAPS: PowerCaps = PowerCaps::new();
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(
+            PowerCap::AbsoluteCount {
+                count: Uint128::zero()
+            }
+            .validate(),
+            Err(PowerCapError::ZeroCount {})
+        );
+        assert_eq!(
+            PowerCap::Percentage {
+                percent: Decimal::zero()
+            }
+            .validate(),
+            Err(PowerCapError::InvalidPercentage {})
+        );
+        assert_eq!(
+            PowerCap::Percentage {
+                percent: Decimal::percent(101)
+            }
+            .validate(),
+            Err(PowerCapError::InvalidPercentage {})
+        );
+        PowerCap::Percentage {
+            percent: Decimal::one(),
+        }
+        .validate()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut storage = MockStorage::new();
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+
+        // Without a cap there is no excess.
+        CAPS.apply(
+            &mut storage,
+            1,
+            &alice,
+            Uint128::new(100),
+            Uint128::new(150),
+        )
+        .unwrap();
+        assert_eq!(
+            CAPS.capped_power_at_height(&storage, &alice, Uint128::new(100), 2)
+                .unwrap(),
+            Uint128::new(100)
+        );
+
+        CAPS.set_cap(
+            &mut storage,
+            Some(PowerCap::AbsoluteCount {
+                count: Uint128::new(30),
+            }),
+        )
+        .unwrap();
+        CAPS.apply(
+            &mut storage,
+            2,
+            &alice,
+            Uint128::new(100),
+            Uint128::new(150),
+        )
+        .unwrap();
+        CAPS.apply(&mut storage, 2, &bob, Uint128::new(50), Uint128::new(150))
+            .unwrap();
+
+        assert_eq!(
+            CAPS.capped_power_at_height(&storage, &alice, Uint128::new(100), 3)
+                .unwrap(),
+            Uint128::new(30)
+        );
+        assert_eq!(
+            CAPS.capped_power_at_height(&storage, &bob, Uint128::new(50), 3)
+                .unwrap(),
+            Uint128::new(30)
+        );
+        assert_eq!(
+            CAPS.capped_total_at_height(&storage, Uint128::new(150), 3)
+                .unwrap(),
+            Uint128::new(60)
+        );
+        // Heights before the cap was applied are unaffected.
+        assert_eq!(
+            CAPS.capped_total_at_height(&storage, Uint128::new(150), 2)
+                .unwrap(),
+            Uint128::new(150)
+        );
+
+        // Percentage caps are of the uncapped total.
+        CAPS.set_cap(
+            &mut storage,
+            Some(PowerCap::Percentage {
+                percent: Decimal::percent(40),
+            }),
+        )
+        .unwrap();
+        CAPS.apply(
+            &mut storage,
+            4,
+            &alice,
+            Uint128::new(100),
+            Uint128::new(150),
+        )
+        .unwrap();
+        assert_eq!(
+            CAPS.capped_power_at_height(&storage, &alice, Uint128::new(100), 5)
+                .unwrap(),
+            Uint128::new(60)
+        );
+        // Bob has not been reapplied, so is still capped at 30.
+        assert_eq!(
+            CAPS.capped_total_at_height(&storage, Uint128::new(150), 5)
+                .unwrap(),
+            Uint128::new(90)
+        );
+
+        // Removing the cap removes the excess once applied.
+        CAPS.set_cap(&mut storage, None).unwrap();
+        CAPS.apply(
+            &mut storage,
+            6,
+            &alice,
+            Uint128::new(100),
+            Uint128::new(150),
+        )
+        .unwrap();
+        CAPS.apply(&mut storage, 6, &bob, Uint128::new(50), Uint128::new(150))
+            .unwrap();
+        assert_eq!(
+            CAPS.capped_total_at_height(&storage, Uint128::new(150), 7)
+                .unwrap(),
+            Uint128::new(150)
+        );
+    }
+
+    #[test]
+    fn test_percentage_stale_after_total_changes() {
+        let mut storage = MockStorage::new();
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+
+        CAPS.set_cap(
+            &mut storage,
+            Some(PowerCap::Percentage {
+                percent: Decimal::percent(50),
+            }),
+        )
+        .unwrap();
+        CAPS.apply(
+            &mut storage,
+            1,
+            &alice,
+            Uint128::new(100),
+            Uint128::new(100),
+        )
+        .unwrap();
+        // Alice has all of the power, so is capped at half of it.
+        assert_eq!(
+            CAPS.capped_power_at_height(&storage, &alice, Uint128::new(100), 2)
+                .unwrap(),
+            Uint128::new(50)
+        );
+
+        // Bob stakes, doubling the total. His own cap is computed
+        // against the new total, but Alice's is not recomputed.
+        CAPS.apply(&mut storage, 2, &bob, Uint128::new(100), Uint128::new(200))
+            .unwrap();
+        assert_eq!(
+            CAPS.capped_power_at_height(&storage, &bob, Uint128::new(100), 3)
+                .unwrap(),
+            Uint128::new(100)
+        );
+        assert_eq!(
+            CAPS.capped_power_at_height(&storage, &alice, Uint128::new(100), 3)
+                .unwrap(),
+            Uint128::new(50)
+        );
+        assert_eq!(
+            CAPS.capped_total_at_height(&storage, Uint128::new(200), 3)
+                .unwrap(),
+            Uint128::new(150)
+        );
+
+        // Once refreshed, Alice's cap is half of the new total.
+        CAPS.apply(
+            &mut storage,
+            3,
+            &alice,
+            Uint128::new(100),
+            Uint128::new(200),
+        )
+        .unwrap();
+        assert_eq!(
+            CAPS.capped_power_at_height(&storage, &alice, Uint128::new(100), 4)
+                .unwrap(),
+            Uint128::new(100)
+        );
+        assert_eq!(
+            CAPS.capped_total_at_height(&storage, Uint128::new(200), 4)
+                .unwrap(),
+            Uint128::new(200)
+        );
+    }
+
+    #[test]
+    fn test_stale_excess_clamped() {
+        let mut storage = MockStorage::new();
+        let alice = Addr::unchecked("alice");
+
+        CAPS.set_cap(
+            &mut storage,
+            Some(PowerCap::AbsoluteCount {
+                count: Uint128::new(10),
+            }),
+        )
+        .unwrap();
+        CAPS.apply(
+            &mut storage,
+            1,
+            &alice,
+            Uint128::new(100),
+            Uint128::new(100),
+        )
+        .unwrap();
+
+        // Alice unstakes without being reapplied, so her excess is
+        // larger than the total.
+        assert_eq!(
+            CAPS.capped_power_at_height(&storage, &alice, Uint128::new(40), 2)
+                .unwrap(),
+            Uint128::zero()
+        );
+        assert_eq!(
+            CAPS.capped_total_at_height(&storage, Uint128::new(40), 2)
+                .unwrap(),
+            Uint128::zero()
+        );
+    }
+}
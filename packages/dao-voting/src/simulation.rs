@@ -0,0 +1,275 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{from_json, Addr, BankMsg, Coin, CosmosMsg, Deps, StdResult, Uint128, WasmMsg};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+
+/// A problem with one of a proposal's messages that may cause the
+/// proposal to fail to execute.
+#[cw_serde]
+pub struct ExecutionWarning {
+    /// The choice the message belongs to, for proposals with more
+    /// than one choice. `None` for single choice proposals.
+    pub option_id: Option<u32>,
+    /// The index of the message in the proposal's messages.
+    pub msg_index: u32,
+    pub warning: String,
+}
+
+/// The response to the `SimulateExecution` query of the proposal
+/// modules.
+#[cw_serde]
+pub struct SimulateExecutionResponse {
+    /// Warnings about the proposal's messages. Empty if no problems
+    /// were found.
+    pub warnings: Vec<ExecutionWarning>,
+}
+
+/// Statically checks MSGS, the messages of choice OPTION_ID, as they
+/// would be executed by DAO. Checks
+/// that addresses are valid, and that the native tokens and cw20
+/// tokens sent or burned by the messages, in total up to and
+/// including each message, do not exceed the DAO's current balance.
+///
+/// This does not execute the messages, so it can not account for
+/// messages that change the DAO's balances or fail for other
+/// reasons. Fails if the amounts spent overflow.
+pub fn simulate_execution(
+    deps: Deps,
+    dao: &Addr,
+    option_id: Option<u32>,
+    msgs: &[CosmosMsg],
+) -> StdResult<Vec<ExecutionWarning>> {
+    let mut sim = Simulation {
+        deps,
+        dao,
+        option_id,
+        native: BTreeMap::new(),
+        cw20: BTreeMap::new(),
+        warnings: vec![],
+    };
+    for (index, msg) in msgs.iter().enumerate() {
+        let index = index as u32;
+        match msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                sim.check_address(index, to_address);
+                sim.spend_native(index, amount)?;
+            }
+            CosmosMsg::Bank(BankMsg::Burn { amount }) => sim.spend_native(index, amount)?,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) => {
+                sim.check_address(index, contract_addr);
+                sim.spend_native(index, funds)?;
+                match from_json(msg) {
+                    Ok(Cw20ExecuteMsg::Transfer { recipient, amount })
+                    | Ok(Cw20ExecuteMsg::Send {
+                        contract: recipient,
+                        amount,
+                        ..
+                    }) => {
+                        sim.check_address(index, &recipient);
+                        sim.spend_cw20(index, contract_addr, amount)?;
+                    }
+                    Ok(Cw20ExecuteMsg::Burn { amount }) => {
+                        sim.spend_cw20(index, contract_addr, amount)?
+                    }
+                    _ => (),
+                }
+            }
+            CosmosMsg::Wasm(WasmMsg::Instantiate { admin, funds, .. })
+            | CosmosMsg::Wasm(WasmMsg::Instantiate2 { admin, funds, .. }) => {
+                if let Some(admin) = admin {
+                    sim.check_address(index, admin);
+                }
+                sim.spend_native(index, funds)?;
+            }
+            CosmosMsg::Wasm(WasmMsg::Migrate { contract_addr, .. })
+            | CosmosMsg::Wasm(WasmMsg::ClearAdmin { contract_addr }) => {
+                sim.check_address(index, contract_addr)
+            }
+            CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+                contract_addr,
+                admin,
+            }) => {
+                sim.check_address(index, contract_addr);
+                sim.check_address(index, admin);
+            }
+            _ => (),
+        }
+    }
+    Ok(sim.warnings)
+}
+
+struct Simulation<'a> {
+    deps: Deps<'a>,
+    dao: &'a Addr,
+    option_id: Option<u32>,
+    /// The amount of each native denom spent so far.
+    native: BTreeMap<String, Uint128>,
+    /// The amount of each cw20 token spent so far.
+    cw20: BTreeMap<String, Uint128>,
+    warnings: Vec<ExecutionWarning>,
+}
+
+impl<'a> Simulation<'a> {
+    fn warn(&mut self, msg_index: u32, warning: String) {
+        self.warnings.push(ExecutionWarning {
+            option_id: self.option_id,
+            msg_index,
+            warning,
+        })
+    }
+
+    fn check_address(&mut self, index: u32, address: &str) {
+        if self.deps.api.addr_validate(address).is_err() {
+            self.warn(index, format!("invalid address ({address})"));
+        }
+    }
+
+    fn spend_native(&mut self, index: u32, coins: &[Coin]) -> StdResult<()> {
+        for Coin { denom, amount } in coins {
+            let spent = self.native.entry(denom.clone()).or_default();
+            *spent = spent.checked_add(*amount)?;
+            let spent = *spent;
+            let balance = self.deps.querier.query_balance(self.dao, denom)?.amount;
+            if spent > balance {
+                self.warn(
+                    index,
+                    format!("insufficient balance: spends {spent}{denom} but the DAO holds {balance}{denom}"),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn spend_cw20(&mut self, index: u32, token: &str, amount: Uint128) -> StdResult<()> {
+        let spent = self.cw20.entry(token.to_string()).or_default();
+        *spent = spent.checked_add(amount)?;
+        let spent = *spent;
+        let balance: StdResult<BalanceResponse> = self.deps.querier.query_wasm_smart(
+            token,
+            &Cw20QueryMsg::Balance {
+                address: self.dao.to_string(),
+            },
+        );
+        match balance {
+            Ok(BalanceResponse { balance }) if spent > balance => self.warn(
+                index,
+                format!(
+                    "insufficient balance: spends {spent} of ({token}) but the DAO holds {balance}"
+                ),
+            ),
+            Ok(_) => (),
+            Err(_) => self.warn(
+                index,
+                format!("unable to query the DAO's balance of ({token})"),
+            ),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        coins,
+        testing::{mock_dependencies, MockQuerier},
+        to_json_binary, ContractResult, Empty, SystemResult, WasmQuery,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_simulate_execution() {
+        let mut deps = mock_dependencies();
+        let dao = Addr::unchecked("dao");
+        deps.querier = MockQuerier::<Empty>::new(&[("dao", &coins(100, "ujuno"))]);
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "token" => {
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&BalanceResponse {
+                        balance: Uint128::new(10),
+                    })
+                    .unwrap(),
+                ))
+            }
+            _ => SystemResult::Ok(ContractResult::Err("not a cw20".to_string())),
+        });
+
+        let transfer = |token: &str, recipient: &str, amount: u128| -> CosmosMsg {
+            WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: Uint128::new(amount),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()
+        };
+        let msgs: Vec<CosmosMsg> = vec![
+            BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: coins(60, "ujuno"),
+            }
+            .into(),
+            BankMsg::Send {
+                to_address: "Bob".to_string(),
+                amount: coins(60, "ujuno"),
+            }
+            .into(),
+            transfer("token", "alice", 10),
+            transfer("token", "alice", 1),
+            transfer("other", "alice", 1),
+        ];
+        let warnings = simulate_execution(deps.as_ref(), &dao, Some(2), &msgs).unwrap();
+        assert_eq!(
+            warnings,
+            vec![
+                ExecutionWarning {
+                    option_id: Some(2),
+                    msg_index: 1,
+                    warning: "invalid address (Bob)".to_string(),
+                },
+                ExecutionWarning {
+                    option_id: Some(2),
+                    msg_index: 1,
+                    warning: "insufficient balance: spends 120ujuno but the DAO holds 100ujuno"
+                        .to_string(),
+                },
+                ExecutionWarning {
+                    option_id: Some(2),
+                    msg_index: 3,
+                    warning: "insufficient balance: spends 11 of (token) but the DAO holds 10"
+                        .to_string(),
+                },
+                ExecutionWarning {
+                    option_id: Some(2),
+                    msg_index: 4,
+                    warning: "unable to query the DAO's balance of (other)".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_simulate_execution_overflow() {
+        let mut deps = mock_dependencies();
+        let dao = Addr::unchecked("dao");
+        deps.querier = MockQuerier::<Empty>::new(&[("dao", &coins(100, "ujuno"))]);
+
+        let burn: CosmosMsg = BankMsg::Burn {
+            amount: vec![Coin {
+                denom: "ujuno".to_string(),
+                amount: Uint128::MAX,
+            }],
+        }
+        .into();
+        let err = simulate_execution(deps.as_ref(), &dao, None, &[burn.clone(), burn]).unwrap_err();
+        assert!(matches!(err, cosmwasm_std::StdError::Overflow { .. }));
+    }
+}
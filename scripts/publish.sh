@@ -140,10 +140,18 @@ cd contracts/pre-propose/dao-pre-propose-approval-single
 cargo hack publish --no-dev-deps --allow-dirty
 cd "$START_DIR"
 
+cd contracts/pre-propose/dao-pre-propose-approval-multiple
+cargo hack publish --no-dev-deps --allow-dirty
+cd "$START_DIR"
+
 cd contracts/pre-propose/dao-pre-propose-approver
 cargo hack publish --no-dev-deps --allow-dirty
 cd "$START_DIR"
 
+cd contracts/pre-propose/dao-pre-propose-template
+cargo hack publish --no-dev-deps --allow-dirty
+cd "$START_DIR"
+
 cd contracts/proposal/dao-proposal-single
 cargo hack publish --no-dev-deps --allow-dirty
 cd "$START_DIR"
@@ -158,6 +166,14 @@ cd contracts/proposal/dao-proposal-condorcet
 cargo hack publish --no-dev-deps --allow-dirty
 cd "$START_DIR"
 
+cd contracts/pre-propose/dao-pre-propose-condorcet
+cargo hack publish --no-dev-deps --allow-dirty
+cd "$START_DIR"
+
+cd contracts/proposal/dao-proposal-election
+cargo hack publish --no-dev-deps --allow-dirty
+cd "$START_DIR"
+
 cd contracts/staking/cw20-stake
 cargo hack publish --no-dev-deps --allow-dirty
 cd "$START_DIR"
@@ -196,6 +212,14 @@ cd contracts/voting/dao-voting-onft-staked
 cargo hack publish --no-dev-deps --allow-dirty
 cd "$START_DIR"
 
+cd contracts/voting/dao-voting-composite
+cargo hack publish --no-dev-deps --allow-dirty
+cd "$START_DIR"
+
+cd contracts/voting/dao-voting-native-staked
+cargo hack publish --no-dev-deps --allow-dirty
+cd "$START_DIR"
+
 cd contracts/dao-dao-core
 cargo hack publish --no-dev-deps --allow-dirty
 cd "$START_DIR"